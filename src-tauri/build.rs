@@ -1,3 +1,112 @@
+// Every command in `generate_handler!` must be listed here, which generates
+// its `allow-` and `deny-` permissions. The permission sets in `permissions/`
+// group them, and `capabilities/` grants the sets to windows.
+const COMMANDS: &[&str] = &[
+    "token_exists",
+    "get_token",
+    "save_token",
+    "delete_token",
+    "verify_token",
+    "get_user",
+    "get_user_stats",
+    "get_global_stats",
+    "check_api_status",
+    "get_status",
+    "get_network_status",
+    "recheck_network",
+    "get_service_status",
+    "start_discord_signin",
+    "launch_proxy",
+    "stop_proxy",
+    "get_proxy_status",
+    "validate_launch_environment",
+    "get_connection_state",
+    "get_proxy_metrics",
+    "send_proxy_stdin",
+    "check_firewall",
+    "create_firewall_rule",
+    "shutdown_all",
+    "upload_crash_report",
+    "export_diagnostics",
+    "upload_logs",
+    "take_launcher_crash",
+    "is_launcher_ready",
+    "send_proxy_command",
+    "get_orphan_proxy",
+    "kill_orphan_proxy",
+    "fetch_releases",
+    "legacy_config_exists",
+    "config_exists",
+    "get_legacy_config",
+    "get_config",
+    "get_legacy_config_value",
+    "get_config_value",
+    "set_config_key",
+    "save_config",
+    "set_autostart",
+    "get_autostart",
+    "get_start_mode",
+    "get_start_page",
+    "get_app_lock_status",
+    "unlock_app",
+    "lock_app",
+    "set_app_pin",
+    "get_hotkey_status",
+    "toggle_overlay",
+    "get_overlay_data",
+    "get_obs_status",
+    "open_logs_window",
+    "get_recent_logs",
+    "check_launcher_update",
+    "download_launcher_update",
+    "defer_launcher_update",
+    "get_disk_usage",
+    "clear_cache",
+    "get_app_state",
+    "set_app_state",
+    "get_telemetry_preview",
+    "record_command_timings",
+    "get_perf_metrics",
+    "measure_ping",
+    "get_match_history",
+    "get_match_timeline",
+    "take_stats_snapshot",
+    "get_stats_trend",
+    "get_stats_digest",
+    "export_stats",
+    "resolve_username",
+    "resolve_uuid",
+    "set_hypixel_api_key",
+    "lookup_player",
+    "get_leaderboard",
+    "get_friends_presence",
+    "get_session_stats",
+    "reset_session_stats",
+    "list_addons",
+    "install_addon",
+    "uninstall_addon",
+    "set_addon_enabled",
+    "list_scheduled_tasks",
+    "run_task_now",
+    "get_available_locales",
+    "rpc_set_enabled",
+    "rpc_set_privacy",
+    "set_streamer_mode",
+    "rpc_preview",
+    "rpc_set_launcher_profile",
+    "get_session_summary",
+    "get_unknown_modes",
+    "rpc_is_enabled",
+    "rpc_get_state",
+    "rpc_set_activity",
+    "rpc_set_image",
+    "rpc_get_valid_image_keys",
+];
+
 fn main() {
-    tauri_build::build()
+    tauri_build::try_build(
+        tauri_build::Attributes::new()
+            .app_manifest(tauri_build::AppManifest::new().commands(COMMANDS)),
+    )
+    .expect("failed to run tauri-build");
 }
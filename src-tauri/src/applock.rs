@@ -0,0 +1,411 @@
+//! Optional PIN guarding the token and account.
+//!
+//! For shared computers. With a PIN set (`set_app_pin`), commands that
+//! reveal the token or replace the signed-in account fail until
+//! `unlock_app` is called with the PIN. The launcher locks again after
+//! `appLockTimeoutMins` without a guarded action, or on `lock_app`.
+//!
+//! The PIN is stored as a salted Argon2id hash in `appPin`, as a PHC string
+//! (`$argon2id$v=19$m=...,t=...,p=...$<salt>$<hash>`), and the hash never
+//! leaves the backend. A few wrong PINs in a row block unlocking for a
+//! while; the count and the block are saved to `app-lock.json` in the data
+//! directory, so restarting the launcher doesn't reset them.
+
+use crate::utils::{self, AppDir};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Error returned by guarded commands while the launcher is locked
+pub(crate) const LOCKED: &str = "The launcher is locked, enter your PIN to continue";
+
+/// Name of the file in the data directory keeping wrong PIN attempts
+const ATTEMPTS_FILE: &str = "app-lock.json";
+
+const SALT_LEN: usize = 16;
+
+/// Longest the launcher stays unlocked, whatever `appLockTimeoutMins` says
+pub(crate) const MAX_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Wrong PINs in a row before unlocking is blocked
+const MAX_ATTEMPTS: u32 = 5;
+
+/// How long unlocking is blocked after too many wrong PINs
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Whether a PIN is set and the launcher is locked, as returned by
+/// `get_app_lock_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockStatus {
+    pub enabled: bool,
+    pub locked: bool,
+}
+
+/// Wrong PIN attempts, kept in [`ATTEMPTS_FILE`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct Attempts {
+    /// Wrong PINs since the last unlock or cooldown
+    failures: u32,
+    /// Unix time in seconds until which unlocking is blocked
+    blocked_until: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct LockState {
+    /// When the launcher locks again, `None` while locked
+    unlocked_until: Option<Instant>,
+    attempts: Attempts,
+    /// PINs being checked right now, counted against [`MAX_ATTEMPTS`] so
+    /// guesses sent in parallel can't get around the cooldown
+    pending: u32,
+}
+
+/// Lock state, managed by Tauri
+#[derive(Default)]
+pub struct AppLock {
+    state: Mutex<LockState>,
+    /// Where the attempts are saved, `None` to keep them in memory only
+    attempts_path: Option<PathBuf>,
+}
+
+impl AppLock {
+    /// Creates the lock state, with the wrong PIN attempts saved by an
+    /// earlier run
+    pub fn new() -> Self {
+        let attempts_path = utils::get_app_path(AppDir::Data, ATTEMPTS_FILE).ok();
+        let attempts = attempts_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            state: Mutex::new(LockState {
+                attempts,
+                ..Default::default()
+            }),
+            attempts_path,
+        }
+    }
+
+    /// Gets whether a PIN is set and the launcher is locked
+    pub fn status(&self, pin_hash: Option<&str>) -> AppLockStatus {
+        let enabled = pin_hash.is_some();
+        let state = self.state.lock().unwrap();
+        AppLockStatus {
+            enabled,
+            locked: enabled && !is_unlocked(&state, Instant::now()),
+        }
+    }
+
+    /// Lets a guarded action through if no PIN is set or the launcher is
+    /// unlocked, which also pushes back locking again
+    pub fn check(&self, pin_hash: Option<&str>, timeout: Duration) -> Result<(), String> {
+        if pin_hash.is_none() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        if !is_unlocked(&state, now) {
+            state.unlocked_until = None;
+            return Err(LOCKED.to_string());
+        }
+        state.unlocked_until = Some(until(now, timeout));
+        Ok(())
+    }
+
+    /// Unlocks the launcher if `pin` matches, until `timeout` passes
+    /// without a guarded action
+    pub async fn unlock(
+        &self,
+        pin: String,
+        pin_hash: String,
+        timeout: Duration,
+    ) -> Result<(), String> {
+        self.attempt(pin, pin_hash, timeout, unix_now()).await
+    }
+
+    async fn attempt(
+        &self,
+        pin: String,
+        pin_hash: String,
+        timeout: Duration,
+        now: u64,
+    ) -> Result<(), String> {
+        self.begin_attempt(now)?;
+        // Argon2 takes a while and a fair bit of memory, so it runs off the
+        // async workers and without holding the lock
+        let matched = tauri::async_runtime::spawn_blocking(move || verify_pin(&pin, &pin_hash))
+            .await
+            .unwrap_or(false);
+        self.finish_attempt(matched, timeout, now)
+    }
+
+    /// Reserves an attempt, unless unlocking is blocked
+    fn begin_attempt(&self, now: u64) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        // Capped, so a clock set back doesn't block unlocking for longer
+        let blocked_for = state
+            .attempts
+            .blocked_until
+            .map_or(0, |until| until.saturating_sub(now).min(COOLDOWN.as_secs()));
+        if blocked_for > 0 {
+            return Err(format!(
+                "Too many wrong PINs, try again in {} seconds",
+                blocked_for
+            ));
+        }
+        if state.attempts.failures + state.pending >= MAX_ATTEMPTS {
+            return Err("Too many PINs at once, try again in a moment".to_string());
+        }
+        state.pending += 1;
+        Ok(())
+    }
+
+    /// Records the outcome of an attempt reserved by `begin_attempt`
+    fn finish_attempt(&self, matched: bool, timeout: Duration, now: u64) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        state.pending -= 1;
+        if !matched {
+            let attempts = &mut state.attempts;
+            attempts.failures += 1;
+            if attempts.failures >= MAX_ATTEMPTS {
+                attempts.failures = 0;
+                attempts.blocked_until = Some(now + COOLDOWN.as_secs());
+            }
+            self.save(&state.attempts);
+            return Err("Wrong PIN".to_string());
+        }
+        if state.attempts != Attempts::default() {
+            state.attempts = Attempts::default();
+            self.save(&state.attempts);
+        }
+        state.unlocked_until = Some(until(Instant::now(), timeout));
+        Ok(())
+    }
+
+    /// Locks the launcher right away
+    pub fn lock(&self) {
+        self.state.lock().unwrap().unlocked_until = None;
+    }
+
+    /// Saves the wrong PIN attempts, so a restart doesn't reset them
+    fn save(&self, attempts: &Attempts) {
+        let Some(path) = &self.attempts_path else {
+            return;
+        };
+        let result = serde_json::to_string(attempts)
+            .map_err(|e| e.to_string())
+            .and_then(|content| utils::write_private(path, content));
+        if let Err(e) = result {
+            eprintln!("[applock] Failed to save wrong PIN attempts: {}", e);
+        }
+    }
+}
+
+/// When `timeout` from `now` is, at most [`MAX_TIMEOUT`] from it
+fn until(now: Instant, timeout: Duration) -> Instant {
+    now.checked_add(timeout.min(MAX_TIMEOUT)).unwrap_or(now)
+}
+
+fn is_unlocked(state: &LockState, now: Instant) -> bool {
+    state.unlocked_until.is_some_and(|until| until > now)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Checks that a PIN is 4 to 12 digits
+pub(crate) fn validate_pin(pin: &str) -> Result<(), String> {
+    if (4..=12).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err("The PIN must be 4 to 12 digits".to_string())
+    }
+}
+
+/// Hashes a PIN with a new random salt, to store as `appPin`
+pub(crate) fn hash_pin(pin: &str) -> Result<String, String> {
+    hash_with(pin, Params::DEFAULT)
+}
+
+fn hash_with(pin: &str, params: Params) -> Result<String, String> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| "Failed to generate a salt".to_string())?;
+    let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash the PIN: {}", e))
+}
+
+/// Checks a PIN against a stored hash, with the parameters it was made
+/// with; a malformed hash matches nothing
+pub(crate) fn verify_pin(pin: &str, pin_hash: &str) -> bool {
+    PasswordHash::new(pin_hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(pin.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(300);
+
+    /// Cheap parameters, so the tests stay fast
+    fn hash(pin: &str) -> String {
+        hash_with(pin, Params::new(Params::MIN_M_COST, 1, 1, None).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_hash_and_verify_pin() {
+        let hash = hash("1234");
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_pin("1234", &hash));
+        assert!(!verify_pin("4321", &hash));
+        assert!(!verify_pin("1234", "not a hash"));
+        // Salted, so the same PIN hashes differently
+        assert_ne!(hash, self::hash("1234"));
+    }
+
+    #[test]
+    fn test_validate_pin() {
+        assert!(validate_pin("0042").is_ok());
+        assert!(validate_pin("123").is_err());
+        assert!(validate_pin("12a4").is_err());
+        assert!(validate_pin("1234567890123").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_requires_unlock() {
+        let lock = AppLock::default();
+        let hash = hash("1234");
+        assert!(lock.check(None, TIMEOUT).is_ok());
+        assert_eq!(lock.check(Some(&hash), TIMEOUT), Err(LOCKED.to_string()));
+
+        lock.unlock("1234".to_string(), hash.clone(), TIMEOUT)
+            .await
+            .unwrap();
+        assert!(lock.check(Some(&hash), TIMEOUT).is_ok());
+        assert!(!lock.status(Some(&hash)).locked);
+
+        lock.lock();
+        assert!(lock.status(Some(&hash)).locked);
+    }
+
+    #[tokio::test]
+    async fn test_huge_timeout_is_clamped() {
+        let lock = AppLock::default();
+        let hash = hash("1234");
+        lock.unlock("1234".to_string(), hash.clone(), Duration::MAX)
+            .await
+            .unwrap();
+        assert!(lock.check(Some(&hash), Duration::MAX).is_ok());
+        let now = Instant::now();
+        assert!(until(now, Duration::MAX) <= now + MAX_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_pins_block_unlocking() {
+        let lock = AppLock::default();
+        let hash = hash("1234");
+        let now = unix_now();
+        for _ in 0..MAX_ATTEMPTS {
+            assert_eq!(
+                lock.attempt("0000".to_string(), hash.clone(), TIMEOUT, now)
+                    .await,
+                Err("Wrong PIN".to_string())
+            );
+        }
+        // Even the right PIN is refused until the cooldown ends
+        assert!(lock
+            .attempt("1234".to_string(), hash.clone(), TIMEOUT, now)
+            .await
+            .is_err());
+        assert!(lock
+            .attempt(
+                "1234".to_string(),
+                hash.clone(),
+                TIMEOUT,
+                now + COOLDOWN.as_secs()
+            )
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_parallel_attempts_count_against_the_limit() {
+        let lock = AppLock::default();
+        let now = unix_now();
+        for _ in 0..MAX_ATTEMPTS {
+            lock.begin_attempt(now).unwrap();
+        }
+        assert!(lock.begin_attempt(now).is_err());
+        assert!(lock.finish_attempt(true, TIMEOUT, now).is_ok());
+        assert!(lock.begin_attempt(now).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_attempts_survive_a_restart() {
+        let dir = std::env::temp_dir().join(format!("duelsplus-applock-{}", std::process::id()));
+        let path = dir.join(ATTEMPTS_FILE);
+        let lock = AppLock {
+            attempts_path: Some(path.clone()),
+            ..Default::default()
+        };
+        let hash = hash("1234");
+        let now = unix_now();
+        for _ in 0..MAX_ATTEMPTS {
+            assert!(lock
+                .attempt("0000".to_string(), hash.clone(), TIMEOUT, now)
+                .await
+                .is_err());
+        }
+
+        let saved: Attempts =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.blocked_until, Some(now + COOLDOWN.as_secs()));
+
+        // Still blocked after a restart, even with the clock set back
+        let restarted = AppLock {
+            state: Mutex::new(LockState {
+                attempts: saved,
+                ..Default::default()
+            }),
+            attempts_path: Some(path),
+        };
+        assert!(restarted
+            .attempt("1234".to_string(), hash.clone(), TIMEOUT, now)
+            .await
+            .is_err());
+        assert!(restarted
+            .attempt("1234".to_string(), hash.clone(), TIMEOUT, 0)
+            .await
+            .is_err());
+        assert!(restarted
+            .attempt(
+                "1234".to_string(),
+                hash.clone(),
+                TIMEOUT,
+                now + COOLDOWN.as_secs()
+            )
+            .await
+            .is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
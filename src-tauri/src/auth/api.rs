@@ -5,8 +5,8 @@
 
 use crate::auth::error::AuthError;
 use crate::auth::models::{
-    GetGlobalStatsResponse, GetStatsResponse, GetStatusResponse, GetUserResponse, User,
-    VerifyTokenResponse,
+    GetFriendsResponse, GetGlobalStatsResponse, GetLeaderboardResponse, GetStatsResponse,
+    GetStatusResponse, GetUserResponse, User, VerifyTokenResponse,
 };
 use crate::auth::API_BASE_URL;
 
@@ -564,6 +564,117 @@ async fn process_get_global_stats_response(
     })
 }
 
+/// Retrieves the leaderboard for a stat.
+///
+/// The token is optional; when given, the API also marks the signed-in
+/// user's entries.
+///
+/// # Arguments
+///
+/// * `stat` - The stat the leaderboard ranks by, e.g. "wins"
+/// * `token` - The authentication token, if signed in
+///
+/// # Returns
+///
+/// Returns a `GetLeaderboardResponse` with:
+/// - `success: true` and the leaderboard data if the request was successful
+/// - `success: false` with HTTP status code for errors (404 for an unknown stat, 500, etc.)
+///
+/// # Errors
+///
+/// Returns `AuthError` if there was a network error or error parsing the response JSON.
+pub async fn get_leaderboard(
+    stat: &str,
+    token: Option<&str>,
+) -> Result<GetLeaderboardResponse, AuthError> {
+    get_leaderboard_with_base_url(stat, token, API_BASE_URL).await
+}
+
+/// Internal function to get a leaderboard with a configurable base URL (for testing).
+async fn get_leaderboard_with_base_url(
+    stat: &str,
+    token: Option<&str>,
+    base_url: &str,
+) -> Result<GetLeaderboardResponse, AuthError> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/leaderboard", base_url);
+    let mut request = client.get(&url).query(&[("stat", stat)]);
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = request.send().await.map_err(AuthError::Network)?;
+    let status = response.status();
+    if status.is_success() {
+        let data: serde_json::Value = response.json().await?;
+        return Ok(GetLeaderboardResponse {
+            success: true,
+            code: None,
+            data: Some(data),
+            message: None,
+        });
+    }
+
+    Ok(GetLeaderboardResponse {
+        success: false,
+        code: Some(crate::auth::models::GetUserCode::Number(status.as_u16())),
+        data: None,
+        message: None,
+    })
+}
+
+/// Retrieves the user's friends and whether they're online.
+///
+/// # Arguments
+///
+/// * `token` - The authentication token
+///
+/// # Returns
+///
+/// Returns a `GetFriendsResponse` with:
+/// - `success: true` and the friends if the request was successful
+/// - `success: false` with HTTP status code for errors (401, 500, etc.)
+///
+/// # Errors
+///
+/// Returns `AuthError` if there was a network error or error parsing the response JSON.
+pub async fn get_friends(token: &str) -> Result<GetFriendsResponse, AuthError> {
+    get_friends_with_base_url(token, API_BASE_URL).await
+}
+
+/// Internal function to get friends with a configurable base URL (for testing).
+async fn get_friends_with_base_url(
+    token: &str,
+    base_url: &str,
+) -> Result<GetFriendsResponse, AuthError> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/user/friends", base_url);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .map_err(AuthError::Network)?;
+    let status = response.status();
+    if status.is_success() {
+        let data: serde_json::Value = response.json().await?;
+        return Ok(GetFriendsResponse {
+            success: true,
+            code: None,
+            data: Some(data),
+            message: None,
+        });
+    }
+
+    Ok(GetFriendsResponse {
+        success: false,
+        code: Some(crate::auth::models::GetUserCode::Number(status.as_u16())),
+        data: None,
+        message: None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1188,6 +1299,70 @@ mod tests {
         mock_unauthorized.assert();
     }
 
+    #[tokio::test]
+    async fn test_get_leaderboard() {
+        let mut server = Server::new_async().await;
+
+        let mock_board = server
+            .mock("GET", "/leaderboard")
+            .match_query(Matcher::UrlEncoded("stat".into(), "wins".into()))
+            .match_header("Authorization", Matcher::Exact("Bearer board_token".into()))
+            .with_status(200)
+            .with_body(r#"{"entries": [{"rank": 1, "username": "Steve", "value": 900}]}"#)
+            .create();
+        let mock_unknown = server
+            .mock("GET", "/leaderboard")
+            .match_query(Matcher::UrlEncoded("stat".into(), "nope".into()))
+            .with_status(404)
+            .create();
+
+        let result = get_leaderboard_with_base_url("wins", Some("board_token"), &server.url())
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.data.unwrap()["entries"][0]["username"], "Steve");
+
+        let result = get_leaderboard_with_base_url("nope", None, &server.url())
+            .await
+            .unwrap();
+        assert!(!result.success);
+        match result.code {
+            Some(crate::auth::models::GetUserCode::Number(404)) => {}
+            _ => panic!("Expected 404 code"),
+        }
+
+        mock_board.assert();
+        mock_unknown.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_friends() {
+        let mut server = Server::new_async().await;
+
+        let mock_friends = server
+            .mock("GET", "/user/friends")
+            .match_header(
+                "Authorization",
+                Matcher::Exact("Bearer friends_token".into()),
+            )
+            .with_status(200)
+            .with_body(r#"{"friends": [{"id": "u1", "username": "Steve", "online": true}]}"#)
+            .create();
+
+        let result = get_friends_with_base_url("friends_token", &server.url())
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.data.unwrap()["friends"][0]["username"], "Steve");
+
+        let result = get_friends_with_base_url("other_token", &server.url())
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        mock_friends.assert();
+    }
+
     #[tokio::test]
     #[ignore] // Ignored by default since it makes real API calls
     async fn test_real_token_load_and_fetch_user_data() {
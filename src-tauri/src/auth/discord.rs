@@ -9,8 +9,11 @@
 
 use crate::auth::error::AuthError;
 use crate::auth::API_BASE_URL;
+use crate::utils;
 use serde::{Deserialize, Serialize};
 use std::net::TcpListener as StdTcpListener;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use tauri::{AppHandle, Emitter};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpListener;
@@ -29,6 +32,30 @@ pub struct DiscordAuthResult {
     pub error: Option<String>,
 }
 
+/// ID of the sign-in waiting for a token, 0 if none
+static PENDING_SIGNIN: AtomicU64 = AtomicU64::new(0);
+
+/// ID handed to the next sign-in
+static NEXT_SIGNIN: AtomicU64 = AtomicU64::new(1);
+
+/// Random value the pending sign-in passes to the API, which a
+/// `duelsplus://auth` link has to bring back
+static SIGNIN_STATE: Mutex<String> = Mutex::new(String::new());
+
+/// Hands the pending sign-in over to a token from elsewhere (a
+/// `duelsplus://auth` link), so its callback doesn't report a result too.
+///
+/// Returns `false` if no sign-in was waiting or `state` isn't its state, in
+/// which case the token must be ignored; otherwise any page could sign the
+/// launcher in, e.g. to an account of theirs.
+pub(crate) fn take_pending_signin(state: Option<&str>) -> bool {
+    let expected = SIGNIN_STATE.lock().unwrap();
+    let matches = state.is_some_and(|state| {
+        !expected.is_empty() && utils::constant_time_eq(state.as_bytes(), expected.as_bytes())
+    });
+    matches && PENDING_SIGNIN.swap(0, Ordering::SeqCst) != 0
+}
+
 /// Finds a random available port by binding to port 0.
 fn find_available_port() -> Result<u16, AuthError> {
     let listener = StdTcpListener::bind("127.0.0.1:0")
@@ -135,7 +162,11 @@ pub async fn start_discord_signin(app: AppHandle) -> Result<(), AuthError> {
 
     // Request the OAuth URL from the API
     let client = reqwest::Client::new();
-    let url = format!("{}/auth/launcher-bridge?port={}", API_BASE_URL, port);
+    let state = utils::random_token(16).map_err(AuthError::Unknown)?;
+    let url = format!(
+        "{}/auth/launcher-bridge?port={}&state={}",
+        API_BASE_URL, port, state
+    );
 
     let response = client
         .get(&url)
@@ -168,6 +199,9 @@ pub async fn start_discord_signin(app: AppHandle) -> Result<(), AuthError> {
 
     // Spawn a task to handle the callback
     let app_clone = app.clone();
+    let signin = NEXT_SIGNIN.fetch_add(1, Ordering::SeqCst);
+    *SIGNIN_STATE.lock().unwrap() = state;
+    PENDING_SIGNIN.store(signin, Ordering::SeqCst);
     tokio::spawn(async move {
         // Set a timeout for the authentication
         let timeout = tokio::time::timeout(
@@ -176,6 +210,14 @@ pub async fn start_discord_signin(app: AppHandle) -> Result<(), AuthError> {
         )
         .await;
 
+        // A deep link or a newer sign-in already took over
+        if PENDING_SIGNIN
+            .compare_exchange(signin, 0, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+
         match timeout {
             Ok(Ok(token)) => {
                 let _ = app_clone.emit(
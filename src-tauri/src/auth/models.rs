@@ -117,6 +117,40 @@ pub struct GetStatsResponse {
     pub message: Option<String>,
 }
 
+/// Response from the friends endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFriendsResponse {
+    /// Whether the request was successful
+    pub success: bool,
+    /// Optional error code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<GetUserCode>,
+    /// Friends and their presence if successful
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Error message if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Response from the leaderboard endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLeaderboardResponse {
+    /// Whether the request was successful
+    pub success: bool,
+    /// Optional error code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<GetUserCode>,
+    /// Leaderboard data if successful
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Error message if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 /// Response from the get global stats endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,220 @@
+//! Starting the launcher on login.
+//!
+//! The launcher registers itself the way each OS expects: a `Run` registry
+//! value on Windows, a LaunchAgent on macOS and an XDG autostart entry on
+//! Linux. When started minimized, the entry passes `--minimized` and the
+//! window stays hidden in the tray.
+
+#[cfg(windows)]
+use crate::utils::hidden_command;
+use std::path::PathBuf;
+
+/// Argument passed by the login entry to start hidden in the tray
+pub(crate) const MINIMIZED_ARG: &str = "--minimized";
+
+/// Name of the `Run` registry value
+#[cfg(windows)]
+const RUN_VALUE: &str = "Duels+ Launcher";
+
+/// Registry key holding the current user's login items
+#[cfg(windows)]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+/// Label of the LaunchAgent, matching the bundle identifier
+#[cfg(target_os = "macos")]
+const AGENT_LABEL: &str = "com.duelsplus.launcher";
+
+/// Gets the path the login entry should start.
+///
+/// AppImages run from a temporary mount, so the AppImage file itself is
+/// used instead of the current executable.
+pub(crate) fn launcher_path() -> Result<PathBuf, String> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+    std::env::current_exe().map_err(|e| format!("Failed to get launcher path: {}", e))
+}
+
+/// Registers or removes the launcher from the OS login items.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the launcher should start on login
+/// * `minimized` - Whether to start hidden in the tray
+pub(crate) fn set_enabled(enabled: bool, minimized: bool) -> Result<(), String> {
+    if enabled {
+        register(&launcher_path()?, minimized)
+    } else {
+        unregister()
+    }
+}
+
+#[cfg(windows)]
+fn register(path: &std::path::Path, minimized: bool) -> Result<(), String> {
+    let mut command = format!("\"{}\"", path.display());
+    if minimized {
+        command.push(' ');
+        command.push_str(MINIMIZED_ARG);
+    }
+    let status = hidden_command("reg")
+        .args(["add", RUN_KEY, "/v", RUN_VALUE, "/t", "REG_SZ", "/d"])
+        .arg(command)
+        .arg("/f")
+        .status()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err("Failed to add the launcher to login items".to_string())
+    }
+}
+
+#[cfg(windows)]
+fn unregister() -> Result<(), String> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let status = hidden_command("reg")
+        .args(["delete", RUN_KEY, "/v", RUN_VALUE, "/f"])
+        .status()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err("Failed to remove the launcher from login items".to_string())
+    }
+}
+
+/// Checks whether the launcher is registered to start on login
+#[cfg(windows)]
+pub(crate) fn is_enabled() -> bool {
+    hidden_command("reg")
+        .args(["query", RUN_KEY, "/v", RUN_VALUE])
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Gets the path of the login entry file
+#[cfg(not(windows))]
+fn entry_path() -> Result<PathBuf, String> {
+    let home = crate::utils::get_home_dir()?;
+    #[cfg(target_os = "macos")]
+    let path = home
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{}.plist", AGENT_LABEL));
+    #[cfg(not(target_os = "macos"))]
+    let path = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| home.join(".config"))
+        .join("autostart")
+        .join("duelsplus-launcher.desktop");
+    Ok(path)
+}
+
+/// Builds the LaunchAgent plist starting the launcher
+#[cfg(target_os = "macos")]
+fn entry_contents(path: &std::path::Path, minimized: bool) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    let mut args = format!(
+        "\n        <string>{}</string>",
+        escape(&path.to_string_lossy())
+    );
+    if minimized {
+        args.push_str(&format!("\n        <string>{}</string>", MINIMIZED_ARG));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>{}
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        AGENT_LABEL, args
+    )
+}
+
+/// Quotes a path for the `Exec` key of a desktop entry, escaping the
+/// characters the spec reserves inside quotes
+#[cfg(not(any(windows, target_os = "macos")))]
+pub(crate) fn desktop_exec(path: &std::path::Path) -> String {
+    let quoted = path
+        .to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('`', "\\`")
+        .replace('$', "\\$");
+    format!("\"{}\"", quoted)
+}
+
+/// Builds the XDG desktop entry starting the launcher
+#[cfg(not(any(windows, target_os = "macos")))]
+fn entry_contents(path: &std::path::Path, minimized: bool) -> String {
+    let mut exec = desktop_exec(path);
+    if minimized {
+        exec.push(' ');
+        exec.push_str(MINIMIZED_ARG);
+    }
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Duels+ Launcher\n\
+         Exec={}\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n",
+        exec
+    )
+}
+
+#[cfg(not(windows))]
+fn register(path: &std::path::Path, minimized: bool) -> Result<(), String> {
+    let entry = entry_path()?;
+    if let Some(dir) = entry.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&entry, entry_contents(path, minimized))
+        .map_err(|e| format!("Failed to write {}: {}", entry.display(), e))
+}
+
+#[cfg(not(windows))]
+fn unregister() -> Result<(), String> {
+    let entry = entry_path()?;
+    match std::fs::remove_file(&entry) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove {}: {}", entry.display(), e)),
+    }
+}
+
+/// Checks whether the launcher is registered to start on login
+#[cfg(not(windows))]
+pub(crate) fn is_enabled() -> bool {
+    entry_path().is_ok_and(|path| path.exists())
+}
+
+#[cfg(all(test, not(any(windows, target_os = "macos"))))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desktop_entry_quotes_path() {
+        let entry = entry_contents(std::path::Path::new("/opt/Duels+ $HOME/launcher"), true);
+        assert!(entry.contains("Exec=\"/opt/Duels+ \\$HOME/launcher\" --minimized\n"));
+
+        let entry = entry_contents(std::path::Path::new("/usr/bin/duelsplus-launcher"), false);
+        assert!(entry.contains("Exec=\"/usr/bin/duelsplus-launcher\"\n"));
+    }
+}
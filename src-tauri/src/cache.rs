@@ -0,0 +1,268 @@
+//! Cached and accumulating files, and cleaning them up.
+//!
+//! Everything the launcher keeps around that it can do without falls into a
+//! [`CacheCategory`], each with its own directory and [`Retention`] policy.
+//! A scheduled cleanup deletes files past their category's age limit, then
+//! the oldest ones until the category fits its size limit. `clear_cache`
+//! empties a category right away, and `get_disk_usage` reports how much
+//! each one (and the installed proxy) takes up.
+
+use crate::proxy::{crash, download};
+use crate::utils::{self, AppDir};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+const MB: u64 = 1024 * 1024;
+
+/// A kind of cached or accumulating file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheCategory {
+    /// Player heads shown on the Discord activity
+    Avatars,
+    /// Proxy release lists and changelogs
+    Releases,
+    /// Player and leaderboard stats fetched from APIs
+    Stats,
+    /// Log files written by the proxy
+    Logs,
+    /// Proxy and launcher crash reports
+    Crashes,
+}
+
+impl CacheCategory {
+    pub const ALL: [CacheCategory; 5] = [
+        CacheCategory::Avatars,
+        CacheCategory::Releases,
+        CacheCategory::Stats,
+        CacheCategory::Logs,
+        CacheCategory::Crashes,
+    ];
+
+    /// How long files are kept, and how much space the category may use
+    fn retention(&self) -> Retention {
+        match self {
+            CacheCategory::Avatars | CacheCategory::Releases | CacheCategory::Stats => Retention {
+                max_age: 7 * DAY,
+                max_size: 50 * MB,
+            },
+            CacheCategory::Logs => Retention {
+                max_age: 14 * DAY,
+                max_size: 100 * MB,
+            },
+            CacheCategory::Crashes => Retention {
+                max_age: 30 * DAY,
+                max_size: 50 * MB,
+            },
+        }
+    }
+}
+
+/// Gets the directory files of a category are kept in, which may not exist
+/// yet
+pub(crate) fn dir(category: CacheCategory) -> Result<PathBuf, String> {
+    match category {
+        CacheCategory::Avatars => utils::get_app_path(AppDir::Cache, "avatars"),
+        CacheCategory::Releases => utils::get_app_path(AppDir::Cache, "releases"),
+        CacheCategory::Stats => utils::get_app_path(AppDir::Cache, "stats"),
+        // Written by the proxy, which still uses `~/.duelsplus`
+        CacheCategory::Logs => Ok(utils::get_app_root()?.join("logs")),
+        CacheCategory::Crashes => crash::get_crash_dir().map_err(|e| e.to_string()),
+    }
+}
+
+/// How long a category's files are kept and how much space they may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Retention {
+    max_age: Duration,
+    max_size: u64,
+}
+
+/// A file found in a cache directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Space used by one category, as returned by `get_disk_usage`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryUsage {
+    pub category: CacheCategory,
+    pub bytes: u64,
+    pub files: usize,
+}
+
+/// Space used by the launcher, as returned by `get_disk_usage`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    /// The installed proxy
+    pub install_bytes: u64,
+    pub categories: Vec<CategoryUsage>,
+    /// Everything above together
+    pub total_bytes: u64,
+}
+
+/// Lists the files under a directory and its subdirectories
+fn files(dir: &Path) -> Vec<CachedFile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            found.extend(files(&entry.path()));
+        } else if metadata.is_file() {
+            found.push(CachedFile {
+                path: entry.path(),
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+    found
+}
+
+fn usage(category: CacheCategory) -> CategoryUsage {
+    let files = dir(category).map(|dir| files(&dir)).unwrap_or_default();
+    CategoryUsage {
+        category,
+        bytes: files.iter().map(|f| f.size).sum(),
+        files: files.len(),
+    }
+}
+
+/// Measures the installed proxy and every cache category
+pub(crate) fn disk_usage() -> DiskUsage {
+    let install_bytes = download::get_install_dir()
+        .map(|dir| files(&dir).iter().map(|f| f.size).sum())
+        .unwrap_or_default();
+    let categories: Vec<CategoryUsage> = CacheCategory::ALL.into_iter().map(usage).collect();
+    let total_bytes = install_bytes + categories.iter().map(|c| c.bytes).sum::<u64>();
+    DiskUsage {
+        install_bytes,
+        categories,
+        total_bytes,
+    }
+}
+
+/// Deletes every file of a category.
+///
+/// # Returns
+///
+/// Returns how many bytes were freed.
+pub(crate) fn clear(category: CacheCategory) -> Result<u64, String> {
+    let files = files(&dir(category)?);
+    Ok(delete(&files.iter().collect::<Vec<_>>()))
+}
+
+/// Applies every category's retention policy.
+///
+/// # Returns
+///
+/// Returns how many bytes were freed.
+pub(crate) fn clean_up() -> u64 {
+    let now = SystemTime::now();
+    CacheCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let files = dir(category).map(|dir| files(&dir)).unwrap_or_default();
+            delete(&expired(&files, category.retention(), now))
+        })
+        .sum()
+}
+
+/// Deletes files, returning the bytes freed by the ones that were deleted
+fn delete(files: &[&CachedFile]) -> u64 {
+    files
+        .iter()
+        .filter_map(|file| match std::fs::remove_file(&file.path) {
+            Ok(()) => Some(file.size),
+            Err(e) => {
+                eprintln!("[cache] Failed to delete {}: {}", file.path.display(), e);
+                None
+            }
+        })
+        .sum()
+}
+
+/// Picks the files a retention policy removes: those older than its age
+/// limit, then the oldest until the rest fits its size limit
+fn expired(files: &[CachedFile], retention: Retention, now: SystemTime) -> Vec<&CachedFile> {
+    let mut newest_first: Vec<&CachedFile> = files.iter().collect();
+    newest_first.sort_by_key(|file| std::cmp::Reverse(file.modified));
+
+    let mut kept_size = 0;
+    newest_first
+        .into_iter()
+        .filter(|file| {
+            let age = now.duration_since(file.modified).unwrap_or_default();
+            if age > retention.max_age || kept_size + file.size > retention.max_size {
+                return true;
+            }
+            kept_size += file.size;
+            false
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: u64, age_days: u32, now: SystemTime) -> CachedFile {
+        CachedFile {
+            path: PathBuf::from(name),
+            size,
+            modified: now - age_days * DAY,
+        }
+    }
+
+    #[test]
+    fn test_expired_by_age_and_size() {
+        let now = SystemTime::now();
+        let retention = Retention {
+            max_age: 7 * DAY,
+            max_size: 100,
+        };
+        let files = vec![
+            file("old", 10, 8, now),
+            file("newest", 60, 0, now),
+            file("older", 30, 2, now),
+            file("recent", 30, 1, now),
+        ];
+        let names: Vec<_> = expired(&files, retention, now)
+            .iter()
+            .map(|f| f.path.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["older", "old"]);
+    }
+
+    #[test]
+    fn test_expired_keeps_everything_within_limits() {
+        let now = SystemTime::now();
+        let files = vec![file("a", 10, 1, now), file("b", 10, 3, now)];
+        assert!(expired(&files, CacheCategory::Logs.retention(), now).is_empty());
+    }
+
+    #[test]
+    fn test_files_walks_subdirectories() {
+        let dir = std::env::temp_dir().join(format!("duelsplus-cache-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("a.txt"), b"abc").unwrap();
+        std::fs::write(dir.join("nested").join("b.txt"), b"de").unwrap();
+
+        let found = files(&dir);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found.iter().map(|f| f.size).sum::<u64>(), 5);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+//! Command-line arguments.
+//!
+//! Supported arguments:
+//! - `--minimized` starts hidden in the tray, as the login entry does
+//! - `--tray-only` starts without a window, see [`crate::tray`]
+//! - `--launch-proxy` launches the proxy on startup
+//! - `--port <port>` launches it on that port instead of the configured one
+//! - `--page <page>` opens a launcher page, e.g. `--page stats`
+//! - `duelsplus://` links, see [`crate::deeplink`]
+//!
+//! Other arguments are ignored, since some OSes pass their own. If a
+//! launcher is already running, the arguments are handed to it as
+//! `duelsplus://` links and the new process quits.
+
+use crate::autostart::MINIMIZED_ARG;
+use crate::deeplink::{self, Page, SCHEME};
+use std::sync::OnceLock;
+
+const TRAY_ONLY_ARG: &str = "--tray-only";
+const LAUNCH_PROXY_ARG: &str = "--launch-proxy";
+const PORT_ARG: &str = "--port";
+const PAGE_ARG: &str = "--page";
+
+/// Arguments the launcher was started with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Args {
+    pub minimized: bool,
+    pub tray_only: bool,
+    pub launch_proxy: bool,
+    /// Port to launch the proxy on, `None` for the configured one
+    pub port: Option<u16>,
+    pub page: Option<Page>,
+    /// `duelsplus://` links to handle
+    pub links: Vec<String>,
+}
+
+impl Args {
+    /// Parses arguments, without the program name
+    pub(crate) fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Both `--port 25566` and `--port=25566`
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} needs a value", name))
+            };
+
+            match name {
+                MINIMIZED_ARG => parsed.minimized = true,
+                TRAY_ONLY_ARG => parsed.tray_only = true,
+                LAUNCH_PROXY_ARG => parsed.launch_proxy = true,
+                PORT_ARG => {
+                    let port = value()?;
+                    parsed.port = Some(
+                        port.parse::<u16>()
+                            .ok()
+                            .filter(|p| *p != 0)
+                            .ok_or_else(|| format!("Invalid port \"{}\"", port))?,
+                    );
+                }
+                PAGE_ARG => parsed.page = Some(value()?.parse()?),
+                _ if arg.starts_with(&format!("{}:", SCHEME)) => parsed.links.push(arg),
+                _ => {}
+            }
+        }
+
+        if parsed.port.is_some() && !parsed.launch_proxy {
+            return Err(format!("{} needs {}", PORT_ARG, LAUNCH_PROXY_ARG));
+        }
+        Ok(parsed)
+    }
+
+    /// The arguments as links, to hand to a running launcher
+    pub(crate) fn to_links(&self) -> Vec<String> {
+        let mut links = self.links.clone();
+        if self.launch_proxy {
+            links.push(match self.port {
+                Some(port) => format!("{}://launch?port={}", SCHEME, port),
+                None => format!("{}://launch", SCHEME),
+            });
+        }
+        if let Some(page) = self.page {
+            links.push(format!("{}://open/{}", SCHEME, page));
+        }
+        links
+    }
+}
+
+/// Gets the arguments this process was started with.
+///
+/// Invalid arguments are reported and ignored, keeping any links.
+pub(crate) fn args() -> &'static Args {
+    static ARGS: OnceLock<Args> = OnceLock::new();
+    ARGS.get_or_init(|| {
+        Args::parse(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("[cli] Ignoring arguments: {}", e);
+            Args {
+                links: deeplink::from_args(),
+                ..Args::default()
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(&["--minimized", "--launch-proxy", "--port", "25566"]).unwrap();
+        assert!(args.minimized);
+        assert!(args.launch_proxy);
+        assert_eq!(args.port, Some(25566));
+
+        assert!(parse(&["--tray-only"]).unwrap().tray_only);
+
+        let args = parse(&["--page=stats", "duelsplus://profile/abc", "-psn_0_1"]).unwrap();
+        assert_eq!(args.page, Some(Page::Stats));
+        assert_eq!(args.links, vec!["duelsplus://profile/abc"]);
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert!(parse(&["--launch-proxy", "--port"]).is_err());
+        assert!(parse(&["--launch-proxy", "--port", "0"]).is_err());
+        assert!(parse(&["--port=25566"]).is_err());
+        assert!(parse(&["--page", "store"]).is_err());
+    }
+
+    #[test]
+    fn test_to_links() {
+        let args = parse(&["--launch-proxy", "--port=25566", "--page", "logs"]).unwrap();
+        assert_eq!(
+            args.to_links(),
+            vec!["duelsplus://launch?port=25566", "duelsplus://open/logs"]
+        );
+        for link in args.to_links() {
+            assert!(deeplink::parse(&link).is_some());
+        }
+        assert!(parse(&["--minimized"]).unwrap().to_links().is_empty());
+    }
+}
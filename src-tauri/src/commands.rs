@@ -3,10 +3,46 @@
 //! This module exposes authentication functions as Tauri commands that can be
 //! invoked from the frontend JavaScript/TypeScript code.
 
+use crate::applock::{self, AppLock, AppLockStatus};
 use crate::auth;
+use crate::autostart;
+use crate::cache::{self, CacheCategory, DiskUsage};
+use crate::cli;
 use crate::config;
-use crate::proxy::{download, models, ProxyManager};
-use crate::rpc::RpcManager;
+use crate::crash_handler::{self, LauncherCrash};
+use crate::deeplink::Page;
+use crate::diagnostics;
+use crate::friends::{self, FriendPresence};
+use crate::hotkeys::{HotkeyManager, HotkeyStatus};
+use crate::hypixel::{self, PlayerLookup};
+use crate::i18n::{self, t, Locale};
+use crate::leaderboard::{self, Leaderboard};
+use crate::logs_window;
+use crate::mc::mojang::{self, MojangProfile};
+use crate::network::{NetConnectivity, NetworkStatus};
+use crate::obs::{self, ObsStatus};
+use crate::overlay::{self, Overlay, OverlayData};
+use crate::perf::{self, CommandMetrics, CommandTiming};
+use crate::ping::{self, PingResult};
+use crate::proxy::connection::ConnectionEvent;
+use crate::proxy::events::ScopedEmitter;
+use crate::proxy::{addons, crash, download, firewall, manager, models, preflight, ProxyManager};
+use crate::rpc::names::{self, UnknownMode};
+use crate::rpc::{CustomActivity, RpcManager, RpcPreview, RpcStateSnapshot, SessionSummary};
+use crate::scheduler::{ScheduledTask, Scheduler};
+use crate::service_status::{self, ServiceStatus};
+use crate::session_stats::{self, SessionStats};
+use crate::state_store;
+use crate::storage::digest::{self, DigestPeriod, StatsDigest};
+use crate::storage::export::{self, ExportFormat, StatsExport};
+use crate::storage::matches::{self, MatchFilter, MatchHistoryPage};
+use crate::storage::snapshots::{self, StatSnapshot, TrendPoint, TrendRange};
+use crate::storage::timelines::{self, MatchTimeline};
+use crate::streamer;
+use crate::telemetry::{self, TelemetryReport};
+use crate::tray::{self, StartMode};
+use crate::updater::{self, LauncherUpdate, LauncherUpdater};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, State};
 
 /// Starts the Discord OAuth sign-in flow.
@@ -43,20 +79,28 @@ pub async fn token_exists() -> Result<bool, String> {
 /// Retrieves the stored authentication token from disk.
 ///
 /// Returns `Some(token)` if the token exists, `None` if it doesn't.
+/// Fails while the launcher is locked with a PIN.
 #[tauri::command]
-pub async fn get_token() -> Result<Option<String>, String> {
+pub async fn get_token(lock: State<'_, AppLock>) -> Result<Option<String>, String> {
+    require_unlocked(&lock).await?;
     auth::token::get_token().await.map_err(|e| e.to_string())
 }
 
 /// Saves an authentication token to disk.
 ///
 /// The token is saved with appropriate file permissions and a verification timestamp.
+/// Replacing a signed-in account fails while the launcher is locked with a PIN.
 ///
 /// # Arguments
 ///
 /// * `token` - The authentication token string to save
+/// * `lock` - The app lock state
 #[tauri::command]
-pub async fn save_token(token: String) -> Result<(), String> {
+pub async fn save_token(token: String, lock: State<'_, AppLock>) -> Result<(), String> {
+    if auth::token::token_exists().await.unwrap_or(false) {
+        require_unlocked(&lock).await?;
+    }
+    streamer::hide(&token);
     auth::token::save_token(token)
         .await
         .map_err(|e| e.to_string())
@@ -65,8 +109,11 @@ pub async fn save_token(token: String) -> Result<(), String> {
 /// Deletes the token file from disk.
 ///
 /// Returns `true` if the file was deleted, `false` if it didn't exist.
+/// Fails while the launcher is locked with a PIN, since signing in again
+/// would switch accounts.
 #[tauri::command]
-pub async fn delete_token() -> Result<bool, String> {
+pub async fn delete_token(lock: State<'_, AppLock>) -> Result<bool, String> {
+    require_unlocked(&lock).await?;
     auth::token::delete_token().await.map_err(|e| e.to_string())
 }
 
@@ -116,7 +163,7 @@ pub async fn get_user_stats() -> Result<auth::models::GetStatsResponse, String>
     let token = auth::token::get_token()
         .await
         .map_err(|e| e.to_string())?
-        .ok_or_else(|| "No token found".to_string())?;
+        .ok_or_else(|| t!("errors.noToken"))?;
 
     auth::api::get_stats(&token)
         .await
@@ -154,6 +201,49 @@ pub async fn get_status() -> Result<auth::models::GetStatusResponse, String> {
     auth::api::get_status().await.map_err(|e| e.to_string())
 }
 
+/// Gets whether the network and the Duels+ API are reachable.
+///
+/// Changes are also emitted as `network-status` events.
+///
+/// # Arguments
+///
+/// * `net` - The connectivity monitor state
+///
+/// # Returns
+///
+/// Returns the status from the latest check.
+#[tauri::command]
+pub fn get_network_status(net: State<'_, NetConnectivity>) -> NetworkStatus {
+    net.status()
+}
+
+/// Checks connectivity again right away, e.g. after a request failed.
+///
+/// The result is emitted as a `network-status` event if it changed.
+///
+/// # Arguments
+///
+/// * `net` - The connectivity monitor state
+#[tauri::command]
+pub fn recheck_network(net: State<'_, NetConnectivity>) {
+    net.recheck();
+}
+
+/// Checks whether Hypixel and Mojang's session servers are up, e.g. after
+/// joining failed.
+///
+/// # Arguments
+///
+/// * `refresh` - Whether to check again even if the last check is recent
+///
+/// # Returns
+///
+/// Returns each service's state, and a sentence to show when one is down.
+#[tauri::command]
+pub async fn get_service_status(refresh: Option<bool>) -> ServiceStatus {
+    service_status::get(refresh.unwrap_or(false)).await
+}
+
 /// Launches the proxy process.
 ///
 /// This command checks for updates, downloads if necessary, and starts the proxy.
@@ -162,19 +252,32 @@ pub async fn get_status() -> Result<auth::models::GetStatusResponse, String> {
 ///
 /// * `app` - The Tauri app handle for emitting events
 /// * `manager` - The proxy manager state
-/// * `port` - The port number for the proxy (default: 25565)
+/// * `port` - The port number for the proxy (defaults to the configured `proxyPort`)
 #[tauri::command]
 pub async fn launch_proxy(
     app: AppHandle,
     manager: State<'_, ProxyManager>,
     port: Option<u16>,
 ) -> Result<(), String> {
-    let port = port.unwrap_or(25565);
+    let port = match port {
+        Some(port) => port,
+        None => {
+            let config = config::manager::get_config()
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            manager::parse_proxy_port(&config.proxy_port).map_err(|e| e.to_string())?
+        }
+    };
     match manager.check_and_launch(app.clone(), port).await {
-        Ok(()) => Ok(()),
+        Ok(()) => {
+            telemetry::record_feature("proxyLaunch");
+            Ok(())
+        }
         Err(e) => {
+            telemetry::record_error(e.category());
             let msg = format!("[proxy] Launch failed: {}", e);
-            let _ = app.emit("log-message", &msg);
+            let _ = app.emit_scoped("log-message", &msg);
             Err(e.to_string())
         }
     }
@@ -182,12 +285,145 @@ pub async fn launch_proxy(
 
 /// Stops the proxy process.
 ///
+/// Escalates to a force kill if the proxy doesn't exit within the
+/// configured shutdown timeout.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+///
+/// # Returns
+///
+/// Returns a `StopOutcome` describing how the proxy was stopped.
+#[tauri::command]
+pub async fn stop_proxy(
+    app: AppHandle,
+    manager: State<'_, ProxyManager>,
+) -> Result<models::StopOutcome, String> {
+    let outcome = manager.stop().await.map_err(|e| e.to_string())?;
+    manager.update_connection(&app, ConnectionEvent::ProxyStopped);
+    let _ = app.emit_scoped(
+        "log-message",
+        format!("[proxy] Proxy stopped ({})", outcome.describe()),
+    );
+    Ok(outcome)
+}
+
+/// Cancels downloads, stops (or detaches) the proxy and clears the Discord
+/// presence before quitting.
+///
+/// Used by the frontend's quit flow; the window close handler does the same.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+/// * `rpc` - The RPC manager state
+///
+/// # Returns
+///
+/// Returns a `ShutdownReport` describing what was stopped.
+#[tauri::command]
+pub async fn shutdown_all(
+    manager: State<'_, ProxyManager>,
+    rpc: State<'_, RpcManager>,
+) -> Result<models::ShutdownReport, String> {
+    let report = manager.shutdown_all().await;
+    rpc.shutdown_and_wait().await;
+    Ok(report)
+}
+
+/// Exports a diagnostic bundle to attach to support tickets.
+///
+/// Zips the launcher and proxy logs, the config, the installed proxy, OS
+/// details and recent crash reports, with the token, webhook URLs and home
+/// directory redacted.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle (for the launcher version)
+/// * `manager` - The proxy manager state
+/// * `path` - Where to write the zip file (defaults to the Downloads folder)
+///
+/// # Returns
+///
+/// Returns the path the bundle was written to.
+#[tauri::command]
+pub async fn export_diagnostics(
+    app: AppHandle,
+    manager: State<'_, ProxyManager>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => diagnostics::default_path()?,
+    };
+
+    let contents = diagnostics::BundleContents {
+        system: diagnostics::SystemInfo::new(app.package_info().version.to_string()),
+        proxy: diagnostics::ProxyInfo {
+            running: manager.is_running().await,
+            version: manager.instance_scope().proxy_version,
+            installed: diagnostics::installed_proxy_files(),
+        },
+        config: config::manager::get_config().await.ok().flatten(),
+        proxy_log: manager.recent_output(),
+    };
+    let token = auth::token::get_token().await.ok().flatten();
+    diagnostics::export(&path, contents, &diagnostics::Redactor::new(token))?;
+    telemetry::record_feature("diagnostics");
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Uploads the recent launcher and proxy logs for support.
+///
+/// Secrets are redacted as in `export_diagnostics`. Only sent when the user
+/// asks, instead of a screenshot of the logs.
+///
 /// # Arguments
 ///
+/// * `app` - The Tauri app handle
 /// * `manager` - The proxy manager state
+///
+/// # Returns
+///
+/// Returns the id and link to share with support.
+#[tauri::command]
+pub async fn upload_logs(
+    app: AppHandle,
+    manager: State<'_, ProxyManager>,
+) -> Result<diagnostics::LogUpload, String> {
+    let token = auth::token::get_token().await.ok().flatten();
+    let upload = diagnostics::upload_logs(
+        app.package_info().version.to_string(),
+        &manager.recent_output(),
+        &diagnostics::Redactor::new(token),
+    )
+    .await?;
+    telemetry::record_feature("uploadLogs");
+    Ok(upload)
+}
+
+/// Checks whether the launcher has finished starting up.
+///
+/// Startup applies the saved config in the background and emits
+/// `launcher-ready` when done; this covers pages mounted after the event.
+#[tauri::command]
+pub fn is_launcher_ready() -> bool {
+    crate::is_ready()
+}
+
+/// Gets the launcher crash from the previous run, if any.
+///
+/// Only returns a crash once, so the frontend can offer to open the crash
+/// folder on the next start without asking again.
 #[tauri::command]
-pub async fn stop_proxy(manager: State<'_, ProxyManager>) -> Result<(), String> {
-    manager.stop().await.map_err(|e| e.to_string())
+pub fn take_launcher_crash() -> Option<LauncherCrash> {
+    let crash = crash_handler::take_last_crash();
+    if crash.is_some() {
+        telemetry::record_error("launcherCrash");
+    }
+    crash
 }
 
 /// Gets the current proxy status.
@@ -202,10 +438,176 @@ pub async fn get_proxy_status(manager: State<'_, ProxyManager>) -> Result<bool,
     Ok(manager.is_running().await)
 }
 
+/// Uploads a crash bundle reported via the `proxy-crashed` event.
+///
+/// Crash bundles are never uploaded automatically; this is only invoked
+/// when the user chooses to send one.
+///
+/// # Arguments
+///
+/// * `path` - Path of the crash bundle, as given in the event
+#[tauri::command]
+pub async fn upload_crash_report(path: String) -> Result<(), String> {
+    crash::upload_crash_report(std::path::Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Queries the running proxy for its network metrics.
+///
+/// Samples are also pushed every few seconds via the `proxy-metrics` event.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+#[tauri::command]
+pub async fn get_proxy_metrics(
+    manager: State<'_, ProxyManager>,
+) -> Result<models::ProxyMetrics, String> {
+    manager.get_metrics().await.map_err(|e| e.to_string())
+}
+
+/// Runs the launch pre-flight checks without launching anything.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+/// * `port` - Port to check; defaults to the configured proxy port
+///
+/// # Returns
+///
+/// A checklist of platform, install directory, port, API and binary checks
+#[tauri::command]
+pub async fn validate_launch_environment(
+    manager: State<'_, ProxyManager>,
+    port: Option<u16>,
+) -> Result<preflight::PreflightReport, String> {
+    Ok(preflight::validate_launch_environment(port, manager.is_running().await).await)
+}
+
+/// Gets the player's connection state through the proxy.
+///
+/// Changes are also pushed via the `connection-state-changed` event.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+#[tauri::command]
+pub async fn get_connection_state(
+    manager: State<'_, ProxyManager>,
+) -> Result<models::ConnectionState, String> {
+    Ok(manager.connection_state())
+}
+
+/// Sends a console command to the proxy's stdin.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+/// * `line` - The command line to send, without a trailing newline
+#[tauri::command]
+pub async fn send_proxy_stdin(
+    manager: State<'_, ProxyManager>,
+    line: String,
+) -> Result<(), String> {
+    manager.send_stdin(&line).await.map_err(|e| e.to_string())
+}
+
+/// Checks whether Windows Firewall lets connections through to the proxy.
+///
+/// Runs automatically after each launch (reported via `firewall-status`).
+///
+/// # Arguments
+///
+/// * `port` - The proxy port to test (defaults to the configured `proxyPort`)
+///
+/// # Returns
+///
+/// Returns `unsupported` on platforms other than Windows.
+#[tauri::command]
+pub async fn check_firewall(port: Option<u16>) -> Result<firewall::FirewallStatus, String> {
+    let port = match port {
+        Some(port) => port,
+        None => {
+            let config = config::manager::get_config()
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            manager::parse_proxy_port(&config.proxy_port).map_err(|e| e.to_string())?
+        }
+    };
+    Ok(firewall::check_firewall(port).await)
+}
+
+/// Adds an inbound Windows Firewall rule for the proxy executable.
+///
+/// Prompts for elevation through UAC.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle for emitting events
+#[tauri::command]
+pub async fn create_firewall_rule(app: AppHandle) -> Result<firewall::FirewallStatus, String> {
+    let status = firewall::create_firewall_rule()
+        .await
+        .map_err(|e| e.to_string())?;
+    let _ = app.emit_scoped("firewall-status", status);
+    Ok(status)
+}
+
+/// Gets a proxy left running by another launcher session, found at startup.
+///
+/// Also reported via the `proxy-orphan-detected` event, which is usually
+/// emitted before the frontend listens.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+///
+/// # Returns
+///
+/// Returns the orphaned proxy, or `None` if there's none or it has exited.
+#[tauri::command]
+pub async fn get_orphan_proxy(
+    manager: State<'_, ProxyManager>,
+) -> Result<Option<models::OrphanProxyInfo>, String> {
+    Ok(manager.orphan().await)
+}
+
+/// Kills a proxy left running by another launcher session.
+///
+/// Only applies to an instance returned by `get_orphan_proxy`.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+#[tauri::command]
+pub async fn kill_orphan_proxy(manager: State<'_, ProxyManager>) -> Result<(), String> {
+    manager.kill_orphan().await.map_err(|e| e.to_string())
+}
+
+/// Sends a command to the running proxy over its control socket.
+///
+/// # Arguments
+///
+/// * `manager` - The proxy manager state
+/// * `command` - The command to send, e.g. `{"type": "set_setting", "key": "...", "value": ...}`
+#[tauri::command]
+pub async fn send_proxy_command(
+    manager: State<'_, ProxyManager>,
+    command: models::ControlCommand,
+) -> Result<(), String> {
+    manager
+        .send_control_command(&command)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Fetches the list of releases from the API.
 ///
 /// Returns a list of all available releases with their version, assets, and metadata.
 /// Respects the `receiveBetaReleases` config setting to determine which API to use.
+/// A list fetched in the last few minutes, e.g. prefetched at startup, is reused.
 ///
 /// # Returns
 ///
@@ -218,7 +620,7 @@ pub async fn fetch_releases() -> Result<Vec<models::Release>, String> {
         .flatten()
         .map(|c| c.receive_beta_releases)
         .unwrap_or(false);
-    download::fetch_releases(use_beta)
+    download::fetch_recent_releases(use_beta)
         .await
         .map_err(|e| e.to_string())
 }
@@ -255,12 +657,17 @@ pub async fn get_legacy_config() -> Result<Option<config::models::Config>, Strin
 
 /// Reads the configuration file.
 ///
-/// Returns the config if it exists, `None` otherwise.
+/// Returns the config if it exists, `None` otherwise. The PIN hash is left
+/// out, see `get_app_lock_status`.
 #[tauri::command]
 pub async fn get_config() -> Result<Option<config::models::Config>, String> {
-    config::manager::get_config()
+    let cfg = config::manager::get_config()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    Ok(cfg.map(|cfg| config::models::Config {
+        app_pin: None,
+        ..cfg
+    }))
 }
 
 /// Reads a specific key from the legacy configuration file.
@@ -287,9 +694,13 @@ pub async fn get_legacy_config_value(key: String) -> Result<Option<serde_json::V
 ///
 /// # Returns
 ///
-/// Returns the value if the key exists, `None` otherwise.
+/// Returns the value if the key exists, `None` otherwise. The PIN hash is
+/// never returned, see `get_app_lock_status`.
 #[tauri::command]
 pub async fn get_config_value(key: String) -> Result<Option<serde_json::Value>, String> {
+    if key == "appPin" {
+        return Ok(None);
+    }
     config::manager::get_config_value(&key)
         .await
         .map_err(|e| e.to_string())
@@ -297,23 +708,45 @@ pub async fn get_config_value(key: String) -> Result<Option<serde_json::Value>,
 
 /// Sets a specific key in the configuration file.
 ///
-/// Also syncs RPC-related settings with the RPC manager automatically.
+/// Also syncs RPC-related settings with the RPC manager automatically, and
+/// forwards settings the proxy uses to the running proxy.
 ///
 /// # Arguments
 ///
 /// * `key` - The configuration key to set
 /// * `value` - The value to set (must be a valid JSON value)
 /// * `rpc` - The RPC manager state (for syncing RPC settings)
+/// * `proxy` - The proxy manager state (for syncing proxy settings)
+/// * `hotkeys` - The global shortcut state (for re-registering `keybinds`)
+/// * `app` - The Tauri app handle for emitting `hotkeys-status`
 #[tauri::command]
 pub async fn set_config_key(
     key: String,
     value: serde_json::Value,
     rpc: State<'_, RpcManager>,
+    proxy: State<'_, ProxyManager>,
+    hotkeys: State<'_, HotkeyManager>,
+    app: AppHandle,
 ) -> Result<(), String> {
+    if key == "appPin" {
+        return Err("The PIN can only be changed with set_app_pin".to_string());
+    }
+    if key == "hypixelApiKey" {
+        return Err("The API key can only be changed with set_hypixel_api_key".to_string());
+    }
     config::manager::set_config_key(&key, value.clone())
         .await
         .map_err(|e| e.to_string())?;
 
+    // Not running or not connected yet is fine, settings are pushed on connect
+    if manager::PROXY_SYNCED_KEYS.contains(&key.as_str()) {
+        let command = models::ControlCommand::SetSetting {
+            key: key.clone(),
+            value: value.clone(),
+        };
+        let _ = proxy.send_control_command(&command).await;
+    }
+
     // Sync RPC-related settings with the RPC manager
     match key.as_str() {
         "enableRpc" => {
@@ -323,12 +756,22 @@ pub async fn set_config_key(
         }
         "rpcAnonymizeProfile" => {
             if let Some(anonymize) = value.as_bool() {
-                rpc.set_anonymize_profile(anonymize);
+                rpc.set_anonymize_profile(anonymize || streamer::is_enabled());
             }
         }
         "rpcAnonymizeLocation" => {
             if let Some(anonymize) = value.as_bool() {
-                rpc.set_anonymize_location(anonymize);
+                rpc.set_anonymize_location(anonymize || streamer::is_enabled());
+            }
+        }
+        "streamerMode" => {
+            if let Some(enabled) = value.as_bool() {
+                apply_streamer_mode(&rpc, enabled).await?;
+            }
+        }
+        "telemetry" => {
+            if let Some(enabled) = value.as_bool() {
+                telemetry::set_enabled(enabled);
             }
         }
         "rpcImage" => {
@@ -336,6 +779,69 @@ pub async fn set_config_key(
                 let _ = rpc.set_image(image_key);
             }
         }
+        "rpcButtons" => {
+            if let Some(enabled) = value.as_bool() {
+                rpc.set_buttons(enabled);
+            }
+        }
+        "rpcShowStreak" => {
+            if let Some(enabled) = value.as_bool() {
+                rpc.set_show_streak(enabled);
+            }
+        }
+        "rpcLauncherProfile" => {
+            if let Some(enabled) = value.as_bool() {
+                rpc.set_show_launcher_profile(enabled);
+            }
+        }
+        "rpcClientId" => {
+            if let Some(client_id) = value.as_str() {
+                let _ = rpc.set_client_id(client_id);
+            }
+        }
+        "rpcDiscordClient" => {
+            if let Ok(discord_client) = serde_json::from_value(value.clone()) {
+                rpc.set_discord_client(discord_client);
+            }
+        }
+        "rpcAfkTimeoutMins" => {
+            if let Some(minutes) = value.as_u64() {
+                rpc.set_afk_timeout(minutes);
+            }
+        }
+        "rpcTimerMode" => {
+            if let Ok(mode) = serde_json::from_value(value.clone()) {
+                rpc.set_timer_mode(mode);
+            }
+        }
+        "locale" => {
+            if let Some(locale) = value.as_str() {
+                rpc.set_locale(locale);
+                i18n::set_locale(locale);
+            }
+        }
+        "obsServer" | "obsPort" => {
+            let cfg = config::manager::get_config()
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            obs::apply(&app, &cfg);
+        }
+        // Keep the login entry in line with the saved settings
+        "autostart" | "autostartMinimized" => {
+            let cfg = config::manager::get_config()
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            autostart::set_enabled(cfg.autostart, cfg.autostart_minimized)?;
+        }
+        "keybinds" => {
+            let cfg = config::manager::get_config()
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            let _ = app.emit("hotkeys-status", hotkeys.apply(&app, &cfg.keybinds));
+        }
         _ => {}
     }
 
@@ -344,43 +850,948 @@ pub async fn set_config_key(
 
 /// Saves the entire configuration structure to the configuration file.
 ///
+/// Pushes the proxy's settings again if it is running.
+///
 /// # Arguments
 ///
 /// * `config` - The complete configuration structure to write
+/// * `proxy` - The proxy manager state (for syncing proxy settings)
+/// * `hotkeys` - The global shortcut state (for re-registering `keybinds`)
+/// * `app` - The Tauri app handle for emitting `hotkeys-status`
 #[tauri::command]
-pub async fn save_config(config: config::models::Config) -> Result<(), String> {
+pub async fn save_config(
+    config: config::models::Config,
+    proxy: State<'_, ProxyManager>,
+    hotkeys: State<'_, HotkeyManager>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let mut config = config;
+    // Only `set_app_pin` changes the PIN, and `set_hypixel_api_key` the key
+    let stored = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?;
+    config.app_pin = stored.as_ref().and_then(|cfg| cfg.app_pin.clone());
+    config.hypixel_api_key = stored.and_then(|cfg| cfg.hypixel_api_key);
+    let keybinds = config.keybinds.clone();
+    i18n::set_locale(&config.locale);
+    streamer::set_enabled(config.streamer_mode);
+    telemetry::set_enabled(config.telemetry);
+    obs::apply(&app, &config);
     config::manager::save_config(config)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    let _ = proxy.sync_proxy_settings().await;
+    let _ = app.emit("hotkeys-status", hotkeys.apply(&app, &keybinds));
+    Ok(())
 }
 
 // ============================================================================
-// Discord RPC Commands
+// App Lock Commands
 // ============================================================================
 
-/// Enables or disables Discord Rich Presence.
-///
-/// # Arguments
-///
-/// * `rpc` - The RPC manager state
-/// * `enabled` - Whether to enable or disable RPC
+/// Fails while the launcher is locked with a PIN, otherwise keeps it
+/// unlocked for another `appLockTimeoutMins`
+async fn require_unlocked(lock: &AppLock) -> Result<(), String> {
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    lock.check(cfg.app_pin.as_deref(), lock_timeout(&cfg))
+}
+
+/// How long the launcher stays unlocked without a PIN-guarded action, at
+/// most [`applock::MAX_TIMEOUT`] whatever the config says
+fn lock_timeout(cfg: &config::models::Config) -> Duration {
+    Duration::from_secs(cfg.app_lock_timeout_mins.saturating_mul(60)).min(applock::MAX_TIMEOUT)
+}
+
+/// Gets whether a PIN is set and the launcher is locked.
 #[tauri::command]
-pub fn rpc_set_enabled(rpc: State<'_, RpcManager>, enabled: bool) {
-    rpc.set_enabled(enabled);
+pub async fn get_app_lock_status(lock: State<'_, AppLock>) -> Result<AppLockStatus, String> {
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    Ok(lock.status(cfg.app_pin.as_deref()))
 }
 
-/// Returns whether Discord Rich Presence is enabled.
+/// Unlocks PIN-guarded commands until `appLockTimeoutMins` pass without
+/// one being used.
 ///
 /// # Arguments
 ///
-/// * `rpc` - The RPC manager state
+/// * `lock` - The app lock state
+/// * `pin` - The PIN
 ///
 /// # Returns
 ///
-/// Returns `true` if RPC is enabled, `false` otherwise.
+/// Returns an error if the PIN is wrong, or after too many wrong PINs.
 #[tauri::command]
-pub fn rpc_is_enabled(rpc: State<'_, RpcManager>) -> bool {
-    rpc.is_enabled()
+pub async fn unlock_app(lock: State<'_, AppLock>, pin: String) -> Result<(), String> {
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    let timeout = lock_timeout(&cfg);
+    let Some(pin_hash) = cfg.app_pin else {
+        return Ok(());
+    };
+    lock.unlock(pin, pin_hash, timeout).await
+}
+
+/// Locks PIN-guarded commands right away.
+#[tauri::command]
+pub fn lock_app(lock: State<'_, AppLock>) {
+    lock.lock();
+}
+
+/// Sets, changes or removes the PIN.
+///
+/// # Arguments
+///
+/// * `lock` - The app lock state
+/// * `current` - The PIN set now, required to change or remove it
+/// * `pin` - The new PIN (4 to 12 digits), or `None` to remove it
+#[tauri::command]
+pub async fn set_app_pin(
+    lock: State<'_, AppLock>,
+    current: Option<String>,
+    pin: Option<String>,
+) -> Result<(), String> {
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    let timeout = lock_timeout(&cfg);
+    if let Some(pin_hash) = cfg.app_pin {
+        let current = current.ok_or("Enter the current PIN")?;
+        lock.unlock(current, pin_hash, timeout).await?;
+    }
+
+    let value = match pin {
+        Some(pin) => {
+            applock::validate_pin(&pin)?;
+            let hash = tauri::async_runtime::spawn_blocking(move || applock::hash_pin(&pin))
+                .await
+                .map_err(|e| e.to_string())??;
+            serde_json::json!(hash)
+        }
+        None => serde_json::Value::Null,
+    };
+    config::manager::set_config_key("appPin", value)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Global Shortcut Commands
+// ============================================================================
+
+/// Gets the result of registering the `keybinds` shortcuts.
+///
+/// Also emitted as `hotkeys-status` whenever the shortcuts are registered.
+///
+/// # Arguments
+///
+/// * `hotkeys` - The global shortcut state
+///
+/// # Returns
+///
+/// Returns shortcuts that couldn't be registered, e.g. because another app
+/// already uses them.
+#[tauri::command]
+pub fn get_hotkey_status(hotkeys: State<'_, HotkeyManager>) -> HotkeyStatus {
+    hotkeys.status()
+}
+
+// ============================================================================
+// Overlay Commands
+// ============================================================================
+
+/// Shows the in-game overlay window, or hides it if it's shown.
+///
+/// # Returns
+///
+/// Returns whether the overlay is now shown.
+#[tauri::command]
+pub async fn toggle_overlay(app: AppHandle) -> Result<bool, String> {
+    telemetry::record_feature("overlay");
+    overlay::toggle(&app).await
+}
+
+/// Gets what the overlay shows: the current game and opponent, and the
+/// session's wins and losses.
+///
+/// Changes are emitted to the overlay window as `overlay-update`.
+#[tauri::command]
+pub fn get_overlay_data(overlay: State<'_, Overlay>) -> OverlayData {
+    overlay.data()
+}
+
+/// Gets whether the OBS overlay server is running, see [`crate::obs`].
+///
+/// # Returns
+///
+/// Returns the port it's on and, if it couldn't start, why.
+#[tauri::command]
+pub async fn get_obs_status() -> Result<ObsStatus, String> {
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    Ok(obs::status(&cfg))
+}
+
+// ============================================================================
+// Logs Window Commands
+// ============================================================================
+
+/// Opens the logs in their own window, or focuses it if it's already open.
+#[tauri::command]
+pub fn open_logs_window(app: AppHandle) -> Result<(), String> {
+    logs_window::open(&app)
+}
+
+/// Gets the proxy's most recent output, oldest first, for a logs view that
+/// opened after it was logged
+#[tauri::command]
+pub fn get_recent_logs(manager: State<'_, ProxyManager>) -> Vec<String> {
+    let lines = manager.recent_output();
+    if streamer::is_enabled() {
+        return lines.iter().map(|line| streamer::scrub(line)).collect();
+    }
+    lines
+}
+
+// ============================================================================
+// Launcher Update Commands
+// ============================================================================
+
+/// Checks the `updateChannel` release channel for a launcher update.
+///
+/// Also emits `launcher-update-available` when one is found.
+///
+/// # Arguments
+///
+/// * `manual` - Whether the user asked to check, which ignores "remind me
+///   later"
+///
+/// # Returns
+///
+/// Returns the update with its release notes, or `None` if the launcher is
+/// up to date or automatic checks are deferred.
+#[tauri::command]
+pub async fn check_launcher_update(
+    app: AppHandle,
+    updater: State<'_, LauncherUpdater>,
+    manual: Option<bool>,
+) -> Result<Option<LauncherUpdate>, String> {
+    updater.check(&app, manual.unwrap_or(false)).await
+}
+
+/// Downloads the update found by `check_launcher_update`.
+///
+/// Progress is emitted as `launcher-update-progress`. With
+/// `installUpdatesOnQuit` set the update installs when the launcher quits,
+/// otherwise right away.
+///
+/// # Returns
+///
+/// Returns `true` if the update is installed and takes effect on restart,
+/// `false` if it installs on quit.
+#[tauri::command]
+pub async fn download_launcher_update(
+    app: AppHandle,
+    updater: State<'_, LauncherUpdater>,
+) -> Result<bool, String> {
+    updater.download(&app).await
+}
+
+/// Skips automatic launcher update checks for a day ("remind me later")
+#[tauri::command]
+pub async fn defer_launcher_update() -> Result<(), String> {
+    updater::defer().await
+}
+
+// ============================================================================
+// Background Task Commands
+// ============================================================================
+
+/// Gets the periodic background tasks, for debugging.
+///
+/// # Returns
+///
+/// Returns each task's interval and jitter, when it last ran (and whether
+/// that failed) and when it runs next.
+#[tauri::command]
+pub fn list_scheduled_tasks(scheduler: State<'_, Scheduler>) -> Vec<ScheduledTask> {
+    scheduler.list()
+}
+
+/// Runs a periodic background task now instead of at its next run.
+///
+/// # Arguments
+///
+/// * `name` - The task's name, as listed by `list_scheduled_tasks`
+#[tauri::command]
+pub fn run_task_now(scheduler: State<'_, Scheduler>, name: String) -> Result<(), String> {
+    scheduler.run_now(&name)
+}
+
+// ============================================================================
+// Disk Usage Commands
+// ============================================================================
+
+/// Measures the space used by the installed proxy and each cache category.
+#[tauri::command]
+pub async fn get_disk_usage() -> DiskUsage {
+    cache::disk_usage()
+}
+
+/// Deletes every file of a cache category.
+///
+/// # Arguments
+///
+/// * `category` - The category to clear, e.g. `avatars` or `logs`
+///
+/// # Returns
+///
+/// Returns how many bytes were freed.
+#[tauri::command]
+pub async fn clear_cache(category: CacheCategory) -> Result<u64, String> {
+    cache::clear(category)
+}
+
+// ============================================================================
+// UI State Commands
+// ============================================================================
+
+/// Gets UI state saved with `set_app_state`.
+///
+/// # Arguments
+///
+/// * `key` - The name it was saved under, e.g. `activeTab`
+///
+/// # Returns
+///
+/// Returns `None` if nothing was saved under the key.
+#[tauri::command]
+pub fn get_app_state(key: String) -> Result<Option<serde_json::Value>, String> {
+    state_store::get(&key)
+}
+
+/// Saves UI state that should survive the webview's storage being wiped.
+///
+/// # Arguments
+///
+/// * `key` - The name to save it under (1 to 64 characters)
+/// * `value` - Any JSON value; `null` removes the key
+#[tauri::command]
+pub fn set_app_state(key: String, value: serde_json::Value) -> Result<(), String> {
+    state_store::set(&key, value)
+}
+
+// ============================================================================
+// Telemetry Commands
+// ============================================================================
+
+/// Gets exactly what the next telemetry upload would send.
+///
+/// Counts stay empty while `telemetry` is off, since nothing is counted.
+#[tauri::command]
+pub fn get_telemetry_preview(app: AppHandle) -> TelemetryReport {
+    telemetry::preview(&app)
+}
+
+// ============================================================================
+// Performance Commands
+// ============================================================================
+
+/// Records command timings measured by the frontend.
+///
+/// # Arguments
+/// * `timings` - Commands called since the last batch, with how long each took
+#[tauri::command]
+pub fn record_command_timings(timings: Vec<CommandTiming>) {
+    perf::record(timings);
+}
+
+/// Gets calls, errors and duration percentiles of each command since the
+/// launcher started, slowest first.
+#[tauri::command]
+pub fn get_perf_metrics() -> Vec<CommandMetrics> {
+    perf::metrics()
+}
+
+// ============================================================================
+// Ping Commands
+// ============================================================================
+
+/// Measures the latency to Hypixel as TCP connect times.
+///
+/// # Arguments
+/// * `include_proxy` - Whether to measure the local proxy port too
+///
+/// # Returns
+/// Returns the min, average and jitter of a few connects to each target.
+#[tauri::command]
+pub async fn measure_ping(include_proxy: Option<bool>) -> Result<Vec<PingResult>, String> {
+    ping::measure(include_proxy.unwrap_or(false)).await
+}
+
+// ============================================================================
+// Match History Commands
+// ============================================================================
+
+/// Gets a page of the recorded matches, newest first.
+///
+/// # Arguments
+/// * `filter` - Which matches to include; every field is optional
+/// * `page` - Page number from 0, of `pageSize` matches each
+///
+/// # Returns
+/// Returns the page and how many matches passed the filter in total.
+#[tauri::command]
+pub fn get_match_history(filter: MatchFilter, page: usize) -> Result<MatchHistoryPage, String> {
+    matches::history(&filter, page)
+}
+
+/// Gets what happened during a recorded match: its kills, deaths, goals
+/// and beds in order, and a summary of each round.
+///
+/// # Arguments
+/// * `match_id` - The match's `id` from `get_match_history`
+///
+/// # Returns
+/// Returns the timeline, or `null` for matches recorded before timelines
+/// were or with nothing in them.
+#[tauri::command]
+pub fn get_match_timeline(match_id: u64) -> Result<Option<MatchTimeline>, String> {
+    timelines::timeline(match_id)
+}
+
+// ============================================================================
+// Stat Trend Commands
+// ============================================================================
+
+/// Saves a snapshot of the user's stats now, on top of the daily ones.
+///
+/// # Returns
+/// Returns the snapshot, or an error if signed out or the API failed.
+#[tauri::command]
+pub async fn take_stats_snapshot() -> Result<StatSnapshot, String> {
+    snapshots::snapshot(true)
+        .await?
+        .ok_or_else(|| "No snapshot was taken".to_string())
+}
+
+/// Gets a stat over time from the saved snapshots, oldest first.
+///
+/// # Arguments
+/// * `stat` - A number from the API's stats, like `winRate`, or `games`
+///   or `gamesPerDay`
+/// * `range` - How far back to go: `week`, `month`, `year` or `all`
+#[tauri::command]
+pub fn get_stats_trend(stat: String, range: TrendRange) -> Result<Vec<TrendPoint>, String> {
+    snapshots::trend(&stat, range)
+}
+
+/// Sums up the recorded matches of today or this week so far.
+///
+/// # Arguments
+/// * `period` - `day` or `week`; weeks start on Monday, in local time
+///
+/// # Returns
+/// Returns the games, win rate, best mode and longest win streak.
+#[tauri::command]
+pub fn get_stats_digest(period: DigestPeriod) -> Result<StatsDigest, String> {
+    digest::digest(period)
+}
+
+/// Exports the match history and stat snapshots for spreadsheets and other
+/// tools. See [`crate::storage::export`] for the schema.
+///
+/// # Arguments
+/// * `format` - `csv` or `json`
+/// * `path` - Where to write the export (defaults to the Downloads folder);
+///   a CSV export writes the snapshots next to it, with `-snapshots`
+///   added to the name
+///
+/// # Returns
+/// Returns the files written and how many matches and snapshots they hold.
+#[tauri::command]
+pub fn export_stats(format: ExportFormat, path: Option<String>) -> Result<StatsExport, String> {
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => export::default_path(format)?,
+    };
+    let result = export::export(format, &path)?;
+    telemetry::record_feature("statsExport");
+    Ok(result)
+}
+
+// ============================================================================
+// Mojang Commands
+// ============================================================================
+
+/// Looks up a Minecraft account by username.
+///
+/// # Arguments
+/// * `name` - The username, in any case
+///
+/// # Returns
+/// Returns the account's UUID and exact name, or `None` if no account has
+/// the name.
+#[tauri::command]
+pub async fn resolve_username(name: String) -> Result<Option<MojangProfile>, String> {
+    mojang::resolve_username(&name).await
+}
+
+/// Looks up a Minecraft account by UUID, with or without dashes.
+///
+/// # Returns
+/// Returns the account's current name, or `None` if no account has the
+/// UUID.
+#[tauri::command]
+pub async fn resolve_uuid(uuid: String) -> Result<Option<MojangProfile>, String> {
+    mojang::resolve_uuid(&uuid).await
+}
+
+// ============================================================================
+// Hypixel Commands
+// ============================================================================
+
+/// Sets or removes the Hypixel API key used for player lookups.
+///
+/// # Arguments
+/// * `key` - The key from the Hypixel developer dashboard, or `None` to
+///   remove it
+#[tauri::command]
+pub async fn set_hypixel_api_key(key: Option<String>) -> Result<(), String> {
+    let value = match key {
+        Some(key) => serde_json::json!(hypixel::seal_api_key(&key)?),
+        None => serde_json::Value::Null,
+    };
+    config::manager::set_config_key("hypixelApiKey", value)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Looks up a player's Duels and BedWars stats on Hypixel.
+///
+/// # Arguments
+/// * `name` - The player's username, in any case, or UUID
+///
+/// # Returns
+/// Returns the player's stats, or `None` if no account has the name or the
+/// player never joined Hypixel.
+#[tauri::command]
+pub async fn lookup_player(name: String) -> Result<Option<PlayerLookup>, String> {
+    hypixel::lookup_player(&name).await
+}
+
+// ============================================================================
+// Leaderboard Commands
+// ============================================================================
+
+/// Gets a Duels+ leaderboard, cached for a few minutes.
+///
+/// # Arguments
+/// * `stat` - The stat it ranks by, e.g. "wins"
+/// * `refresh` - Whether to fetch it even if the cached one is recent
+/// * `app` - The Tauri app handle for emitting `leaderboard-updated`
+///
+/// # Returns
+/// Returns the leaderboard, with the signed-in player's rank if they're on it.
+#[tauri::command]
+pub async fn get_leaderboard(
+    stat: String,
+    refresh: Option<bool>,
+    app: AppHandle,
+) -> Result<Leaderboard, String> {
+    leaderboard::get(&app, &stat, refresh.unwrap_or(false)).await
+}
+
+// ============================================================================
+// Friends Commands
+// ============================================================================
+
+/// Gets the user's friends and what they're doing, from the last poll.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle for emitting `friends-presence-updated`
+///
+/// # Returns
+/// Returns the friends in game first, then online, then offline.
+#[tauri::command]
+pub async fn get_friends_presence(app: AppHandle) -> Result<Vec<FriendPresence>, String> {
+    friends::get(&app).await
+}
+
+// ============================================================================
+// Session Stats Commands
+// ============================================================================
+
+/// Gets the wins, losses, kills and deaths since the proxy launched.
+#[tauri::command]
+pub fn get_session_stats() -> SessionStats {
+    session_stats::get()
+}
+
+/// Starts the session stats over, emitting `session-stats-updated`.
+#[tauri::command]
+pub fn reset_session_stats(app: AppHandle) {
+    session_stats::reset(&app);
+}
+
+// ============================================================================
+// Proxy Addon Commands
+// ============================================================================
+
+/// Lists the proxy addons offered by the API and those installed, with
+/// their versions and whether they're enabled.
+///
+/// Offline, only installed addons are listed.
+#[tauri::command]
+pub async fn list_addons() -> Result<Vec<models::AddonInfo>, String> {
+    let installed = addons::read_installed().map_err(|e| e.to_string())?;
+    let available = addons::fetch_addons().await.unwrap_or_else(|e| {
+        eprintln!("[proxy] Failed to fetch addons: {}", e);
+        Vec::new()
+    });
+    let disabled = config::manager::get_config()
+        .await
+        .ok()
+        .flatten()
+        .map(|c| c.disabled_addons)
+        .unwrap_or_default();
+    Ok(addons::list(&available, &installed, &disabled))
+}
+
+/// Installs or updates a proxy addon to its latest version.
+///
+/// The proxy loads it the next time it starts.
+///
+/// # Arguments
+///
+/// * `id` - The addon to install
+///
+/// # Returns
+///
+/// Returns the version installed.
+#[tauri::command]
+pub async fn install_addon(id: String) -> Result<String, String> {
+    let version = addons::install(&id).await.map_err(|e| {
+        telemetry::record_error(e.category());
+        e.to_string()
+    })?;
+    telemetry::record_feature("addons");
+    Ok(version)
+}
+
+/// Removes an installed proxy addon.
+///
+/// # Arguments
+///
+/// * `id` - The addon to remove
+#[tauri::command]
+pub async fn uninstall_addon(id: String) -> Result<(), String> {
+    addons::uninstall(&id).map_err(|e| e.to_string())
+}
+
+/// Enables or disables an installed proxy addon.
+///
+/// Updates `disabledAddons` and pushes it to the proxy if it's running.
+/// Emits `config-changed` so open settings views stay in sync.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle for emitting events
+/// * `proxy` - The proxy manager state
+/// * `id` - The addon to toggle
+/// * `enabled` - Whether the proxy should load it
+#[tauri::command]
+pub async fn set_addon_enabled(
+    app: AppHandle,
+    proxy: State<'_, ProxyManager>,
+    id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut disabled = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default()
+        .disabled_addons;
+    disabled.retain(|d| *d != id);
+    if !enabled {
+        disabled.push(id);
+    }
+
+    let value = serde_json::json!(disabled);
+    config::manager::set_config_key("disabledAddons", value.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    // Not running or not connected yet is fine, settings are pushed on connect
+    let command = models::ControlCommand::SetSetting {
+        key: "disabledAddons".to_string(),
+        value: value.clone(),
+    };
+    let _ = proxy.send_control_command(&command).await;
+
+    let _ = app.emit(
+        "config-changed",
+        serde_json::json!({ "disabledAddons": value }),
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Translation Commands
+// ============================================================================
+
+/// Gets the locales the launcher has translations for.
+///
+/// # Returns
+///
+/// Returns each locale's code (as used by the `locale` config) and its name
+/// in its own language.
+#[tauri::command]
+pub fn get_available_locales() -> Vec<Locale> {
+    i18n::available_locales()
+}
+
+// ============================================================================
+// Autostart Commands
+// ============================================================================
+
+/// Registers or removes the launcher from the OS login items.
+///
+/// Starts minimized to the tray if `autostartMinimized` is set, and saves
+/// `autostart` to the config.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle for notifying the settings page
+/// * `enabled` - Whether the launcher should start on login
+///
+/// # Returns
+///
+/// Returns an error if the login entry or the config could not be written.
+#[tauri::command]
+pub async fn set_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    autostart::set_enabled(enabled, cfg.autostart_minimized)?;
+
+    config::manager::set_config_key("autostart", serde_json::json!(enabled))
+        .await
+        .map_err(|e| e.to_string())?;
+    let _ = app.emit(
+        "config-changed",
+        serde_json::json!({ "autostart": enabled }),
+    );
+    Ok(())
+}
+
+/// Checks whether the launcher is registered to start on login.
+///
+/// Reads the OS login items rather than the config, so an entry removed
+/// outside the launcher shows up as disabled.
+#[tauri::command]
+pub fn get_autostart() -> bool {
+    autostart::is_enabled()
+}
+
+/// Gets how the launcher window should appear on startup.
+///
+/// The launcher starts minimized when started with `--minimized` (as the
+/// login entry does with `autostartMinimized` set) or when `startMinimized`
+/// is set. It hides in the tray if the tray icon is available to bring the
+/// window back, and minimizes to the taskbar otherwise.
+///
+/// # Returns
+/// `normal`, `tray` or `minimized`
+#[tauri::command]
+pub async fn get_start_mode(app: AppHandle) -> StartMode {
+    let start_minimized = config::manager::get_config()
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|cfg| cfg.start_minimized);
+    StartMode::new(
+        cli::args().minimized || start_minimized,
+        tray::is_available(&app),
+    )
+}
+
+/// Gets the page the launcher was started on with `--page`, if any
+#[tauri::command]
+pub fn get_start_page() -> Option<Page> {
+    cli::args().page
+}
+
+// ============================================================================
+// Discord RPC Commands
+// ============================================================================
+
+/// Enables or disables Discord Rich Presence.
+///
+/// # Arguments
+///
+/// * `rpc` - The RPC manager state
+/// * `enabled` - Whether to enable or disable RPC
+#[tauri::command]
+pub fn rpc_set_enabled(rpc: State<'_, RpcManager>, enabled: bool) {
+    rpc.set_enabled(enabled);
+}
+
+/// Sets both Discord Rich Presence anonymization options at once.
+///
+/// Applies them to the activity immediately, saves `rpcAnonymizeProfile` and
+/// `rpcAnonymizeLocation` in one config write, and emits `config-changed`
+/// with the updated keys so open settings views stay in sync.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle for emitting events
+/// * `rpc` - The RPC manager state
+/// * `profile` - Whether to hide the player's name and avatar
+/// * `location` - Whether to hide the current game and location
+///
+/// # Returns
+///
+/// Returns an error if the config could not be saved.
+#[tauri::command]
+pub async fn rpc_set_privacy(
+    app: AppHandle,
+    rpc: State<'_, RpcManager>,
+    profile: bool,
+    location: bool,
+) -> Result<(), String> {
+    let (shown_profile, shown_location) = streamer::rpc_privacy(profile, location);
+    rpc.set_anonymization(shown_profile, shown_location);
+
+    let changes = serde_json::json!({
+        "rpcAnonymizeProfile": profile,
+        "rpcAnonymizeLocation": location,
+    });
+    config::manager::set_config_keys(vec![
+        (
+            "rpcAnonymizeProfile",
+            changes["rpcAnonymizeProfile"].clone(),
+        ),
+        (
+            "rpcAnonymizeLocation",
+            changes["rpcAnonymizeLocation"].clone(),
+        ),
+    ])
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let _ = app.emit("config-changed", changes);
+    Ok(())
+}
+
+/// Turns streamer mode on or off and saves `streamerMode`.
+///
+/// While it's on, the Discord activity hides the profile and location,
+/// the player's IGN and secrets are hidden in events and logs sent to the
+/// frontend, and notifications showing usernames are skipped. The
+/// `rpcAnonymize*` settings are left alone and apply again once it's off.
+/// Emits `config-changed` so open settings views stay in sync.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle for emitting events
+/// * `rpc` - The RPC manager state
+/// * `enabled` - Whether to turn streamer mode on
+///
+/// # Returns
+///
+/// Returns an error if the config could not be saved.
+#[tauri::command]
+pub async fn set_streamer_mode(
+    app: AppHandle,
+    rpc: State<'_, RpcManager>,
+    enabled: bool,
+) -> Result<(), String> {
+    config::manager::set_config_key("streamerMode", serde_json::json!(enabled))
+        .await
+        .map_err(|e| e.to_string())?;
+    apply_streamer_mode(&rpc, enabled).await?;
+
+    let _ = app.emit(
+        "config-changed",
+        serde_json::json!({ "streamerMode": enabled }),
+    );
+    Ok(())
+}
+
+/// Switches streamer mode and reapplies the Discord privacy options
+async fn apply_streamer_mode(rpc: &RpcManager, enabled: bool) -> Result<(), String> {
+    streamer::set_enabled(enabled);
+    if enabled {
+        telemetry::record_feature("streamerMode");
+    }
+    let cfg = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    let (profile, location) =
+        streamer::rpc_privacy(cfg.rpc_anonymize_profile, cfg.rpc_anonymize_location);
+    rpc.set_anonymization(profile, location);
+    Ok(())
+}
+
+/// Returns a snapshot of the Discord Rich Presence state.
+///
+/// Includes the connection state, current player and location, and the
+/// details line and timer exactly as shown on Discord.
+///
+/// # Arguments
+///
+/// * `rpc` - The RPC manager state
+#[tauri::command]
+pub fn rpc_get_state(rpc: State<'_, RpcManager>) -> RpcStateSnapshot {
+    rpc.get_state()
+}
+
+/// Returns the game modes seen this session that have no display name.
+///
+/// Lets contributors submit names for them; most played first.
+#[tauri::command]
+pub fn get_unknown_modes() -> Vec<UnknownMode> {
+    names::unknown_modes()
+}
+
+/// Returns a summary of the launcher session for the recap screen.
+///
+/// Covers total session time, time in game versus in the launcher, time
+/// per game mode and the session's wins and losses.
+///
+/// # Arguments
+///
+/// * `rpc` - The RPC manager state
+#[tauri::command]
+pub fn get_session_summary(rpc: State<'_, RpcManager>) -> SessionSummary {
+    rpc.get_session_summary()
+}
+
+/// Returns whether Discord Rich Presence is enabled.
+///
+/// # Arguments
+///
+/// * `rpc` - The RPC manager state
+///
+/// # Returns
+///
+/// Returns `true` if RPC is enabled, `false` otherwise.
+#[tauri::command]
+pub fn rpc_is_enabled(rpc: State<'_, RpcManager>) -> bool {
+    rpc.is_enabled()
 }
 
 /// Sets the Discord Rich Presence activity.
@@ -388,23 +1799,75 @@ pub fn rpc_is_enabled(rpc: State<'_, RpcManager>) -> bool {
 /// # Arguments
 ///
 /// * `rpc` - The RPC manager state
-/// * `activity` - The activity type: "launcher", "launching", "playing", or "clear"
+/// * `activity` - The activity type: "launcher", "launching", "playing", "clear",
+///   "custom", or "clearCustom"
 /// * `ign` - Optional in-game name (for "playing" activity)
 /// * `uuid` - Optional player UUID (for "playing" activity)
+/// * `custom` - The activity to show (for "custom" activity); it overrides
+///   automatic updates until "clearCustom"
+///
+/// # Returns
+///
+/// Returns an error if a custom activity is missing or invalid.
 #[tauri::command]
 pub fn rpc_set_activity(
     rpc: State<'_, RpcManager>,
     activity: String,
     ign: Option<String>,
     uuid: Option<String>,
-) {
+    custom: Option<CustomActivity>,
+) -> Result<(), String> {
     match activity.as_str() {
         "launcher" => rpc.set_in_launcher(),
         "launching" => rpc.set_launching(),
         "playing" => rpc.set_playing(ign, uuid),
         "clear" => rpc.clear_activity(),
+        "custom" => {
+            let custom = custom.ok_or_else(|| t!("errors.missingCustomActivity"))?;
+            rpc.set_custom_activity(custom)?;
+        }
+        "clearCustom" => rpc.clear_custom_activity(),
         _ => {}
     }
+    Ok(())
+}
+
+/// Previews the Discord Rich Presence activity.
+///
+/// Shows a sample game on Discord for 30 seconds using the current privacy,
+/// image, button and language settings, then restores the real activity.
+///
+/// # Arguments
+///
+/// * `rpc` - The RPC manager state
+/// * `activity` - The sample game to show; defaults to a Classic Duel on Arena
+///
+/// # Returns
+///
+/// Returns an error if Rich Presence is disabled.
+#[tauri::command]
+pub fn rpc_preview(rpc: State<'_, RpcManager>, activity: Option<RpcPreview>) -> Result<(), String> {
+    rpc.preview(activity.unwrap_or_default())
+}
+
+/// Shows the signed-in Duels+ user on Discord while in the launcher.
+///
+/// Replaces the generic idle state when `rpcLauncherProfile` is enabled;
+/// the username is hidden when the profile is anonymized.
+///
+/// # Arguments
+///
+/// * `rpc` - The RPC manager state
+/// * `username` - The Duels+ username from `get_user`
+/// * `browsing_stats` - Whether the stats page is open ("Browsing stats"
+///   instead of "In Launcher")
+#[tauri::command]
+pub fn rpc_set_launcher_profile(
+    rpc: State<'_, RpcManager>,
+    username: String,
+    browsing_stats: bool,
+) {
+    rpc.set_in_launcher_with_profile(username, browsing_stats);
 }
 
 /// Sets the Discord Rich Presence image.
@@ -72,7 +72,8 @@ fn get_legacy_config_path() -> Result<PathBuf, ConfigError> {
 /// Gets the full path to the configuration file.
 ///
 /// Config file location:
-/// - All platforms: `~/.duelsplus/config.json`
+/// - The platform's config directory, e.g. `~/.config/duelsplus/config.json`
+///   on Linux (see [`utils::get_app_dir`])
 ///
 /// # Returns
 ///
@@ -90,8 +91,7 @@ fn get_config_path() -> Result<PathBuf, ConfigError> {
         }
     }
 
-    let app_root = utils::get_app_root().map_err(|e| ConfigError::Unknown(e))?;
-    Ok(app_root.join(CONFIG_FILE))
+    utils::get_app_path(utils::AppDir::Config, CONFIG_FILE).map_err(ConfigError::Unknown)
 }
 
 /// Checks if the legacy configuration file exists.
@@ -219,6 +219,22 @@ pub async fn get_config_value(key: &str) -> Result<Option<serde_json::Value>, Co
 /// - `Ok(())` if the key was successfully set
 /// - `Err(ConfigError)` if there was an error reading, updating, or writing the config file
 pub async fn set_config_key(key: &str, value: serde_json::Value) -> Result<(), ConfigError> {
+    set_config_keys(vec![(key, value)]).await
+}
+
+/// Sets several keys in the configuration file in a single write.
+///
+/// Either all keys are updated or, if any value is invalid, none are.
+///
+/// # Arguments
+///
+/// * `updates` - The configuration keys to set and their values
+///
+/// # Returns
+///
+/// - `Ok(())` if the keys were successfully set
+/// - `Err(ConfigError)` if there was an error reading, updating, or writing the config file
+pub async fn set_config_keys(updates: Vec<(&str, serde_json::Value)>) -> Result<(), ConfigError> {
     let config_path = get_config_path()?;
     let config_dir = config_path
         .parent()
@@ -230,9 +246,11 @@ pub async fn set_config_key(key: &str, value: serde_json::Value) -> Result<(), C
     // Read existing config or use default
     let mut config = get_config().await?.unwrap_or_default();
 
-    // Update the config with the new value
+    // Update the config with the new values
     let mut json_value = serde_json::to_value(&config)?;
-    json_value[key] = value;
+    for (key, value) in updates {
+        json_value[key] = value;
+    }
 
     // Deserialize back to Config to validate
     config = serde_json::from_value(json_value)?;
@@ -393,6 +411,37 @@ mod tests {
         assert!(config.minimize_to_tray);
     }
 
+    #[tokio::test]
+    async fn test_set_config_keys() {
+        let _ctx = TestContext::new();
+
+        set_config_keys(vec![
+            ("rpcAnonymizeProfile", serde_json::json!(true)),
+            ("rpcAnonymizeLocation", serde_json::json!(true)),
+        ])
+        .await
+        .unwrap();
+
+        let config = get_config().await.unwrap().unwrap();
+        assert!(config.rpc_anonymize_profile);
+        assert!(config.rpc_anonymize_location);
+    }
+
+    #[tokio::test]
+    async fn test_set_config_keys_invalid_value_writes_nothing() {
+        let _ctx = TestContext::new();
+
+        let result = set_config_keys(vec![
+            ("minimizeToTray", serde_json::json!(true)),
+            ("autoUpdate", serde_json::json!("not a bool")),
+        ])
+        .await;
+        assert!(result.is_err());
+
+        // The valid key was not written either
+        assert!(get_config().await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_get_legacy_config_value() {
         let _ctx = TestContext::new();
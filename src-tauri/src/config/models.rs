@@ -13,10 +13,45 @@ pub struct Config {
     #[serde(default)]
     pub minimize_to_tray: bool,
 
+    /// Whether to start the launcher on login
+    #[serde(default)]
+    pub autostart: bool,
+
+    /// Whether to start hidden in the tray when started on login
+    #[serde(default)]
+    pub autostart_minimized: bool,
+
+    /// Whether to always start hidden in the tray, or minimized without one
+    #[serde(default)]
+    pub start_minimized: bool,
+
+    /// Whether to run in the background with no window until it's opened
+    /// from the tray or a link
+    #[serde(default)]
+    pub tray_only: bool,
+
+    /// Whether to launch the proxy when the launcher starts
+    #[serde(default)]
+    pub launch_proxy_on_start: bool,
+
     /// Whether to automatically check for and install updates
     #[serde(default = "default_true")]
     pub auto_update: bool,
 
+    /// Release channel for launcher updates
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+
+    /// Unix time until which automatic update checks are skipped ("remind
+    /// me later")
+    #[serde(default)]
+    pub update_deferred_until: Option<u64>,
+
+    /// Whether to install downloaded launcher updates when the launcher
+    /// quits instead of right away
+    #[serde(default)]
+    pub install_updates_on_quit: bool,
+
     /// Whether to open logs when the application launches
     #[serde(default = "default_true")]
     pub open_logs_on_launch: bool,
@@ -37,10 +72,82 @@ pub struct Config {
     #[serde(default)]
     pub rpc_anonymize_location: bool,
 
+    /// Hides the player's name and other personal details everywhere at
+    /// once, for streaming; see [`crate::streamer`]
+    #[serde(default)]
+    pub streamer_mode: bool,
+
     /// Custom Discord Rich Presence image asset key
     #[serde(default = "default_rpc_image")]
     pub rpc_image: String,
 
+    /// Whether to show the stats and website buttons on the Discord activity
+    #[serde(default = "default_true")]
+    pub rpc_buttons: bool,
+
+    /// Whether to show the session win streak / record on the Discord activity while playing Duels
+    #[serde(default = "default_true")]
+    pub rpc_show_streak: bool,
+
+    /// Whether to show the Duels+ username on the Discord activity while in the launcher
+    #[serde(default)]
+    pub rpc_launcher_profile: bool,
+
+    /// Discord application ID to show the activity under; empty uses the Duels+ app
+    #[serde(default)]
+    pub rpc_client_id: String,
+
+    /// Which Discord client to show the activity on when several are running
+    #[serde(default)]
+    pub rpc_discord_client: DiscordClient,
+
+    /// Minutes idle in a lobby before the Discord activity shows AFK (0 = never)
+    #[serde(default = "default_rpc_afk_timeout_mins")]
+    pub rpc_afk_timeout_mins: u64,
+
+    /// What the Discord activity's elapsed timer counts
+    #[serde(default)]
+    pub rpc_timer_mode: RpcTimerMode,
+
+    /// Whether to send game modes without a Discord activity name to the API
+    #[serde(default)]
+    pub report_unknown_modes: bool,
+
+    /// Whether to send anonymous usage statistics (see `get_telemetry_preview`)
+    #[serde(default)]
+    pub telemetry: bool,
+
+    /// Whether to sample the ping to Hypixel for the latency indicator
+    #[serde(default)]
+    pub ping_indicator: bool,
+
+    /// Whether to serve live stats on localhost for OBS browser sources,
+    /// see [`crate::obs`]
+    #[serde(default)]
+    pub obs_server: bool,
+
+    /// Port the OBS server listens on
+    #[serde(default = "default_obs_port")]
+    pub obs_port: u16,
+
+    /// Hash of the PIN guarding the token and account, if set; only
+    /// changed through `set_app_pin`, see [`crate::applock`]
+    #[serde(default)]
+    pub app_pin: Option<String>,
+
+    /// Hypixel API key for player lookups, sealed with [`crate::secrets`];
+    /// only changed through `set_hypixel_api_key`
+    #[serde(default)]
+    pub hypixel_api_key: Option<String>,
+
+    /// Minutes without a PIN-guarded action before the launcher locks again
+    #[serde(default = "default_app_lock_timeout_mins")]
+    pub app_lock_timeout_mins: u64,
+
+    /// Language of the Discord activity text (e.g. "de" or "pt-BR"), falling back to English
+    #[serde(default = "default_locale")]
+    pub locale: String,
+
     /// Port number for the proxy server (as string)
     #[serde(default = "default_proxy_port")]
     pub proxy_port: String,
@@ -52,34 +159,232 @@ pub struct Config {
     /// Whether to receive beta releases instead of stable releases
     #[serde(default)]
     pub receive_beta_releases: bool,
+
+    /// Ids of installed proxy addons the proxy shouldn't load
+    #[serde(default)]
+    pub disabled_addons: Vec<String>,
+
+    /// Whether to leave the proxy running when the launcher is closed
+    #[serde(default)]
+    pub keep_proxy_on_exit: bool,
+
+    /// Seconds to wait at each proxy shutdown step before escalating to a force kill,
+    /// at most 6 so quitting the launcher always gets to the force kill
+    #[serde(default = "default_proxy_shutdown_timeout_secs")]
+    pub proxy_shutdown_timeout_secs: u64,
+
+    /// Whether to start the Minecraft launcher once the proxy is running
+    #[serde(default)]
+    pub launch_minecraft: bool,
+
+    /// Program or command line started when `launch_minecraft` is enabled
+    #[serde(default)]
+    pub post_launch_command: String,
+
+    /// Whether to show the proxy console tab for sending stdin commands
+    #[serde(default)]
+    pub enable_proxy_console: bool,
+
+    /// Scheduling priority of the proxy process
+    #[serde(default)]
+    pub proxy_priority: ProxyPriority,
+
+    /// Global shortcuts for launcher actions
+    #[serde(default)]
+    pub keybinds: Keybinds,
+
+    /// Whether to notify when the proxy crashes
+    #[serde(default = "default_true")]
+    pub notify_proxy_crash: bool,
+
+    /// Whether to notify when a proxy update is available
+    #[serde(default = "default_true")]
+    pub notify_updates: bool,
+
+    /// Whether to notify when a proxy download finishes
+    #[serde(default = "default_true")]
+    pub notify_downloads: bool,
+
+    /// Whether to notify when a Hypixel friend comes online
+    #[serde(default)]
+    pub notify_friends: bool,
+
+    /// Whether to notify at win streak milestones
+    #[serde(default = "default_true")]
+    pub notify_win_streaks: bool,
+
+    /// Whether to notify when the player moves up a leaderboard
+    #[serde(default = "default_true")]
+    pub notify_leaderboard: bool,
+
+    /// Whether to send a recap of last week's matches on Mondays
+    #[serde(default)]
+    pub notify_weekly_digest: bool,
+
+    /// Where the overlay window was last moved to; `None` puts it in the
+    /// top right corner of the main monitor
+    #[serde(default)]
+    pub overlay_position: Option<WindowPosition>,
+}
+
+/// Global shortcuts, e.g. `Ctrl+Shift+D`; empty leaves an action unbound
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Keybinds {
+    /// Shows or hides the launcher window
+    #[serde(default)]
+    pub show_hide_window: String,
+
+    /// Launches the proxy, or stops it if running
+    #[serde(default)]
+    pub launch_stop_proxy: String,
+
+    /// Hides or shows the profile and location on the Discord activity
+    #[serde(default)]
+    pub toggle_rpc_privacy: String,
+
+    /// Shows or hides the in-game overlay
+    #[serde(default)]
+    pub toggle_overlay: String,
+}
+
+/// Position of a window's top left corner, in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Release channel for launcher updates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+/// Scheduling priority for the proxy process
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyPriority {
+    /// Leaves more CPU time for the game on weak machines
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// What the Discord Rich Presence elapsed timer counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RpcTimerMode {
+    /// Time since the launcher started
+    #[default]
+    Session,
+    /// Time in the current game, falling back to session time in lobbies
+    PerGame,
+}
+
+/// Discord client the Rich Presence connects to when several are running
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscordClient {
+    /// Whichever client opened the first IPC pipe
+    #[default]
+    Auto,
+    Stable,
+    Ptb,
+    Canary,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_locale() -> String {
+    "en".to_string()
+}
+
 fn default_proxy_port() -> String {
     "25565".to_string()
 }
 
+fn default_obs_port() -> u16 {
+    24050
+}
+
 fn default_rpc_image() -> String {
     "logo-v1".to_string()
 }
 
+fn default_rpc_afk_timeout_mins() -> u64 {
+    10
+}
+
+fn default_app_lock_timeout_mins() -> u64 {
+    5
+}
+
+fn default_proxy_shutdown_timeout_secs() -> u64 {
+    5
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             minimize_to_tray: false,
+            autostart: false,
+            autostart_minimized: false,
+            start_minimized: false,
+            tray_only: false,
+            launch_proxy_on_start: false,
             auto_update: true,
+            update_channel: UpdateChannel::Stable,
+            update_deferred_until: None,
+            install_updates_on_quit: false,
             open_logs_on_launch: true,
             reduced_motion: false,
             enable_rpc: true,
             rpc_anonymize_profile: false,
             rpc_anonymize_location: false,
+            streamer_mode: false,
             rpc_image: "logo-v1".to_string(),
+            rpc_buttons: true,
+            rpc_show_streak: true,
+            rpc_launcher_profile: false,
+            rpc_client_id: String::new(),
+            rpc_discord_client: DiscordClient::Auto,
+            rpc_afk_timeout_mins: 10,
+            rpc_timer_mode: RpcTimerMode::Session,
+            report_unknown_modes: false,
+            telemetry: false,
+            ping_indicator: false,
+            obs_server: false,
+            obs_port: 24050,
+            app_pin: None,
+            hypixel_api_key: None,
+            app_lock_timeout_mins: 5,
+            locale: "en".to_string(),
             proxy_port: "25565".to_string(),
             enable_msa: false,
             receive_beta_releases: false,
+            disabled_addons: Vec::new(),
+            keep_proxy_on_exit: false,
+            proxy_shutdown_timeout_secs: 5,
+            launch_minecraft: false,
+            post_launch_command: String::new(),
+            enable_proxy_console: false,
+            proxy_priority: ProxyPriority::Normal,
+            keybinds: Keybinds::default(),
+            notify_proxy_crash: true,
+            notify_updates: true,
+            notify_downloads: true,
+            notify_friends: false,
+            notify_win_streaks: true,
+            notify_leaderboard: true,
+            notify_weekly_digest: false,
+            overlay_position: None,
         }
     }
 }
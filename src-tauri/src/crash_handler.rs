@@ -0,0 +1,291 @@
+//! Crash reports for the launcher itself.
+//!
+//! A panic hook writes the message, location and backtrace to
+//! `crashes/launcher-crash-<time>.txt` in the data directory. A panic on the main
+//! thread takes the launcher down, so those also show a native error dialog
+//! offering to copy the details, and leave a marker so the next start can
+//! offer to open the crash folder. Panics in background tasks only kill the
+//! task and are just recorded.
+
+use crate::proxy::crash::get_crash_dir;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Marker naming the report of the last fatal crash, removed once the user
+/// has been told about it
+const MARKER_FILE: &str = "last-launcher-crash";
+
+/// Set while a fatal crash is being reported, so a panic in the dialog
+/// code doesn't report again
+static REPORTING: AtomicBool = AtomicBool::new(false);
+
+/// A fatal crash from the previous run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherCrash {
+    /// Path of the crash report
+    pub path: String,
+    /// Panic message
+    pub message: String,
+}
+
+/// Installs the panic hook.
+///
+/// # Arguments
+///
+/// * `version` - Launcher version written to reports
+pub(crate) fn install(version: String) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+
+        let message = panic_message(info.payload());
+        let thread = std::thread::current();
+        let thread_name = thread.name().unwrap_or("unnamed");
+        let report = format_report(
+            &version,
+            thread_name,
+            info.location().map(|l| l.to_string()).as_deref(),
+            &message,
+            &std::backtrace::Backtrace::force_capture().to_string(),
+        );
+        let path = match write_report(&report) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                eprintln!("[launcher] Failed to write crash report: {}", e);
+                None
+            }
+        };
+
+        if thread_name != "main" || REPORTING.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        if let Some(path) = &path {
+            let _ = write_marker(path);
+        }
+        show_dialog(&message, path.as_deref(), &report);
+    }));
+}
+
+/// Extracts the message a panic was raised with
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+/// Builds the text of a crash report
+fn format_report(
+    version: &str,
+    thread: &str,
+    location: Option<&str>,
+    message: &str,
+    backtrace: &str,
+) -> String {
+    format!(
+        "Duels+ Launcher {} crashed\n\
+         Time: {}\n\
+         OS: {} ({})\n\
+         Thread: {}\n\
+         Location: {}\n\
+         \n\
+         {}\n\
+         \n\
+         Backtrace:\n\
+         {}\n",
+        version,
+        chrono::Utc::now().to_rfc3339(),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread,
+        location.unwrap_or("unknown"),
+        message,
+        backtrace
+    )
+}
+
+/// Writes a crash report and returns its path
+fn write_report(report: &str) -> Result<PathBuf, String> {
+    let dir = get_crash_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!(
+        "launcher-crash-{}.txt",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, report).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn write_marker(report: &Path) -> Result<(), String> {
+    let dir = get_crash_dir().map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(MARKER_FILE), report.to_string_lossy().as_bytes())
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the fatal crash from the previous run, if the user hasn't been told
+/// about it yet, and clears it
+pub(crate) fn take_last_crash() -> Option<LauncherCrash> {
+    let marker = get_crash_dir().ok()?.join(MARKER_FILE);
+    let path = std::fs::read_to_string(&marker).ok()?;
+    let _ = std::fs::remove_file(&marker);
+
+    let report = std::fs::read_to_string(path.trim()).ok()?;
+    Some(LauncherCrash {
+        path: path.trim().to_string(),
+        message: report_message(&report).to_string(),
+    })
+}
+
+/// Reads the panic message back out of a report
+fn report_message(report: &str) -> &str {
+    report
+        .split("\n\n")
+        .nth(1)
+        .map(str::trim)
+        .unwrap_or_default()
+}
+
+/// Text of the crash dialog
+fn dialog_text(message: &str, path: Option<&Path>) -> String {
+    let saved = match path {
+        Some(path) => format!("Details were saved to {}.", path.display()),
+        None => "The details could not be saved.".to_string(),
+    };
+    format!(
+        "The launcher ran into a problem and has to close.\n\n{}\n\n{}\n\nCopy the details to share them in a support ticket?",
+        message, saved
+    )
+}
+
+/// Pipes text into a clipboard tool
+fn pipe_to(mut command: std::process::Command, text: &str) -> bool {
+    use std::io::Write;
+    let Ok(mut child) = command
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok_and(|s| s.success())
+}
+
+/// Shows the crash dialog, copying the report if asked to
+#[cfg(windows)]
+fn show_dialog(message: &str, path: Option<&Path>, report: &str) {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{MessageBoxW, IDYES, MB_ICONERROR, MB_YESNO};
+
+    let wide = |s: &str| s.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    let text = wide(&dialog_text(message, path));
+    let title = wide("Duels+ Launcher");
+    // SAFETY: both strings are NUL-terminated and outlive the call
+    let choice = unsafe {
+        MessageBoxW(
+            std::ptr::null_mut(),
+            text.as_ptr(),
+            title.as_ptr(),
+            MB_YESNO | MB_ICONERROR,
+        )
+    };
+    if choice == IDYES {
+        pipe_to(crate::utils::hidden_command("clip"), report);
+    }
+}
+
+/// Shows the crash dialog, copying the report if asked to
+#[cfg(target_os = "macos")]
+fn show_dialog(message: &str, path: Option<&Path>, report: &str) {
+    let text = dialog_text(message, path)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    let script = format!(
+        "display dialog \"{}\" with title \"Duels+ Launcher\" buttons {{\"Close\", \"Copy Details\"}} default button \"Copy Details\" with icon stop",
+        text
+    );
+    let copy = std::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains("Copy Details"));
+    if copy {
+        pipe_to(std::process::Command::new("pbcopy"), report);
+    }
+}
+
+/// Shows the crash dialog, copying the report if asked to
+#[cfg(not(any(windows, target_os = "macos")))]
+fn show_dialog(message: &str, path: Option<&Path>, report: &str) {
+    use std::process::Command;
+
+    let text = dialog_text(message, path);
+    // zenity on GNOME and most others, kdialog on KDE; exit code 0 is "copy"
+    let copy = Command::new("zenity")
+        .args([
+            "--question",
+            "--icon-name=dialog-error",
+            "--title=Duels+ Launcher",
+            "--ok-label=Copy Details",
+            "--cancel-label=Close",
+            "--no-markup",
+        ])
+        .arg(format!("--text={}", text))
+        .status()
+        .or_else(|_| {
+            Command::new("kdialog")
+                .args(["--title", "Duels+ Launcher", "--yesno"])
+                .arg(&text)
+                .status()
+        })
+        .is_ok_and(|s| s.success());
+    if !copy {
+        return;
+    }
+
+    let copied =
+        std::env::var_os("WAYLAND_DISPLAY").is_some() && pipe_to(Command::new("wl-copy"), report);
+    if !copied {
+        let mut xclip = Command::new("xclip");
+        xclip.args(["-selection", "clipboard"]);
+        pipe_to(xclip, report);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_message_round_trip() {
+        let report = format_report(
+            "1.0.0",
+            "main",
+            Some("src/lib.rs:1:1"),
+            "called `Option::unwrap()` on a `None` value",
+            "0: main",
+        );
+        assert!(report.starts_with("Duels+ Launcher 1.0.0 crashed\n"));
+        assert!(report.contains("Location: src/lib.rs:1:1\n"));
+        assert_eq!(
+            report_message(&report),
+            "called `Option::unwrap()` on a `None` value"
+        );
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new(String::from("boom"));
+        assert_eq!(panic_message(payload.as_ref()), "boom");
+        let payload: Box<dyn std::any::Any + Send> = Box::new("static boom");
+        assert_eq!(panic_message(payload.as_ref()), "static boom");
+        let payload: Box<dyn std::any::Any + Send> = Box::new(42);
+        assert_eq!(panic_message(payload.as_ref()), "Unknown panic");
+    }
+}
@@ -0,0 +1,521 @@
+//! `duelsplus://` link handling.
+//!
+//! Supported links:
+//! - `duelsplus://launch?port=25566` asks to launch the proxy (on the
+//!   configured port if none is given); the frontend confirms it first, as
+//!   any page can open a link
+//! - `duelsplus://auth?type=token&token=...&state=...` completes a pending
+//!   Discord sign-in, like the localhost callback does, if `state` is the
+//!   one the sign-in started with
+//! - `duelsplus://profile/<id>` looks a player up in the launcher
+//! - `duelsplus://open/<page>` opens a launcher page, e.g. `open/stats`
+//!
+//! Windows and Linux start a new launcher process with the link as an
+//! argument. If a launcher is already running, the new process hands the
+//! link to it over a loopback socket and quits, so links always reach the
+//! open window. The running launcher listens on a random port and writes it
+//! with a secret to `forward.json` in the data directory, readable only by
+//! the user; links are only taken from connections that send the secret
+//! first, and the new process only quits once the secret is acknowledged.
+//! macOS delivers links to the running app directly.
+//!
+//! Every handled link is also emitted to the frontend as `deep-link`.
+
+use crate::autostart::launcher_path;
+use crate::proxy::ProxyManager;
+use crate::utils::{self, AppDir};
+use crate::{auth, commands, tray};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::str::FromStr;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+/// URI scheme registered for the launcher
+pub(crate) const SCHEME: &str = "duelsplus";
+
+/// Name of the file in the data directory saying where the running
+/// launcher accepts forwarded links
+const FORWARD_FILE: &str = "forward.json";
+
+/// Bytes of the secret forwarded links have to come with
+const FORWARD_SECRET_LEN: usize = 32;
+
+/// Reply confirming the secret, and each forwarded link after it, reached
+/// a launcher
+const FORWARD_ACK: &str = "ok";
+
+/// How long to wait for a running launcher to take a forwarded link
+const FORWARD_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A launcher page, named like the frontend's tabs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Page {
+    Home,
+    Logs,
+    Console,
+    Stats,
+    History,
+    Lookup,
+    Settings,
+}
+
+impl fmt::Display for Page {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Page::Home => "home",
+            Page::Logs => "logs",
+            Page::Console => "console",
+            Page::Stats => "stats",
+            Page::History => "history",
+            Page::Lookup => "lookup",
+            Page::Settings => "settings",
+        })
+    }
+}
+
+impl FromStr for Page {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "home" => Ok(Page::Home),
+            "logs" => Ok(Page::Logs),
+            "console" => Ok(Page::Console),
+            "stats" => Ok(Page::Stats),
+            "history" => Ok(Page::History),
+            "lookup" => Ok(Page::Lookup),
+            "settings" => Ok(Page::Settings),
+            _ => Err(format!("Unknown page \"{}\"", s)),
+        }
+    }
+}
+
+/// A parsed `duelsplus://` link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum DeepLink {
+    Launch {
+        port: Option<u16>,
+    },
+    Auth {
+        /// `None` if the sign-in failed or was cancelled
+        #[serde(skip)]
+        token: Option<String>,
+        /// Has to match the pending sign-in's
+        #[serde(skip)]
+        state: Option<String>,
+    },
+    Profile {
+        id: String,
+    },
+    Open {
+        page: Page,
+    },
+}
+
+/// Parses a `duelsplus://` link, returning `None` for other schemes and
+/// unknown actions
+pub(crate) fn parse(link: &str) -> Option<DeepLink> {
+    let url = Url::parse(link).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    let query = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    match url.host_str()? {
+        "launch" => {
+            let port = match query("port") {
+                Some(port) => Some(port.parse::<u16>().ok().filter(|p| *p != 0)?),
+                None => None,
+            };
+            Some(DeepLink::Launch { port })
+        }
+        "auth" => {
+            let token = query("token").filter(|t| !t.is_empty());
+            let success = query("type").as_deref() == Some("token");
+            Some(DeepLink::Auth {
+                token: token.filter(|_| success),
+                state: query("state"),
+            })
+        }
+        "profile" => {
+            let id = url.path().trim_matches('/');
+            if id.is_empty() || id.contains('/') {
+                return None;
+            }
+            Some(DeepLink::Profile { id: id.to_string() })
+        }
+        "open" => {
+            let page = url.path().trim_matches('/').parse().ok()?;
+            Some(DeepLink::Open { page })
+        }
+        _ => None,
+    }
+}
+
+/// Gets the `duelsplus://` links this process was started with
+pub(crate) fn from_args() -> Vec<String> {
+    std::env::args()
+        .skip(1)
+        .filter(|arg| arg.starts_with(&format!("{}:", SCHEME)))
+        .collect()
+}
+
+/// Where the running launcher takes forwarded links, kept in
+/// [`FORWARD_FILE`]
+#[derive(Debug, Serialize, Deserialize)]
+struct ForwardTarget {
+    port: u16,
+    secret: String,
+}
+
+/// Hands links to an already running launcher.
+///
+/// # Returns
+/// `true` if a launcher took them, i.e. this process can quit
+pub(crate) fn forward(links: &[String]) -> bool {
+    let Some(target) = utils::get_app_dir(AppDir::Data)
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(FORWARD_FILE)).ok())
+        .and_then(|content| serde_json::from_str::<ForwardTarget>(&content).ok())
+    else {
+        return false;
+    };
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, target.port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, FORWARD_TIMEOUT) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(FORWARD_TIMEOUT));
+
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(s) => s,
+        Err(_) => return false,
+    });
+    // `forward.json` may be left over from a launcher that's gone, with
+    // something else listening on the port by now: only a launcher that
+    // knows the secret answers it
+    let mut acked = |stream: &mut TcpStream, line: &str| {
+        let mut reply = String::new();
+        writeln!(stream, "{}", line).is_ok()
+            && reader.read_line(&mut reply).is_ok()
+            && reply.trim() == FORWARD_ACK
+    };
+    acked(&mut stream, &target.secret) && links.iter().all(|link| acked(&mut stream, link))
+}
+
+/// Accepts links forwarded by launchers started after this one
+pub(crate) fn listen(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("[deeplink] Failed to listen for forwarded links: {}", e);
+                return;
+            }
+        };
+        let target = match publish_target(&listener) {
+            Ok(target) => target,
+            Err(e) => {
+                eprintln!("[deeplink] Failed to accept forwarded links: {}", e);
+                return;
+            }
+        };
+
+        while let Ok((stream, _)) = listener.accept().await {
+            let app = app.clone();
+            let secret = target.secret.clone();
+            tauri::async_runtime::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = tokio::io::BufReader::new(read).lines();
+                // Anything else on this computer can connect too
+                let authorized = tokio::time::timeout(FORWARD_TIMEOUT, lines.next_line())
+                    .await
+                    .ok()
+                    .and_then(|line| line.ok().flatten())
+                    .is_some_and(|line| {
+                        utils::constant_time_eq(line.trim().as_bytes(), secret.as_bytes())
+                    });
+                if !authorized {
+                    eprintln!("[deeplink] Ignoring a forwarded link without the secret");
+                    return;
+                }
+                let ack = format!("{}\n", FORWARD_ACK);
+                // Even without links, the new process only quits once this
+                // launcher answered
+                if write.write_all(ack.as_bytes()).await.is_err() {
+                    return;
+                }
+                while let Ok(Some(link)) = lines.next_line().await {
+                    let _ = write.write_all(ack.as_bytes()).await;
+                    handle(&app, link.trim());
+                }
+            });
+        }
+    });
+}
+
+/// Writes where `listener` takes forwarded links, with a new secret
+fn publish_target(listener: &tokio::net::TcpListener) -> Result<ForwardTarget, String> {
+    let target = ForwardTarget {
+        port: listener.local_addr().map_err(|e| e.to_string())?.port(),
+        secret: utils::random_token(FORWARD_SECRET_LEN)?,
+    };
+    let content = serde_json::to_string(&target).map_err(|e| e.to_string())?;
+    utils::write_private(
+        &utils::get_app_dir(AppDir::Data)?.join(FORWARD_FILE),
+        content,
+    )?;
+    Ok(target)
+}
+
+/// Acts on a link and tells the frontend about it
+pub(crate) fn handle(app: &AppHandle, link: &str) {
+    let Some(link) = parse(link) else {
+        eprintln!("[deeplink] Ignoring unsupported link: {}", link);
+        return;
+    };
+    tray::show_window(app);
+
+    match &link {
+        DeepLink::Auth { token, state } => {
+            if !auth::discord::take_pending_signin(state.as_deref()) {
+                eprintln!("[deeplink] Ignoring sign-in link, it's not for the sign-in in progress");
+                return;
+            }
+            let _ = app.emit(
+                "discord-auth-result",
+                auth::discord::DiscordAuthResult {
+                    success: token.is_some(),
+                    token: token.clone(),
+                    error: token
+                        .is_none()
+                        .then(|| "Authentication failed or was cancelled".to_string()),
+                },
+            );
+        }
+        // Launching waits for the frontend to confirm it
+        DeepLink::Launch { .. } | DeepLink::Profile { .. } | DeepLink::Open { .. } => {}
+    }
+
+    let _ = app.emit("deep-link", &link);
+}
+
+/// Launches the proxy unless it's already running
+pub(crate) fn launch_proxy(app: &AppHandle, port: Option<u16>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(manager) = app.try_state::<ProxyManager>() else {
+            return;
+        };
+        if manager.is_running().await {
+            return;
+        }
+        // Failures are already reported to the logs by the command
+        let _ = commands::launch_proxy(app.clone(), manager, port).await;
+    });
+}
+
+/// Registers the launcher as the `duelsplus://` handler for the current
+/// user, unless it already is or another app is
+#[cfg(windows)]
+pub(crate) fn register() -> Result<(), String> {
+    use crate::utils::hidden_command;
+
+    let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", launcher_path()?.display());
+    let current = hidden_command("reg")
+        .args(["query", &format!(r"{}\shell\open\command", key), "/ve"])
+        .stdout(std::process::Stdio::piped())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+    if let Some(current) = current {
+        if !current.contains(&command) {
+            println!(
+                "[deeplink] Another app handles {}:// links, leaving it",
+                SCHEME
+            );
+        }
+        return Ok(());
+    }
+    let entries: [(String, &[&str], String); 3] = [
+        (key.clone(), &["/ve"], "URL:Duels+ Launcher".to_string()),
+        (key.clone(), &["/v", "URL Protocol"], String::new()),
+        (format!(r"{}\shell\open\command", key), &["/ve"], command),
+    ];
+    for (key, value, data) in entries {
+        let status = hidden_command("reg")
+            .args(["add", &key])
+            .args(value)
+            .args(["/t", "REG_SZ", "/d", &data, "/f"])
+            .status()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        if !status.success() {
+            return Err(format!("Failed to register the {}:// scheme", SCHEME));
+        }
+    }
+    Ok(())
+}
+
+/// Registers the launcher as the `duelsplus://` handler for the current user
+#[cfg(target_os = "macos")]
+pub(crate) fn register() -> Result<(), String> {
+    // Declared in Info.plist and registered by Launch Services
+    Ok(())
+}
+
+/// Registers the launcher as the `duelsplus://` handler for the current
+/// user, unless it already is or another app is
+#[cfg(not(any(windows, target_os = "macos")))]
+pub(crate) fn register() -> Result<(), String> {
+    const ENTRY_NAME: &str = "duelsplus-launcher-url.desktop";
+
+    let current = std::process::Command::new("xdg-mime")
+        .args(["query", "default"])
+        .arg(format!("x-scheme-handler/{}", SCHEME))
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    if !current.is_empty() && current != ENTRY_NAME {
+        println!(
+            "[deeplink] {} handles {}:// links, leaving it",
+            current, SCHEME
+        );
+        return Ok(());
+    }
+
+    let home = crate::utils::get_home_dir()?;
+    let applications = std::env::var_os("XDG_DATA_HOME")
+        .map(std::path::PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| home.join(".local").join("share"))
+        .join("applications");
+    std::fs::create_dir_all(&applications).map_err(|e| e.to_string())?;
+
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Duels+ Launcher\n\
+         Exec={} %u\n\
+         Terminal=false\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/{};\n",
+        crate::autostart::desktop_exec(&launcher_path()?),
+        SCHEME
+    );
+    let path = applications.join(ENTRY_NAME);
+    if current == ENTRY_NAME && std::fs::read_to_string(&path).is_ok_and(|c| c == entry) {
+        return Ok(());
+    }
+    std::fs::write(&path, entry)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let status = std::process::Command::new("xdg-mime")
+        .args(["default", ENTRY_NAME])
+        .arg(format!("x-scheme-handler/{}", SCHEME))
+        .status()
+        .map_err(|e| format!("Failed to run xdg-mime: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Failed to register the {}:// scheme", SCHEME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launch() {
+        assert_eq!(
+            parse("duelsplus://launch?port=25566"),
+            Some(DeepLink::Launch { port: Some(25566) })
+        );
+        assert_eq!(
+            parse("duelsplus://launch"),
+            Some(DeepLink::Launch { port: None })
+        );
+        assert_eq!(parse("duelsplus://launch?port=abc"), None);
+        assert_eq!(parse("duelsplus://launch?port=0"), None);
+    }
+
+    #[test]
+    fn test_parse_auth() {
+        assert_eq!(
+            parse("duelsplus://auth?type=token&token=abc%20123"),
+            Some(DeepLink::Auth {
+                token: Some("abc 123".to_string()),
+                state: None,
+            })
+        );
+        assert_eq!(
+            parse("duelsplus://auth?type=error"),
+            Some(DeepLink::Auth {
+                token: None,
+                state: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_auth_state() {
+        assert_eq!(
+            parse("duelsplus://auth?type=token&token=abc&state=f00d"),
+            Some(DeepLink::Auth {
+                token: Some("abc".to_string()),
+                state: Some("f00d".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(
+            parse("duelsplus://profile/abc123"),
+            Some(DeepLink::Profile {
+                id: "abc123".to_string()
+            })
+        );
+        assert_eq!(parse("duelsplus://profile/"), None);
+        assert_eq!(parse("duelsplus://profile/a/b"), None);
+    }
+
+    #[test]
+    fn test_parse_open() {
+        assert_eq!(
+            parse("duelsplus://open/stats"),
+            Some(DeepLink::Open { page: Page::Stats })
+        );
+        assert_eq!(parse("duelsplus://open/"), None);
+        assert_eq!(parse("duelsplus://open/store"), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_links() {
+        assert_eq!(parse("https://launch?port=25566"), None);
+        assert_eq!(parse("duelsplus://uninstall"), None);
+        assert_eq!(parse("not a url"), None);
+    }
+
+    #[test]
+    fn test_auth_token_is_not_emitted() {
+        let link = DeepLink::Auth {
+            token: Some("secret".to_string()),
+            state: Some("state".to_string()),
+        };
+        let json = serde_json::to_value(&link).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "auth" }));
+    }
+}
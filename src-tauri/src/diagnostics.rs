@@ -0,0 +1,463 @@
+//! Diagnostic bundles for support tickets.
+//!
+//! `export` zips up what support usually asks for: the launcher's log
+//! output, the proxy's recent output, the config, the installed proxy,
+//! OS details and the latest crash reports. Everything goes through
+//! `Redactor` first, so the auth token, webhook URLs and the user's home
+//! directory never end up in the file.
+//!
+//! `upload_logs` sends just the launcher and proxy logs, redacted the same
+//! way, to the API, which answers with a short link to paste in support
+//! channels.
+
+use crate::auth::API_BASE_URL;
+use crate::config::models::Config;
+use crate::proxy::{crash, download};
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+/// Launcher log lines kept for the bundle
+const LOG_LINES: usize = 1000;
+
+/// Number of crash reports included, newest first
+const RECENT_CRASHES: usize = 5;
+
+/// Replacement for redacted secrets
+const REDACTED: &str = "[redacted]";
+
+/// Config keys whose values are always redacted
+const SECRET_KEYS: [&str; 6] = ["token", "webhook", "secret", "password", "apikey", "pin"];
+
+/// Markers followed by a secret, redacted up to the next whitespace or quote
+const SECRET_MARKERS: [&str; 2] = ["/api/webhooks/", "Bearer "];
+
+/// Recent launcher log output, oldest first
+static LAUNCHER_LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Keeps a log line shown in the launcher for the next bundle
+pub(crate) fn record_log(line: &str) {
+    let mut log = LAUNCHER_LOG.lock().unwrap_or_else(|e| e.into_inner());
+    if log.len() == LOG_LINES {
+        log.pop_front();
+    }
+    log.push_back(format!(
+        "[{}] {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        line
+    ));
+}
+
+/// Details about the machine and launcher
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemInfo {
+    pub created_at: String,
+    pub launcher_version: String,
+    pub tauri_version: String,
+    pub webview_version: Option<String>,
+    pub os: String,
+    pub os_family: String,
+    pub arch: String,
+}
+
+impl SystemInfo {
+    /// Collects details for the current machine
+    pub fn new(launcher_version: String) -> Self {
+        Self {
+            created_at: chrono::Utc::now().to_rfc3339(),
+            launcher_version,
+            tauri_version: tauri::VERSION.to_string(),
+            webview_version: tauri::webview_version().ok(),
+            os: std::env::consts::OS.to_string(),
+            os_family: std::env::consts::FAMILY.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+/// State of the installed proxy
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyInfo {
+    pub running: bool,
+    /// Release version of the running instance, if known
+    pub version: Option<String>,
+    /// Files in the install directory
+    pub installed: Vec<InstalledFile>,
+}
+
+/// A file in the proxy install directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledFile {
+    pub name: String,
+    pub size: u64,
+    /// RFC 3339 modification time
+    pub modified: Option<String>,
+}
+
+/// Lists the files in the proxy install directory
+pub(crate) fn installed_proxy_files() -> Vec<InstalledFile> {
+    let Ok(dir) = download::get_install_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<InstalledFile> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(InstalledFile {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                size: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    files
+}
+
+/// Removes secrets from everything written to a bundle
+#[derive(Debug, Default)]
+pub(crate) struct Redactor {
+    /// The signed-in user's token
+    token: Option<String>,
+    /// Home directory, replaced with `~`
+    home: Option<String>,
+}
+
+impl Redactor {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.filter(|t| !t.is_empty()),
+            home: utils::get_home_dir()
+                .ok()
+                .map(|h| h.to_string_lossy().into_owned())
+                .filter(|h| !h.is_empty()),
+        }
+    }
+
+    /// Redacts secrets in free-form text such as log lines
+    pub fn text(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if let Some(token) = &self.token {
+            text = text.replace(token.as_str(), REDACTED);
+        }
+        for marker in SECRET_MARKERS {
+            text = redact_after(&text, marker);
+        }
+        if let Some(home) = &self.home {
+            text = text.replace(home.as_str(), "~");
+        }
+        text
+    }
+
+    /// Redacts secrets in a JSON value, dropping values of secret keys
+    pub fn value(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.text(s),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.value(item)),
+            Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    let key = key.to_ascii_lowercase();
+                    if SECRET_KEYS.iter().any(|secret| key.contains(secret)) {
+                        *item = Value::String(REDACTED.to_string());
+                    } else {
+                        self.value(item);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Replaces whatever follows each `marker` up to the next whitespace or quote
+fn redact_after(text: &str, marker: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(marker) {
+        let (before, after) = rest.split_at(start + marker.len());
+        result.push_str(before);
+        let end = after
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            .unwrap_or(after.len());
+        if end > 0 {
+            result.push_str(REDACTED);
+        }
+        rest = &after[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Gets where a bundle is saved when no path is given: the Downloads
+/// folder if there is one, the launcher's data directory otherwise
+pub(crate) fn default_path() -> Result<PathBuf, String> {
+    let name = format!(
+        "duelsplus-diagnostics-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    utils::get_export_path(&name)
+}
+
+/// Reads the most recent crash reports, newest first
+fn recent_crash_reports() -> Vec<(String, Value)> {
+    let Ok(dir) = crash::get_crash_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    // Names carry the timestamp, so they sort chronologically
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .rev()
+        .take(RECENT_CRASHES)
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            let report = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+            Some((name, report))
+        })
+        .collect()
+}
+
+/// Everything that goes into a bundle besides what's read from disk
+pub(crate) struct BundleContents {
+    pub system: SystemInfo,
+    pub proxy: ProxyInfo,
+    pub config: Option<Config>,
+    /// Recent proxy output, oldest first
+    pub proxy_log: Vec<String>,
+}
+
+/// Writes a diagnostic bundle to `path`.
+///
+/// # Arguments
+///
+/// * `path` - Where to write the zip file
+/// * `contents` - System, proxy and config details to include
+/// * `redactor` - Removes secrets from every file
+pub(crate) fn export(
+    path: &Path,
+    contents: BundleContents,
+    redactor: &Redactor,
+) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut add = |name: &str, data: String| -> Result<(), String> {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(data.as_bytes()).map_err(|e| e.to_string())
+    };
+    let json = |value: &mut Value| {
+        redactor.value(value);
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    };
+
+    add(
+        "system.json",
+        json(&mut serde_json::to_value(&contents.system).unwrap_or_default()),
+    )?;
+    add(
+        "proxy.json",
+        json(&mut serde_json::to_value(&contents.proxy).unwrap_or_default()),
+    )?;
+    if let Some(config) = &contents.config {
+        add("config.json", json(&mut crash::scrub_config(config)))?;
+    }
+
+    add("launcher.log", launcher_log(redactor))?;
+    add("proxy.log", redact_lines(&contents.proxy_log, redactor))?;
+
+    for (name, mut report) in recent_crash_reports() {
+        add(&format!("crashes/{}", name), json(&mut report))?;
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Gets the recent launcher log output, redacted
+fn launcher_log(redactor: &Redactor) -> String {
+    let log = LAUNCHER_LOG.lock().unwrap_or_else(|e| e.into_inner());
+    redact_lines(log.iter(), redactor)
+}
+
+fn redact_lines<'a>(lines: impl IntoIterator<Item = &'a String>, redactor: &Redactor) -> String {
+    lines
+        .into_iter()
+        .map(|line| redactor.text(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Logs sent by `upload_logs`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogUploadRequest {
+    launcher_version: String,
+    os: String,
+    launcher_log: String,
+    proxy_log: String,
+}
+
+/// Uploaded logs, as returned by `upload_logs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogUpload {
+    /// Short id support can look the logs up by
+    pub id: String,
+    /// Link to the uploaded logs
+    pub url: String,
+}
+
+/// Uploads the recent launcher and proxy logs, redacted, so they can be
+/// shared with support.
+///
+/// # Arguments
+///
+/// * `launcher_version` - The running launcher's version
+/// * `proxy_log` - The proxy's recent output
+/// * `redactor` - Removes secrets from the logs
+pub(crate) async fn upload_logs(
+    launcher_version: String,
+    proxy_log: &[String],
+    redactor: &Redactor,
+) -> Result<LogUpload, String> {
+    let body = LogUploadRequest {
+        launcher_version,
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        launcher_log: launcher_log(redactor),
+        proxy_log: redact_lines(proxy_log, redactor),
+    };
+    let response = reqwest::Client::new()
+        .post(format!("{}/logs", API_BASE_URL))
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(30))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to upload logs: {}", e))?;
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected response to the log upload: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIN_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2g";
+
+    #[test]
+    fn test_redact_text() {
+        let redactor = Redactor {
+            token: Some("abc123secret".to_string()),
+            home: Some("/home/steve".to_string()),
+        };
+        assert_eq!(
+            redactor.text("token abc123secret in /home/steve/.duelsplus"),
+            "token [redacted] in ~/.duelsplus"
+        );
+        assert_eq!(
+            redactor.text("posting to https://discord.com/api/webhooks/123/xyz now"),
+            "posting to https://discord.com/api/webhooks/[redacted] now"
+        );
+        assert_eq!(
+            redactor.text("Authorization: \"Bearer eyJhbGci.x.y\""),
+            "Authorization: \"Bearer [redacted]\""
+        );
+    }
+
+    #[test]
+    fn test_redact_value_drops_secret_keys() {
+        let redactor = Redactor::default();
+        let mut value = serde_json::json!({
+            "discordWebhookUrl": "https://discord.com/api/webhooks/1/a",
+            "nested": { "authToken": "abc" },
+            "proxyPort": "25565",
+        });
+        redactor.value(&mut value);
+        assert_eq!(value["discordWebhookUrl"], REDACTED);
+        assert_eq!(value["nested"]["authToken"], REDACTED);
+        assert_eq!(value["proxyPort"], "25565");
+    }
+
+    #[test]
+    fn test_redact_lines() {
+        let redactor = Redactor {
+            token: Some("abc123secret".to_string()),
+            home: None,
+        };
+        let lines = vec!["[i] ready".to_string(), "token=abc123secret".to_string()];
+        assert_eq!(
+            redact_lines(&lines, &redactor),
+            "[i] ready\ntoken=[redacted]"
+        );
+    }
+
+    #[test]
+    fn test_export_writes_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.zip");
+        record_log("[proxy] Proxy started");
+        let contents = BundleContents {
+            system: SystemInfo::new("1.0.0".to_string()),
+            proxy: ProxyInfo {
+                running: false,
+                version: None,
+                installed: Vec::new(),
+            },
+            config: Some(Config {
+                app_pin: Some(PIN_HASH.to_string()),
+                ..Config::default()
+            }),
+            proxy_log: vec!["Bearer abc".to_string()],
+        };
+        export(&path, contents, &Redactor::default()).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        for name in [
+            "system.json",
+            "proxy.json",
+            "config.json",
+            "launcher.log",
+            "proxy.log",
+        ] {
+            assert!(archive.by_name(name).is_ok(), "missing {}", name);
+        }
+        let mut proxy_log = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("proxy.log").unwrap(), &mut proxy_log)
+            .unwrap();
+        assert_eq!(proxy_log, "Bearer [redacted]");
+        let mut config = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("config.json").unwrap(), &mut config)
+            .unwrap();
+        assert!(!config.contains(PIN_HASH));
+    }
+}
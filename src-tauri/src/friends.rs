@@ -0,0 +1,266 @@
+//! Which of the player's Duels+ friends are online.
+//!
+//! The `friends-presence` task polls the friends API every minute while
+//! signed in. Each poll is compared with the last: when anything changed,
+//! the whole list is emitted as `friends-presence-updated`, and friends who
+//! came online are notified about (see [`notifications::Category`]). The first
+//! poll after starting or signing in only records who's online, so it
+//! doesn't notify about everyone at once.
+
+use crate::auth;
+use crate::i18n::t;
+use crate::notifications::{self, Category};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Friends from the last poll by ID, `None` until the first one
+static PRESENCE: Mutex<Option<HashMap<String, FriendPresence>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FriendStatus {
+    InGame,
+    Online,
+    Offline,
+}
+
+/// A friend and what they're doing, as returned by `get_friends_presence`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FriendPresence {
+    /// Duels+ user ID
+    pub id: String,
+    pub username: String,
+    pub status: FriendStatus,
+    /// Game mode being played, only while in game
+    pub mode: Option<String>,
+    /// Unix time in seconds the status was first seen, 0 if it hasn't
+    /// changed since the launcher started
+    pub since: i64,
+}
+
+/// Gets the friends from the last poll, polling first if there's none yet
+pub(crate) async fn get(app: &AppHandle) -> Result<Vec<FriendPresence>, String> {
+    let polled = PRESENCE.lock().unwrap().is_some();
+    if !polled {
+        poll(app.clone()).await?;
+    }
+    Ok(sorted(PRESENCE.lock().unwrap().as_ref()))
+}
+
+/// Fetches the friends and records what changed, for the scheduler
+pub(crate) async fn poll(app: AppHandle) -> Result<(), String> {
+    let Some(token) = auth::token::get_token().await.map_err(|e| e.to_string())? else {
+        // Signed out; the next sign-in starts over
+        *PRESENCE.lock().unwrap() = None;
+        return Ok(());
+    };
+    let response = auth::api::get_friends(&token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let fetched = match response.data {
+        Some(data) if response.success => parse(&data),
+        _ => {
+            return Err(response
+                .message
+                .unwrap_or_else(|| "The API returned no friends".to_string()))
+        }
+    };
+
+    let (friends, came_online) = {
+        let mut presence = PRESENCE.lock().unwrap();
+        let previous = presence.take();
+        let (current, came_online, changed) =
+            merge(previous.as_ref(), fetched, chrono::Utc::now().timestamp());
+        *presence = Some(current);
+        if !changed {
+            return Ok(());
+        }
+        (sorted(presence.as_ref()), came_online)
+    };
+
+    for friend in &came_online {
+        notifications::notify(
+            &app,
+            Category::FriendOnline,
+            t!("notifications.friendOnline.title", name = friend.username),
+            t!("notifications.friendOnline.body", name = friend.username),
+        );
+    }
+    let _ = app.emit("friends-presence-updated", &friends);
+    Ok(())
+}
+
+/// Combines a poll with the last one, returning the friends by ID, who came
+/// online, and whether anything changed. Nobody comes online on the first
+/// poll.
+fn merge(
+    previous: Option<&HashMap<String, FriendPresence>>,
+    fetched: Vec<FriendPresence>,
+    now: i64,
+) -> (HashMap<String, FriendPresence>, Vec<FriendPresence>, bool) {
+    let mut came_online = Vec::new();
+    let mut changed = previous.is_none_or(|previous| previous.len() != fetched.len());
+    let current = fetched
+        .into_iter()
+        .map(|mut friend| {
+            match previous.and_then(|previous| previous.get(&friend.id)) {
+                Some(last) if last.status == friend.status => {
+                    friend.since = last.since;
+                    changed |= last.username != friend.username || last.mode != friend.mode;
+                }
+                last => {
+                    if previous.is_some() {
+                        friend.since = now;
+                        changed = true;
+                    }
+                    let was_offline = last.is_none_or(|last| last.status == FriendStatus::Offline);
+                    if previous.is_some() && was_offline && friend.status != FriendStatus::Offline {
+                        came_online.push(friend.clone());
+                    }
+                }
+            }
+            (friend.id.clone(), friend)
+        })
+        .collect();
+    (current, came_online, changed)
+}
+
+/// Friends in game first, then online, then offline, each by name
+fn sorted(presence: Option<&HashMap<String, FriendPresence>>) -> Vec<FriendPresence> {
+    let mut friends: Vec<FriendPresence> = presence
+        .into_iter()
+        .flatten()
+        .map(|(_, f)| f.clone())
+        .collect();
+    friends.sort_by(|a, b| {
+        a.status
+            .cmp(&b.status)
+            .then_with(|| a.username.to_lowercase().cmp(&b.username.to_lowercase()))
+    });
+    friends
+}
+
+/// Reads the API's `{ "friends": [{ "id", "username", "online", "mode" }] }`,
+/// where `mode` is only set while the friend is in a game
+fn parse(data: &Value) -> Vec<FriendPresence> {
+    data.get("friends")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|friend| {
+            let mode = friend
+                .get("mode")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let online = friend
+                .get("online")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            Some(FriendPresence {
+                id: friend.get("id")?.as_str()?.to_string(),
+                username: friend.get("username")?.as_str()?.to_string(),
+                status: match (online, &mode) {
+                    (false, _) => FriendStatus::Offline,
+                    (true, Some(_)) => FriendStatus::InGame,
+                    (true, None) => FriendStatus::Online,
+                },
+                mode: mode.filter(|_| online),
+                since: 0,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn friend(id: &str, status: FriendStatus) -> FriendPresence {
+        FriendPresence {
+            id: id.to_string(),
+            username: id.to_uppercase(),
+            status,
+            mode: None,
+            since: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let friends = parse(&json!({
+            "friends": [
+                { "id": "a", "username": "Steve", "online": true, "mode": "DUELS_SUMO_DUEL" },
+                { "id": "b", "username": "Alex", "online": true },
+                { "id": "c", "username": "Herobrine", "online": false, "mode": "DUELS_UHC_DUEL" },
+                { "username": "NoId", "online": true },
+            ],
+        }));
+        assert_eq!(friends.len(), 3);
+        assert_eq!(friends[0].status, FriendStatus::InGame);
+        assert_eq!(friends[1].status, FriendStatus::Online);
+        assert_eq!(friends[2].status, FriendStatus::Offline);
+        assert_eq!(friends[2].mode, None);
+    }
+
+    #[test]
+    fn test_first_poll_notifies_nobody() {
+        let (current, came_online, changed) =
+            merge(None, vec![friend("a", FriendStatus::Online)], 100);
+        assert!(came_online.is_empty());
+        assert!(changed);
+        assert_eq!(current["a"].since, 0);
+    }
+
+    #[test]
+    fn test_merge() {
+        let (previous, _, _) = merge(
+            None,
+            vec![
+                friend("a", FriendStatus::Offline),
+                friend("b", FriendStatus::Online),
+            ],
+            100,
+        );
+
+        let (current, came_online, changed) = merge(
+            Some(&previous),
+            vec![
+                friend("a", FriendStatus::InGame),
+                friend("b", FriendStatus::Online),
+                // Added since the last poll
+                friend("c", FriendStatus::Online),
+            ],
+            200,
+        );
+        assert!(changed);
+        let ids: Vec<&str> = came_online.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(current["a"].since, 200);
+        assert_eq!(current["b"].since, 0);
+
+        let fetched = current.values().cloned().collect();
+        let (_, came_online, changed) = merge(Some(&current), fetched, 300);
+        assert!(came_online.is_empty());
+        assert!(!changed);
+    }
+
+    #[test]
+    fn test_sorted() {
+        let presence: HashMap<String, FriendPresence> = [
+            friend("c", FriendStatus::Offline),
+            friend("b", FriendStatus::Online),
+            friend("a", FriendStatus::Online),
+            friend("d", FriendStatus::InGame),
+        ]
+        .into_iter()
+        .map(|f| (f.id.clone(), f))
+        .collect();
+        let ids: Vec<String> = sorted(Some(&presence)).into_iter().map(|f| f.id).collect();
+        assert_eq!(ids, ["d", "a", "b", "c"]);
+    }
+}
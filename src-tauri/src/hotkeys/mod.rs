@@ -0,0 +1,313 @@
+//! Global keyboard shortcuts.
+//!
+//! Shortcuts from the `keybinds` config work while the launcher is in the
+//! background: showing or hiding the window, launching or stopping the
+//! proxy, toggling Discord presence privacy and showing or hiding the
+//! overlay. They are registered through the global shortcut plugin, on
+//! Windows, macOS and X11. Wayland doesn't pass key presses on to it, so
+//! there they're reported as unsupported.
+//!
+//! A shortcut bound to two actions, or already taken by another app, is
+//! reported as a conflict instead of silently not working.
+
+mod shortcut;
+
+use crate::config::models::Keybinds;
+use crate::i18n::t;
+use crate::proxy::ProxyManager;
+use crate::rpc::RpcManager;
+use crate::{commands, config, overlay, tray};
+use serde::Serialize;
+use shortcut::Shortcut;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// Something a global shortcut can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HotkeyAction {
+    ShowHideWindow,
+    LaunchStopProxy,
+    ToggleRpcPrivacy,
+    ToggleOverlay,
+}
+
+impl HotkeyAction {
+    /// Name shown in conflict messages
+    fn label(&self) -> String {
+        match self {
+            HotkeyAction::ShowHideWindow => t!("hotkeys.showHideWindow"),
+            HotkeyAction::LaunchStopProxy => t!("hotkeys.launchStopProxy"),
+            HotkeyAction::ToggleRpcPrivacy => t!("hotkeys.toggleRpcPrivacy"),
+            HotkeyAction::ToggleOverlay => t!("hotkeys.toggleOverlay"),
+        }
+    }
+}
+
+/// A shortcut that couldn't be registered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyConflict {
+    pub action: HotkeyAction,
+    /// The shortcut as written in the config
+    pub shortcut: String,
+    pub reason: String,
+}
+
+/// Result of registering the configured shortcuts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyStatus {
+    /// Why shortcuts don't work on this system, if they don't
+    pub unsupported: Option<String>,
+    pub conflicts: Vec<HotkeyConflict>,
+}
+
+/// Parses the configured shortcuts, leaving out unbound actions.
+///
+/// Invalid shortcuts and shortcuts already bound to an earlier action are
+/// returned as conflicts.
+fn resolve(keybinds: &Keybinds) -> (Vec<(HotkeyAction, Shortcut)>, Vec<HotkeyConflict>) {
+    let configured = [
+        (HotkeyAction::ShowHideWindow, &keybinds.show_hide_window),
+        (HotkeyAction::LaunchStopProxy, &keybinds.launch_stop_proxy),
+        (HotkeyAction::ToggleRpcPrivacy, &keybinds.toggle_rpc_privacy),
+        (HotkeyAction::ToggleOverlay, &keybinds.toggle_overlay),
+    ];
+
+    let mut bindings: Vec<(HotkeyAction, Shortcut)> = Vec::new();
+    let mut conflicts = Vec::new();
+    for (action, raw) in configured {
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let conflict = |reason: String| HotkeyConflict {
+            action,
+            shortcut: raw.clone(),
+            reason,
+        };
+        match raw.parse::<Shortcut>() {
+            Ok(shortcut) => match bindings.iter().find(|(_, s)| *s == shortcut) {
+                Some((other, _)) => {
+                    conflicts.push(conflict(t!("hotkeys.usedFor", action = other.label())))
+                }
+                None => bindings.push((action, shortcut)),
+            },
+            Err(e) => conflicts.push(conflict(e)),
+        }
+    }
+    (bindings, conflicts)
+}
+
+type TriggerHandler = Arc<dyn Fn(HotkeyAction) + Send + Sync>;
+
+/// Keeps the configured shortcuts registered with the OS
+#[derive(Default)]
+pub struct HotkeyManager {
+    trigger_handler: Arc<Mutex<Option<TriggerHandler>>>,
+    status: Mutex<HotkeyStatus>,
+}
+
+impl HotkeyManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the callback run whenever a shortcut is pressed, replacing any
+    /// previous one
+    pub fn on_trigger<F>(&self, handler: F)
+    where
+        F: Fn(HotkeyAction) + Send + Sync + 'static,
+    {
+        *self.trigger_handler.lock().unwrap() = Some(Arc::new(handler));
+    }
+
+    /// Gets the result of the last registration
+    pub fn status(&self) -> HotkeyStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Replaces the registered shortcuts with the configured ones.
+    ///
+    /// # Returns
+    ///
+    /// Returns the shortcuts that couldn't be registered and why.
+    pub fn apply(&self, app: &AppHandle, keybinds: &Keybinds) -> HotkeyStatus {
+        let (bindings, mut conflicts) = resolve(keybinds);
+
+        let global_shortcut = app.global_shortcut();
+        if let Err(e) = global_shortcut.unregister_all() {
+            eprintln!("[hotkeys] Failed to unregister shortcuts: {}", e);
+        }
+        if let Some(reason) = unsupported().filter(|_| !bindings.is_empty()) {
+            return self.set_status(HotkeyStatus {
+                unsupported: Some(reason),
+                conflicts,
+            });
+        }
+
+        for (action, shortcut) in bindings {
+            let handler = self.trigger_handler.clone();
+            let result =
+                global_shortcut.on_shortcut(shortcut.to_string().as_str(), move |_, _, event| {
+                    if event.state() != ShortcutState::Pressed {
+                        return;
+                    }
+                    let handler = handler.lock().unwrap().clone();
+                    if let Some(handler) = handler {
+                        handler(action);
+                    }
+                });
+            if let Err(e) = result {
+                eprintln!("[hotkeys] Failed to register {}: {}", shortcut, e);
+                conflicts.push(HotkeyConflict {
+                    action,
+                    shortcut: shortcut.to_string(),
+                    reason: t!("hotkeys.usedByAnotherApp"),
+                });
+            }
+        }
+        self.set_status(HotkeyStatus {
+            unsupported: None,
+            conflicts,
+        })
+    }
+
+    fn set_status(&self, status: HotkeyStatus) -> HotkeyStatus {
+        for conflict in &status.conflicts {
+            eprintln!(
+                "[hotkeys] {} ({}): {}",
+                conflict.action.label(),
+                conflict.shortcut,
+                conflict.reason
+            );
+        }
+        *self.status.lock().unwrap() = status.clone();
+        status
+    }
+}
+
+/// Why global shortcuts don't work on this system, if they don't. The
+/// plugin grabs keys through X11, which Wayland only lets X11 windows see
+fn unsupported() -> Option<String> {
+    let wayland = cfg!(all(unix, not(target_os = "macos")))
+        && std::env::var("XDG_SESSION_TYPE").is_ok_and(|t| t == "wayland");
+    wayland.then(|| t!("hotkeys.unsupportedWayland"))
+}
+
+/// Runs the action of a pressed shortcut
+pub(crate) fn handle(app: &AppHandle, action: HotkeyAction) {
+    match action {
+        HotkeyAction::ShowHideWindow => tray::toggle_window(app),
+        HotkeyAction::LaunchStopProxy => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let Some(manager) = app.try_state::<ProxyManager>() else {
+                    return;
+                };
+                // Failures are already reported to the logs by the commands
+                if manager.is_running().await {
+                    let _ = commands::stop_proxy(app.clone(), manager).await;
+                } else {
+                    let _ = commands::launch_proxy(app.clone(), manager, None).await;
+                }
+            });
+        }
+        HotkeyAction::ToggleRpcPrivacy => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let Some(rpc) = app.try_state::<RpcManager>() else {
+                    return;
+                };
+                let cfg = config::manager::get_config()
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default();
+                // Hide everything unless something is already hidden
+                let hide = !(cfg.rpc_anonymize_profile || cfg.rpc_anonymize_location);
+                if let Err(e) = commands::rpc_set_privacy(app.clone(), rpc, hide, hide).await {
+                    eprintln!("[hotkeys] Failed to toggle presence privacy: {}", e);
+                }
+            });
+        }
+        HotkeyAction::ToggleOverlay => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = overlay::toggle(&app).await {
+                    eprintln!("[hotkeys] {}", e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keybinds(
+        show_hide_window: &str,
+        launch_stop_proxy: &str,
+        toggle_rpc_privacy: &str,
+    ) -> Keybinds {
+        Keybinds {
+            show_hide_window: show_hide_window.to_string(),
+            launch_stop_proxy: launch_stop_proxy.to_string(),
+            toggle_rpc_privacy: toggle_rpc_privacy.to_string(),
+            toggle_overlay: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_skips_unbound_actions() {
+        let (bindings, conflicts) = resolve(&keybinds("Ctrl+Shift+D", "", " "));
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].0, HotkeyAction::ShowHideWindow);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_reports_duplicates_and_invalid_shortcuts() {
+        let (bindings, conflicts) = resolve(&keybinds("Ctrl+Shift+D", "shift+ctrl+d", "Ctrl+Nope"));
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(
+            conflicts,
+            vec![
+                HotkeyConflict {
+                    action: HotkeyAction::LaunchStopProxy,
+                    shortcut: "shift+ctrl+d".to_string(),
+                    reason: "Already used for Show/Hide Launcher".to_string(),
+                },
+                HotkeyConflict {
+                    action: HotkeyAction::ToggleRpcPrivacy,
+                    shortcut: "Ctrl+Nope".to_string(),
+                    reason: "Unknown key \"Nope\"".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shortcuts_parse_as_plugin_shortcuts() {
+        for raw in [
+            "Ctrl+Shift+D",
+            "Cmd+Alt+PageDown",
+            "Ctrl+7",
+            "F13",
+            "Shift+Space",
+            "Ctrl+Insert",
+        ] {
+            let shortcut: Shortcut = raw.parse().unwrap();
+            assert!(
+                shortcut
+                    .to_string()
+                    .parse::<tauri_plugin_global_shortcut::Shortcut>()
+                    .is_ok(),
+                "{} isn't understood by the plugin",
+                shortcut
+            );
+        }
+    }
+}
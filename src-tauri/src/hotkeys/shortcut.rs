@@ -0,0 +1,177 @@
+//! Parsing of shortcut strings like `Ctrl+Shift+D`.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Modifier keys held for a shortcut
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// The Windows key, Super on Linux
+    pub meta: bool,
+}
+
+impl Modifiers {
+    fn any(&self) -> bool {
+        self.ctrl || self.alt || self.shift || self.meta
+    }
+}
+
+/// Main key of a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// `A` to `Z`, stored uppercase
+    Letter(char),
+    /// `0` to `9`
+    Digit(u8),
+    /// `F1` to `F24`
+    Function(u8),
+    Space,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Letter(c) => write!(f, "{}", c),
+            Key::Digit(d) => write!(f, "{}", d),
+            Key::Function(n) => write!(f, "F{}", n),
+            Key::Space => f.write_str("Space"),
+            Key::Insert => f.write_str("Insert"),
+            Key::Delete => f.write_str("Delete"),
+            Key::Home => f.write_str("Home"),
+            Key::End => f.write_str("End"),
+            Key::PageUp => f.write_str("PageUp"),
+            Key::PageDown => f.write_str("PageDown"),
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if c.is_ascii_alphabetic() {
+                return Ok(Key::Letter(c.to_ascii_uppercase()));
+            }
+            if let Some(d) = c.to_digit(10) {
+                return Ok(Key::Digit(d as u8));
+            }
+        }
+
+        let key = match s.to_ascii_lowercase().as_str() {
+            "space" => Key::Space,
+            "insert" => Key::Insert,
+            "delete" => Key::Delete,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            lower => lower
+                .strip_prefix('f')
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=24).contains(n))
+                .map(Key::Function)
+                .ok_or_else(|| format!("Unknown key \"{}\"", s))?,
+        };
+        Ok(key)
+    }
+}
+
+/// A key combination, e.g. `Ctrl+Shift+D`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Modifiers {
+            ctrl,
+            alt,
+            shift,
+            meta,
+        } = self.modifiers;
+        for (held, name) in [
+            (ctrl, "Ctrl"),
+            (alt, "Alt"),
+            (shift, "Shift"),
+            (meta, "Super"),
+        ] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = String;
+
+    /// Parses a `+`-separated shortcut, ignoring case and spaces.
+    ///
+    /// Keys that type text need a modifier, so binding them doesn't stop
+    /// them working in other apps.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key = parts
+            .pop()
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| "Missing a key".to_string())?;
+
+        let mut modifiers = Modifiers::default();
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "alt" | "option" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                "super" | "meta" | "win" | "cmd" | "command" => modifiers.meta = true,
+                _ => return Err(format!("Unknown modifier \"{}\"", part)),
+            }
+        }
+
+        let key: Key = key.parse()?;
+        if !modifiers.any() && !matches!(key, Key::Function(_)) {
+            return Err(format!("{} needs Ctrl, Alt, Shift or Super", key));
+        }
+        Ok(Shortcut { modifiers, key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let shortcut: Shortcut = "shift + ctrl + d".parse().unwrap();
+        assert_eq!(shortcut.to_string(), "Ctrl+Shift+D");
+        assert_eq!(
+            "Cmd+Alt+PageDown".parse::<Shortcut>().unwrap().to_string(),
+            "Alt+Super+PageDown"
+        );
+        assert_eq!("F13".parse::<Shortcut>().unwrap().key, Key::Function(13));
+        assert_eq!("Ctrl+7".parse::<Shortcut>().unwrap().key, Key::Digit(7));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert!("".parse::<Shortcut>().is_err());
+        assert!("Ctrl+".parse::<Shortcut>().is_err());
+        assert!("D".parse::<Shortcut>().is_err());
+        assert!("Ctrl+F25".parse::<Shortcut>().is_err());
+        assert!("Hyper+D".parse::<Shortcut>().is_err());
+        assert!("Ctrl+Enter".parse::<Shortcut>().is_err());
+    }
+}
@@ -0,0 +1,278 @@
+//! Player lookups through the Hypixel public API.
+//!
+//! Needs the player's own API key from the Hypixel developer dashboard,
+//! set with `set_hypixel_api_key` and kept sealed in `hypixelApiKey` (see
+//! [`crate::secrets`]). `lookup_player` resolves a name with Mojang, then
+//! gets the player's Duels and BedWars stats. The overlay also uses it for
+//! opponents the proxy knows the UUID of but couldn't get stats for.
+//!
+//! Lookups are cached per player for a few minutes. Hypixel reports how many
+//! requests the key has left in its `RateLimit-*` headers; once they run out,
+//! or it answers 429, nothing is sent until the limit resets.
+
+use crate::config;
+use crate::mc::mojang;
+use crate::secrets;
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const PLAYER_URL: &str = "https://api.hypixel.net/v2/player";
+
+/// How long a lookup is reused
+const CACHE_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// Wait after a 429 without `RateLimit-Reset`
+const DEFAULT_RESET: Duration = Duration::from_secs(60);
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lookups and when they were made, `None` for players who never joined
+/// Hypixel
+type LookupCache = HashMap<String, (Instant, Option<PlayerLookup>)>;
+
+/// Lookups by dashed UUID
+static CACHE: Mutex<Option<LookupCache>> = Mutex::new(None);
+
+/// No requests are sent before this, once the key's limit ran out
+static BLOCKED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// A player's stats, as returned by `lookup_player`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerLookup {
+    /// With dashes
+    pub uuid: String,
+    /// Name as shown on Hypixel
+    pub name: String,
+    /// `None` if the player never played Duels
+    pub duels: Option<DuelsStats>,
+    /// `None` if the player never played BedWars
+    pub bedwars: Option<BedwarsStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuelsStats {
+    pub wins: u32,
+    pub losses: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    /// `None` when the player hides it in their API settings
+    pub winstreak: Option<u32>,
+    pub best_winstreak: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedwarsStats {
+    /// Stars
+    pub level: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub final_kills: u32,
+    pub final_deaths: u32,
+    pub beds_broken: u32,
+}
+
+/// Checks and seals an API key to store as `hypixelApiKey`
+pub(crate) fn seal_api_key(key: &str) -> Result<String, String> {
+    // Hypixel keys are UUIDs
+    let key = mojang::dashed(key.trim()).ok_or("That isn't a Hypixel API key")?;
+    // A new key gets a new limit
+    *BLOCKED_UNTIL.lock().unwrap() = None;
+    secrets::seal(&key)
+}
+
+/// Whether an API key is set
+pub(crate) async fn has_api_key() -> bool {
+    config::manager::get_config()
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|cfg| cfg.hypixel_api_key.is_some())
+}
+
+async fn api_key() -> Result<String, String> {
+    let sealed = config::manager::get_config()
+        .await
+        .map_err(|e| e.to_string())?
+        .and_then(|cfg| cfg.hypixel_api_key)
+        .ok_or("Add your Hypixel API key first")?;
+    secrets::open(&sealed)
+}
+
+/// Looks up a player by name or UUID, `None` if there's no such account or the
+/// player never joined Hypixel
+pub(crate) async fn lookup_player(name: &str) -> Result<Option<PlayerLookup>, String> {
+    // From a `duelsplus://profile/<uuid>` link
+    if mojang::dashed(name).is_some() {
+        return lookup_uuid(name).await;
+    }
+    match mojang::resolve_username(name).await? {
+        Some(profile) => lookup_uuid(&profile.uuid).await,
+        None => Ok(None),
+    }
+}
+
+/// Looks up a player by UUID, `None` if they never joined Hypixel
+pub(crate) async fn lookup_uuid(uuid: &str) -> Result<Option<PlayerLookup>, String> {
+    let uuid = mojang::dashed(uuid).ok_or_else(|| format!("\"{}\" isn't a UUID", uuid))?;
+    let cached = CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .get(&uuid)
+        .filter(|(at, _)| at.elapsed() < CACHE_MAX_AGE)
+        .map(|(_, lookup)| lookup.clone());
+    if let Some(lookup) = cached {
+        return Ok(lookup);
+    }
+
+    let key = api_key().await?;
+    let player = fetch_player(&key, &uuid).await?;
+    let lookup = player.map(|player| parse_player(&uuid, &player));
+    let mut cache = CACHE.lock().unwrap();
+    let cache = cache.get_or_insert_with(HashMap::new);
+    cache.retain(|_, (at, _)| at.elapsed() < CACHE_MAX_AGE);
+    cache.insert(uuid, (Instant::now(), lookup.clone()));
+    Ok(lookup)
+}
+
+/// Gets the `player` object, `None` if the player never joined
+async fn fetch_player(key: &str, uuid: &str) -> Result<Option<Value>, String> {
+    let blocked = BLOCKED_UNTIL
+        .lock()
+        .unwrap()
+        .and_then(|until| until.checked_duration_since(Instant::now()));
+    if let Some(wait) = blocked {
+        return Err(rate_limited(wait));
+    }
+
+    let response = reqwest::Client::new()
+        .get(PLAYER_URL)
+        .query(&[("uuid", uuid)])
+        .header("API-Key", key)
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Hypixel: {}", e))?;
+
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    let reset = header("RateLimit-Reset")
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RESET);
+    let status = response.status();
+    if status == StatusCode::TOO_MANY_REQUESTS || header("RateLimit-Remaining") == Some(0) {
+        *BLOCKED_UNTIL.lock().unwrap() = Some(Instant::now() + reset);
+    }
+
+    let body: Value = response.json().await.unwrap_or_default();
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => Err(rate_limited(reset)),
+        StatusCode::FORBIDDEN => Err("Hypixel rejected the API key".to_string()),
+        status if status.is_success() => Ok(body.get("player").filter(|p| !p.is_null()).cloned()),
+        status => Err(body
+            .get("cause")
+            .and_then(Value::as_str)
+            .map(|cause| format!("Hypixel returned an error: {}", cause))
+            .unwrap_or_else(|| format!("Hypixel returned {}", status))),
+    }
+}
+
+fn rate_limited(wait: Duration) -> String {
+    format!(
+        "The Hypixel API key is out of requests, try again in {}s",
+        wait.as_secs().max(1)
+    )
+}
+
+fn parse_player(uuid: &str, player: &Value) -> PlayerLookup {
+    let count =
+        |stats: &Value, name: &str| stats.get(name).and_then(Value::as_u64).map(|n| n as u32);
+    let duels = player.pointer("/stats/Duels").map(|stats| DuelsStats {
+        wins: count(stats, "wins").unwrap_or(0),
+        losses: count(stats, "losses").unwrap_or(0),
+        kills: count(stats, "kills").unwrap_or(0),
+        deaths: count(stats, "deaths").unwrap_or(0),
+        winstreak: count(stats, "current_winstreak"),
+        best_winstreak: count(stats, "best_overall_winstreak"),
+    });
+    let bedwars = player.pointer("/stats/Bedwars").map(|stats| BedwarsStats {
+        level: player
+            .pointer("/achievements/bedwars_level")
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as u32,
+        wins: count(stats, "wins_bedwars").unwrap_or(0),
+        losses: count(stats, "losses_bedwars").unwrap_or(0),
+        final_kills: count(stats, "final_kills_bedwars").unwrap_or(0),
+        final_deaths: count(stats, "final_deaths_bedwars").unwrap_or(0),
+        beds_broken: count(stats, "beds_broken_bedwars").unwrap_or(0),
+    });
+    PlayerLookup {
+        uuid: uuid.to_string(),
+        name: player
+            .get("displayname")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        duels,
+        bedwars,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_player() {
+        let player = json!({
+            "displayname": "Technoblade",
+            "achievements": { "bedwars_level": 312 },
+            "stats": {
+                "Duels": {
+                    "wins": 1200,
+                    "losses": 300,
+                    "kills": 1500,
+                    "deaths": 320,
+                    "best_overall_winstreak": 45,
+                },
+                "Bedwars": {
+                    "wins_bedwars": 900,
+                    "losses_bedwars": 200,
+                    "final_kills_bedwars": 4000,
+                    "final_deaths_bedwars": 180,
+                    "beds_broken_bedwars": 2100,
+                },
+            },
+        });
+        let lookup = parse_player("b876ec32-e396-476b-a115-8438d83c67d4", &player);
+        assert_eq!(lookup.name, "Technoblade");
+        let duels = lookup.duels.unwrap();
+        assert_eq!((duels.wins, duels.losses), (1200, 300));
+        // Hidden in the player's API settings
+        assert_eq!(duels.winstreak, None);
+        assert_eq!(duels.best_winstreak, Some(45));
+        let bedwars = lookup.bedwars.unwrap();
+        assert_eq!(bedwars.level, 312);
+        assert_eq!(bedwars.beds_broken, 2100);
+    }
+
+    #[test]
+    fn test_parse_player_without_stats() {
+        let lookup = parse_player("b876ec32-e396-476b-a115-8438d83c67d4", &json!({}));
+        assert_eq!(lookup.duels, None);
+        assert_eq!(lookup.bedwars, None);
+    }
+}
@@ -0,0 +1,192 @@
+//! Translations for text generated by the launcher backend.
+//!
+//! Each locale has a flat JSON catalog embedded in the binary, keyed like
+//! `notifications.update.title`. Messages can have named placeholders such
+//! as `{version}`, filled by [`t!`]. Keys missing from a catalog fall back
+//! to English, and unknown keys to the key itself.
+//!
+//! Text in the Discord activity has its own phrases in [`crate::rpc::i18n`].
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+/// Embedded catalogs, by primary language subtag; the first is the fallback
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("en.json")),
+    ("de", include_str!("de.json")),
+    ("es", include_str!("es.json")),
+    ("fr", include_str!("fr.json")),
+    ("pt", include_str!("pt.json")),
+];
+
+/// Catalog key holding a locale's own name
+const NAME_KEY: &str = "locale.name";
+
+/// Language of the `locale` config, empty until the config is loaded
+static LANGUAGE: RwLock<String> = RwLock::new(String::new());
+
+type Catalog = HashMap<String, String>;
+
+/// A locale with a catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Locale {
+    /// e.g. `de`
+    pub code: String,
+    /// The locale's name in its own language, e.g. "Deutsch"
+    pub name: String,
+}
+
+/// Translates a catalog key into the current locale.
+///
+/// ```ignore
+/// t!("notifications.friendOnline.title", name = friend)
+/// ```
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+pub(crate) use t;
+
+fn catalogs() -> &'static [(&'static str, Catalog)] {
+    static PARSED: OnceLock<Vec<(&str, Catalog)>> = OnceLock::new();
+    PARSED.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(code, json)| {
+                let catalog = serde_json::from_str(json)
+                    .unwrap_or_else(|e| panic!("embedded {}.json is invalid: {}", code, e));
+                (*code, catalog)
+            })
+            .collect()
+    })
+}
+
+/// Gets the primary language subtag of a locale such as `pt-BR`
+fn language(locale: &str) -> String {
+    locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Switches the locale used by [`t!`], e.g. when the `locale` config changes
+pub(crate) fn set_locale(locale: &str) {
+    *LANGUAGE.write().unwrap() = language(locale);
+}
+
+/// Gets the locales with a catalog
+pub(crate) fn available_locales() -> Vec<Locale> {
+    catalogs()
+        .iter()
+        .map(|(code, catalog)| Locale {
+            code: code.to_string(),
+            name: catalog.get(NAME_KEY).cloned().unwrap_or(code.to_string()),
+        })
+        .collect()
+}
+
+/// Translates a key into the current locale, see [`t!`]
+pub(crate) fn translate(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    translate_in(&LANGUAGE.read().unwrap(), key, args)
+}
+
+/// Translates a key into a language, filling `{name}` placeholders
+fn translate_in(language: &str, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let catalogs = catalogs();
+    let message = catalogs
+        .iter()
+        .find(|(code, _)| *code == language)
+        .and_then(|(_, catalog)| catalog.get(key))
+        .or_else(|| catalogs[0].1.get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    args.iter()
+        .fold(message.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Placeholder names in a message
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_catalogs_match_english() {
+        let (_, english) = &catalogs()[0];
+        for (code, catalog) in catalogs() {
+            for (key, message) in english {
+                let translated = catalog
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} is missing {}", code, key));
+                assert_eq!(
+                    placeholders(translated),
+                    placeholders(message),
+                    "{} has different placeholders for {}",
+                    code,
+                    key
+                );
+            }
+            assert_eq!(catalog.len(), english.len(), "{} has unknown keys", code);
+        }
+    }
+
+    #[test]
+    fn test_translate_in() {
+        assert_eq!(
+            translate_in(
+                "de",
+                "notifications.friendOnline.title",
+                &[("name", &"Steve")]
+            ),
+            "Steve ist online"
+        );
+        assert_eq!(
+            translate_in("xx", "notifications.winStreak.title", &[("streak", &5)]),
+            "5 win streak!"
+        );
+        assert_eq!(translate_in("en", "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn test_language() {
+        assert_eq!(language("pt-BR"), "pt");
+        assert_eq!(language("FR_fr"), "fr");
+        assert_eq!(language(""), "");
+    }
+
+    #[test]
+    fn test_available_locales() {
+        let locales = available_locales();
+        assert_eq!(locales.len(), CATALOGS.len());
+        assert_eq!(
+            locales[1],
+            Locale {
+                code: "de".to_string(),
+                name: "Deutsch".to_string()
+            }
+        );
+    }
+}
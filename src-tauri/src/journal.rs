@@ -0,0 +1,256 @@
+//! Journal of operations that leave files behind if interrupted.
+//!
+//! A download writes straight to its destination, so a launcher killed
+//! mid-download used to leave a partial file that later looked installed.
+//! Likewise, a launcher update kept for `installUpdatesOnQuit` was lost if
+//! the launcher never quit cleanly. Both are recorded in `operations.json`
+//! in the data directory while they're in progress, and cleaned up on the
+//! next start:
+//! - a partial download is deleted, so it's downloaded again when needed
+//! - a staged launcher update is downloaded again for the next quit if it's
+//!   still the latest release, and deleted otherwise. The file left behind
+//!   is never installed, as only a download checks the update's signature
+//!
+//! What was cleaned up is emitted as `operations-recovered` once the window
+//! has shown.
+
+use crate::updater::LauncherUpdater;
+use crate::utils::{self, AppDir};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Name of the journal in the data directory
+const JOURNAL_FILE: &str = "operations.json";
+
+/// Serializes changes to the journal file
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Found on startup and not dealt with yet
+static INTERRUPTED: Mutex<Vec<Operation>> = Mutex::new(Vec::new());
+
+/// What was cleaned up, kept until it's emitted
+static RECOVERED: Mutex<Vec<RecoveredOperation>> = Mutex::new(Vec::new());
+
+/// An operation in progress
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum Operation {
+    /// A download into `path`, incomplete until it's removed from the
+    /// journal
+    Download { path: PathBuf },
+    /// A launcher update downloaded to `path`, to install when the launcher
+    /// quits
+    StagedUpdate { path: PathBuf, version: String },
+}
+
+impl Operation {
+    fn path(&self) -> &Path {
+        match self {
+            Operation::Download { path } | Operation::StagedUpdate { path, .. } => path,
+        }
+    }
+}
+
+/// What happened to an interrupted operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Recovery {
+    /// Picked up where it left off
+    Resumed,
+    /// Undone, removing what it left behind
+    RolledBack,
+}
+
+/// An interrupted operation and what was done about it, emitted in
+/// `operations-recovered`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredOperation {
+    pub operation: Operation,
+    pub recovery: Recovery,
+}
+
+fn journal_path() -> Result<PathBuf, String> {
+    utils::get_app_path(AppDir::Data, JOURNAL_FILE)
+}
+
+/// Reads the operations in a journal, none if it's missing or malformed
+fn read(path: &Path) -> Vec<Operation> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| match serde_json::from_str(&content) {
+            Ok(operations) => Some(operations),
+            Err(e) => {
+                eprintln!("[journal] Ignoring invalid {}: {}", JOURNAL_FILE, e);
+                None
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Writes a journal through a temporary file, removing it once it's empty
+fn write(path: &Path, operations: &[Operation]) -> Result<(), String> {
+    if operations.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(operations).map_err(|e| e.to_string())?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, content).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp, path).map_err(|e| e.to_string())
+}
+
+/// Changes the operations in the journal
+fn update(f: impl FnOnce(&mut Vec<Operation>)) {
+    let _lock = LOCK.lock().unwrap();
+    let result = journal_path().and_then(|path| {
+        let mut operations = read(&path);
+        f(&mut operations);
+        write(&path, &operations)
+    });
+    if let Err(e) = result {
+        eprintln!("[journal] Failed to update {}: {}", JOURNAL_FILE, e);
+    }
+}
+
+/// Records an operation that's starting, replacing any other on the same
+/// file
+pub(crate) fn record(operation: &Operation) {
+    update(|operations| {
+        operations.retain(|op| op.path() != operation.path());
+        operations.push(operation.clone());
+    });
+}
+
+/// Removes a finished (or cleanly abandoned) operation from the journal
+pub(crate) fn remove(operation: &Operation) {
+    update(|operations| operations.retain(|op| op != operation));
+}
+
+/// Rolls back interrupted downloads, to call on startup before anything
+/// looks at downloaded files.
+///
+/// Staged updates are kept until [`resume`] can check them.
+pub(crate) fn recover() {
+    let _lock = LOCK.lock().unwrap();
+    let Ok(path) = journal_path() else {
+        return;
+    };
+    let (downloads, staged): (Vec<_>, Vec<_>) = read(&path)
+        .into_iter()
+        .partition(|op| matches!(op, Operation::Download { .. }));
+
+    for operation in downloads {
+        println!(
+            "[journal] Removing interrupted download {}",
+            operation.path().display()
+        );
+        remove_file(operation.path());
+        RECOVERED.lock().unwrap().push(RecoveredOperation {
+            operation,
+            recovery: Recovery::RolledBack,
+        });
+    }
+    if let Err(e) = write(&path, &staged) {
+        eprintln!("[journal] Failed to update {}: {}", JOURNAL_FILE, e);
+    }
+    *INTERRUPTED.lock().unwrap() = staged;
+}
+
+/// Keeps or deletes staged updates found by [`recover`], then emits
+/// `operations-recovered` if anything was interrupted
+pub(crate) async fn resume(app: &AppHandle) {
+    let staged = std::mem::take(&mut *INTERRUPTED.lock().unwrap());
+    for operation in staged {
+        let Operation::StagedUpdate { path, version } = &operation else {
+            continue;
+        };
+        let restaged = match app.try_state::<LauncherUpdater>() {
+            Some(updater) => updater.restage(app, version).await.unwrap_or_else(|e| {
+                eprintln!("[journal] Failed to restage launcher {}: {}", version, e);
+                false
+            }),
+            None => false,
+        };
+        if !restaged {
+            remove_file(path);
+            remove(&operation);
+        }
+        RECOVERED.lock().unwrap().push(RecoveredOperation {
+            operation,
+            recovery: if restaged {
+                Recovery::Resumed
+            } else {
+                Recovery::RolledBack
+            },
+        });
+    }
+
+    let recovered = std::mem::take(&mut *RECOVERED.lock().unwrap());
+    if !recovered.is_empty() {
+        let _ = app.emit("operations-recovered", recovered);
+    }
+}
+
+fn remove_file(path: &Path) {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            eprintln!("[journal] Failed to remove {}: {}", path.display(), e)
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_read() {
+        let dir =
+            std::env::temp_dir().join(format!("duelsplus-journal-test-{}", std::process::id()));
+        let path = dir.join(JOURNAL_FILE);
+        let operations = vec![
+            Operation::Download {
+                path: dir.join("duelsplus-1.4.0"),
+            },
+            Operation::StagedUpdate {
+                path: dir.join("launcher-update"),
+                version: "2.1.0".to_string(),
+            },
+        ];
+
+        write(&path, &operations).unwrap();
+        assert_eq!(read(&path), operations);
+
+        // An empty journal isn't kept around
+        write(&path, &[]).unwrap();
+        assert!(!path.exists());
+        assert!(read(&path).is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_operation_format() {
+        let operation = Operation::StagedUpdate {
+            path: PathBuf::from("launcher-update"),
+            version: "2.1.0".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&operation).unwrap(),
+            serde_json::json!({
+                "kind": "stagedUpdate",
+                "path": "launcher-update",
+                "version": "2.1.0",
+            })
+        );
+    }
+}
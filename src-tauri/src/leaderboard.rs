@@ -0,0 +1,303 @@
+//! Duels+ leaderboards, cached and refreshed in the background.
+//!
+//! `get_leaderboard` returns a board from `leaderboards.json` in the stats
+//! cache, fetching it when it's missing, older than [`MAX_AGE_SECS`] or a
+//! refresh is asked for. Boards that have been looked at once are refreshed
+//! by the `leaderboard-refresh` task from then on.
+//!
+//! Every fetch is emitted as `leaderboard-updated`, with how the signed-in
+//! player's rank changed since the last one. Moving up also notifies.
+
+use crate::auth;
+use crate::cache::{self, CacheCategory};
+use crate::i18n::t;
+use crate::notifications::{self, Category};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Name of the cache file in the stats cache
+const CACHE_FILE: &str = "leaderboards.json";
+
+/// How long a board is returned without fetching it again
+const MAX_AGE_SECS: i64 = 10 * 60;
+
+/// Boards loaded from the cache file, `None` until first used
+static CACHE: Mutex<Option<BoardCache>> = Mutex::new(None);
+
+/// One place on a leaderboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    /// 1 for first place
+    pub rank: u32,
+    pub username: String,
+    pub value: f64,
+}
+
+/// A leaderboard, as returned by `get_leaderboard`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Leaderboard {
+    /// The stat it ranks by, e.g. "wins"
+    pub stat: String,
+    /// Best first
+    pub entries: Vec<LeaderboardEntry>,
+    /// The signed-in player's rank, `None` if signed out or unranked
+    pub own_rank: Option<u32>,
+    /// Unix time in seconds
+    pub fetched_at: i64,
+}
+
+/// A fetched leaderboard, emitted as `leaderboard-updated`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardUpdate {
+    pub leaderboard: Leaderboard,
+    /// The player's rank on the last fetch
+    pub previous_rank: Option<u32>,
+    /// Places moved up, negative for down; `None` unless both ranks are
+    /// known
+    pub rank_delta: Option<i64>,
+}
+
+impl LeaderboardUpdate {
+    fn new(leaderboard: Leaderboard, previous_rank: Option<u32>) -> Self {
+        let rank_delta = previous_rank
+            .zip(leaderboard.own_rank)
+            .map(|(previous, rank)| previous as i64 - rank as i64);
+        Self {
+            leaderboard,
+            previous_rank,
+            rank_delta,
+        }
+    }
+}
+
+/// Boards by stat, and the file they're saved to
+#[derive(Debug, Default)]
+struct BoardCache {
+    path: Option<PathBuf>,
+    boards: HashMap<String, Leaderboard>,
+}
+
+impl BoardCache {
+    fn open(path: Option<PathBuf>) -> Self {
+        let boards = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, boards }
+    }
+
+    /// Stores a board, returning the player's rank on the one it replaces
+    fn insert(&mut self, board: Leaderboard) -> Option<u32> {
+        let previous = self
+            .boards
+            .insert(board.stat.clone(), board)
+            .and_then(|previous| previous.own_rank);
+        self.save();
+        previous
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string(&self.boards)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(path, content).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            eprintln!("[leaderboard] Failed to save {}: {}", CACHE_FILE, e);
+        }
+    }
+}
+
+fn with_cache<T>(f: impl FnOnce(&mut BoardCache) -> T) -> T {
+    let mut cache = CACHE.lock().unwrap();
+    let cache = cache.get_or_insert_with(|| {
+        BoardCache::open(
+            cache::dir(CacheCategory::Stats)
+                .ok()
+                .map(|dir| dir.join(CACHE_FILE)),
+        )
+    });
+    f(cache)
+}
+
+/// Whether a name could be a stat, so it's safe to put in a query
+fn is_stat(stat: &str) -> bool {
+    (1..=32).contains(&stat.len()) && stat.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Gets a leaderboard, from the cache unless it's stale or `refresh` is set
+pub(crate) async fn get(app: &AppHandle, stat: &str, refresh: bool) -> Result<Leaderboard, String> {
+    if !is_stat(stat) {
+        return Err(format!("\"{}\" isn't a stat", stat));
+    }
+    let now = chrono::Utc::now().timestamp();
+    let cached = with_cache(|cache| cache.boards.get(stat).cloned());
+    match cached {
+        Some(board) if !refresh && now - board.fetched_at < MAX_AGE_SECS => Ok(board),
+        _ => fetch(app, stat).await,
+    }
+}
+
+/// Refreshes every board that's been looked at, for the scheduler
+pub(crate) async fn refresh_all(app: AppHandle) -> Result<(), String> {
+    let stats: Vec<String> = with_cache(|cache| cache.boards.keys().cloned().collect());
+    let mut failed = None;
+    for stat in stats {
+        if let Err(e) = fetch(&app, &stat).await {
+            failed = Some(format!("{}: {}", stat, e));
+        }
+    }
+    failed.map_or(Ok(()), Err)
+}
+
+async fn fetch(app: &AppHandle, stat: &str) -> Result<Leaderboard, String> {
+    let token = auth::token::get_token().await.ok().flatten();
+    let response = auth::api::get_leaderboard(stat, token.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    let board = match response.data {
+        Some(data) if response.success => parse(stat, &data, chrono::Utc::now().timestamp()),
+        _ => {
+            return Err(response
+                .message
+                .unwrap_or_else(|| "The API returned no leaderboard".to_string()))
+        }
+    };
+
+    let previous = with_cache(|cache| cache.insert(board.clone()));
+    let update = LeaderboardUpdate::new(board.clone(), previous);
+    if let (Some(delta), Some(rank), Some(previous)) = (update.rank_delta, board.own_rank, previous)
+    {
+        if delta > 0 {
+            notifications::notify(
+                app,
+                Category::LeaderboardRank,
+                t!("notifications.leaderboardRank.title", rank = rank),
+                t!(
+                    "notifications.leaderboardRank.body",
+                    previous = previous,
+                    stat = stat
+                ),
+            );
+        }
+    }
+    let _ = app.emit("leaderboard-updated", &update);
+    Ok(board)
+}
+
+/// Reads the API's leaderboard, `{ "entries": [...], "self": { "rank" } }`,
+/// where `self` is only there for a signed-in player who's ranked
+fn parse(stat: &str, data: &Value, now: i64) -> Leaderboard {
+    let entries: Vec<LeaderboardEntry> = data
+        .get("entries")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            Some(LeaderboardEntry {
+                rank: entry
+                    .get("rank")
+                    .and_then(Value::as_u64)
+                    .map_or(i as u32 + 1, |rank| rank as u32),
+                username: entry.get("username")?.as_str()?.to_string(),
+                value: entry.get("value").and_then(Value::as_f64).unwrap_or(0.0),
+            })
+        })
+        .collect();
+    Leaderboard {
+        stat: stat.to_string(),
+        entries,
+        own_rank: data
+            .pointer("/self/rank")
+            .and_then(Value::as_u64)
+            .map(|rank| rank as u32),
+        fetched_at: now,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        let data = json!({
+            "entries": [
+                { "rank": 1, "username": "Steve", "value": 900 },
+                { "username": "Alex", "value": 850.5 },
+                { "rank": 3, "value": 12 },
+            ],
+            "self": { "rank": 42, "value": 120 },
+        });
+        let board = parse("wins", &data, 100);
+        assert_eq!(board.entries.len(), 2);
+        assert_eq!(board.entries[1].rank, 2);
+        assert_eq!(board.entries[1].value, 850.5);
+        assert_eq!(board.own_rank, Some(42));
+
+        assert_eq!(parse("wins", &json!({}), 100).entries, vec![]);
+    }
+
+    #[test]
+    fn test_rank_delta() {
+        let board = |own_rank| Leaderboard {
+            stat: "wins".to_string(),
+            entries: vec![],
+            own_rank,
+            fetched_at: 0,
+        };
+        assert_eq!(
+            LeaderboardUpdate::new(board(Some(7)), Some(10)).rank_delta,
+            Some(3)
+        );
+        assert_eq!(
+            LeaderboardUpdate::new(board(Some(12)), Some(10)).rank_delta,
+            Some(-2)
+        );
+        assert_eq!(
+            LeaderboardUpdate::new(board(None), Some(10)).rank_delta,
+            None
+        );
+        assert_eq!(
+            LeaderboardUpdate::new(board(Some(7)), None).rank_delta,
+            None
+        );
+    }
+
+    #[test]
+    fn test_insert_returns_previous_rank() {
+        let mut cache = BoardCache::default();
+        let board = |own_rank| Leaderboard {
+            stat: "wins".to_string(),
+            entries: vec![],
+            own_rank,
+            fetched_at: 0,
+        };
+        assert_eq!(cache.insert(board(Some(10))), None);
+        assert_eq!(cache.insert(board(Some(7))), Some(10));
+    }
+
+    #[test]
+    fn test_is_stat() {
+        assert!(is_stat("wins"));
+        assert!(is_stat("best_winstreak"));
+        assert!(!is_stat(""));
+        assert!(!is_stat("wins&stat=losses"));
+    }
+}
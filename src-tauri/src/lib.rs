@@ -3,17 +3,106 @@
 //! This module initializes the Tauri application and registers all
 //! available commands that can be invoked from the frontend.
 
+mod applock;
 mod auth;
+mod autostart;
+mod cache;
+mod cli;
 mod commands;
 mod config;
+mod crash_handler;
+mod deeplink;
+mod diagnostics;
+mod friends;
+mod hotkeys;
+mod hypixel;
+mod i18n;
+mod journal;
+mod leaderboard;
+mod logs_window;
+mod mc;
+mod migrate;
+mod network;
+mod notification_router;
+mod notifications;
+mod obs;
+mod overlay;
+mod perf;
+mod ping;
+mod power;
 mod proxy;
 mod rpc;
+mod scheduler;
+mod secrets;
+mod service_status;
+mod session_stats;
+mod startup;
+mod state_store;
+mod storage;
+mod streamer;
+mod telemetry;
+mod tray;
+mod updater;
 mod utils;
 
+use applock::AppLock;
 use commands::*;
+use hotkeys::HotkeyManager;
+use network::{NetConnectivity, NetworkStatus};
+use notification_router::{NotificationAction, Route};
+use overlay::Overlay;
 use proxy::ProxyManager;
 use rpc::RpcManager;
-use tauri::{Manager, WindowEvent};
+use scheduler::Scheduler;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, RunEvent, WindowEvent};
+use updater::LauncherUpdater;
+
+/// Set once startup tasks have applied the saved config
+static READY: AtomicBool = AtomicBool::new(false);
+
+/// Set when shutting down has started, so closing twice doesn't run it twice
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Set when shutting down has finished and the launcher may exit
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Whether startup tasks have finished, for pages opened after the
+/// `launcher-ready` event
+pub(crate) fn is_ready() -> bool {
+    READY.load(Ordering::SeqCst)
+}
+
+/// Stops the proxy and any download and clears the Discord presence,
+/// before the launcher quits
+async fn shutdown(app: &AppHandle) {
+    if let Some(proxy) = app.try_state::<ProxyManager>() {
+        let report = proxy.shutdown_all().await;
+        println!("[launcher] Shutdown: {:?}", report);
+    }
+    // Clear the Discord presence right away instead of at exit
+    if let Some(rpc) = app.try_state::<RpcManager>() {
+        rpc.shutdown_and_wait().await;
+    }
+    if let Some(updater) = app.try_state::<LauncherUpdater>() {
+        updater.install_pending();
+    }
+}
+
+/// Shuts down in the background and then quits, keeping the main thread
+/// free while the proxy stops
+pub(crate) fn shutdown_and_exit(app: &AppHandle) {
+    if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        shutdown(&app).await;
+        SHUT_DOWN.store(true, Ordering::SeqCst);
+        app.exit(0);
+    });
+}
 
 /// Initializes and runs the Tauri application.
 ///
@@ -21,11 +110,39 @@ use tauri::{Manager, WindowEvent};
 /// all authentication-related commands for frontend access.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let context = tauri::generate_context!();
+    crash_handler::install(context.package_info().version.to_string());
+
+    // The panic hook already reported the crash; exit instead of unwinding
+    // any further
+    if std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run_app(context))).is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// Builds the Tauri application and runs its event loop
+fn run_app(context: tauri::Context) {
     #[cfg(target_os = "linux")]
     if std::env::var_os("WEBKIT_DISABLE_DMABUF_RENDERER").is_none() {
         std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
     }
 
+    // A link opened or a shortcut run while the launcher is running starts
+    // a second process; hand its arguments to the open launcher instead.
+    // With only `--minimized` (or `--tray-only`) there's nothing to hand
+    // over, but the launcher is already running
+    let args = cli::args();
+    let links = args.to_links();
+    if (!links.is_empty() || args.minimized || args.tray_only) && deeplink::forward(&links) {
+        return;
+    }
+
+    startup::begin();
+
+    // Before anything reads the config or the installed proxy
+    migrate::run();
+    journal::recover();
+
     // Create RPC manager (is_dev will be set in setup hook)
     let rpc_manager = RpcManager::new(false); // Temporary, will be updated in setup
 
@@ -33,9 +150,16 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .manage(ProxyManager::new())
         .manage(rpc_manager)
+        .manage(NetConnectivity::new())
+        .manage(HotkeyManager::new())
+        .manage(LauncherUpdater::new())
+        .manage(Scheduler::new())
+        .manage(Overlay::new())
+        .manage(AppLock::new())
         .invoke_handler(tauri::generate_handler![
             // Authentication handling
             token_exists,
@@ -48,11 +172,29 @@ pub fn run() {
             get_global_stats,
             check_api_status,
             get_status,
+            get_network_status,
+            recheck_network,
+            get_service_status,
             start_discord_signin,
             // Process management
             launch_proxy,
             stop_proxy,
             get_proxy_status,
+            validate_launch_environment,
+            get_connection_state,
+            get_proxy_metrics,
+            send_proxy_stdin,
+            check_firewall,
+            create_firewall_rule,
+            shutdown_all,
+            upload_crash_report,
+            export_diagnostics,
+            upload_logs,
+            take_launcher_crash,
+            is_launcher_ready,
+            send_proxy_command,
+            get_orphan_proxy,
+            kill_orphan_proxy,
             fetch_releases,
             // Configuration management
             legacy_config_exists,
@@ -63,14 +205,88 @@ pub fn run() {
             get_config_value,
             set_config_key,
             save_config,
+            // Autostart
+            set_autostart,
+            get_autostart,
+            get_start_mode,
+            get_start_page,
+            // App lock
+            get_app_lock_status,
+            unlock_app,
+            lock_app,
+            set_app_pin,
+            // Global shortcuts
+            get_hotkey_status,
+            // Overlay
+            toggle_overlay,
+            get_overlay_data,
+            get_obs_status,
+            // Logs window
+            open_logs_window,
+            get_recent_logs,
+            // Launcher updates
+            check_launcher_update,
+            download_launcher_update,
+            defer_launcher_update,
+            // Disk usage
+            get_disk_usage,
+            clear_cache,
+            // UI state
+            get_app_state,
+            set_app_state,
+            // Telemetry
+            get_telemetry_preview,
+            // Performance
+            record_command_timings,
+            get_perf_metrics,
+            // Ping
+            measure_ping,
+            // Match history
+            get_match_history,
+            get_match_timeline,
+            // Stat trends
+            take_stats_snapshot,
+            get_stats_trend,
+            get_stats_digest,
+            export_stats,
+            // Mojang
+            resolve_username,
+            resolve_uuid,
+            // Hypixel
+            set_hypixel_api_key,
+            lookup_player,
+            // Leaderboards
+            get_leaderboard,
+            // Friends
+            get_friends_presence,
+            // Session stats
+            get_session_stats,
+            reset_session_stats,
+            // Proxy addons
+            list_addons,
+            install_addon,
+            uninstall_addon,
+            set_addon_enabled,
+            // Background tasks
+            list_scheduled_tasks,
+            run_task_now,
+            // Translations
+            get_available_locales,
             // Discord RPC
             rpc_set_enabled,
+            rpc_set_privacy,
+            set_streamer_mode,
+            rpc_preview,
+            rpc_set_launcher_profile,
+            get_session_summary,
+            get_unknown_modes,
             rpc_is_enabled,
+            rpc_get_state,
             rpc_set_activity,
             rpc_set_image,
             rpc_get_valid_image_keys,
         ])
-        .setup(|app| {
+        .setup(move |app| {
             // Fix xdg-open in AppImages
             if std::env::var("APPIMAGE").is_ok() {
                 if let Ok(current_path) = std::env::var("PATH") {
@@ -86,40 +302,383 @@ pub fn run() {
                 rpc.set_dev_mode(is_dev);
             }
 
-            // Load config and apply RPC settings
-            let rpc_state = app.try_state::<RpcManager>();
-            if let Some(rpc) = rpc_state {
-                // Try to load saved config and apply RPC settings
-                // Use tokio runtime to run the async config loading
-                let rt = tokio::runtime::Runtime::new().unwrap();
-                if let Ok(Some(cfg)) = rt.block_on(config::manager::get_config()) {
-                    rpc.set_enabled(cfg.enable_rpc);
-                    rpc.set_anonymization(cfg.rpc_anonymize_profile, cfg.rpc_anonymize_location);
-                    // Apply saved RPC image (ignore errors for invalid keys)
-                    let _ = rpc.set_image(&cfg.rpc_image);
+            // Forward RPC status and join events
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                // Relay accepted "Ask to Join" requests to the proxy, which
+                // arranges the party invite
+                let join_handle = app.handle().clone();
+                // Let the settings page show whether Discord is reachable
+                let status_handle = app.handle().clone();
+                rpc.on_status(move |status| {
+                    let _ = status_handle.emit("rpc-status", status);
+                });
+                rpc.on_join(move |party_id| {
+                    let app_handle = join_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Some(proxy) = app_handle.try_state::<ProxyManager>() {
+                            let command = proxy::models::ControlCommand::JoinParty { party_id };
+                            if let Err(e) = proxy.send_control_command(&command).await {
+                                eprintln!("[rpc] Failed to relay party join: {}", e);
+                            }
+                        }
+                    });
+                });
+            }
+
+            // Tell the user about a proxy update before their next launch;
+            // also warms the release list for the first `fetch_releases`
+            let prefetch_handle = app.handle().clone();
+            let prefetch = tauri::async_runtime::spawn(async move {
+                let use_beta = config::manager::get_config()
+                    .await
+                    .ok()
+                    .flatten()
+                    .is_some_and(|cfg| cfg.receive_beta_releases);
+                match proxy::download::find_pending_update(use_beta).await {
+                    Ok(Some(version)) => notifications::notify(
+                        &prefetch_handle,
+                        notifications::Category::Update,
+                        i18n::t!("notifications.update.title"),
+                        i18n::t!("notifications.update.body", version = version),
+                    ),
+                    Ok(None) => {}
+                    Err(e) => eprintln!("[proxy] Failed to check for updates: {}", e),
+                }
+                startup::mark("releases");
+            });
+
+            // Load the config and apply it off the main thread, then tell
+            // the frontend everything is set up
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let (cfg, token) = tokio::join!(
+                    async {
+                        let cfg = config::manager::get_config().await.ok().flatten();
+                        startup::mark("config");
+                        cfg
+                    },
+                    async {
+                        let token = auth::token::get_token().await.ok().flatten();
+                        startup::mark("token");
+                        token
+                    },
+                );
+                if let Some(cfg) = &cfg {
+                    i18n::set_locale(&cfg.locale);
+                    streamer::set_enabled(cfg.streamer_mode);
+                    telemetry::set_enabled(cfg.telemetry);
+                    telemetry::record_launch();
+                    obs::apply(&app_handle, cfg);
+                }
+                if let Some(token) = &token {
+                    streamer::hide(token);
+                }
+                if let Some(rpc) = app_handle.try_state::<RpcManager>() {
+                    if let Some(cfg) = &cfg {
+                        rpc.set_enabled(cfg.enable_rpc);
+                        let (profile, location) = streamer::rpc_privacy(
+                            cfg.rpc_anonymize_profile,
+                            cfg.rpc_anonymize_location,
+                        );
+                        rpc.set_anonymization(profile, location);
+                        // Apply saved RPC image (ignore errors for invalid keys)
+                        let _ = rpc.set_image(&cfg.rpc_image);
+                        rpc.set_buttons(cfg.rpc_buttons);
+                        rpc.set_show_streak(cfg.rpc_show_streak);
+                        rpc.set_show_launcher_profile(cfg.rpc_launcher_profile);
+                        rpc.set_timer_mode(cfg.rpc_timer_mode);
+                        rpc.set_locale(&cfg.locale);
+                        rpc.set_afk_timeout(cfg.rpc_afk_timeout_mins);
+                        let _ = rpc.set_client_id(&cfg.rpc_client_id);
+                        rpc.set_discord_client(cfg.rpc_discord_client);
+                    }
+                }
+
+                // Register the saved global shortcuts
+                if let Some(hotkeys) = app_handle.try_state::<HotkeyManager>() {
+                    let keybinds = cfg
+                        .as_ref()
+                        .map(|cfg| cfg.keybinds.clone())
+                        .unwrap_or_default();
+                    let status = hotkeys.apply(&app_handle, &keybinds);
+                    let _ = app_handle.emit("hotkeys-status", status);
+                }
+
+                // Rewrite the login entry in case the launcher moved
+                if let Some(cfg) = cfg.as_ref().filter(|cfg| cfg.autostart) {
+                    if let Err(e) = autostart::set_enabled(true, cfg.autostart_minimized) {
+                        eprintln!("[autostart] Failed to update login entry: {}", e);
+                    }
+                }
+
+                // `--launch-proxy` already launched it
+                if !cli::args().launch_proxy
+                    && cfg.as_ref().is_some_and(|cfg| cfg.launch_proxy_on_start)
+                {
+                    deeplink::launch_proxy(&app_handle, None);
                 }
+
+                if let Some(report) = migrate::take_report() {
+                    let _ = app_handle.emit("data-migrated", report);
+                }
+
+                READY.store(true, Ordering::SeqCst);
+                let _ = app_handle.emit("launcher-ready", ());
+                startup::mark("ready");
+
+                // Connecting to Discord can take a moment; let the window
+                // show first
+                startup::first_frame().await;
+
+                // Start and connect RPC
+                if let Some(rpc) = app_handle.try_state::<RpcManager>() {
+                    rpc.start();
+                    rpc.connect();
+                }
+                startup::mark("rpc");
+
+                let _ = prefetch.await;
+                let _ = app_handle.emit("startup-timings", startup::timings());
+
+                // May check for a launcher update, so once the rest is done
+                journal::resume(&app_handle).await;
+            });
+
+            // Pick up display names for modes added since this release
+            tauri::async_runtime::spawn(async {
+                if let Err(e) = rpc::names::refresh().await {
+                    eprintln!("[rpc] Failed to refresh display names: {}", e);
+                }
+            });
+
+            // Periodic work; the frontend offers updates found as
+            // `launcher-update-available`
+            if let Some(scheduler) = app.try_state::<Scheduler>() {
+                let six_hours = Duration::from_secs(6 * 60 * 60);
+                let half_hour = Duration::from_secs(30 * 60);
+                scheduler.register(app.handle(), "rpc-names", six_hours, half_hour, |_| {
+                    rpc::names::refresh()
+                });
+                scheduler.register(
+                    app.handle(),
+                    "launcher-update",
+                    six_hours,
+                    half_hour,
+                    |app| async move {
+                        let updater = app.state::<LauncherUpdater>();
+                        if let Some(update) = updater.check(&app, false).await? {
+                            notifications::notify_with(
+                                &app,
+                                notifications::Category::Update,
+                                Route::action(NotificationAction::ShowUpdate),
+                                i18n::t!("notifications.launcherUpdate.title"),
+                                i18n::t!(
+                                    "notifications.launcherUpdate.body",
+                                    version = update.version
+                                ),
+                            );
+                        }
+                        Ok(())
+                    },
+                );
+                scheduler.register(
+                    app.handle(),
+                    "telemetry",
+                    Duration::from_secs(24 * 60 * 60),
+                    Duration::from_secs(60 * 60),
+                    telemetry::upload,
+                );
+                // Checked hourly, but only snapshots once a day
+                scheduler.register(
+                    app.handle(),
+                    "stat-snapshot",
+                    Duration::from_secs(60 * 60),
+                    Duration::from_secs(10 * 60),
+                    |_| async { storage::snapshots::snapshot(false).await.map(|_| ()) },
+                );
+                // Checked hourly, but only recaps on Mondays
+                scheduler.register(
+                    app.handle(),
+                    "stats-digest",
+                    Duration::from_secs(60 * 60),
+                    Duration::from_secs(10 * 60),
+                    storage::digest::check_weekly,
+                );
+                scheduler.register(
+                    app.handle(),
+                    "leaderboard-refresh",
+                    Duration::from_secs(15 * 60),
+                    Duration::from_secs(5 * 60),
+                    leaderboard::refresh_all,
+                );
+                scheduler.register(
+                    app.handle(),
+                    "friends-presence",
+                    Duration::from_secs(60),
+                    Duration::from_secs(15),
+                    friends::poll,
+                );
+                // Does nothing while the latency indicator is off
+                scheduler.register(
+                    app.handle(),
+                    "ping",
+                    Duration::from_secs(30),
+                    Duration::from_secs(5),
+                    ping::sample,
+                );
+                scheduler.register(
+                    app.handle(),
+                    "cache-cleanup",
+                    Duration::from_secs(24 * 60 * 60),
+                    Duration::from_secs(60 * 60),
+                    |_| async {
+                        let freed = tauri::async_runtime::spawn_blocking(cache::clean_up)
+                            .await
+                            .map_err(|e| e.to_string())?;
+                        if freed > 0 {
+                            println!("[cache] Cleaned up {} bytes", freed);
+                        }
+                        Ok(())
+                    },
+                );
             }
 
-            // Start and connect RPC
-            if let Some(rpc) = app.try_state::<RpcManager>() {
-                rpc.start();
-                rpc.connect();
+            // Watch connectivity; names missed while offline are fetched
+            // again once the API is back
+            if let Some(net) = app.try_state::<NetConnectivity>() {
+                let app_handle = app.handle().clone();
+                net.on_change(move |status| {
+                    let _ = app_handle.emit("network-status", status);
+                    if status == NetworkStatus::Online {
+                        tauri::async_runtime::spawn(async {
+                            if let Err(e) = rpc::names::refresh().await {
+                                eprintln!("[rpc] Failed to refresh display names: {}", e);
+                            }
+                        });
+                    }
+                });
+            }
+            network::monitor(app.handle());
+            power::watch(app.handle());
+
+            // Run global shortcut actions
+            if let Some(hotkeys) = app.try_state::<HotkeyManager>() {
+                let app_handle = app.handle().clone();
+                hotkeys.on_trigger(move |action| hotkeys::handle(&app_handle, action));
             }
 
+            if let Err(e) = tray::create(app.handle()) {
+                eprintln!("[tray] Failed to create tray icon: {}", e);
+            }
+
+            // The window isn't created with the app, so tray-only mode can
+            // start without one
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let tray_only = args.tray_only
+                    || config::manager::get_config()
+                        .await
+                        .ok()
+                        .flatten()
+                        .is_some_and(|cfg| cfg.tray_only);
+                tray::start(&app_handle, tray_only);
+            });
+
+            // Handle duelsplus:// links
+            deeplink::listen(app.handle());
+            for link in &args.links {
+                deeplink::handle(app.handle(), link);
+            }
+            if args.launch_proxy {
+                deeplink::launch_proxy(app.handle(), args.port);
+            }
+            tauri::async_runtime::spawn_blocking(|| {
+                if let Err(e) = deeplink::register() {
+                    eprintln!("[deeplink] {}", e);
+                }
+            });
+
+            // Clean up stale lock files and adopt or report leftover proxies
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(proxy) = app_handle.try_state::<ProxyManager>() {
+                    proxy.reconcile_lock_file(app_handle.clone()).await;
+                }
+            });
+
             Ok(())
         })
+        .on_page_load(|webview, payload| {
+            if webview.label() == tray::MAIN_WINDOW
+                && payload.event() == tauri::webview::PageLoadEvent::Finished
+            {
+                startup::on_first_frame();
+            }
+        })
         .on_window_event(|window, event| {
-            // Stop the proxy when the window is closed
-            if let WindowEvent::CloseRequested { .. } = event {
-                let app = window.app_handle();
-                if let Some(proxy) = app.try_state::<ProxyManager>() {
-                    // Use tauri's async runtime to stop the proxy
-                    tauri::async_runtime::block_on(async {
-                        let _ = proxy.stop().await;
-                    });
-                }
+            if window.label() == overlay::OVERLAY_WINDOW {
+                overlay::on_window_event(window, event);
+                return;
+            }
+            // The logs window just closes
+            if window.label() == logs_window::LOGS_WINDOW {
+                return;
+            }
+
+            // Clicking a notification focuses the launcher; follow its route
+            if let WindowEvent::Focused(true) = event {
+                notification_router::on_focus(window.app_handle());
+            }
+
+            // Stop the proxy and any download when the window is closed,
+            // unless the proxy should keep running
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                // Decide off the main thread; the launcher quits (or hides)
+                // once that's done
+                api.prevent_close();
+                let app = window.app_handle().clone();
+                let window = window.clone();
+                tauri::async_runtime::spawn(async move {
+                    // Hide to the tray instead, keeping the proxy and RPC
+                    // alive; only if the tray icon exists to bring the window
+                    // back
+                    let minimize_to_tray = config::manager::get_config()
+                        .await
+                        .ok()
+                        .flatten()
+                        .is_some_and(|cfg| cfg.minimize_to_tray);
+                    if tray::is_tray_only() {
+                        // Free the webview; the tray creates it again
+                        let _ = window.destroy();
+                    } else if minimize_to_tray && tray::is_available(&app) {
+                        let _ = window.hide();
+                    } else {
+                        shutdown_and_exit(&app);
+                    }
+                });
             }
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            match event {
+                // Tray-only mode keeps running with no windows left
+                RunEvent::ExitRequested {
+                    api, code: None, ..
+                } if tray::is_tray_only() => api.prevent_exit(),
+                // Quitting without closing the window (e.g. Cmd+Q on macOS);
+                // shut down first, then exit for real
+                RunEvent::ExitRequested { api, .. } if !SHUT_DOWN.load(Ordering::SeqCst) => {
+                    api.prevent_exit();
+                    shutdown_and_exit(app);
+                }
+                #[cfg(target_os = "macos")]
+                RunEvent::Opened { urls } => {
+                    for url in urls {
+                        deeplink::handle(app, url.as_str());
+                    }
+                }
+                _ => {}
+            }
+        });
 }
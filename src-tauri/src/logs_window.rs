@@ -0,0 +1,62 @@
+//! Log viewer in its own window.
+//!
+//! `open_logs_window` shows the logs in a separate, resizable window, so
+//! they can stay on a second monitor while playing. The window loads the
+//! same page as the launcher, which shows only the logs under this label,
+//! and receives `log-message` like the launcher window does. It starts with
+//! the proxy's recent output, from `get_recent_logs`.
+//!
+//! While the window is open, a proxy launched with `openLogsOnLaunch` set
+//! brings it up, instead of the launcher switching to its Logs tab.
+
+use crate::config;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Label of the logs window
+pub(crate) const LOGS_WINDOW: &str = "logs";
+
+const WIDTH: f64 = 900.0;
+const HEIGHT: f64 = 600.0;
+const MIN_WIDTH: f64 = 480.0;
+const MIN_HEIGHT: f64 = 320.0;
+
+/// Shows and focuses the logs window, creating it if it isn't open
+pub(crate) fn open(app: &AppHandle) -> Result<(), String> {
+    let window = match app.get_webview_window(LOGS_WINDOW) {
+        Some(window) => window,
+        None => create(app).map_err(|e| format!("Failed to open the logs window: {}", e))?,
+    };
+    let _ = window.unminimize();
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+fn create(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    WebviewWindowBuilder::new(app, LOGS_WINDOW, WebviewUrl::default())
+        .title("Duels+ Logs")
+        .inner_size(WIDTH, HEIGHT)
+        .min_inner_size(MIN_WIDTH, MIN_HEIGHT)
+        .resizable(true)
+        .build()
+}
+
+/// Brings up the logs window if it's open and `openLogsOnLaunch` is set,
+/// to call when the proxy has launched
+pub(crate) fn on_proxy_launched(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(LOGS_WINDOW) else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        let open_logs = config::manager::get_config()
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .open_logs_on_launch;
+        if open_logs {
+            // Not focused, the game is probably starting
+            let _ = window.unminimize();
+            let _ = window.show();
+        }
+    });
+}
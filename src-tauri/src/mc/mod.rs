@@ -0,0 +1,3 @@
+//! Minecraft services used by the launcher.
+
+pub(crate) mod mojang;
@@ -0,0 +1,360 @@
+//! Username and UUID lookups through the Mojang API.
+//!
+//! `resolve_username` finds the UUID and exact name of an account from its
+//! name, and `resolve_uuid` finds the current name of a UUID. Lookups,
+//! including accounts that don't exist, are cached in `mojang.json` in the
+//! stats cache, so the same player isn't looked up again for a while.
+//!
+//! Mojang rate limits lookups per IP. When it answers 429, nothing is sent
+//! until its `Retry-After` has passed; cached results are used meanwhile,
+//! even stale ones.
+
+use crate::cache::{self, CacheCategory};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const PROFILE_BY_NAME_URL: &str = "https://api.mojang.com/users/profiles/minecraft";
+const PROFILE_BY_UUID_URL: &str = "https://sessionserver.mojang.com/session/minecraft/profile";
+
+/// Name of the cache file in the stats cache
+const CACHE_FILE: &str = "mojang.json";
+
+/// How long a found account is trusted; names change at most once a month
+const FOUND_MAX_AGE_SECS: i64 = 24 * 60 * 60;
+
+/// How long an unknown name or UUID is trusted, since the name may be
+/// taken soon
+const MISSING_MAX_AGE_SECS: i64 = 60 * 60;
+
+/// Wait after a 429 without `Retry-After`
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lookups loaded from the cache file, `None` until first used
+static CACHE: Mutex<Option<ProfileCache>> = Mutex::new(None);
+
+/// No lookups are sent before this, after Mojang rate limited us
+static BLOCKED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// A Minecraft account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MojangProfile {
+    /// With dashes
+    pub uuid: String,
+    /// Current name, in its exact case
+    pub name: String,
+}
+
+/// A profile as the Mojang API returns it
+#[derive(Debug, Deserialize)]
+struct ApiProfile {
+    /// Without dashes
+    id: String,
+    name: String,
+}
+
+/// A lookup and when it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    /// `None` if the account doesn't exist
+    profile: Option<MojangProfile>,
+    /// Unix time in seconds
+    fetched_at: i64,
+}
+
+impl Entry {
+    fn is_fresh(&self, now: i64) -> bool {
+        let max_age = if self.profile.is_some() {
+            FOUND_MAX_AGE_SECS
+        } else {
+            MISSING_MAX_AGE_SECS
+        };
+        now - self.fetched_at < max_age
+    }
+}
+
+/// Lookups by key (`name:<lowercase name>` or `uuid:<undashed uuid>`), and
+/// the file they're saved to
+#[derive(Debug, Default)]
+struct ProfileCache {
+    path: Option<PathBuf>,
+    entries: HashMap<String, Entry>,
+}
+
+impl ProfileCache {
+    fn open(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    /// Records a lookup under `key`, and a found account under its UUID too
+    fn insert(&mut self, key: String, profile: Option<MojangProfile>, now: i64) {
+        // Drop what's expired anyway, so the file doesn't only grow
+        self.entries
+            .retain(|_, entry| now - entry.fetched_at < FOUND_MAX_AGE_SECS);
+        if let Some(profile) = &profile {
+            self.entries.insert(
+                uuid_key(&profile.uuid),
+                Entry {
+                    profile: Some(profile.clone()),
+                    fetched_at: now,
+                },
+            );
+        }
+        self.entries.insert(
+            key,
+            Entry {
+                profile,
+                fetched_at: now,
+            },
+        );
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string(&self.entries)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(path, content).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            eprintln!("[mojang] Failed to save {}: {}", CACHE_FILE, e);
+        }
+    }
+}
+
+fn with_cache<T>(f: impl FnOnce(&mut ProfileCache) -> T) -> T {
+    let mut cache = CACHE.lock().unwrap();
+    let cache = cache.get_or_insert_with(|| {
+        ProfileCache::open(
+            cache::dir(CacheCategory::Stats)
+                .ok()
+                .map(|dir| dir.join(CACHE_FILE)),
+        )
+    });
+    f(cache)
+}
+
+/// Formats a UUID with dashes, `None` if it isn't one
+pub(crate) fn dashed(uuid: &str) -> Option<String> {
+    let hex: String = uuid.chars().filter(|c| *c != '-').collect();
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_lowercase();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+/// Whether a name could be a Minecraft username
+fn is_username(name: &str) -> bool {
+    (1..=16).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn name_key(name: &str) -> String {
+    format!("name:{}", name.to_ascii_lowercase())
+}
+
+fn uuid_key(uuid: &str) -> String {
+    format!("uuid:{}", uuid.replace('-', ""))
+}
+
+/// Finds the account with a username, `None` if there's none
+pub(crate) async fn resolve_username(name: &str) -> Result<Option<MojangProfile>, String> {
+    let name = name.trim();
+    if !is_username(name) {
+        return Err(format!("\"{}\" isn't a Minecraft username", name));
+    }
+    lookup(name_key(name), format!("{}/{}", PROFILE_BY_NAME_URL, name)).await
+}
+
+/// Finds the account with a UUID, `None` if there's none
+pub(crate) async fn resolve_uuid(uuid: &str) -> Result<Option<MojangProfile>, String> {
+    let Some(uuid) = dashed(uuid.trim()) else {
+        return Err(format!("\"{}\" isn't a UUID", uuid));
+    };
+    lookup(
+        uuid_key(&uuid),
+        format!("{}/{}", PROFILE_BY_UUID_URL, uuid.replace('-', "")),
+    )
+    .await
+}
+
+/// Gets a lookup from the cache, or from Mojang if it's missing or stale
+async fn lookup(key: String, url: String) -> Result<Option<MojangProfile>, String> {
+    let now = chrono::Utc::now().timestamp();
+    let cached = with_cache(|cache| cache.entries.get(&key).cloned());
+    if let Some(entry) = cached.as_ref().filter(|entry| entry.is_fresh(now)) {
+        return Ok(entry.profile.clone());
+    }
+
+    let blocked = BLOCKED_UNTIL
+        .lock()
+        .unwrap()
+        .and_then(|until| until.checked_duration_since(Instant::now()));
+    if let Some(wait) = blocked {
+        return match cached {
+            Some(entry) => Ok(entry.profile),
+            None => Err(rate_limited(wait)),
+        };
+    }
+
+    match fetch(&url).await {
+        Ok(profile) => {
+            with_cache(|cache| cache.insert(key, profile.clone(), now));
+            Ok(profile)
+        }
+        Err(e) => match cached {
+            // Better an old name than none
+            Some(entry) => {
+                eprintln!("[mojang] Using a stale lookup: {}", e);
+                Ok(entry.profile)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+async fn fetch(url: &str) -> Result<Option<MojangProfile>, String> {
+    let response = reqwest::Client::new()
+        .get(url)
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Mojang: {}", e))?;
+
+    match response.status() {
+        StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(None),
+        StatusCode::TOO_MANY_REQUESTS => {
+            let wait = retry_after(
+                response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok()),
+            );
+            *BLOCKED_UNTIL.lock().unwrap() = Some(Instant::now() + wait);
+            eprintln!("[mojang] Rate limited for {}s", wait.as_secs());
+            Err(rate_limited(wait))
+        }
+        status if status.is_success() => {
+            let profile: ApiProfile = response
+                .json()
+                .await
+                .map_err(|e| format!("Invalid Mojang profile: {}", e))?;
+            let uuid = dashed(&profile.id)
+                .ok_or_else(|| format!("Invalid UUID from Mojang: {}", profile.id))?;
+            Ok(Some(MojangProfile {
+                uuid,
+                name: profile.name,
+            }))
+        }
+        status => Err(format!("Mojang returned {}", status)),
+    }
+}
+
+/// How long to wait after a 429, from its `Retry-After` in seconds
+fn retry_after(header: Option<&str>) -> Duration {
+    header
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+fn rate_limited(wait: Duration) -> String {
+    format!(
+        "Mojang is rate limiting lookups, try again in {}s",
+        wait.as_secs().max(1)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashed() {
+        assert_eq!(
+            dashed("069A79F444E94726A5BEFCA90E38AAF5").as_deref(),
+            Some("069a79f4-44e9-4726-a5be-fca90e38aaf5")
+        );
+        assert_eq!(
+            dashed("069a79f4-44e9-4726-a5be-fca90e38aaf5").as_deref(),
+            Some("069a79f4-44e9-4726-a5be-fca90e38aaf5")
+        );
+        assert_eq!(dashed("../../etc/passwd"), None);
+        assert_eq!(dashed("069a79f444e94726a5befca90e38aaf"), None);
+    }
+
+    #[test]
+    fn test_is_username() {
+        assert!(is_username("Notch"));
+        assert!(is_username("jeb_"));
+        assert!(!is_username(""));
+        assert!(!is_username("a name"));
+        assert!(!is_username("seventeen_letters"));
+    }
+
+    #[test]
+    fn test_entry_freshness() {
+        let found = Entry {
+            profile: Some(MojangProfile {
+                uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+                name: "Notch".to_string(),
+            }),
+            fetched_at: 0,
+        };
+        assert!(found.is_fresh(MISSING_MAX_AGE_SECS + 1));
+        assert!(!found.is_fresh(FOUND_MAX_AGE_SECS));
+
+        let missing = Entry {
+            profile: None,
+            fetched_at: 0,
+        };
+        assert!(!missing.is_fresh(MISSING_MAX_AGE_SECS));
+    }
+
+    #[test]
+    fn test_found_names_are_cached_by_uuid() {
+        let mut cache = ProfileCache::default();
+        let profile = MojangProfile {
+            uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+            name: "Notch".to_string(),
+        };
+        cache.insert(name_key("NOTCH"), Some(profile.clone()), 100);
+        assert_eq!(
+            cache.entries[&uuid_key("069a79f444e94726a5befca90e38aaf5")].profile,
+            Some(profile)
+        );
+        assert!(cache.entries.contains_key("name:notch"));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        assert_eq!(retry_after(Some("30")), Duration::from_secs(30));
+        assert_eq!(retry_after(Some("soon")), DEFAULT_RETRY_AFTER);
+        assert_eq!(retry_after(None), DEFAULT_RETRY_AFTER);
+    }
+}
@@ -0,0 +1,144 @@
+//! Moves launcher data out of `~/.duelsplus`.
+//!
+//! Older launchers kept everything in `~/.duelsplus`, and some settings
+//! ended up in `~/.config/duelsplus`. Launcher data now lives in the
+//! platform's directories (see [`utils::get_app_dir`]), where users and
+//! backup tools expect it. On startup, whatever is still in an old location
+//! is moved there.
+//!
+//! What the proxy reads or writes itself stays in `~/.duelsplus`: the
+//! sign-in token, its lock file, its logs and its addons. Anything that
+//! can't be moved, e.g. the install directory of a proxy that's still
+//! running on Windows, keeps being used from where it is and is moved on a
+//! later start.
+//!
+//! A start that moved anything emits `data-migrated` once the launcher is
+//! ready.
+
+use crate::utils::{self, AppDir};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Files and directories that move, by where they move to
+const MOVED: [(AppDir, &str); 7] = [
+    (AppDir::Config, "config.json"),
+    (AppDir::Config, "rpc-names.json"),
+    (AppDir::Data, "proxy"),
+    (AppDir::Data, "crashes"),
+    (AppDir::Cache, "avatars"),
+    (AppDir::Cache, "releases"),
+    (AppDir::Cache, "stats"),
+];
+
+/// Result of the migration, kept until it's emitted
+static REPORT: Mutex<Option<DataMigrated>> = Mutex::new(None);
+
+/// Payload of the `data-migrated` event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataMigrated {
+    pub moved: Vec<MovedPath>,
+    /// Why some paths couldn't be moved; they're tried again next start
+    pub failed: Vec<String>,
+}
+
+/// A file or directory moved to a new location
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedPath {
+    pub from: String,
+    pub to: String,
+}
+
+/// Moves whatever is left in the old locations, to call on startup before
+/// anything reads launcher data
+pub(crate) fn run() {
+    let mut report = DataMigrated::default();
+    for (dir, name) in MOVED {
+        let Ok(to) = utils::get_app_dir(dir).map(|d| d.join(name)) else {
+            continue;
+        };
+        for from in sources(dir, name) {
+            match move_if_missing(&from, &to) {
+                Ok(true) => {
+                    println!("[migrate] Moved {} to {}", from.display(), to.display());
+                    report.moved.push(MovedPath {
+                        from: from.to_string_lossy().into_owned(),
+                        to: to.to_string_lossy().into_owned(),
+                    });
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!("[migrate] {}", e);
+                    report.failed.push(e);
+                }
+            }
+        }
+    }
+    if !report.moved.is_empty() {
+        *REPORT.lock().unwrap() = Some(report);
+    }
+}
+
+/// Takes the result of this start's migration, if it moved anything, to
+/// emit as `data-migrated`
+pub(crate) fn take_report() -> Option<DataMigrated> {
+    REPORT.lock().unwrap().take()
+}
+
+/// Old locations of a moved file or directory
+fn sources(dir: AppDir, name: &str) -> Vec<PathBuf> {
+    let mut sources: Vec<PathBuf> = utils::get_legacy_path(dir, name).into_iter().collect();
+    if dir == AppDir::Config {
+        if let Ok(home) = utils::get_home_dir() {
+            sources.push(home.join(".config").join("duelsplus").join(name));
+        }
+    }
+    sources
+}
+
+/// Moves `from` to `to` unless there's nothing to move or `to` already
+/// exists, which would be newer.
+///
+/// # Returns
+///
+/// Returns whether it was moved.
+fn move_if_missing(from: &Path, to: &Path) -> Result<bool, String> {
+    if from == to || !from.exists() || to.exists() {
+        return Ok(false);
+    }
+    let fail = |e: std::io::Error| format!("Failed to move {}: {}", from.display(), e);
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).map_err(fail)?;
+    }
+    std::fs::rename(from, to).map_err(fail)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_if_missing() {
+        let dir =
+            std::env::temp_dir().join(format!("duelsplus-migrate-test-{}", std::process::id()));
+        let from = dir.join("old").join("config.json");
+        let to = dir.join("new").join("nested").join("config.json");
+        std::fs::create_dir_all(from.parent().unwrap()).unwrap();
+        std::fs::write(&from, "{}").unwrap();
+
+        assert_eq!(move_if_missing(&from, &to), Ok(true));
+        assert!(!from.exists());
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "{}");
+
+        // Nothing left to move, and an existing copy is never replaced
+        assert_eq!(move_if_missing(&from, &to), Ok(false));
+        std::fs::write(&from, "old").unwrap();
+        assert_eq!(move_if_missing(&from, &to), Ok(false));
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "{}");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+//! Network connectivity monitor.
+//!
+//! Checks the Duels+ API and a generic endpoint on an interval, so losing
+//! the connection is noticed once instead of by every feature failing on
+//! its own. Reaching the generic endpoint but not the API means the API is
+//! down; reaching neither means the network is.
+
+use crate::auth::API_BASE_URL;
+use crate::startup;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Endpoints checked to tell whether the internet is reachable at all
+const INTERNET_CHECK_URLS: [&str; 2] = [
+    "https://www.gstatic.com/generate_204",
+    "https://cloudflare.com/cdn-cgi/trace",
+];
+
+/// How long a single check may take
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time between checks while online
+const ONLINE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Time between checks while offline or the API is down, to notice the
+/// connection coming back quickly
+const OFFLINE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Whether the launcher can reach the network and the Duels+ API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkStatus {
+    /// The API is reachable (assumed until the first check finishes)
+    #[default]
+    Online,
+    /// The internet is reachable but the API isn't
+    ApiDown,
+    /// Nothing is reachable
+    Offline,
+}
+
+impl NetworkStatus {
+    /// Works out the status from the results of a check
+    fn classify(api_reachable: bool, internet_reachable: bool) -> Self {
+        match (api_reachable, internet_reachable) {
+            (true, _) => NetworkStatus::Online,
+            (false, true) => NetworkStatus::ApiDown,
+            (false, false) => NetworkStatus::Offline,
+        }
+    }
+}
+
+type ChangeHandler = Arc<dyn Fn(NetworkStatus) + Send + Sync>;
+
+/// Tracks the latest connectivity status
+#[derive(Default)]
+pub struct NetConnectivity {
+    status: Mutex<NetworkStatus>,
+    change_handler: Mutex<Option<ChangeHandler>>,
+    /// Wakes the monitor for an immediate check
+    check_now: tokio::sync::Notify,
+}
+
+impl NetConnectivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the status from the latest check
+    pub fn status(&self) -> NetworkStatus {
+        *self.status.lock().unwrap()
+    }
+
+    /// Sets the callback run with every status change, replacing any
+    /// previous one
+    pub fn on_change<F>(&self, handler: F)
+    where
+        F: Fn(NetworkStatus) + Send + Sync + 'static,
+    {
+        *self.change_handler.lock().unwrap() = Some(Arc::new(handler));
+    }
+
+    /// Asks the monitor to check again right away, e.g. after a request
+    /// failed
+    pub fn recheck(&self) {
+        self.check_now.notify_one();
+    }
+
+    /// Stores the result of a check, running the change callback if the
+    /// status changed
+    fn update(&self, status: NetworkStatus) {
+        let previous = std::mem::replace(&mut *self.status.lock().unwrap(), status);
+        if previous == status {
+            return;
+        }
+        println!("[network] Status changed: {:?} -> {:?}", previous, status);
+        let handler = self.change_handler.lock().unwrap().clone();
+        if let Some(handler) = handler {
+            handler(status);
+        }
+    }
+}
+
+/// Starts checking connectivity in the background
+pub(crate) fn monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(CHECK_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                eprintln!("[network] Failed to create HTTP client: {}", e);
+                return;
+            }
+        };
+        let mut first_check = true;
+        loop {
+            let status = check(&client).await;
+            let Some(net) = app.try_state::<NetConnectivity>() else {
+                return;
+            };
+            net.update(status);
+            if std::mem::take(&mut first_check) {
+                startup::mark("network");
+            }
+
+            let interval = match status {
+                NetworkStatus::Online => ONLINE_INTERVAL,
+                _ => OFFLINE_INTERVAL,
+            };
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = net.check_now.notified() => {}
+            }
+        }
+    });
+}
+
+/// Checks the API and the generic endpoints
+async fn check(client: &reqwest::Client) -> NetworkStatus {
+    let api = async {
+        client
+            .get(format!("{}/health", API_BASE_URL))
+            .send()
+            .await
+            .is_ok_and(|res| res.status().is_success())
+    };
+    // Any response at all means the internet is reachable
+    let internet = async {
+        for url in INTERNET_CHECK_URLS {
+            if client.head(url).send().await.is_ok() {
+                return true;
+            }
+        }
+        false
+    };
+    let (api_reachable, internet_reachable) = tokio::join!(api, internet);
+    NetworkStatus::classify(api_reachable, internet_reachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(NetworkStatus::classify(true, true), NetworkStatus::Online);
+        assert_eq!(NetworkStatus::classify(true, false), NetworkStatus::Online);
+        assert_eq!(NetworkStatus::classify(false, true), NetworkStatus::ApiDown);
+        assert_eq!(
+            NetworkStatus::classify(false, false),
+            NetworkStatus::Offline
+        );
+    }
+
+    #[test]
+    fn test_update_only_reports_changes() {
+        let net = NetConnectivity::new();
+        let changes = Arc::new(AtomicUsize::new(0));
+        let counter = changes.clone();
+        net.on_change(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        net.update(NetworkStatus::Online);
+        assert_eq!(changes.load(Ordering::SeqCst), 0);
+        net.update(NetworkStatus::Offline);
+        net.update(NetworkStatus::Offline);
+        assert_eq!(changes.load(Ordering::SeqCst), 1);
+        assert_eq!(net.status(), NetworkStatus::Offline);
+    }
+
+    #[test]
+    fn test_status_serializes_camel_case() {
+        assert_eq!(
+            serde_json::to_value(NetworkStatus::ApiDown).unwrap(),
+            serde_json::json!("apiDown")
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Where clicking a notification takes the user.
+//!
+//! A notification carries a [`Route`]: the page it's about, and optionally
+//! an action to take there, like offering an update or showing a crash
+//! report. Focusing the window isn't a click, so actions only ever show
+//! something; anything more, like installing, waits for a button. Desktop notifications can't show buttons or report clicks, but
+//! clicking one brings the launcher to the front, so the route of the last
+//! notification is remembered and followed when the window gains focus
+//! soon after.
+//!
+//! Following a route emits `navigate`, which the frontend handles by opening
+//! the page and running the action.
+
+use crate::deeplink::Page;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// How long after a notification focusing the launcher still follows its
+/// route
+const OPEN_WINDOW: Duration = Duration::from_secs(120);
+
+/// Route of the last notification and when it was shown
+static PENDING: Mutex<Option<(Route, Instant)>> = Mutex::new(None);
+
+/// Something to do once the launcher is open
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum NotificationAction {
+    /// Offers the launcher update found in the background, unless the user
+    /// put it off
+    ShowUpdate,
+    /// Shows the proxy crash report written to `path`
+    ViewCrashReport {
+        path: String,
+    },
+    OpenLogs,
+}
+
+impl NotificationAction {
+    fn page(&self) -> Page {
+        match self {
+            NotificationAction::ShowUpdate => Page::Home,
+            NotificationAction::ViewCrashReport { .. } | NotificationAction::OpenLogs => Page::Logs,
+        }
+    }
+}
+
+/// A page and what to do there, emitted as `navigate`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Route {
+    pub page: Page,
+    pub action: Option<NotificationAction>,
+}
+
+impl Route {
+    /// Just opens `page`
+    pub(crate) fn page(page: Page) -> Self {
+        Self { page, action: None }
+    }
+
+    /// Opens the page `action` belongs on and runs it
+    pub(crate) fn action(action: NotificationAction) -> Self {
+        Self {
+            page: action.page(),
+            action: Some(action),
+        }
+    }
+}
+
+/// Remembers the route of a notification that was just shown
+pub(crate) fn remember(route: Route) {
+    *PENDING.lock().unwrap() = Some((route, Instant::now()));
+}
+
+/// Follows the route of a recent notification, to call when the window
+/// gains focus
+pub(crate) fn on_focus(app: &AppHandle) {
+    let pending = PENDING.lock().unwrap().take();
+    if let Some(route) = pending.and_then(|(route, at)| recent(at, Instant::now()).then_some(route))
+    {
+        let _ = app.emit("navigate", route);
+    }
+}
+
+/// Whether a notification shown at `shown` can still be followed
+fn recent(shown: Instant, now: Instant) -> bool {
+    now.saturating_duration_since(shown) <= OPEN_WINDOW
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent() {
+        let shown = Instant::now();
+        assert!(recent(shown, shown + Duration::from_secs(30)));
+        assert!(!recent(shown, shown + OPEN_WINDOW + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_actions_open_their_page() {
+        let route = Route::action(NotificationAction::ViewCrashReport {
+            path: "crash.json".to_string(),
+        });
+        assert_eq!(route.page, Page::Logs);
+        assert_eq!(
+            serde_json::to_value(&route).unwrap(),
+            serde_json::json!({
+                "page": "logs",
+                "action": { "kind": "viewCrashReport", "path": "crash.json" },
+            })
+        );
+        assert_eq!(
+            Route::action(NotificationAction::ShowUpdate).page,
+            Page::Home
+        );
+        assert_eq!(Route::page(Page::Stats).action, None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Native notifications for launcher and game events.
+//!
+//! Each category has its own `notify*` config toggle. Notifications are only
+//! shown while the launcher window isn't focused, since the launcher already
+//! shows the same thing otherwise.
+//!
+//! Each notification carries a [`Route`] to follow when it's clicked, see
+//! [`crate::notification_router`].
+
+use crate::config;
+use crate::config::models::Config;
+use crate::deeplink::Page;
+use crate::i18n::t;
+use crate::notification_router::{self, NotificationAction, Route};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Win streaks notified about, every this many wins
+const STREAK_MILESTONE: u32 = 5;
+
+/// Label of the launcher window
+const MAIN_WINDOW: &str = "main";
+
+/// What a notification is about, each with its own config toggle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Category {
+    ProxyCrash,
+    Update,
+    Download,
+    FriendOnline,
+    WinStreak,
+    LeaderboardRank,
+    WeeklyDigest,
+}
+
+impl Category {
+    fn enabled(&self, cfg: &Config) -> bool {
+        match self {
+            Category::ProxyCrash => cfg.notify_proxy_crash,
+            Category::Update => cfg.notify_updates,
+            Category::Download => cfg.notify_downloads,
+            // Shows the friend's name, which shouldn't end up on stream
+            Category::FriendOnline => cfg.notify_friends && !cfg.streamer_mode,
+            Category::WinStreak => cfg.notify_win_streaks,
+            Category::LeaderboardRank => cfg.notify_leaderboard,
+            Category::WeeklyDigest => cfg.notify_weekly_digest,
+        }
+    }
+
+    /// Where the notification takes the user, unless it's shown with its
+    /// own route
+    fn route(&self) -> Route {
+        match self {
+            Category::ProxyCrash => Route::action(NotificationAction::OpenLogs),
+            Category::Update | Category::Download | Category::FriendOnline => {
+                Route::page(Page::Home)
+            }
+            Category::WinStreak | Category::LeaderboardRank => Route::page(Page::Stats),
+            Category::WeeklyDigest => Route::page(Page::History),
+        }
+    }
+}
+
+/// Shows a notification if its category is enabled and the launcher isn't
+/// focused
+pub(crate) fn notify(app: &AppHandle, category: Category, title: String, body: String) {
+    notify_with(app, category, category.route(), title, body);
+}
+
+/// Like [`notify`], taking the user to `route` when clicked
+pub(crate) fn notify_with(
+    app: &AppHandle,
+    category: Category,
+    route: Route,
+    title: String,
+    body: String,
+) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let cfg = config::manager::get_config()
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        if !category.enabled(&cfg) {
+            return;
+        }
+        let focused = app
+            .get_webview_window(MAIN_WINDOW)
+            .is_some_and(|w| w.is_focused().unwrap_or(false));
+        if focused {
+            return;
+        }
+
+        match app.notification().builder().title(title).body(body).show() {
+            Ok(()) => notification_router::remember(route),
+            Err(e) => eprintln!("[notifications] Failed to show notification: {}", e),
+        }
+    });
+}
+
+/// Notifies if a win brought the session's win streak to a milestone
+pub(crate) fn on_win_streak(app: &AppHandle, streak: u32) {
+    if is_milestone(streak) {
+        notify(
+            app,
+            Category::WinStreak,
+            t!("notifications.winStreak.title", streak = streak),
+            t!("notifications.winStreak.body", streak = streak),
+        );
+    }
+}
+
+fn is_milestone(streak: u32) -> bool {
+    streak > 0 && streak.is_multiple_of(STREAK_MILESTONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_milestone() {
+        assert!(!is_milestone(0));
+        assert!(!is_milestone(4));
+        assert!(is_milestone(5));
+        assert!(!is_milestone(6));
+        assert!(is_milestone(10));
+    }
+
+    #[test]
+    fn test_categories_follow_their_toggle() {
+        let mut cfg = Config::default();
+        assert!(Category::ProxyCrash.enabled(&cfg));
+        assert!(!Category::FriendOnline.enabled(&cfg));
+        cfg.notify_proxy_crash = false;
+        cfg.notify_friends = true;
+        assert!(!Category::ProxyCrash.enabled(&cfg));
+        assert!(Category::FriendOnline.enabled(&cfg));
+    }
+}
@@ -0,0 +1,330 @@
+//! Live stats for OBS overlays, served on localhost.
+//!
+//! With `obsServer` on, the launcher listens on `127.0.0.1:<obsPort>` for
+//! browser sources:
+//! - `ws://localhost:<obsPort>/` is a WebSocket that sends [`ObsState`] as
+//!   JSON when it connects and again on every change
+//! - `http://localhost:<obsPort>/stats` returns the same JSON once, for
+//!   sources that poll instead
+//!
+//! The state is the session's stats (see [`crate::session_stats`]) and the
+//! current mode and map (see [`crate::overlay`]), nothing that names the
+//! player, so it doesn't need hiding in streamer mode. Browsers let any page
+//! read it, like OBS's own WebSocket, but only from this computer.
+
+use crate::config::models::Config;
+use crate::overlay::Overlay;
+use crate::session_stats::{self, SessionStats};
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Most the request line and headers may take
+const MAX_HEAD_LEN: usize = 8 * 1024;
+
+/// Most a client message may carry, browser sources only send control frames
+const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+/// How long a client has to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connections served at once, so a stuck source can't pile them up
+const MAX_CONNECTIONS: usize = 16;
+
+/// Wait after failing to accept a connection, e.g. when out of file handles
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// The server's task and the port it's on, while it runs
+static SERVER: Mutex<Option<(u16, JoinHandle<()>)>> = Mutex::new(None);
+
+/// Why the server last failed to start, cleared when it starts
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+/// The latest state, which every WebSocket client watches
+static STATE: OnceLock<watch::Sender<ObsState>> = OnceLock::new();
+
+/// What overlays get, over the WebSocket and from `/stats`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsState {
+    pub in_game: bool,
+    pub mode: Option<String>,
+    pub map: Option<String>,
+    /// Including the win streak
+    pub session: SessionStats,
+}
+
+/// Whether the server is running, as returned by `get_obs_status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsStatus {
+    pub running: bool,
+    /// Port it's on, or would be on if it's off
+    pub port: u16,
+    /// Why it couldn't start, e.g. the port is in use
+    pub error: Option<String>,
+}
+
+fn state() -> &'static watch::Sender<ObsState> {
+    STATE.get_or_init(|| watch::channel(ObsState::default()).0)
+}
+
+/// Sends the latest stats to overlays, if they changed. Called whenever
+/// the session stats or the overlay data change.
+pub(crate) fn refresh(app: &AppHandle) {
+    let (in_game, mode, map) = app
+        .try_state::<Overlay>()
+        .map(|overlay| {
+            let data = overlay.data();
+            (data.in_game, data.mode, data.map)
+        })
+        .unwrap_or_default();
+    let next = ObsState {
+        in_game,
+        mode,
+        map,
+        session: session_stats::get(),
+    };
+    state().send_if_modified(|current| {
+        if *current == next {
+            return false;
+        }
+        *current = next;
+        true
+    });
+}
+
+/// Gets whether the server is running and on what port
+pub(crate) fn status(cfg: &Config) -> ObsStatus {
+    let server = SERVER.lock().unwrap();
+    ObsStatus {
+        running: server.is_some(),
+        port: server.as_ref().map_or(cfg.obs_port, |(port, _)| *port),
+        error: LAST_ERROR.lock().unwrap().clone(),
+    }
+}
+
+/// Starts, stops or moves the server to follow `obsServer` and `obsPort`
+pub(crate) fn apply(app: &AppHandle, cfg: &Config) {
+    let mut server = SERVER.lock().unwrap();
+    let wanted = cfg.obs_server.then_some(cfg.obs_port);
+    if server.as_ref().map(|(port, _)| *port) == wanted {
+        return;
+    }
+    if let Some((port, task)) = server.take() {
+        task.abort();
+        println!("[obs] Stopped listening on port {}", port);
+    }
+    *LAST_ERROR.lock().unwrap() = None;
+    let Some(port) = wanted else {
+        return;
+    };
+
+    refresh(app);
+    let task = tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("[obs] Failed to listen on port {}: {}", port, e);
+                *LAST_ERROR.lock().unwrap() = Some(format!("Port {} is unavailable: {}", port, e));
+                // Off until the settings change again
+                let mut server = SERVER.lock().unwrap();
+                if server.as_ref().is_some_and(|(current, _)| *current == port) {
+                    *server = None;
+                }
+                return;
+            }
+        };
+        println!("[obs] Listening on 127.0.0.1:{}", port);
+        serve(listener).await;
+    });
+    *server = Some((port, task));
+}
+
+/// Accepts connections until the task is aborted, which also drops them
+async fn serve(listener: TcpListener) {
+    let mut connections = JoinSet::new();
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("[obs] Failed to accept a connection: {}", e);
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        while connections.try_join_next().is_some() {}
+        if connections.len() >= MAX_CONNECTIONS {
+            continue;
+        }
+        connections.spawn(async move {
+            if let Err(e) = handle(stream).await {
+                eprintln!("[obs] Connection failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream) -> Result<(), String> {
+    let path = tokio::time::timeout(REQUEST_TIMEOUT, peek_path(&stream))
+        .await
+        .map_err(|_| "Timed out waiting for the request".to_string())??;
+    if path.split('?').next() != Some("/stats") {
+        return websocket(stream).await;
+    }
+
+    let head = tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream))
+        .await
+        .map_err(|_| "Timed out waiting for the request".to_string())??;
+    let request = Request::parse(&head).ok_or("Invalid request")?;
+    if request.method != "GET" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "").await;
+    }
+    let body = serde_json::to_string(&*state().borrow()).map_err(|e| e.to_string())?;
+    respond(&mut stream, "200 OK", "application/json", &body).await
+}
+
+/// Gets the path from the request line without reading it, so the
+/// WebSocket handshake still sees the whole request
+async fn peek_path(stream: &TcpStream) -> Result<String, String> {
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = stream.peek(&mut buf).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err("Closed before the request ended".to_string());
+        }
+        if let Some(end) = buf[..n].windows(2).position(|w| w == b"\r\n") {
+            let line = std::str::from_utf8(&buf[..end]).map_err(|_| "Request isn't UTF-8")?;
+            return request_path(line).ok_or_else(|| "Invalid request".to_string());
+        }
+        if n == buf.len() {
+            return Err("Request too large".to_string());
+        }
+        // Peeking doesn't wait for more, so give the rest of the line a moment
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Reads up to the blank line after the headers
+async fn read_head(stream: &mut TcpStream) -> Result<String, String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.ends_with(b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err("Closed before the request ended".to_string());
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.len() > MAX_HEAD_LEN {
+            return Err("Request too large".to_string());
+        }
+    }
+    String::from_utf8(head).map_err(|_| "Request isn't UTF-8".to_string())
+}
+
+async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> Result<(), String> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nCache-Control: no-store\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(response.as_bytes())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The request line of an HTTP request
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+}
+
+impl Request {
+    fn parse(head: &str) -> Option<Self> {
+        let line = head.split("\r\n").next()?;
+        Some(Self {
+            method: line.split(' ').next()?.to_string(),
+            path: request_path(line)?,
+        })
+    }
+}
+
+/// Gets the path from a request line such as `GET /stats HTTP/1.1`
+fn request_path(line: &str) -> Option<String> {
+    line.split(' ').nth(1).map(str::to_string)
+}
+
+/// Answers the handshake, then sends the state on every change until the
+/// client leaves
+async fn websocket(stream: TcpStream) -> Result<(), String> {
+    let config = WebSocketConfig {
+        max_message_size: Some(MAX_MESSAGE_LEN),
+        max_frame_size: Some(MAX_MESSAGE_LEN),
+        ..Default::default()
+    };
+    let handshake = tokio_tungstenite::accept_async_with_config(stream, Some(config));
+    let mut socket = tokio::time::timeout(REQUEST_TIMEOUT, handshake)
+        .await
+        .map_err(|_| "Timed out waiting for the handshake".to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let mut state = state().subscribe();
+    let mut changed = true;
+    loop {
+        if changed {
+            let text =
+                serde_json::to_string(&*state.borrow_and_update()).map_err(|e| e.to_string())?;
+            socket
+                .send(Message::Text(text))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        tokio::select! {
+            // Reading also answers pings and closes
+            message = socket.next() => match message {
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => changed = false,
+                Some(Err(e)) => return Err(e.to_string()),
+            },
+            result = state.changed() => {
+                result.map_err(|e| e.to_string())?;
+                changed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = Request::parse("GET /stats?x=1 HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        assert_eq!(
+            (request.method.as_str(), request.path.as_str()),
+            ("GET", "/stats?x=1")
+        );
+        assert!(Request::parse("GET\r\n\r\n").is_none());
+    }
+}
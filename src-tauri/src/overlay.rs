@@ -0,0 +1,391 @@
+//! Always-on-top overlay window with live game data.
+//!
+//! A small frameless window, shown over the game with the `toggleOverlay`
+//! shortcut or `toggle_overlay`, with the current opponent's stats and the
+//! session's wins and losses. It's fed by the control socket: game modes,
+//! game events and `opponent` messages update [`OverlayData`], which is sent
+//! to the overlay window only as `overlay-update`.
+//!
+//! The window is created the first time it's shown and hidden afterwards,
+//! including when it's asked to close. Where it's dragged to is saved as
+//! `overlayPosition` and used the next time it's created.
+//!
+//! When the proxy couldn't get an opponent's stats but knows their UUID, and
+//! a Hypixel API key is set, they're looked up with [`crate::hypixel`].
+
+use crate::config;
+use crate::config::models::WindowPosition;
+use crate::hypixel;
+use crate::obs;
+use crate::proxy::models::{GameEvent, GameEventKind, Opponent};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    Window, WindowEvent,
+};
+
+/// Label of the overlay window, which the frontend renders the overlay for
+pub(crate) const OVERLAY_WINDOW: &str = "overlay";
+
+const WIDTH: f64 = 280.0;
+const HEIGHT: f64 = 132.0;
+
+/// Gap between the overlay and the screen edges when it's first shown
+const MARGIN: i32 = 24;
+
+/// How long the overlay has to stay put before its position is saved, so
+/// dragging it doesn't write the config on every step
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// What the overlay shows, emitted as `overlay-update`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayData {
+    pub in_game: bool,
+    pub mode: Option<String>,
+    pub map: Option<String>,
+    /// `None` until the proxy reports who the opponent is
+    pub opponent: Option<Opponent>,
+    /// Wins since the launcher started
+    pub wins: u32,
+    /// Losses since the launcher started
+    pub losses: u32,
+}
+
+impl OverlayData {
+    fn set_game_mode(&mut self, mode: Option<String>, map: Option<String>) {
+        // Back in a lobby, or a different game
+        if mode.is_none() || mode != self.mode || map != self.map {
+            self.opponent = None;
+        }
+        self.in_game = mode.is_some();
+        self.mode = mode;
+        self.map = map;
+    }
+
+    fn apply(&mut self, event: &GameEvent) {
+        match event.event {
+            GameEventKind::GameStart => {
+                self.in_game = true;
+                if event.mode.is_some() {
+                    self.mode = event.mode.clone();
+                }
+                if event.map.is_some() {
+                    self.map = event.map.clone();
+                }
+            }
+            GameEventKind::Win => self.wins += 1,
+            GameEventKind::Loss => self.losses += 1,
+            GameEventKind::GameEnd => {
+                self.in_game = false;
+                self.opponent = None;
+            }
+            GameEventKind::Kill
+            | GameEventKind::Death
+            | GameEventKind::Goal
+            | GameEventKind::GoalConceded
+            | GameEventKind::BedBroken
+            | GameEventKind::BedLost => {}
+        }
+    }
+}
+
+/// Overlay state, managed by Tauri
+#[derive(Default)]
+pub struct Overlay {
+    data: Mutex<OverlayData>,
+    /// Bumped on every move, so only the last of a drag saves the position
+    moves: AtomicU64,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets what the overlay currently shows
+    pub fn data(&self) -> OverlayData {
+        self.data.lock().unwrap().clone()
+    }
+
+    /// Changes the data and sends it to the overlay window, if it changed
+    fn update(&self, app: &AppHandle, change: impl FnOnce(&mut OverlayData)) {
+        let data = {
+            let mut data = self.data.lock().unwrap();
+            let before = data.clone();
+            change(&mut data);
+            if *data == before {
+                return;
+            }
+            data.clone()
+        };
+        let _ = app.emit_to(OVERLAY_WINDOW, "overlay-update", data);
+    }
+}
+
+fn update(app: &AppHandle, change: impl FnOnce(&mut OverlayData)) {
+    if let Some(overlay) = app.try_state::<Overlay>() {
+        overlay.update(app, change);
+        obs::refresh(app);
+    }
+}
+
+/// Records the game mode from the control socket, `None` in a lobby
+pub(crate) fn on_game_mode(app: &AppHandle, mode: Option<String>, map: Option<String>) {
+    update(app, |data| data.set_game_mode(mode, map));
+}
+
+/// Records a game event from the control socket
+pub(crate) fn on_game_event(app: &AppHandle, event: &GameEvent) {
+    update(app, |data| data.apply(event));
+}
+
+/// Records the opponent in the current duel
+pub(crate) fn on_opponent(app: &AppHandle, opponent: Opponent) {
+    if opponent.wins.is_none() {
+        if let Some(uuid) = opponent.uuid.clone() {
+            tauri::async_runtime::spawn(fill_in_stats(app.clone(), uuid));
+        }
+    }
+    update(app, |data| data.opponent = Some(opponent));
+}
+
+/// Fills in an opponent's Duels stats from Hypixel, if they're still the
+/// one shown
+async fn fill_in_stats(app: AppHandle, uuid: String) {
+    if !hypixel::has_api_key().await {
+        return;
+    }
+    let duels = match hypixel::lookup_uuid(&uuid).await {
+        Ok(lookup) => match lookup.and_then(|lookup| lookup.duels) {
+            Some(duels) => duels,
+            None => return,
+        },
+        Err(e) => {
+            eprintln!("[overlay] Failed to look up the opponent: {}", e);
+            return;
+        }
+    };
+    update(&app, |data| {
+        if let Some(opponent) = data
+            .opponent
+            .as_mut()
+            .filter(|opponent| opponent.uuid.as_deref() == Some(uuid.as_str()))
+        {
+            opponent.wins = Some(duels.wins);
+            opponent.losses = Some(duels.losses);
+            opponent.winstreak = duels.winstreak;
+            opponent.best_winstreak = duels.best_winstreak;
+        }
+    });
+}
+
+/// Shows the overlay, creating it the first time, or hides it.
+///
+/// # Returns
+///
+/// Returns whether the overlay is now shown.
+pub(crate) async fn toggle(app: &AppHandle) -> Result<bool, String> {
+    let window = match app.get_webview_window(OVERLAY_WINDOW) {
+        Some(window) if window.is_visible().unwrap_or(false) => {
+            window.hide().map_err(|e| e.to_string())?;
+            return Ok(false);
+        }
+        Some(window) => window,
+        None => {
+            let saved = config::manager::get_config()
+                .await
+                .ok()
+                .flatten()
+                .and_then(|cfg| cfg.overlay_position);
+            create(app, saved).map_err(|e| format!("Failed to open the overlay: {}", e))?
+        }
+    };
+    window.show().map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn create(app: &AppHandle, saved: Option<WindowPosition>) -> tauri::Result<WebviewWindow> {
+    let builder = WebviewWindowBuilder::new(app, OVERLAY_WINDOW, WebviewUrl::default())
+        .title("Duels+ Overlay")
+        .inner_size(WIDTH, HEIGHT)
+        .decorations(false)
+        .shadow(false)
+        .resizable(false)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .visible(false);
+    // Transparent windows need private APIs on macOS
+    #[cfg(not(target_os = "macos"))]
+    let builder = builder.transparent(true);
+    let window = builder.build()?;
+
+    if let Some(position) = initial_position(&window, saved) {
+        window.set_position(PhysicalPosition::new(position.x, position.y))?;
+    }
+    Ok(window)
+}
+
+/// Picks where the overlay opens: where it was left if that's still on a
+/// monitor, otherwise the top right corner of the main monitor
+fn initial_position(
+    window: &WebviewWindow,
+    saved: Option<WindowPosition>,
+) -> Option<WindowPosition> {
+    let monitors: Vec<Rect> = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| Rect {
+            x: m.position().x,
+            y: m.position().y,
+            width: m.size().width as i32,
+            height: m.size().height as i32,
+        })
+        .collect();
+    if let Some(saved) = saved.filter(|saved| is_on_screen(*saved, &monitors)) {
+        return Some(saved);
+    }
+
+    let monitor = window.primary_monitor().ok().flatten()?;
+    let width = window.outer_size().ok()?.width as i32;
+    Some(WindowPosition {
+        x: monitor.position().x + monitor.size().width as i32 - width - MARGIN,
+        y: monitor.position().y + MARGIN,
+    })
+}
+
+/// A monitor's area, in physical pixels
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+/// Whether a window's top left corner is on one of the monitors
+fn is_on_screen(position: WindowPosition, monitors: &[Rect]) -> bool {
+    monitors.iter().any(|m| {
+        (m.x..m.x + m.width).contains(&position.x) && (m.y..m.y + m.height).contains(&position.y)
+    })
+}
+
+/// Handles events of the overlay window: saves where it's moved to, and
+/// hides it instead of closing
+pub(crate) fn on_window_event(window: &Window, event: &WindowEvent) {
+    match event {
+        WindowEvent::CloseRequested { api, .. } => {
+            api.prevent_close();
+            let _ = window.hide();
+        }
+        WindowEvent::Moved(position) => {
+            let app = window.app_handle().clone();
+            let position = WindowPosition {
+                x: position.x,
+                y: position.y,
+            };
+            let Some(overlay) = app.try_state::<Overlay>() else {
+                return;
+            };
+            let generation = overlay.moves.fetch_add(1, Ordering::SeqCst) + 1;
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(SAVE_DELAY).await;
+                let overlay = app.state::<Overlay>();
+                if overlay.moves.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                let value = serde_json::json!(position);
+                if let Err(e) = config::manager::set_config_key("overlayPosition", value).await {
+                    eprintln!("[overlay] Failed to save the overlay position: {}", e);
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: GameEventKind) -> GameEvent {
+        GameEvent {
+            event: kind,
+            mode: Some("DUELS_SUMO".to_string()),
+            map: None,
+            player: None,
+            round: None,
+            final_blow: false,
+        }
+    }
+
+    fn opponent() -> Opponent {
+        Opponent {
+            name: "Steve".to_string(),
+            uuid: None,
+            wins: Some(10),
+            losses: Some(3),
+            winstreak: None,
+            best_winstreak: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_counts_session_results() {
+        let mut data = OverlayData::default();
+        data.apply(&event(GameEventKind::GameStart));
+        assert!(data.in_game);
+        assert_eq!(data.mode.as_deref(), Some("DUELS_SUMO"));
+
+        data.opponent = Some(opponent());
+        data.apply(&event(GameEventKind::Kill));
+        data.apply(&event(GameEventKind::Win));
+        data.apply(&event(GameEventKind::GameEnd));
+        data.apply(&event(GameEventKind::Loss));
+        assert!(!data.in_game);
+        assert_eq!(data.opponent, None);
+        assert_eq!((data.wins, data.losses), (1, 1));
+    }
+
+    #[test]
+    fn test_set_game_mode_clears_opponent_on_change() {
+        let mut data = OverlayData::default();
+        data.set_game_mode(Some("DUELS_SUMO".to_string()), Some("Arena".to_string()));
+        data.opponent = Some(opponent());
+
+        data.set_game_mode(Some("DUELS_SUMO".to_string()), Some("Arena".to_string()));
+        assert!(data.opponent.is_some());
+
+        data.set_game_mode(None, None);
+        assert!(!data.in_game);
+        assert_eq!(data.opponent, None);
+    }
+
+    #[test]
+    fn test_is_on_screen() {
+        let monitors = [
+            Rect {
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080,
+            },
+            Rect {
+                x: -1280,
+                y: 0,
+                width: 1280,
+                height: 1024,
+            },
+        ];
+        assert!(is_on_screen(WindowPosition { x: 1600, y: 24 }, &monitors));
+        assert!(is_on_screen(WindowPosition { x: -300, y: 500 }, &monitors));
+        assert!(!is_on_screen(WindowPosition { x: 1920, y: 24 }, &monitors));
+        assert!(!is_on_screen(WindowPosition { x: 100, y: 2000 }, &[]));
+    }
+}
@@ -0,0 +1,160 @@
+//! Command timings, for tracking down "the launcher feels laggy".
+//!
+//! Tauri doesn't report when an async command's response is sent, so the
+//! frontend wraps `invoke` and times every command from the call to its
+//! reply, which is also the wait the user sees. Timings are sent in batches
+//! with `record_command_timings`; `get_perf_metrics` returns calls, errors
+//! and percentiles for each command since the launcher started.
+//!
+//! Commands slower than [`SLOW_COMMAND`] are logged.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Commands taking longer than this are logged
+const SLOW_COMMAND: Duration = Duration::from_millis(500);
+
+/// Durations kept per command for percentiles
+const MAX_SAMPLES: usize = 256;
+
+/// Longer command names are ignored, since they aren't ours
+const MAX_NAME_LEN: usize = 64;
+
+static METRICS: Mutex<BTreeMap<String, Samples>> = Mutex::new(BTreeMap::new());
+
+/// One command call, as recorded by the frontend
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandTiming {
+    pub command: String,
+    /// From the call to the reply
+    pub ms: f64,
+    /// Whether the command failed
+    pub error: bool,
+}
+
+/// Calls of a command and how long the latest took
+#[derive(Debug, Default)]
+struct Samples {
+    calls: u64,
+    errors: u64,
+    /// Latest durations in milliseconds, oldest first
+    recent: VecDeque<f64>,
+}
+
+impl Samples {
+    fn add(&mut self, ms: f64, error: bool) {
+        self.calls += 1;
+        if error {
+            self.errors += 1;
+        }
+        if self.recent.len() == MAX_SAMPLES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(ms);
+    }
+}
+
+/// Timings of a command, as returned by `get_perf_metrics`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    pub command: String,
+    pub calls: u64,
+    pub errors: u64,
+    /// Percentiles over the latest calls, in milliseconds
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Records command timings sent by the frontend, logging slow ones
+pub(crate) fn record(timings: Vec<CommandTiming>) {
+    let mut metrics = METRICS.lock().unwrap();
+    for timing in timings {
+        if timing.command.is_empty()
+            || timing.command.len() > MAX_NAME_LEN
+            || !timing.ms.is_finite()
+            || timing.ms < 0.0
+        {
+            continue;
+        }
+        if timing.ms > SLOW_COMMAND.as_millis() as f64 {
+            eprintln!(
+                "[perf] Slow command {}: {:.0} ms{}",
+                timing.command,
+                timing.ms,
+                if timing.error { " (failed)" } else { "" }
+            );
+        }
+        metrics
+            .entry(timing.command)
+            .or_default()
+            .add(timing.ms, timing.error);
+    }
+}
+
+/// Gets each command's timings, slowest first
+pub(crate) fn metrics() -> Vec<CommandMetrics> {
+    let metrics = METRICS.lock().unwrap();
+    let mut all: Vec<CommandMetrics> = metrics
+        .iter()
+        .map(|(command, samples)| summarize(command, samples))
+        .collect();
+    all.sort_by(|a, b| b.p90_ms.total_cmp(&a.p90_ms));
+    all
+}
+
+fn summarize(command: &str, samples: &Samples) -> CommandMetrics {
+    let mut sorted: Vec<f64> = samples.recent.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    CommandMetrics {
+        command: command.to_string(),
+        calls: samples.calls,
+        errors: samples.errors,
+        p50_ms: percentile(&sorted, 50),
+        p90_ms: percentile(&sorted, 90),
+        p99_ms: percentile(&sorted, 99),
+        max_ms: sorted.last().copied().unwrap_or_default(),
+    }
+}
+
+/// Nearest-rank percentile of sorted durations, 0 if there are none
+fn percentile(sorted: &[f64], p: usize) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 50), 50.0);
+        assert_eq!(percentile(&sorted, 90), 90.0);
+        assert_eq!(percentile(&sorted, 99), 99.0);
+        assert_eq!(percentile(&[7.0], 99), 7.0);
+        assert_eq!(percentile(&[], 50), 0.0);
+    }
+
+    #[test]
+    fn test_samples_keep_the_latest() {
+        let mut samples = Samples::default();
+        for ms in 0..(MAX_SAMPLES + 10) {
+            samples.add(ms as f64, ms % 2 == 0);
+        }
+        let summary = summarize("get_config", &samples);
+        assert_eq!(summary.calls, (MAX_SAMPLES + 10) as u64);
+        assert_eq!(summary.errors, ((MAX_SAMPLES + 10) / 2) as u64);
+        assert_eq!(samples.recent.len(), MAX_SAMPLES);
+        assert_eq!(summary.max_ms, (MAX_SAMPLES + 9) as f64);
+    }
+}
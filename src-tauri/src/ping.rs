@@ -0,0 +1,199 @@
+//! Latency to Hypixel, measured as TCP connect times.
+//!
+//! Minecraft's own ping needs a handshake the server may rate limit, while
+//! a TCP connect takes one round trip and nothing else, so it's close
+//! enough for telling whether the connection is the problem. Hypixel serves
+//! every region from `mc.hypixel.net`, so that's the one host measured; the
+//! local proxy port can be measured too, to tell the proxy's own overhead
+//! apart.
+//!
+//! With `pingIndicator` on, the `ping` task samples Hypixel every 30
+//! seconds and emits `ping-updated` for the latency indicator.
+
+use crate::config;
+use crate::proxy::manager::parse_proxy_port;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+pub(crate) const HYPIXEL_HOST: &str = "mc.hypixel.net";
+pub(crate) const MINECRAFT_PORT: u16 = 25565;
+
+/// Connects made by `measure_ping`
+const SAMPLES: usize = 5;
+
+/// Connects made by each run of the sampler, fewer to stay light
+const SAMPLER_SAMPLES: usize = 3;
+
+/// How long a connect may take before it counts as lost
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Pause between connects, so they don't queue behind each other
+const SAMPLE_GAP: Duration = Duration::from_millis(200);
+
+/// What's measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PingTarget {
+    Hypixel,
+    /// The proxy's port on this computer
+    Proxy,
+}
+
+/// Connect times to one target, as returned by `measure_ping` and emitted
+/// as `ping-updated`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingResult {
+    pub target: PingTarget,
+    /// Host and port connected to
+    pub address: String,
+    /// Connects that succeeded
+    pub received: u32,
+    /// Connects that failed or timed out
+    pub lost: u32,
+    /// `None` when every connect failed, like the averages
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    /// Average difference between consecutive connect times
+    pub jitter_ms: Option<f64>,
+}
+
+impl PingResult {
+    fn new(target: PingTarget, address: String, samples: &[Option<f64>]) -> Self {
+        let times: Vec<f64> = samples.iter().flatten().copied().collect();
+        let received = times.len() as u32;
+        let (min_ms, avg_ms) = if times.is_empty() {
+            (None, None)
+        } else {
+            (
+                times.iter().copied().reduce(f64::min),
+                Some(times.iter().sum::<f64>() / times.len() as f64),
+            )
+        };
+        let jitter_ms = (times.len() > 1).then(|| {
+            times
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).abs())
+                .sum::<f64>()
+                / (times.len() - 1) as f64
+        });
+        Self {
+            target,
+            address,
+            received,
+            lost: samples.len() as u32 - received,
+            min_ms,
+            avg_ms,
+            jitter_ms,
+        }
+    }
+}
+
+/// Times one TCP connect in milliseconds, `None` if it fails or times out
+pub(crate) async fn connect_time(host: &str, port: u16) -> Option<f64> {
+    let start = Instant::now();
+    let connect = tokio::net::TcpStream::connect((host, port));
+    match tokio::time::timeout(CONNECT_TIMEOUT, connect).await {
+        Ok(Ok(_stream)) => Some(start.elapsed().as_secs_f64() * 1000.0),
+        _ => None,
+    }
+}
+
+async fn measure_target(target: PingTarget, samples: usize) -> Result<PingResult, String> {
+    let (host, port) = match target {
+        PingTarget::Hypixel => (HYPIXEL_HOST.to_string(), MINECRAFT_PORT),
+        PingTarget::Proxy => {
+            let cfg = config::manager::get_config()
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            let port = parse_proxy_port(&cfg.proxy_port).map_err(|e| e.to_string())?;
+            ("127.0.0.1".to_string(), port)
+        }
+    };
+
+    // Resolve once up front, so DNS isn't counted in the first connect
+    let addr = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| format!("Couldn't resolve {}", host))?;
+    let ip = addr.ip().to_string();
+
+    let mut times = Vec::with_capacity(samples);
+    for i in 0..samples {
+        if i > 0 {
+            tokio::time::sleep(SAMPLE_GAP).await;
+        }
+        times.push(connect_time(&ip, port).await);
+    }
+    Ok(PingResult::new(
+        target,
+        format!("{}:{}", host, port),
+        &times,
+    ))
+}
+
+/// Measures Hypixel, and the proxy port if `include_proxy` is set
+pub(crate) async fn measure(include_proxy: bool) -> Result<Vec<PingResult>, String> {
+    let mut results = vec![measure_target(PingTarget::Hypixel, SAMPLES).await?];
+    if include_proxy {
+        results.push(measure_target(PingTarget::Proxy, SAMPLES).await?);
+    }
+    Ok(results)
+}
+
+/// Samples Hypixel for the latency indicator, if it's on
+pub(crate) async fn sample(app: AppHandle) -> Result<(), String> {
+    let enabled = config::manager::get_config()
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|cfg| cfg.ping_indicator);
+    if !enabled {
+        return Ok(());
+    }
+    let result = measure_target(PingTarget::Hypixel, SAMPLER_SAMPLES).await?;
+    let _ = app.emit("ping-updated", &result);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result() {
+        let result = PingResult::new(
+            PingTarget::Hypixel,
+            "mc.hypixel.net:25565".to_string(),
+            &[Some(40.0), None, Some(50.0), Some(45.0)],
+        );
+        assert_eq!((result.received, result.lost), (3, 1));
+        assert_eq!(result.min_ms, Some(40.0));
+        assert_eq!(result.avg_ms, Some(45.0));
+        // |50 - 40| and |45 - 50|
+        assert_eq!(result.jitter_ms, Some(7.5));
+    }
+
+    #[test]
+    fn test_result_when_everything_is_lost() {
+        let result = PingResult::new(PingTarget::Proxy, String::new(), &[None, None]);
+        assert_eq!((result.received, result.lost), (0, 2));
+        assert_eq!(result.avg_ms, None);
+        assert_eq!(result.jitter_ms, None);
+
+        let one = PingResult::new(PingTarget::Proxy, String::new(), &[Some(1.0)]);
+        assert_eq!(one.jitter_ms, None);
+    }
+
+    #[tokio::test]
+    async fn test_connect_time() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(connect_time("127.0.0.1", port).await.is_some());
+        drop(listener);
+        assert!(connect_time("127.0.0.1", port).await.is_none());
+    }
+}
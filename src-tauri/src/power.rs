@@ -0,0 +1,234 @@
+//! Suspend and resume handling.
+//!
+//! After the machine sleeps, the Discord pipe and the proxy's control
+//! socket are usually dead without either side having noticed. On resume
+//! the launcher re-checks the proxy process, reconnects the control socket
+//! and Discord, and re-checks connectivity, then emits `power-resumed`.
+//!
+//! Windows reports sleep through `PowerRegisterSuspendResumeNotification`
+//! and Linux through logind's `PrepareForSleep` signal. On other systems,
+//! or when those aren't available, a resume shows up as the wall clock
+//! jumping ahead while a short timer waits.
+
+use crate::network::NetConnectivity;
+use crate::proxy::ProxyManager;
+use crate::rpc::RpcManager;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often the wall clock is compared against the timer
+const CLOCK_TICK: Duration = Duration::from_secs(10);
+
+/// How far the wall clock has to run ahead of the timer to count as sleep
+const CLOCK_JUMP: Duration = Duration::from_secs(60);
+
+/// Resumes reported this soon after the last one are the same resume,
+/// e.g. seen by both the OS watcher and the clock
+const RESUME_DEBOUNCE: Duration = Duration::from_secs(30);
+
+/// When the last resume was handled
+static LAST_RESUME: Mutex<Option<Instant>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerEvent {
+    Suspend,
+    Resume,
+}
+
+/// Starts watching for the machine going to sleep and waking up
+pub(crate) fn watch(app: &AppHandle) {
+    if let Err(e) = platform::watch(app.clone()) {
+        eprintln!("[power] {}; relying on the clock to notice sleep", e);
+    }
+    watch_clock(app.clone());
+}
+
+fn handle(app: &AppHandle, event: PowerEvent) {
+    match event {
+        PowerEvent::Suspend => println!("[power] Going to sleep"),
+        PowerEvent::Resume => {
+            let now = Instant::now();
+            {
+                let mut last = LAST_RESUME.lock().unwrap();
+                if is_duplicate(*last, now) {
+                    return;
+                }
+                *last = Some(now);
+            }
+            tauri::async_runtime::spawn(on_resume(app.clone()));
+        }
+    }
+}
+
+async fn on_resume(app: AppHandle) {
+    println!("[power] Woke up from sleep, reconnecting");
+    if let Some(proxy) = app.try_state::<ProxyManager>() {
+        proxy.recover_after_resume().await;
+    }
+    if let Some(rpc) = app.try_state::<RpcManager>() {
+        rpc.reconnect();
+    }
+    if let Some(net) = app.try_state::<NetConnectivity>() {
+        net.recheck();
+    }
+    let _ = app.emit("power-resumed", ());
+}
+
+/// Notices sleep by the wall clock running ahead of a timer, which doesn't
+/// count (or fires late after) the time asleep
+fn watch_clock(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let before = SystemTime::now();
+            tokio::time::sleep(CLOCK_TICK).await;
+            let elapsed = SystemTime::now().duration_since(before).unwrap_or_default();
+            if slept(elapsed) {
+                handle(&app, PowerEvent::Resume);
+            }
+        }
+    });
+}
+
+/// Whether a wall clock time of `elapsed` over one tick means the machine
+/// slept
+fn slept(elapsed: Duration) -> bool {
+    elapsed > CLOCK_TICK + CLOCK_JUMP
+}
+
+fn is_duplicate(last: Option<Instant>, now: Instant) -> bool {
+    last.is_some_and(|last| now.saturating_duration_since(last) < RESUME_DEBOUNCE)
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::{handle, PowerEvent};
+    use std::ffi::c_void;
+    use std::sync::OnceLock;
+    use tauri::AppHandle;
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Power::{
+        PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND,
+    };
+
+    /// App the power callback reports to
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+
+    unsafe extern "system" fn on_power(
+        _context: *const c_void,
+        kind: u32,
+        _setting: *const c_void,
+    ) -> u32 {
+        let event = match kind {
+            PBT_APMSUSPEND => PowerEvent::Suspend,
+            PBT_APMRESUMEAUTOMATIC => PowerEvent::Resume,
+            _ => return ERROR_SUCCESS,
+        };
+        if let Some(app) = APP.get() {
+            handle(app, event);
+        }
+        ERROR_SUCCESS
+    }
+
+    pub(super) fn watch(app: AppHandle) -> Result<(), String> {
+        if APP.set(app).is_err() {
+            return Ok(());
+        }
+        // Has to outlive the registration, which lasts as long as the
+        // launcher runs
+        let params = Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+            Callback: Some(on_power),
+            Context: std::ptr::null_mut(),
+        }));
+        let mut registration = std::ptr::null_mut();
+        // SAFETY: `params` is valid for the rest of the process
+        let result = unsafe {
+            PowerRegisterSuspendResumeNotification(
+                DEVICE_NOTIFY_CALLBACK,
+                params as *mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS as *mut c_void,
+                &mut registration,
+            )
+        };
+        if result == ERROR_SUCCESS {
+            Ok(())
+        } else {
+            Err(format!(
+                "Failed to register for power notifications (error {})",
+                result
+            ))
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use super::{handle, PowerEvent};
+    use tauri::AppHandle;
+    use zbus::blocking::{Connection, Proxy};
+
+    pub(super) fn watch(app: AppHandle) -> Result<(), String> {
+        std::thread::Builder::new()
+            .name("power".to_string())
+            .spawn(move || {
+                if let Err(e) = listen(&app) {
+                    eprintln!("[power] Stopped watching logind for sleep: {}", e);
+                }
+            })
+            .map(|_| ())
+            .map_err(|e| format!("Failed to start the power thread: {}", e))
+    }
+
+    /// Reports logind's `PrepareForSleep` signals, `true` before sleeping
+    /// and `false` after waking up
+    fn listen(app: &AppHandle) -> zbus::Result<()> {
+        let connection = Connection::system()?;
+        let logind = Proxy::new(
+            &connection,
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )?;
+        for signal in logind.receive_signal("PrepareForSleep")? {
+            let sleeping: bool = signal.body().deserialize()?;
+            let event = if sleeping {
+                PowerEvent::Suspend
+            } else {
+                PowerEvent::Resume
+            };
+            handle(app, event);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub(super) fn watch(_app: AppHandle) -> Result<(), String> {
+        Err("Sleep notifications aren't supported on this system".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slept() {
+        assert!(!slept(CLOCK_TICK));
+        assert!(!slept(CLOCK_TICK + Duration::from_secs(5)));
+        assert!(slept(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_is_duplicate() {
+        let now = Instant::now();
+        assert!(!is_duplicate(None, now));
+        assert!(is_duplicate(Some(now), now + Duration::from_secs(5)));
+        assert!(!is_duplicate(Some(now), now + RESUME_DEBOUNCE));
+    }
+}
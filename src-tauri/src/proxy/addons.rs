@@ -0,0 +1,244 @@
+//! Optional proxy feature modules.
+//!
+//! The API lists the addons the proxy supports. Installing one downloads it
+//! into the proxy's addon directory, `~/.duelsplus/addons`, which the proxy
+//! loads on start. Which version of each addon is installed is recorded in
+//! `addons.json` in the launcher's data directory, to offer updates.
+//!
+//! Installed addons are loaded unless listed in `disabledAddons`, which is
+//! pushed to a running proxy like the other `PROXY_SYNCED_KEYS`.
+
+use super::download;
+use super::error::ProxyError;
+use super::models::{Addon, AddonInfo};
+use crate::utils::{self, AppDir};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+
+const API_BASE: &str = "https://proxy-updates.duelsplus.com/v1/addons";
+
+/// Name of the installed addons record in the data directory
+const INSTALLED_FILE: &str = "addons.json";
+
+/// An installed addon, as recorded in `addons.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledAddon {
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    /// File name in the addon directory
+    pub file: String,
+}
+
+/// Gets the directory the proxy loads addons from
+pub fn get_addon_dir() -> Result<PathBuf, ProxyError> {
+    // Read by the proxy, which still uses `~/.duelsplus`
+    Ok(utils::get_app_root()
+        .map_err(ProxyError::Unknown)?
+        .join("addons"))
+}
+
+fn installed_path() -> Result<PathBuf, ProxyError> {
+    utils::get_app_path(AppDir::Data, INSTALLED_FILE).map_err(ProxyError::Unknown)
+}
+
+/// Reads the installed addons by id, empty if none were installed
+pub fn read_installed() -> Result<BTreeMap<String, InstalledAddon>, ProxyError> {
+    match fs::read_to_string(installed_path()?) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_installed(installed: &BTreeMap<String, InstalledAddon>) -> Result<(), ProxyError> {
+    let path = installed_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(installed)?)?;
+    Ok(())
+}
+
+/// Fetches the addons available for the proxy from the API
+pub async fn fetch_addons() -> Result<Vec<Addon>, ProxyError> {
+    let response = reqwest::Client::new().get(API_BASE).send().await?;
+    let addons: Vec<Addon> = response.error_for_status()?.json().await?;
+    Ok(addons)
+}
+
+/// Lists available and installed addons with their versions and whether
+/// they're enabled.
+///
+/// Addons installed but no longer offered are listed after the rest.
+pub fn list(
+    available: &[Addon],
+    installed: &BTreeMap<String, InstalledAddon>,
+    disabled: &[String],
+) -> Vec<AddonInfo> {
+    let enabled = |id: &str| !disabled.iter().any(|d| d == id);
+    let mut addons: Vec<AddonInfo> = available
+        .iter()
+        .map(|addon| {
+            let installed_version = installed.get(&addon.id).map(|i| i.version.clone());
+            AddonInfo {
+                id: addon.id.clone(),
+                name: addon.name.clone(),
+                description: addon.description.clone(),
+                update_available: installed_version
+                    .as_ref()
+                    .is_some_and(|version| *version != addon.version),
+                latest_version: Some(addon.version.clone()),
+                installed_version,
+                enabled: enabled(&addon.id),
+            }
+        })
+        .collect();
+
+    addons.extend(
+        installed
+            .iter()
+            .filter(|(id, _)| !available.iter().any(|a| &a.id == *id))
+            .map(|(id, addon)| AddonInfo {
+                id: id.clone(),
+                name: addon.name.clone(),
+                description: addon.description.clone(),
+                latest_version: None,
+                installed_version: Some(addon.version.clone()),
+                enabled: enabled(id),
+                update_available: false,
+            }),
+    );
+    addons
+}
+
+/// Installs the latest version of an addon, replacing an installed one.
+///
+/// The proxy picks it up the next time it starts.
+///
+/// # Returns
+///
+/// Returns the version installed.
+pub async fn install(id: &str) -> Result<String, ProxyError> {
+    let addons = fetch_addons().await?;
+    let addon = addons
+        .iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| ProxyError::NoAddonFound(id.to_string()))?;
+
+    let dir = get_addon_dir()?;
+    let dest = dir.join(&addon.asset.name);
+    // Download next to it first, so a failed download keeps the old version
+    let partial = dir.join(format!("{}.part", addon.asset.name));
+    let url = format!("{}/signed?assetId={}", API_BASE, addon.asset.id);
+    download::download_file(&url, &partial, &AtomicBool::new(false), |_| {}).await?;
+    fs::rename(&partial, &dest)?;
+
+    let mut installed = read_installed()?;
+    let previous = installed.insert(
+        addon.id.clone(),
+        InstalledAddon {
+            name: addon.name.clone(),
+            description: addon.description.clone(),
+            version: addon.version.clone(),
+            file: addon.asset.name.clone(),
+        },
+    );
+    write_installed(&installed)?;
+
+    // A new version may come as a differently named file
+    if let Some(previous) = previous.filter(|p| p.file != addon.asset.name) {
+        let _ = fs::remove_file(dir.join(previous.file));
+    }
+    Ok(addon.version.clone())
+}
+
+/// Removes an installed addon.
+///
+/// The proxy stops loading it the next time it starts.
+pub fn uninstall(id: &str) -> Result<(), ProxyError> {
+    let mut installed = read_installed()?;
+    let addon = installed
+        .remove(id)
+        .ok_or_else(|| ProxyError::NoAddonFound(id.to_string()))?;
+
+    match fs::remove_file(get_addon_dir()?.join(&addon.file)) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    write_installed(&installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::models::Asset;
+
+    fn addon(id: &str, version: &str) -> Addon {
+        Addon {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            version: version.to_string(),
+            changelog: String::new(),
+            asset: Asset {
+                id: format!("{}-asset", id),
+                name: format!("{}.jar", id),
+                url: String::new(),
+            },
+        }
+    }
+
+    fn installed(id: &str, version: &str) -> (String, InstalledAddon) {
+        (
+            id.to_string(),
+            InstalledAddon {
+                name: id.to_string(),
+                description: String::new(),
+                version: version.to_string(),
+                file: format!("{}.jar", id),
+            },
+        )
+    }
+
+    #[test]
+    fn test_list_merges_available_and_installed() {
+        let available = vec![addon("autogg", "1.2.0"), addon("tags", "2.0.0")];
+        let installed: BTreeMap<_, _> =
+            [installed("autogg", "1.1.0"), installed("legacy", "0.1.0")].into();
+        let addons = list(&available, &installed, &["legacy".to_string()]);
+
+        let summary: Vec<_> = addons
+            .iter()
+            .map(|a| {
+                (
+                    a.id.as_str(),
+                    a.installed_version.as_deref(),
+                    a.enabled,
+                    a.update_available,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("autogg", Some("1.1.0"), true, true),
+                ("tags", None, true, false),
+                ("legacy", Some("0.1.0"), false, false),
+            ]
+        );
+        assert_eq!(addons[2].latest_version, None);
+    }
+
+    #[test]
+    fn test_list_up_to_date() {
+        let installed: BTreeMap<_, _> = [installed("autogg", "1.2.0")].into();
+        let addons = list(&[addon("autogg", "1.2.0")], &installed, &[]);
+        assert!(!addons[0].update_available);
+    }
+}
@@ -0,0 +1,156 @@
+//! Connection state tracking for the proxy.
+//!
+//! The proxy reports what the player is doing through scattered control
+//! messages. This module folds those into a single explicit state so the UI
+//! and Discord RPC don't each have to reconstruct it.
+
+use super::models::ConnectionState;
+
+/// Something that happened to the proxy or the player's connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The proxy process is being spawned
+    ProxyStarting,
+    /// The proxy finished launching (or was adopted) and accepts clients
+    ProxyLaunched,
+    /// A Minecraft client logged in through the proxy
+    ClientConnected,
+    /// The player joined a game (`in_game`) or returned to a lobby
+    GameMode { in_game: bool },
+    /// The player disconnected from Hypixel
+    ClientDisconnected,
+    /// The proxy process stopped or failed to start
+    ProxyStopped,
+}
+
+impl ConnectionState {
+    /// Returns the state after `event`, or `None` if the event doesn't apply
+    pub fn transition(self, event: &ConnectionEvent) -> Option<ConnectionState> {
+        use ConnectionState::*;
+
+        let next = match event {
+            ConnectionEvent::ProxyStopped => Stopped,
+            // Adopted proxies skip straight to WaitingForClient
+            ConnectionEvent::ProxyStarting if self == Stopped => ProxyStarting,
+            ConnectionEvent::ProxyLaunched if matches!(self, Stopped | ProxyStarting) => {
+                WaitingForClient
+            }
+            ConnectionEvent::ProxyStarting | ConnectionEvent::ProxyLaunched => return None,
+            // Client events can't arrive without a running proxy
+            _ if self == Stopped => return None,
+            ConnectionEvent::ClientConnected => ClientConnected,
+            // A game mode update implies a client even if user data was missed
+            ConnectionEvent::GameMode { in_game: true } => InGame,
+            ConnectionEvent::GameMode { in_game: false } => ClientConnected,
+            ConnectionEvent::ClientDisconnected if matches!(self, ClientConnected | InGame) => {
+                Disconnected
+            }
+            ConnectionEvent::ClientDisconnected => return None,
+        };
+
+        (next != self).then_some(next)
+    }
+}
+
+/// Holds the current connection state behind a lock
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    state: std::sync::Mutex<ConnectionState>,
+}
+
+impl ConnectionTracker {
+    /// Returns the current connection state
+    pub fn get(&self) -> ConnectionState {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Applies an event, returning the new state if it changed
+    pub fn apply(&self, event: &ConnectionEvent) -> Option<ConnectionState> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let next = state.transition(event)?;
+        *state = next;
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(events: &[ConnectionEvent]) -> ConnectionState {
+        let tracker = ConnectionTracker::default();
+        for event in events {
+            tracker.apply(event);
+        }
+        tracker.get()
+    }
+
+    #[test]
+    fn test_full_session() {
+        let tracker = ConnectionTracker::default();
+        assert_eq!(tracker.get(), ConnectionState::Stopped);
+
+        let steps = [
+            (
+                ConnectionEvent::ProxyStarting,
+                ConnectionState::ProxyStarting,
+            ),
+            (
+                ConnectionEvent::ProxyLaunched,
+                ConnectionState::WaitingForClient,
+            ),
+            (
+                ConnectionEvent::ClientConnected,
+                ConnectionState::ClientConnected,
+            ),
+            (
+                ConnectionEvent::GameMode { in_game: true },
+                ConnectionState::InGame,
+            ),
+            (
+                ConnectionEvent::GameMode { in_game: false },
+                ConnectionState::ClientConnected,
+            ),
+            (
+                ConnectionEvent::ClientDisconnected,
+                ConnectionState::Disconnected,
+            ),
+            (
+                ConnectionEvent::ClientConnected,
+                ConnectionState::ClientConnected,
+            ),
+            (ConnectionEvent::ProxyStopped, ConnectionState::Stopped),
+        ];
+        for (event, expected) in steps {
+            assert_eq!(tracker.apply(&event), Some(expected), "{:?}", event);
+        }
+    }
+
+    #[test]
+    fn test_ignored_events() {
+        let tracker = ConnectionTracker::default();
+        assert_eq!(tracker.apply(&ConnectionEvent::ClientConnected), None);
+        assert_eq!(tracker.apply(&ConnectionEvent::ProxyStopped), None);
+
+        tracker.apply(&ConnectionEvent::ProxyStarting);
+        tracker.apply(&ConnectionEvent::ProxyLaunched);
+        assert_eq!(tracker.apply(&ConnectionEvent::ClientDisconnected), None);
+        assert_eq!(tracker.apply(&ConnectionEvent::ProxyStarting), None);
+        assert_eq!(tracker.get(), ConnectionState::WaitingForClient);
+    }
+
+    #[test]
+    fn test_game_mode_without_user_data() {
+        let state = run(&[
+            ConnectionEvent::ProxyLaunched,
+            ConnectionEvent::GameMode { in_game: true },
+        ]);
+        assert_eq!(state, ConnectionState::InGame);
+    }
+
+    #[test]
+    fn test_connection_state_serialization() {
+        let json = serde_json::to_string(&ConnectionState::WaitingForClient).unwrap();
+        assert_eq!(json, r#""waitingForClient""#);
+    }
+}
@@ -0,0 +1,194 @@
+//! Crash reports for unexpected proxy exits.
+//!
+//! When the proxy dies without being asked to, the launcher writes a JSON
+//! bundle to `crashes/` in the data directory with recent output, the exit code and
+//! some environment details. Bundles stay local unless the user explicitly
+//! uploads one.
+
+use super::error::ProxyError;
+use crate::auth::{token, API_BASE_URL};
+use crate::config::models::Config;
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Contents of a crash bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    /// RFC 3339 timestamp of when the crash was recorded
+    pub created_at: String,
+    /// Exit code, if the process exited normally and the launcher saw it
+    pub exit_code: Option<i32>,
+    /// Human readable exit status (includes signals on Unix)
+    pub exit_status: Option<String>,
+    pub proxy_version: Option<String>,
+    pub launcher_version: String,
+    pub os: String,
+    pub arch: String,
+    /// Launcher config with user specific paths removed
+    pub config: serde_json::Value,
+    /// Most recent proxy output, oldest first
+    pub logs: Vec<String>,
+}
+
+impl CrashReport {
+    /// Builds a report for the current machine
+    pub fn new(
+        exit_status: Option<std::process::ExitStatus>,
+        proxy_version: Option<String>,
+        launcher_version: String,
+        config: &Config,
+        logs: Vec<String>,
+    ) -> Self {
+        Self {
+            created_at: chrono::Utc::now().to_rfc3339(),
+            exit_code: exit_status.and_then(|s| s.code()),
+            exit_status: exit_status.map(|s| s.to_string()),
+            proxy_version,
+            launcher_version,
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            config: scrub_config(config),
+            logs,
+        }
+    }
+}
+
+/// Gets the directory crash bundles are written to
+pub fn get_crash_dir() -> Result<PathBuf, ProxyError> {
+    utils::get_app_path(utils::AppDir::Data, "crashes").map_err(ProxyError::Unknown)
+}
+
+/// Serializes the config without the app PIN hash and the sealed API key,
+/// replacing the user's home directory in any string value so bundles don't
+/// leak account names
+pub fn scrub_config(config: &Config) -> serde_json::Value {
+    let config = Config {
+        app_pin: None,
+        hypixel_api_key: None,
+        ..config.clone()
+    };
+    let mut value = serde_json::to_value(&config).unwrap_or_default();
+    if let Ok(home) = utils::get_home_dir() {
+        scrub_value(&mut value, &home.to_string_lossy());
+    }
+    value
+}
+
+fn scrub_value(value: &mut serde_json::Value, home: &str) {
+    match value {
+        serde_json::Value::String(s) if !home.is_empty() => {
+            *s = s.replace(home, "~");
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                scrub_value(item, home);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values_mut() {
+                scrub_value(item, home);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Writes a crash bundle and returns its path
+pub fn write_crash_report(report: &CrashReport) -> Result<PathBuf, ProxyError> {
+    let dir = get_crash_dir()?;
+    std::fs::create_dir_all(&dir)?;
+
+    let name = format!(
+        "crash-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = dir.join(name);
+    std::fs::write(&path, serde_json::to_string_pretty(report)?)?;
+    Ok(path)
+}
+
+/// Uploads a crash bundle to the API.
+///
+/// Only bundles inside the crash directory are accepted. The user's token is
+/// attached when signed in so reports can be followed up on.
+pub async fn upload_crash_report(path: &Path) -> Result<(), ProxyError> {
+    let dir = get_crash_dir()?.canonicalize()?;
+    let path = path.canonicalize()?;
+    if !path.starts_with(&dir) {
+        return Err(ProxyError::CrashReport(
+            "Crash report is not in the crash directory".to_string(),
+        ));
+    }
+
+    let report: CrashReport = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+
+    let client = reqwest::Client::new();
+    let mut request = client
+        .post(format!("{}/crash-reports", API_BASE_URL))
+        .json(&report);
+    if let Ok(Some(token)) = token::get_token().await {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(ProxyError::CrashReport(format!(
+            "Upload failed with status {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_value_replaces_home_dir() {
+        let mut value = serde_json::json!({
+            "postLaunchCommand": "/home/steve/.minecraft/launcher",
+            "nested": ["/home/steve/a", 1, true],
+            "proxyPort": "25565",
+        });
+        scrub_value(&mut value, "/home/steve");
+
+        assert_eq!(value["postLaunchCommand"], "~/.minecraft/launcher");
+        assert_eq!(value["nested"][0], "~/a");
+        assert_eq!(value["nested"][1], 1);
+        assert_eq!(value["proxyPort"], "25565");
+    }
+
+    #[test]
+    fn test_scrub_config_drops_secrets() {
+        let config = Config {
+            app_pin: Some("$argon2id$v=19$m=19456,t=2,p=1$salt$hash".to_string()),
+            hypixel_api_key: Some("chacha20poly1305$00$00".to_string()),
+            ..Config::default()
+        };
+        let value = scrub_config(&config);
+
+        assert_eq!(value["appPin"], serde_json::Value::Null);
+        assert_eq!(value["hypixelApiKey"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_crash_report_serialization() {
+        let report = CrashReport::new(
+            None,
+            Some("1.2.3".to_string()),
+            "0.1.0".to_string(),
+            &Config::default(),
+            vec!["line".to_string()],
+        );
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["proxyVersion"], "1.2.3");
+        assert_eq!(json["exitCode"], serde_json::Value::Null);
+        assert_eq!(json["logs"][0], "line");
+        assert!(json["config"].is_object());
+    }
+}
@@ -5,14 +5,24 @@ use super::models::{Asset, DownloadProgress, Release};
 use reqwest;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::io::AsyncWriteExt;
 
+use crate::journal::{self, Operation};
 use crate::utils;
 
 const API_BASE: &str = "https://proxy-updates.duelsplus.com/v1/releases";
 const API_BASE_BETA: &str = "https://proxy-updates.duelsplus.com/v1/releases/beta";
 const MIN_FILE_SIZE_MB: f64 = 50.0;
 
+/// How long a fetched release list is reused by `fetch_recent_releases`
+const RECENT_RELEASES_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Release lists fetched recently, with whether they're beta and when
+static RECENT_RELEASES: Mutex<Vec<(bool, Instant, Vec<Release>)>> = Mutex::new(Vec::new());
+
 /// Gets the platform-specific tag for binary selection
 pub fn get_platform_tag() -> Result<String, ProxyError> {
     let tag = match std::env::consts::OS {
@@ -28,8 +38,7 @@ pub fn get_platform_tag() -> Result<String, ProxyError> {
 
 /// Gets the installation directory for the proxy
 pub fn get_install_dir() -> Result<PathBuf, ProxyError> {
-    let app_root = utils::get_app_root().map_err(|e| ProxyError::Unknown(e))?;
-    Ok(app_root.join("proxy"))
+    utils::get_app_path(utils::AppDir::Data, "proxy").map_err(ProxyError::Unknown)
 }
 
 /// Fetches the list of releases from the API
@@ -48,6 +57,26 @@ pub async fn fetch_releases(use_beta: bool) -> Result<Vec<Release>, ProxyError>
     Ok(releases)
 }
 
+/// Fetches the list of releases, reusing one fetched in the last few
+/// minutes, e.g. by the startup prefetch
+pub async fn fetch_recent_releases(use_beta: bool) -> Result<Vec<Release>, ProxyError> {
+    let recent = RECENT_RELEASES
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(beta, fetched, _)| *beta == use_beta && fetched.elapsed() < RECENT_RELEASES_TTL)
+        .map(|(_, _, releases)| releases.clone());
+    if let Some(releases) = recent {
+        return Ok(releases);
+    }
+
+    let releases = fetch_releases(use_beta).await?;
+    let mut recent = RECENT_RELEASES.lock().unwrap();
+    recent.retain(|(beta, _, _)| *beta != use_beta);
+    recent.push((use_beta, Instant::now(), releases.clone()));
+    Ok(releases)
+}
+
 /// Finds the latest release.
 /// Prefers the release marked `is_latest`; falls back to the first (newest) release
 /// when the API omits the flag (e.g. stable /v1/releases).
@@ -71,6 +100,29 @@ pub fn find_platform_asset<'a>(
         .ok_or_else(|| ProxyError::NoAssetFound(platform_tag.to_string()))
 }
 
+/// Checks whether a newer proxy than the installed one has been released.
+///
+/// Returns the new version, or `None` if it's already installed or no proxy
+/// has been installed yet (the first launch downloads it anyway).
+pub async fn find_pending_update(use_beta: bool) -> Result<Option<String>, ProxyError> {
+    let install_dir = get_install_dir()?;
+    let installed = fs::read_dir(&install_dir)
+        .map(|entries| entries.flatten().any(|e| e.path().is_file()))
+        .unwrap_or(false);
+    if !installed {
+        return Ok(None);
+    }
+
+    let releases = fetch_recent_releases(use_beta).await?;
+    let latest = find_latest_release(&releases)?;
+    let asset = find_platform_asset(latest, &get_platform_tag()?)?;
+    if is_file_valid(&install_dir.join(&asset.name)) {
+        Ok(None)
+    } else {
+        Ok(Some(latest.version.clone()))
+    }
+}
+
 /// Checks if the file exists and is valid (not corrupted/incomplete)
 pub fn is_file_valid(path: &PathBuf) -> bool {
     if !path.exists() {
@@ -87,18 +139,68 @@ pub fn is_file_valid(path: &PathBuf) -> bool {
 
 /// Downloads an artifact with progress tracking.
 /// Signed URL is always /releases/signed for both stable and beta assets.
+/// Setting `cancel` aborts the download and removes the partial file.
 pub async fn download_artifact<F>(
     asset_id: &str,
     dest_path: &PathBuf,
-    mut progress_callback: F,
+    cancel: &AtomicBool,
+    progress_callback: F,
 ) -> Result<(), ProxyError>
 where
     F: FnMut(DownloadProgress),
 {
     let url = format!("{}/signed?assetId={}", API_BASE, asset_id);
+    download_file(&url, dest_path, cancel, progress_callback).await?;
+
+    // Set executable permissions on Unix systems
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(dest_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(dest_path, perms)?;
+    }
+
+    Ok(())
+}
+
+/// Downloads a file with progress tracking.
+/// Setting `cancel` aborts the download and removes the partial file, as
+/// does a failed download. The download is journaled while it runs, so a
+/// partial file left by a killed launcher is removed on the next start.
+pub async fn download_file<F>(
+    url: &str,
+    dest_path: &PathBuf,
+    cancel: &AtomicBool,
+    progress_callback: F,
+) -> Result<(), ProxyError>
+where
+    F: FnMut(DownloadProgress),
+{
+    let operation = Operation::Download {
+        path: dest_path.clone(),
+    };
+    journal::record(&operation);
+    let result = transfer(url, dest_path, cancel, progress_callback).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(dest_path).await;
+    }
+    journal::remove(&operation);
+    result
+}
+
+async fn transfer<F>(
+    url: &str,
+    dest_path: &PathBuf,
+    cancel: &AtomicBool,
+    mut progress_callback: F,
+) -> Result<(), ProxyError>
+where
+    F: FnMut(DownloadProgress),
+{
     let client = reqwest::Client::new();
 
-    let response = client.get(&url).send().await?;
+    let response = client.get(url).send().await?;
 
     if !response.status().is_success() {
         return Err(ProxyError::Network(
@@ -121,6 +223,10 @@ where
     use futures_util::StreamExt;
 
     while let Some(chunk) = stream.next().await {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(ProxyError::Cancelled);
+        }
+
         let chunk = chunk?;
         file.write_all(&chunk).await?;
         downloaded += chunk.len() as u64;
@@ -140,16 +246,6 @@ where
     }
 
     file.flush().await?;
-
-    // Set executable permissions on Unix systems
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(dest_path)?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(dest_path, perms)?;
-    }
-
     Ok(())
 }
 
@@ -21,14 +21,50 @@ pub enum ProxyError {
     #[error("Proxy process error: {0}")]
     ProcessError(String),
 
+    /// Operation was cancelled (e.g. a download during shutdown)
+    #[error("Operation was cancelled")]
+    Cancelled,
+
     /// Proxy already running
     #[error("Proxy is already running")]
     AlreadyRunning,
 
+    /// Proxy is being downloaded or launched and can't take the request yet
+    #[error("Proxy is starting, try again once it is ready")]
+    Busy,
+
     /// Proxy not running
     #[error("Proxy is not running")]
     NotRunning,
 
+    /// Control socket is not connected
+    #[error("Proxy control socket is not connected")]
+    ControlSocketUnavailable,
+
+    /// Proxy did not answer a metrics query in time
+    #[error("Proxy did not report metrics")]
+    MetricsUnavailable,
+
+    /// Proxy stdin is not available (detached or adopted instance)
+    #[error("Proxy console is not available for this instance")]
+    StdinUnavailable,
+
+    /// Proxy was spawned but never became ready
+    #[error("Proxy failed to start: {0}")]
+    LaunchFailed(String),
+
+    /// Firewall rule could not be checked or created
+    #[error("Firewall error: {0}")]
+    Firewall(String),
+
+    /// Crash report could not be uploaded
+    #[error("Crash report error: {0}")]
+    CrashReport(String),
+
+    /// Configured proxy port is not a valid port number
+    #[error("Invalid proxy port: {0}")]
+    InvalidPort(String),
+
     /// Unsupported platform
     #[error("Unsupported platform: {0}")]
     UnsupportedPlatform(String),
@@ -41,11 +77,43 @@ pub enum ProxyError {
     #[error("No asset found for platform: {0}")]
     NoAssetFound(String),
 
+    /// No addon with this id is offered or installed
+    #[error("No addon found: {0}")]
+    NoAddonFound(String),
+
     /// Generic error for unexpected situations
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
+impl ProxyError {
+    /// Gets the kind of error without its details, for telemetry
+    pub fn category(&self) -> &'static str {
+        match self {
+            ProxyError::Io(_) => "io",
+            ProxyError::Network(_) => "network",
+            ProxyError::Json(_) => "json",
+            ProxyError::ProcessError(_) => "process",
+            ProxyError::Cancelled => "cancelled",
+            ProxyError::AlreadyRunning => "alreadyRunning",
+            ProxyError::Busy => "busy",
+            ProxyError::NotRunning => "notRunning",
+            ProxyError::ControlSocketUnavailable => "controlSocketUnavailable",
+            ProxyError::MetricsUnavailable => "metricsUnavailable",
+            ProxyError::StdinUnavailable => "stdinUnavailable",
+            ProxyError::LaunchFailed(_) => "launchFailed",
+            ProxyError::Firewall(_) => "firewall",
+            ProxyError::CrashReport(_) => "crashReport",
+            ProxyError::InvalidPort(_) => "invalidPort",
+            ProxyError::UnsupportedPlatform(_) => "unsupportedPlatform",
+            ProxyError::NoReleaseFound => "noReleaseFound",
+            ProxyError::NoAssetFound(_) => "noAssetFound",
+            ProxyError::NoAddonFound(_) => "noAddonFound",
+            ProxyError::Unknown(_) => "unknown",
+        }
+    }
+}
+
 impl serde::Serialize for ProxyError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -0,0 +1,101 @@
+//! Instance-scoped proxy events.
+//!
+//! Every event emitted by the proxy module is wrapped in a [`ScopedEvent`]
+//! naming the proxy instance and version it came from, so the frontend can
+//! route output to the right place instead of one global stream.
+//!
+//! In streamer mode, names and secrets in the payload are hidden first.
+
+use super::ProxyManager;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Identifies the proxy instance an event belongs to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceScope {
+    /// Unique per launch or adoption; empty before the first one
+    pub instance_id: String,
+    /// Proxy release version, `None` until known (e.g. for adopted instances)
+    pub proxy_version: Option<String>,
+}
+
+impl InstanceScope {
+    /// Creates a scope with a fresh instance id and no version
+    pub fn new() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        Self {
+            instance_id: format!("{:x}", nanos),
+            proxy_version: None,
+        }
+    }
+}
+
+/// Envelope sent to the frontend for every proxy event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopedEvent<T> {
+    #[serde(flatten)]
+    pub scope: InstanceScope,
+    pub payload: T,
+}
+
+/// `Emitter::emit` counterpart that tags the payload with the current instance
+pub trait ScopedEmitter {
+    fn emit_scoped<S: Serialize + Clone>(&self, event: &str, payload: S) -> tauri::Result<()>;
+}
+
+impl ScopedEmitter for AppHandle {
+    fn emit_scoped<S: Serialize + Clone>(&self, event: &str, payload: S) -> tauri::Result<()> {
+        // Keep what the Logs tab shows for diagnostic bundles
+        if event == "log-message" {
+            if let Some(line) = serde_json::to_value(&payload)
+                .ok()
+                .as_ref()
+                .and_then(serde_json::Value::as_str)
+            {
+                crate::diagnostics::record_log(line);
+            }
+        }
+        let scope = self
+            .try_state::<ProxyManager>()
+            .map(|proxy| proxy.instance_scope())
+            .unwrap_or_default();
+        if crate::streamer::is_enabled() {
+            let mut payload = serde_json::to_value(&payload)?;
+            crate::streamer::scrub_value(&mut payload);
+            return self.emit(event, ScopedEvent { scope, payload });
+        }
+        self.emit(event, ScopedEvent { scope, payload })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_event_serialization() {
+        let event = ScopedEvent {
+            scope: InstanceScope {
+                instance_id: "abc".to_string(),
+                proxy_version: Some("1.2.3".to_string()),
+            },
+            payload: "Proxy started",
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["instanceId"], "abc");
+        assert_eq!(value["proxyVersion"], "1.2.3");
+        assert_eq!(value["payload"], "Proxy started");
+    }
+
+    #[test]
+    fn test_instance_scope_new() {
+        let scope = InstanceScope::new();
+        assert!(!scope.instance_id.is_empty());
+        assert!(scope.proxy_version.is_none());
+    }
+}
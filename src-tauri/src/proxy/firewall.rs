@@ -0,0 +1,156 @@
+//! Windows Firewall assistance for the proxy port.
+//!
+//! On a fresh install Windows Firewall often blocks inbound connections to
+//! the proxy without any prompt, so the launcher checks the firewall's
+//! rules after launch and can add one (elevated through UAC) on request.
+//! Connecting to the machine's own address would tell nothing, since
+//! Windows routes that over loopback, past the firewall. Other platforms
+//! report `Unsupported`.
+
+use super::error::ProxyError;
+use serde::{Deserialize, Serialize};
+
+/// Name of the inbound rule created for the proxy executable
+#[cfg(windows)]
+const RULE_NAME: &str = "Duels+ Proxy";
+
+/// Result of a firewall check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FirewallStatus {
+    /// An enabled inbound rule allows the proxy or its port, or the
+    /// firewall is off
+    Allowed,
+    /// No enabled inbound rule allows them, or one blocks them
+    Blocked,
+    /// The check itself failed (e.g. the proxy isn't installed)
+    Unknown,
+    /// Not running on Windows
+    Unsupported,
+}
+
+/// Checks whether the firewall lets connections through to the proxy port
+#[cfg(windows)]
+pub async fn check_firewall(port: u16) -> FirewallStatus {
+    let Ok(program) = find_proxy_executable() else {
+        return FirewallStatus::Unknown;
+    };
+    // Block rules win over allow rules, like in the firewall itself
+    let script = format!(
+        "$program = '{}'; $port = '{}'; \
+         if (-not (Get-NetFirewallProfile | Where-Object Enabled -eq 'True')) {{ 'allowed'; exit }}; \
+         $rules = @(Get-NetFirewallApplicationFilter -Program $program -ErrorAction SilentlyContinue | Get-NetFirewallRule -ErrorAction SilentlyContinue) + \
+           @(Get-NetFirewallPortFilter -Protocol TCP -ErrorAction SilentlyContinue | Where-Object {{ $_.LocalPort -contains $port }} | Get-NetFirewallRule -ErrorAction SilentlyContinue); \
+         $rules = @($rules | Where-Object {{ $_.Enabled -eq 'True' -and $_.Direction -eq 'Inbound' }}); \
+         if ($rules | Where-Object Action -eq 'Block') {{ 'blocked' }} \
+         elseif ($rules | Where-Object Action -eq 'Allow') {{ 'allowed' }} \
+         else {{ 'blocked' }}",
+        program.display().to_string().replace('\'', "''"),
+        port
+    );
+    let output = hidden_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .stdout(std::process::Stdio::piped())
+        .output()
+        .await;
+    match output {
+        Ok(output) if output.status.success() => {
+            match String::from_utf8_lossy(&output.stdout).trim() {
+                "allowed" => FirewallStatus::Allowed,
+                "blocked" => FirewallStatus::Blocked,
+                _ => FirewallStatus::Unknown,
+            }
+        }
+        _ => FirewallStatus::Unknown,
+    }
+}
+
+/// Checks whether the firewall lets connections through to the proxy port
+#[cfg(not(windows))]
+pub async fn check_firewall(_port: u16) -> FirewallStatus {
+    FirewallStatus::Unsupported
+}
+
+/// Adds an inbound allow rule for the installed proxy executable.
+///
+/// Prompts for elevation through UAC and waits for `netsh` to finish, then
+/// verifies the rule was actually created (the user may decline the prompt).
+#[cfg(windows)]
+pub async fn create_firewall_rule() -> Result<FirewallStatus, ProxyError> {
+    let program = find_proxy_executable()?;
+
+    let netsh_args = format!(
+        "advfirewall firewall add rule name=\"{}\" dir=in action=allow program=\"{}\" enable=yes",
+        RULE_NAME,
+        program.display()
+    );
+    // Single quotes are escaped by doubling them in PowerShell strings
+    let script = format!(
+        "Start-Process -FilePath netsh -Verb RunAs -Wait -WindowStyle Hidden -ArgumentList '{}'",
+        netsh_args.replace('\'', "''")
+    );
+
+    let status = hidden_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(ProxyError::Firewall(
+            "Elevation was cancelled or failed".to_string(),
+        ));
+    }
+
+    if rule_exists().await {
+        Ok(FirewallStatus::Allowed)
+    } else {
+        Err(ProxyError::Firewall(
+            "Firewall rule was not created".to_string(),
+        ))
+    }
+}
+
+/// Adds an inbound allow rule for the installed proxy executable.
+#[cfg(not(windows))]
+pub async fn create_firewall_rule() -> Result<FirewallStatus, ProxyError> {
+    Err(ProxyError::Firewall(
+        "Firewall rules are only managed on Windows".to_string(),
+    ))
+}
+
+/// Builds a command that doesn't flash a console window
+#[cfg(windows)]
+fn hidden_command(program: &str) -> tokio::process::Command {
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let mut cmd = tokio::process::Command::new(program);
+    cmd.creation_flags(CREATE_NO_WINDOW)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    cmd
+}
+
+/// Checks whether the launcher's inbound rule is present
+#[cfg(windows)]
+async fn rule_exists() -> bool {
+    hidden_command("netsh")
+        .args(["advfirewall", "firewall", "show", "rule"])
+        .arg(format!("name={}", RULE_NAME))
+        .status()
+        .await
+        .is_ok_and(|s| s.success())
+}
+
+/// Finds the proxy executable in the install directory
+#[cfg(windows)]
+fn find_proxy_executable() -> Result<std::path::PathBuf, ProxyError> {
+    let install_dir = super::download::get_install_dir()?;
+    std::fs::read_dir(&install_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("exe"))
+        })
+        .ok_or_else(|| {
+            ProxyError::Firewall("Proxy is not installed yet, launch it first".to_string())
+        })
+}
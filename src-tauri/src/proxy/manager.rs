@@ -1,39 +1,126 @@
 //! Proxy process management.
 
+use super::connection::{ConnectionEvent, ConnectionTracker};
+use super::crash::{write_crash_report, CrashReport};
 use super::download::{
     cleanup_old_executables, download_artifact, fetch_releases, find_latest_release,
     find_platform_asset, get_install_dir, get_platform_tag, is_file_valid,
 };
 use super::error::ProxyError;
-use super::models::{ErrorCategory, ErrorSeverity, ProxyErrorData, ProxyStatus, RpcUserData};
+use super::events::{InstanceScope, ScopedEmitter};
+use super::firewall::{check_firewall, FirewallStatus};
+use super::models::{
+    ConnectionState, ControlCommand, ErrorCategory, ErrorSeverity, GameEvent, LaunchFailure,
+    MinecraftLaunchInfo, Opponent, OrphanProxyInfo, ProtocolMismatch, ProxyCrashInfo,
+    ProxyErrorData, ProxyMetrics, ProxyStatus, RpcUserData, ShutdownReport, StopOutcome,
+};
+use super::process::{
+    bind_to_launcher, is_process_alive, kill_process, kill_process_group, runs_from,
+    set_process_priority, spawn_detached, LifetimeGuard,
+};
+#[cfg(unix)]
+use super::process::{terminate_process, terminate_process_group};
+use super::transport::{self, ControlStream, ControlWriter};
 use crate::config::manager::get_config;
+use crate::config::models::ProxyPriority;
+use crate::i18n::t;
+use crate::logs_window;
+use crate::notification_router::{NotificationAction, Route};
+use crate::notifications::{self, Category};
+use crate::overlay;
 use crate::rpc::RpcManager;
+use crate::session_stats;
+use crate::storage::matches;
+use crate::streamer;
+use crate::telemetry;
 use crate::utils::get_home_dir;
 use serde::Deserialize;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
-use tokio::process::{Child, Command};
-use tokio::sync::Mutex;
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{Mutex, Notify};
+
+/// How long a freshly spawned proxy has to start accepting connections
+const PROXY_READY_TIMEOUT_SECS: u64 = 30;
+
+/// Number of stderr lines kept for launch failure reports
+const STDERR_TAIL_LINES: usize = 50;
+
+/// Number of output lines kept for crash reports
+const LOG_TAIL_LINES: usize = 500;
+
+/// Config keys (camelCase) mirrored to the proxy over the control socket
+pub const PROXY_SYNCED_KEYS: &[&str] = &[
+    "enableMsa",
+    "rpcAnonymizeProfile",
+    "rpcAnonymizeLocation",
+    "disabledAddons",
+];
+
+/// Upper bound for `shutdown_all`, so quitting never hangs on the proxy
+const SHUTDOWN_ALL_TIMEOUT_SECS: u64 = 20;
+
+/// Shutdown timeout used when the config doesn't specify one
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 5;
+
+/// Longest shutdown timeout used, whatever the config says. A stop waits it
+/// out twice before the force kill, which has to happen within
+/// `SHUTDOWN_ALL_TIMEOUT_SECS` when the launcher quits
+const MAX_SHUTDOWN_TIMEOUT_SECS: u64 = SHUTDOWN_ALL_TIMEOUT_SECS / 3;
+
+/// Control protocol version spoken by this launcher build
+pub const CONTROL_PROTOCOL_VERSION: u32 = 1;
+
+/// Parses a configured proxy port, rejecting anything outside 1-65535
+pub fn parse_proxy_port(value: &str) -> Result<u16, ProxyError> {
+    match value.trim().parse::<u16>() {
+        Ok(port) if port != 0 => Ok(port),
+        _ => Err(ProxyError::InvalidPort(value.to_string())),
+    }
+}
 
 /// Lock file data structure written by the proxy
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct LockFileData {
-    #[allow(dead_code)]
     pid: u32,
-    #[allow(dead_code)]
     port: u16,
     control_port: Option<u16>,
+    /// Unix socket path or Windows pipe name for the control channel,
+    /// preferred over `control_port` when present
+    #[serde(default)]
+    control_socket: Option<String>,
+    /// Set by the launcher when it exits and intentionally leaves the proxy running
+    #[serde(default)]
+    launcher_detached: bool,
+}
+
+impl LockFileData {
+    /// Connects to the control channel advertised by this lock file
+    async fn connect_control(&self) -> Option<ControlStream> {
+        transport::connect(self.control_socket.as_deref(), self.control_port).await
+    }
+
+    /// Whether the proxy advertises any control channel at all
+    fn has_control_channel(&self) -> bool {
+        self.control_socket.is_some() || self.control_port.is_some()
+    }
 }
 
 /// Control socket message types
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ControlMessage {
+    /// Reply to the launcher's `hello`, carrying the proxy's protocol version
+    Hello {
+        protocol_version: u32,
+    },
     UserData {
         ign: String,
         uuid: String,
@@ -43,8 +130,40 @@ enum ControlMessage {
         map: Option<String>,
         gametype: Option<String>,
         lobbyname: Option<String>,
+        /// Expected end of a timed game, as a unix timestamp (seconds)
+        #[serde(default)]
+        ends_at: Option<i64>,
     },
     Disconnect,
+    /// Hypixel party changes; `id` is `None` when the player isn't in a party
+    Party {
+        id: Option<String>,
+        #[serde(default)]
+        size: u32,
+        #[serde(default)]
+        max: u32,
+    },
+    /// The player started or stopped spectating a game or watching a replay;
+    /// `target` is the mode being watched, `None` when it ended
+    Spectating {
+        target: Option<String>,
+    },
+    /// Kill, death, win, loss or game start/end in the current game
+    GameEvent(GameEvent),
+    /// The opponent in the current duel, sent once the proxy knows who it is
+    Opponent(Opponent),
+    /// A Hypixel friend joined the server
+    FriendOnline {
+        name: String,
+    },
+    /// Reply to `get_metrics`
+    Metrics(ProxyMetrics),
+    /// Reply to a heartbeat ping
+    Pong,
+    /// Proxy log line, only sent after a `subscribe_logs` command
+    Log {
+        line: String,
+    },
     ProxyError {
         code: String,
         title: String,
@@ -73,12 +192,42 @@ fn read_lock_file() -> Option<LockFileData> {
     serde_json::from_str(&content).ok()
 }
 
-/// Sends a shutdown command to the proxy via TCP control socket
-async fn send_shutdown_command(control_port: u16) -> bool {
-    let addr = format!("127.0.0.1:{}", control_port);
+/// Records in the lock file whether the launcher left this proxy running on
+/// purpose
+fn set_lock_file_detached(detached: bool) -> Result<(), ProxyError> {
+    let path = get_lock_file_path()
+        .ok_or_else(|| ProxyError::Unknown("Failed to get lock file path".to_string()))?;
+    let content = std::fs::read_to_string(&path)?;
+
+    let mut data: serde_json::Value = serde_json::from_str(&content)?;
+    data["launcherDetached"] = serde_json::Value::Bool(detached);
 
+    std::fs::write(&path, serde_json::to_string(&data)?)?;
+    Ok(())
+}
+
+/// Deletes the proxy lock file, ignoring errors
+fn remove_lock_file() {
+    if let Some(path) = get_lock_file_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Whether `pid` is alive and runs a proxy from the install directory.
+///
+/// A pid from the lock file may belong to an unrelated process by now, e.g.
+/// after a reboot, so it's only trusted once its executable matches.
+fn is_proxy_process(pid: u32) -> bool {
+    is_process_alive(pid) && get_install_dir().is_ok_and(|dir| runs_from(pid, &dir))
+}
+
+/// Sends a shutdown command to the proxy over its control channel
+async fn send_shutdown_command(lock_data: &LockFileData) -> bool {
     let result = tokio::time::timeout(std::time::Duration::from_secs(5), async {
-        let mut stream = TcpStream::connect(&addr).await?;
+        let mut stream = lock_data
+            .connect_control()
+            .await
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotConnected))?;
         stream.write_all(b"shutdown").await?;
 
         let mut buf = [0u8; 16];
@@ -95,113 +244,641 @@ async fn send_shutdown_command(control_port: u16) -> bool {
     }
 }
 
-/// Connects to the control socket and listens for user data messages
-async fn listen_control_socket(app: AppHandle, is_running: Arc<Mutex<bool>>) {
+/// Initial delay before retrying a failed control socket connection
+const CONTROL_RECONNECT_INITIAL_MS: u64 = 200;
+
+/// Upper bound for the control socket reconnect backoff
+const CONTROL_RECONNECT_MAX_MS: u64 = 5000;
+
+/// How often a ping is sent over the control socket
+const HEARTBEAT_INTERVAL_SECS: u64 = 5;
+
+/// How long the proxy may stay silent before it is reported as unresponsive
+const HEARTBEAT_TIMEOUT_SECS: u64 = 15;
+
+/// How often metrics are sampled for `proxy-metrics` events
+const METRICS_INTERVAL_SECS: u64 = 2;
+
+/// How long `get_metrics` waits for the proxy to answer
+const METRICS_QUERY_TIMEOUT_SECS: u64 = 2;
+
+/// Attempts a single connection to the control channel advertised in the lock file
+async fn connect_control_socket() -> Option<ControlStream> {
+    read_lock_file()?.connect_control().await
+}
+
+/// Connects to the control socket and listens for user data messages.
+///
+/// The connection is re-established with exponential backoff whenever it
+/// drops (e.g. the proxy restarts its control server), for as long as the
+/// proxy is marked as running. When `subscribe_logs` is set, the proxy is
+/// asked to stream its log lines over the socket after every connect; this
+/// is used for adopted instances whose stdout the launcher doesn't own.
+async fn listen_control_socket(
+    app: AppHandle,
+    is_running: Arc<Mutex<bool>>,
+    control_writer: Arc<Mutex<Option<ControlWriter>>>,
+    reconnect: Arc<Notify>,
+    subscribe_logs: bool,
+) {
     // Wait a bit for the proxy to start and write the lock file
     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
 
-    // Retry connecting to the control socket
-    let mut stream = None;
-    for _ in 0..10 {
-        if !*is_running.lock().await {
-            return;
-        }
+    let mut delay_ms = CONTROL_RECONNECT_INITIAL_MS;
 
-        if let Some(lock_data) = read_lock_file() {
-            if let Some(control_port) = lock_data.control_port {
-                let addr = format!("127.0.0.1:{}", control_port);
-                if let Ok(s) = TcpStream::connect(&addr).await {
-                    stream = Some(s);
-                    break;
-                }
+    while *is_running.lock().await {
+        let stream = match connect_control_socket().await {
+            Some(s) => s,
+            None => {
+                tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                delay_ms = (delay_ms * 2).min(CONTROL_RECONNECT_MAX_MS);
+                continue;
             }
-        }
-        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        };
+
+        // Connected, reset the backoff for the next drop
+        delay_ms = CONTROL_RECONNECT_INITIAL_MS;
+
+        read_control_messages(
+            &app,
+            stream,
+            &is_running,
+            &control_writer,
+            &reconnect,
+            subscribe_logs,
+        )
+        .await;
+        *control_writer.lock().await = None;
     }
+}
 
-    let stream = match stream {
-        Some(s) => s,
-        None => return,
+/// Reads control messages from a connected socket until it closes or
+/// `reconnect` is notified.
+///
+/// Metrics are requested every [`METRICS_INTERVAL_SECS`]; proxies that
+/// don't know the query ignore it. Also sends a ping every
+/// [`HEARTBEAT_INTERVAL_SECS`]. Once the proxy has
+/// answered at least one ping, going silent for longer than
+/// [`HEARTBEAT_TIMEOUT_SECS`] emits `proxy-unresponsive`; the next message
+/// received emits `proxy-responsive`. Proxies that never answer pings are
+/// not monitored, so older builds don't get flagged as hung.
+async fn read_control_messages(
+    app: &AppHandle,
+    stream: ControlStream,
+    is_running: &Arc<Mutex<bool>>,
+    control_writer: &Arc<Mutex<Option<ControlWriter>>>,
+    reconnect: &Notify,
+    subscribe_logs: bool,
+) {
+    let (read_half, write_half) = tokio::io::split(stream);
+    let mut lines = BufReader::new(read_half).lines();
+
+    // Share the write half so commands can be sent over the same connection
+    *control_writer.lock().await = Some(write_half);
+
+    let hello = ControlCommand::Hello {
+        protocol_version: CONTROL_PROTOCOL_VERSION,
     };
+    if write_control_command(control_writer, &hello).await.is_err() {
+        return;
+    }
 
-    let reader = BufReader::new(stream);
-    let mut lines = reader.lines();
+    if subscribe_logs
+        && write_control_command(control_writer, &ControlCommand::SubscribeLogs)
+            .await
+            .is_err()
+    {
+        return;
+    }
+
+    if push_proxy_settings(control_writer).await.is_err() {
+        return;
+    }
+
+    let mut heartbeat =
+        tokio::time::interval(std::time::Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
+    let timeout = std::time::Duration::from_secs(HEARTBEAT_TIMEOUT_SECS);
+    let mut metrics = tokio::time::interval(std::time::Duration::from_secs(METRICS_INTERVAL_SECS));
+    let mut last_seen = std::time::Instant::now();
+    let mut supports_heartbeat = false;
+    let mut unresponsive = false;
+    let mut unknown_types = std::collections::HashSet::new();
 
     while *is_running.lock().await {
-        match lines.next_line().await {
-            Ok(Some(line)) => {
-                if let Ok(msg) = serde_json::from_str::<ControlMessage>(&line) {
-                    match msg {
-                        ControlMessage::UserData { ign, uuid } => {
-                            // Emit event for frontend
-                            let _ = app.emit(
-                                "rpc-user-data",
-                                RpcUserData {
-                                    ign: ign.clone(),
-                                    uuid: uuid.clone(),
-                                },
-                            );
-
-                            // Update Discord RPC directly
-                            if let Some(rpc) = app.try_state::<RpcManager>() {
-                                rpc.set_user_data(Some(ign), Some(uuid));
-                            }
-                        }
-                        ControlMessage::GameMode {
-                            mode,
-                            map,
-                            gametype,
-                            lobbyname,
-                        } => {
-                            // Update Discord RPC with game mode (mode can be null when in lobby)
-                            if let Some(rpc) = app.try_state::<RpcManager>() {
-                                rpc.set_game_mode(mode, map, gametype, lobbyname);
-                            }
+        tokio::select! {
+            result = lines.next_line() => {
+                match result {
+                    Ok(Some(line)) => {
+                        last_seen = std::time::Instant::now();
+                        if unresponsive {
+                            unresponsive = false;
+                            let _ = app.emit_scoped("proxy-responsive", ());
                         }
-                        ControlMessage::Disconnect => {
-                            // User disconnected from Hypixel, reset RPC to idle
-                            if let Some(rpc) = app.try_state::<RpcManager>() {
-                                rpc.set_disconnected();
+
+                        match serde_json::from_str::<ControlMessage>(&line) {
+                            Ok(ControlMessage::Pong) => supports_heartbeat = true,
+                            Ok(msg) => handle_control_message(app, msg),
+                            Err(_) => {
+                                // Log each unhandled type once per connection
+                                if let Some(kind) = control_message_type(&line) {
+                                    if unknown_types.insert(kind.clone()) {
+                                        println!(
+                                            "[proxy] Ignoring unsupported control message type: {}",
+                                            kind
+                                        );
+                                    }
+                                }
                             }
                         }
-                        ControlMessage::ProxyError {
-                            code,
-                            title,
-                            message,
-                            suggestion,
-                            severity,
-                            category,
-                            original_message,
-                            context,
-                            timestamp,
-                        } => {
-                            // Emit error event for frontend to handle
-                            let error_data = ProxyErrorData {
-                                code,
-                                title,
-                                message,
-                                suggestion,
-                                severity,
-                                category,
-                                original_message,
-                                context,
-                                timestamp,
-                            };
-                            let _ = app.emit("proxy-error", error_data);
-                        }
                     }
+                    Ok(None) => break,
+                    Err(_) => break,
                 }
             }
-            Ok(None) => break,
-            Err(_) => break,
+            _ = heartbeat.tick() => {
+                if supports_heartbeat && !unresponsive && last_seen.elapsed() > timeout {
+                    unresponsive = true;
+                    println!("[proxy] Control socket unresponsive");
+                    let _ = app.emit_scoped("proxy-unresponsive", ());
+                }
+
+                if write_control_command(control_writer, &ControlCommand::Ping)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            _ = metrics.tick() => {
+                if write_control_command(control_writer, &ControlCommand::GetMetrics)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            _ = reconnect.notified() => break,
+        }
+    }
+}
+
+/// Extracts the `type` tag of a control message line, if it has one
+fn control_message_type(line: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    value.get("type")?.as_str().map(str::to_string)
+}
+
+/// Warns when the proxy speaks a different control protocol version.
+///
+/// Known message types keep being handled either way; the event only lets
+/// the user know some features may be missing until both sides are updated.
+fn check_protocol_version(app: &AppHandle, proxy_version: u32) {
+    if proxy_version == CONTROL_PROTOCOL_VERSION {
+        return;
+    }
+
+    println!(
+        "[proxy] Control protocol mismatch: launcher v{}, proxy v{}",
+        CONTROL_PROTOCOL_VERSION, proxy_version
+    );
+    let _ = app.emit_scoped(
+        "control-protocol-mismatch",
+        ProtocolMismatch {
+            launcher_version: CONTROL_PROTOCOL_VERSION,
+            proxy_version,
+        },
+    );
+}
+
+/// Serializes a command as a JSON line and writes it to the control socket
+async fn write_control_command(
+    control_writer: &Arc<Mutex<Option<ControlWriter>>>,
+    command: &ControlCommand,
+) -> Result<(), ProxyError> {
+    let mut payload = serde_json::to_vec(command)?;
+    payload.push(b'\n');
+
+    let mut writer = control_writer.lock().await;
+    let stream = writer
+        .as_mut()
+        .ok_or(ProxyError::ControlSocketUnavailable)?;
+    stream.write_all(&payload).await?;
+    Ok(())
+}
+
+/// Pushes every launcher-managed setting the proxy cares about as
+/// `set_setting` commands
+async fn push_proxy_settings(
+    control_writer: &Arc<Mutex<Option<ControlWriter>>>,
+) -> Result<(), ProxyError> {
+    let config = get_config().await.ok().flatten().unwrap_or_default();
+    let values = serde_json::to_value(&config)?;
+
+    for key in PROXY_SYNCED_KEYS {
+        if let Some(value) = values.get(*key) {
+            let command = ControlCommand::SetSetting {
+                key: key.to_string(),
+                value: value.clone(),
+            };
+            write_control_command(control_writer, &command).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Feeds an event into the managed `ProxyManager`'s connection state machine
+fn update_connection_state(app: &AppHandle, event: ConnectionEvent) {
+    if let Some(proxy) = app.try_state::<ProxyManager>() {
+        proxy.update_connection(app, event);
+    }
+}
+
+/// Dispatches a single control message to the frontend and RPC manager
+fn handle_control_message(app: &AppHandle, msg: ControlMessage) {
+    match msg {
+        ControlMessage::UserData { ign, uuid } => {
+            update_connection_state(app, ConnectionEvent::ClientConnected);
+            streamer::hide(&ign);
+            streamer::hide(&uuid);
+
+            // Emit event for frontend
+            let _ = app.emit_scoped(
+                "rpc-user-data",
+                RpcUserData {
+                    ign: ign.clone(),
+                    uuid: uuid.clone(),
+                },
+            );
+
+            // Update Discord RPC directly
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                rpc.set_user_data(Some(ign), Some(uuid));
+            }
+        }
+        ControlMessage::GameMode {
+            mode,
+            map,
+            gametype,
+            lobbyname,
+            ends_at,
+        } => {
+            update_connection_state(
+                app,
+                ConnectionEvent::GameMode {
+                    in_game: mode.is_some(),
+                },
+            );
+            overlay::on_game_mode(app, mode.clone(), map.clone());
+
+            // Update Discord RPC with game mode (mode can be null when in lobby)
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                rpc.set_game_mode(mode, map, gametype, lobbyname, ends_at);
+            }
+        }
+        ControlMessage::Disconnect => {
+            update_connection_state(app, ConnectionEvent::ClientDisconnected);
+            overlay::on_game_mode(app, None, None);
+            matches::abandon();
+            // User disconnected from Hypixel, reset RPC to idle
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                rpc.set_disconnected();
+            }
+        }
+        ControlMessage::Party { id, size, max } => {
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                rpc.set_party(id, size, max);
+            }
+        }
+        ControlMessage::Spectating { target } => {
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                rpc.set_spectating(target);
+            }
+        }
+        ControlMessage::GameEvent(event) => {
+            if let Some(rpc) = app.try_state::<RpcManager>() {
+                rpc.note_activity();
+            }
+            session_stats::on_game_event(app, event.event);
+            overlay::on_game_event(app, &event);
+            if let Some(record) = matches::on_game_event(&event) {
+                let _ = app.emit("match-recorded", record);
+            }
+            let _ = app.emit_scoped("game-event", event);
+        }
+        ControlMessage::Opponent(opponent) => {
+            matches::on_opponent(&opponent);
+            overlay::on_opponent(app, opponent);
+        }
+        ControlMessage::FriendOnline { name } => {
+            notifications::notify(
+                app,
+                Category::FriendOnline,
+                t!("notifications.friendOnline.title", name = name),
+                t!("notifications.friendOnline.body", name = name),
+            );
+            let _ = app.emit_scoped("friend-online", name);
+        }
+        ControlMessage::Metrics(metrics) => {
+            if let Some(proxy) = app.try_state::<ProxyManager>() {
+                proxy.resolve_metrics(&metrics);
+            }
+            let _ = app.emit_scoped("proxy-metrics", metrics);
+        }
+        ControlMessage::Hello { protocol_version } => {
+            check_protocol_version(app, protocol_version);
+        }
+        // Heartbeat replies are consumed by the reader loop
+        ControlMessage::Pong => {}
+        ControlMessage::Log { line } => {
+            if let Some(proxy) = app.try_state::<ProxyManager>() {
+                proxy
+                    .output_tail
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(&line, false);
+            }
+            println!("[proxy] {}", line);
+            let _ = app.emit_scoped("log-message", line);
+        }
+        ControlMessage::ProxyError {
+            code,
+            title,
+            message,
+            suggestion,
+            severity,
+            category,
+            original_message,
+            context,
+            timestamp,
+        } => {
+            // Emit error event for frontend to handle
+            let error_data = ProxyErrorData {
+                code,
+                title,
+                message,
+                suggestion,
+                severity,
+                category,
+                original_message,
+                context,
+                timestamp,
+            };
+            let _ = app.emit_scoped("proxy-error", error_data);
         }
     }
 }
 
+/// Asks the proxy to shut down over the control socket, if one is advertised
+async fn request_graceful_shutdown() -> bool {
+    match read_lock_file() {
+        Some(lock_data) if lock_data.has_control_channel() => {
+            send_shutdown_command(&lock_data).await
+        }
+        _ => false,
+    }
+}
+
+/// Stops a spawned proxy, escalating from a graceful shutdown to a force kill
+/// Gets how long each stop step waits from `proxyShutdownTimeoutSecs`, at
+/// most [`MAX_SHUTDOWN_TIMEOUT_SECS`]
+fn shutdown_timeout(configured_secs: Option<u64>) -> std::time::Duration {
+    std::time::Duration::from_secs(
+        configured_secs
+            .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS)
+            .min(MAX_SHUTDOWN_TIMEOUT_SECS),
+    )
+}
+
+async fn stop_child(child: &mut Child, timeout: std::time::Duration) -> StopOutcome {
+    if request_graceful_shutdown().await
+        && tokio::time::timeout(timeout, child.wait()).await.is_ok()
+    {
+        return StopOutcome::Graceful;
+    }
+
+    if let Some(pid) = child.id() {
+        #[cfg(unix)]
+        {
+            terminate_process_group(pid);
+            if tokio::time::timeout(timeout, child.wait()).await.is_ok() {
+                return StopOutcome::Terminated;
+            }
+        }
+
+        // Also takes down anything else in the proxy's process group
+        kill_process_group(pid);
+    }
+
+    let _ = child.kill().await;
+    StopOutcome::Killed
+}
+
+/// Stops a proxy known only by pid: SIGTERM first on Unix, then a force kill.
+///
+/// Only the pid itself is signalled, as it isn't known to lead its own
+/// process group.
+async fn stop_pid(pid: u32, timeout: std::time::Duration) -> StopOutcome {
+    #[cfg(unix)]
+    {
+        terminate_process(pid);
+        if wait_for_pid_exit(pid, timeout).await {
+            return StopOutcome::Terminated;
+        }
+    }
+
+    kill_process(pid);
+    wait_for_pid_exit(pid, timeout).await;
+    StopOutcome::Killed
+}
+
+/// Starts the user's Minecraft launcher after the proxy has launched
+fn run_post_launch_command(app: &AppHandle, command: &str) {
+    let command = command.trim();
+    if command.is_empty() {
+        let _ = app.emit_scoped(
+            "log-message",
+            "[launcher] Launch Minecraft is enabled but no launcher path is set",
+        );
+        return;
+    }
+
+    match spawn_detached(command) {
+        Ok(pid) => {
+            println!("[launcher] Started Minecraft launcher (pid {})", pid);
+            let _ = app.emit_scoped(
+                "minecraft-launched",
+                MinecraftLaunchInfo {
+                    pid,
+                    command: command.to_string(),
+                },
+            );
+        }
+        Err(e) => {
+            let msg = format!("[launcher] Failed to start Minecraft launcher: {}", e);
+            eprintln!("{}", msg);
+            let _ = app.emit_scoped("log-message", msg);
+        }
+    }
+}
+
+/// Legacy codepage used for proxy output that isn't valid UTF-8
+#[cfg(windows)]
+fn system_encoding() -> Option<&'static encoding_rs::Encoding> {
+    use windows_sys::Win32::Globalization::{GetACP, GetOEMCP};
+
+    // encoding_rs lacks some OEM codepages (e.g. 437), so fall back to ANSI
+    let (oem, ansi) = unsafe { (GetOEMCP(), GetACP()) };
+    codepage::to_encoding(oem as u16).or_else(|| codepage::to_encoding(ansi as u16))
+}
+
+/// Legacy codepage used for proxy output that isn't valid UTF-8
+#[cfg(not(windows))]
+fn system_encoding() -> Option<&'static encoding_rs::Encoding> {
+    None
+}
+
+/// Describes what's wrong with the proxy port if something already holds
+/// it, either bound to all interfaces or accepting on loopback
+async fn port_taken(port: u16) -> Option<String> {
+    let check = super::preflight::check_port(port);
+    if check.status == super::preflight::PreflightStatus::Failed {
+        return Some(check.message);
+    }
+    let connect = TcpStream::connect(("127.0.0.1", port));
+    matches!(
+        tokio::time::timeout(std::time::Duration::from_millis(500), connect).await,
+        Ok(Ok(_))
+    )
+    .then(|| format!("Port {} is already in use by another program", port))
+}
+
+/// Waits until a process without a `Child` handle exits, up to `timeout`
+async fn wait_for_pid_exit(pid: u32, timeout: std::time::Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    while is_process_alive(pid) {
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    true
+}
+
+/// Marks the proxy as stopped and resets the frontend and RPC state
+async fn mark_process_exited(app: &AppHandle, is_running: &Arc<Mutex<bool>>, reason: Option<&str>) {
+    *is_running.lock().await = false;
+    update_connection_state(app, ConnectionEvent::ProxyStopped);
+    matches::abandon();
+    let _ = app.emit_scoped("updater:status", ProxyStatus::Error);
+    let msg = match reason {
+        Some(reason) => format!("Proxy process exited: {}", reason),
+        None => "Proxy process exited".to_string(),
+    };
+    println!("[proxy] {}", msg);
+    let _ = app.emit_scoped("log-message", msg);
+
+    // Reset RPC to "In Launcher"
+    if let Some(rpc) = app.try_state::<RpcManager>() {
+        rpc.clear_activity();
+    }
+}
+
+/// Polls an adopted proxy's pid and cleans up once it exits
+async fn watch_adopted_process(
+    app: AppHandle,
+    pid: u32,
+    is_running: Arc<Mutex<bool>>,
+    adopted_pid: Arc<Mutex<Option<u32>>>,
+) {
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        // Stopped (or replaced) through the manager
+        if *adopted_pid.lock().await != Some(pid) {
+            return;
+        }
+
+        if !is_process_alive(pid) {
+            *adopted_pid.lock().await = None;
+            if let Some(proxy) = app.try_state::<ProxyManager>() {
+                // Exit codes of processes we didn't spawn aren't observable
+                proxy.report_crash(&app, None);
+            }
+            mark_process_exited(&app, &is_running, None).await;
+            return;
+        }
+    }
+}
+
+/// Recent proxy output kept for launch failure and crash reports
+#[derive(Debug, Default)]
+struct OutputTail {
+    /// All output lines (stdout, stderr and streamed logs)
+    logs: VecDeque<String>,
+    stderr: VecDeque<String>,
+}
+
+impl OutputTail {
+    fn push(&mut self, line: &str, is_stderr: bool) {
+        push_capped(&mut self.logs, line, LOG_TAIL_LINES);
+        if is_stderr {
+            push_capped(&mut self.stderr, line, STDERR_TAIL_LINES);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.logs.clear();
+        self.stderr.clear();
+    }
+}
+
+fn push_capped(lines: &mut VecDeque<String>, line: &str, cap: usize) {
+    if lines.len() == cap {
+        lines.pop_front();
+    }
+    lines.push_back(line.to_string());
+}
+
+/// Clears a flag when dropped, so every exit path of a guarded section resets it
+struct FlagGuard<'a>(&'a AtomicBool);
+
+impl Drop for FlagGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
 /// Proxy process manager
 pub struct ProxyManager {
     process: Arc<Mutex<Option<Child>>>,
     is_running: Arc<Mutex<bool>>,
+    control_writer: Arc<Mutex<Option<ControlWriter>>>,
+    /// Pid of a proxy tracked without a `Child` handle, either adopted from a
+    /// previous launcher session or launched in detached mode
+    adopted_pid: Arc<Mutex<Option<u32>>>,
+    /// A live proxy found at startup that could not be adopted
+    orphan: Mutex<Option<OrphanProxyInfo>>,
+    /// Ties the spawned proxy to the launcher's lifetime (Job Object on Windows)
+    lifetime_guard: Mutex<Option<LifetimeGuard>>,
+    /// Stdin of the spawned proxy, used for console commands
+    stdin: Mutex<Option<ChildStdin>>,
+    /// Whether `check_and_launch` is between its start and the proxy being
+    /// ready (or failing); requests that would race it are rejected as `Busy`
+    starting: AtomicBool,
+    /// Whether a proxy download is in progress
+    downloading: AtomicBool,
+    /// Set to abort the in-progress download
+    download_cancel: AtomicBool,
+    /// Where the player's connection through the proxy stands
+    connection: ConnectionTracker,
+    /// Most recent output of the proxy, for launch failure and crash reports
+    output_tail: Arc<std::sync::Mutex<OutputTail>>,
+    /// Instance id and version attached to every event from this proxy
+    instance: std::sync::Mutex<InstanceScope>,
+    /// Pending `get_metrics` calls, answered by the next `metrics` message
+    metrics_waiters: std::sync::Mutex<Vec<tokio::sync::oneshot::Sender<ProxyMetrics>>>,
+    /// Notified to drop the control socket so it reconnects, e.g. after sleep
+    control_reconnect: Arc<Notify>,
 }
 
 impl ProxyManager {
@@ -210,6 +887,19 @@ impl ProxyManager {
         Self {
             process: Arc::new(Mutex::new(None)),
             is_running: Arc::new(Mutex::new(false)),
+            control_writer: Arc::new(Mutex::new(None)),
+            adopted_pid: Arc::new(Mutex::new(None)),
+            orphan: Mutex::new(None),
+            lifetime_guard: Mutex::new(None),
+            stdin: Mutex::new(None),
+            starting: AtomicBool::new(false),
+            downloading: AtomicBool::new(false),
+            download_cancel: AtomicBool::new(false),
+            connection: ConnectionTracker::default(),
+            output_tail: Arc::new(std::sync::Mutex::new(OutputTail::default())),
+            instance: std::sync::Mutex::new(InstanceScope::default()),
+            metrics_waiters: std::sync::Mutex::new(Vec::new()),
+            control_reconnect: Arc::new(Notify::new()),
         }
     }
 
@@ -218,28 +908,377 @@ impl ProxyManager {
         *self.is_running.lock().await
     }
 
-    /// Checks for updates, downloads if necessary, and launches the proxy
+    /// Checks on the proxy after the machine wakes up from sleep.
+    ///
+    /// A proxy that died while asleep is reported by its exit watcher; a
+    /// live one gets a fresh control connection, since the old one may be
+    /// dead without having failed yet.
+    pub async fn recover_after_resume(&self) {
+        if !self.is_running().await {
+            return;
+        }
+
+        let adopted = *self.adopted_pid.lock().await;
+        let pid = match adopted {
+            Some(pid) => Some(pid),
+            None => self.process.lock().await.as_ref().and_then(|c| c.id()),
+        };
+        if pid.is_some_and(|pid| !is_process_alive(pid)) {
+            println!("[proxy] Proxy exited while asleep");
+            return;
+        }
+
+        println!("[proxy] Reconnecting the control socket after sleep");
+        self.control_reconnect.notify_waiters();
+    }
+
+    /// Checks if the proxy is being downloaded or launched
+    pub fn is_starting(&self) -> bool {
+        self.starting.load(Ordering::SeqCst)
+    }
+
+    /// Fails with `Busy` while a launch is in progress
+    fn ensure_settled(&self) -> Result<(), ProxyError> {
+        if self.is_starting() {
+            return Err(ProxyError::Busy);
+        }
+        Ok(())
+    }
+
+    /// Checks if a proxy download is in progress
+    pub fn is_downloading(&self) -> bool {
+        self.downloading.load(Ordering::SeqCst)
+    }
+
+    /// Returns the proxy's most recent output, oldest first
+    pub fn recent_output(&self) -> Vec<String> {
+        self.output_tail
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .logs
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the scope tagged onto events from the current proxy instance
+    pub fn instance_scope(&self) -> InstanceScope {
+        self.instance
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Starts a new instance scope, so events from here on are told apart
+    /// from those of the previous proxy
+    fn begin_instance(&self) {
+        *self.instance.lock().unwrap_or_else(|e| e.into_inner()) = InstanceScope::new();
+    }
+
+    /// Returns the current connection state
+    pub fn connection_state(&self) -> ConnectionState {
+        self.connection.get()
+    }
+
+    /// Advances the connection state machine and emits
+    /// `connection-state-changed` if the state changed
+    pub fn update_connection(&self, app: &AppHandle, event: ConnectionEvent) {
+        if let Some(state) = self.connection.apply(&event) {
+            let _ = app.emit_scoped("connection-state-changed", state);
+        }
+    }
+
+    /// Sends a command to the running proxy over the control socket.
+    ///
+    /// Commands are written as a single JSON line on the connection opened by
+    /// the control socket listener. Any reply arrives as a regular control message.
+    pub async fn send_control_command(&self, command: &ControlCommand) -> Result<(), ProxyError> {
+        self.ensure_settled()?;
+        if !self.is_running().await {
+            return Err(ProxyError::NotRunning);
+        }
+        write_control_command(&self.control_writer, command).await
+    }
+
+    /// Queries the running proxy for its current network metrics.
+    ///
+    /// Fails with `MetricsUnavailable` if no reply arrives within
+    /// `METRICS_QUERY_TIMEOUT_SECS`, e.g. for proxy builds without metrics.
+    pub async fn get_metrics(&self) -> Result<ProxyMetrics, ProxyError> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        {
+            let mut waiters = self
+                .metrics_waiters
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            // Drop waiters left behind by queries that timed out
+            waiters.retain(|w| !w.is_closed());
+            waiters.push(tx);
+        }
+
+        self.send_control_command(&ControlCommand::GetMetrics)
+            .await?;
+
+        match tokio::time::timeout(
+            std::time::Duration::from_secs(METRICS_QUERY_TIMEOUT_SECS),
+            rx,
+        )
+        .await
+        {
+            Ok(Ok(metrics)) => Ok(metrics),
+            _ => Err(ProxyError::MetricsUnavailable),
+        }
+    }
+
+    /// Hands a metrics reply to every pending `get_metrics` call
+    fn resolve_metrics(&self, metrics: &ProxyMetrics) {
+        let waiters = std::mem::take(
+            &mut *self
+                .metrics_waiters
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        );
+        for waiter in waiters {
+            let _ = waiter.send(metrics.clone());
+        }
+    }
+
+    /// Writes a console command line to the proxy's stdin.
+    ///
+    /// Only available for proxies spawned attached to this launcher; detached
+    /// and adopted instances have no stdin the launcher can write to.
+    pub async fn send_stdin(&self, line: &str) -> Result<(), ProxyError> {
+        self.ensure_settled()?;
+        if !self.is_running().await {
+            return Err(ProxyError::NotRunning);
+        }
+
+        let mut stdin = self.stdin.lock().await;
+        let stdin = stdin.as_mut().ok_or(ProxyError::StdinUnavailable)?;
+
+        let mut payload = line.trim_end_matches(['\r', '\n']).as_bytes().to_vec();
+        payload.push(b'\n');
+        stdin.write_all(&payload).await?;
+        stdin.flush().await?;
+        Ok(())
+    }
+
+    /// Pushes all settings in `PROXY_SYNCED_KEYS` to the running proxy.
+    ///
+    /// Runs automatically whenever the control socket connects.
+    pub async fn sync_proxy_settings(&self) -> Result<(), ProxyError> {
+        self.ensure_settled()?;
+        if !self.is_running().await {
+            return Err(ProxyError::NotRunning);
+        }
+        push_proxy_settings(&self.control_writer).await
+    }
+
+    /// Reconciles `proxy.lock` with the processes actually running.
+    ///
+    /// - A lock pointing to a dead pid, or to one that doesn't run a proxy
+    ///   from the install directory, is stale and is deleted
+    /// - A live proxy that answers on its control port is adopted
+    /// - A live proxy that cannot be adopted is recorded as an orphan, for
+    ///   `get_orphan_proxy`, and reported via `proxy-orphan-detected` so it
+    ///   can be killed. This runs during setup, usually before the frontend
+    ///   listens, so the frontend asks for it on mount too
+    pub async fn reconcile_lock_file(&self, app: AppHandle) {
+        let lock_data = match read_lock_file() {
+            Some(data) => data,
+            None => return,
+        };
+
+        if !is_proxy_process(lock_data.pid) {
+            println!("[proxy] Removing stale lock file (pid {})", lock_data.pid);
+            remove_lock_file();
+            return;
+        }
+
+        if self.adopt_existing(app.clone()).await {
+            return;
+        }
+
+        println!("[proxy] Found orphaned proxy (pid {})", lock_data.pid);
+        let orphan = OrphanProxyInfo {
+            pid: lock_data.pid,
+            port: lock_data.port,
+        };
+        *self.orphan.lock().await = Some(orphan.clone());
+        let _ = app.emit_scoped("proxy-orphan-detected", orphan);
+    }
+
+    /// Gets the orphaned proxy found by [`Self::reconcile_lock_file`], if
+    /// it's still running
+    pub async fn orphan(&self) -> Option<OrphanProxyInfo> {
+        let mut orphan = self.orphan.lock().await;
+        if orphan.as_ref().is_some_and(|o| !is_proxy_process(o.pid)) {
+            *orphan = None;
+        }
+        orphan.clone()
+    }
+
+    /// Kills the orphaned proxy detected by [`Self::reconcile_lock_file`]
+    /// and removes its lock file.
+    pub async fn kill_orphan(&self) -> Result<(), ProxyError> {
+        let orphan = self
+            .orphan
+            .lock()
+            .await
+            .take()
+            .ok_or(ProxyError::NotRunning)?;
+        let pid = orphan.pid;
+
+        // The pid may have been reused since it was detected
+        if is_proxy_process(pid) {
+            let timeout = std::time::Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_SECS);
+            stop_pid(pid, timeout).await;
+            if is_process_alive(pid) {
+                *self.orphan.lock().await = Some(orphan);
+                return Err(ProxyError::ProcessError(format!(
+                    "Failed to terminate orphaned proxy (pid {})",
+                    pid
+                )));
+            }
+        }
+
+        remove_lock_file();
+        Ok(())
+    }
+
+    /// Adopts a proxy that is still running from a previous launcher session.
+    ///
+    /// Reads `proxy.lock`, verifies the pid is alive, runs a proxy from the
+    /// install directory and answers on its control port, then treats the
+    /// instance as running: status is reported as launched, logs are
+    /// streamed over the control socket and `stop` works. Proxies the
+    /// launcher detached on exit are adopted even if their control port
+    /// isn't up yet, and are no longer marked detached once adopted.
+    ///
+    /// Returns `true` if an instance was adopted.
+    pub async fn adopt_existing(&self, app: AppHandle) -> bool {
+        if self.is_running().await {
+            return false;
+        }
+
+        let lock_data = match read_lock_file() {
+            Some(data) => data,
+            None => return false,
+        };
+
+        // The pid alone may have been reused, e.g. after a reboot
+        if !is_proxy_process(lock_data.pid) {
+            return false;
+        }
+
+        if !lock_data.launcher_detached {
+            let answers = tokio::time::timeout(
+                std::time::Duration::from_secs(2),
+                lock_data.connect_control(),
+            )
+            .await;
+            if !matches!(answers, Ok(Some(_))) {
+                return false;
+            }
+        } else if let Err(e) = set_lock_file_detached(false) {
+            eprintln!("[proxy] Failed to update lock file: {}", e);
+        }
+
+        println!("[proxy] Adopted running proxy (pid {})", lock_data.pid);
+        self.begin_instance();
+        let _ = app.emit_scoped("log-message", "Reconnected to running proxy");
+
+        self.track_unmanaged(app, lock_data.pid, lock_data.port)
+            .await;
+        true
+    }
+
+    /// Marks a proxy without a `Child` handle as running and starts the
+    /// control socket listener (with log streaming) and exit watcher for it.
+    async fn track_unmanaged(&self, app: AppHandle, pid: u32, port: u16) {
+        *self.adopted_pid.lock().await = Some(pid);
+        *self.is_running.lock().await = true;
+
+        let _ = app.emit_scoped("updater:status", ProxyStatus::Launched { port });
+        self.update_connection(&app, ConnectionEvent::ProxyLaunched);
+
+        if let Some(rpc) = app.try_state::<RpcManager>() {
+            rpc.set_in_launcher();
+        }
+
+        let app_clone = app.clone();
+        let is_running_clone = self.is_running.clone();
+        let control_writer_clone = self.control_writer.clone();
+        let reconnect = self.control_reconnect.clone();
+        tokio::spawn(async move {
+            listen_control_socket(
+                app_clone,
+                is_running_clone,
+                control_writer_clone,
+                reconnect,
+                true,
+            )
+            .await;
+        });
+
+        let is_running_clone = self.is_running.clone();
+        let adopted_pid_clone = self.adopted_pid.clone();
+        tokio::spawn(async move {
+            watch_adopted_process(app, pid, is_running_clone, adopted_pid_clone).await;
+        });
+    }
+
+    /// Leaves a detached proxy running when the launcher exits.
+    ///
+    /// Only proxies launched in detached mode (or adopted) can be left
+    /// behind; the lock file is marked so the next launcher start re-adopts it.
+    pub async fn detach(&self) -> Result<(), ProxyError> {
+        if self.adopted_pid.lock().await.is_none() {
+            return Err(ProxyError::ProcessError(
+                "Proxy was not launched in detached mode".to_string(),
+            ));
+        }
+
+        set_lock_file_detached(true)?;
+        println!("[proxy] Leaving proxy running in the background");
+        Ok(())
+    }
+
+    /// Checks for updates, downloads if necessary, and launches the proxy.
+    ///
+    /// Only one launch runs at a time; a second call made while the first is
+    /// still downloading or waiting for the proxy fails with `Busy`.
     pub async fn check_and_launch(&self, app: AppHandle, port: u16) -> Result<(), ProxyError> {
+        if self
+            .starting
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(ProxyError::Busy);
+        }
+        let _starting = FlagGuard(&self.starting);
+
         // Check if already running
         if self.is_running().await {
             return Err(ProxyError::AlreadyRunning);
         }
 
+        // Only now, so a refused launch leaves the running instance's scope
+        self.begin_instance();
+
         // Emit status
-        let _ = app.emit("updater:show", ());
-        let _ = app.emit("updater:status", ProxyStatus::Checking);
+        let _ = app.emit_scoped("updater:show", ());
+        let _ = app.emit_scoped("updater:status", ProxyStatus::Checking);
+
+        let config = get_config().await.ok().flatten().unwrap_or_default();
+        self.download_cancel.store(false, Ordering::SeqCst);
 
         // Check if beta releases are enabled
-        let use_beta = get_config()
-            .await
-            .ok()
-            .flatten()
-            .map(|c| c.receive_beta_releases)
-            .unwrap_or(false);
+        let use_beta = config.receive_beta_releases;
 
         /*if use_beta {
             println!("[proxy] Beta releases enabled");
-            let _ = app.emit("log-message", "Beta releases enabled");
+            let _ = app.emit_scoped("log-message", "Beta releases enabled");
         }*/
 
         // Get platform and install directory
@@ -260,7 +1299,7 @@ impl ProxyManager {
             //let msg = format!("Downloading version {}", latest.version);
             //println!("[proxy] {}", msg);
             //let _ = app.emit("log-message", msg);
-            let _ = app.emit(
+            let _ = app.emit_scoped(
                 "updater:status",
                 ProxyStatus::Downloading {
                     version: latest.version.clone(),
@@ -269,12 +1308,26 @@ impl ProxyManager {
 
             // Download with progress tracking
             let app_clone = app.clone();
-            download_artifact(&asset.id, &file_path, move |progress| {
-                if let Err(e) = app_clone.emit("updater:progress", &progress) {
-                    eprintln!("Failed to emit progress event: {:?}", e);
-                }
-            })
-            .await?;
+            self.downloading.store(true, Ordering::SeqCst);
+            let result = download_artifact(
+                &asset.id,
+                &file_path,
+                &self.download_cancel,
+                move |progress| {
+                    if let Err(e) = app_clone.emit_scoped("updater:progress", &progress) {
+                        eprintln!("Failed to emit progress event: {:?}", e);
+                    }
+                },
+            )
+            .await;
+            self.downloading.store(false, Ordering::SeqCst);
+            result?;
+            notifications::notify(
+                &app,
+                Category::Download,
+                t!("notifications.download.title"),
+                t!("notifications.download.body", version = latest.version),
+            );
 
             //println!("[proxy] Download complete!");
             //let _ = app.emit("log-message", "Download complete!");
@@ -287,25 +1340,62 @@ impl ProxyManager {
         // Clean up old executables
         cleanup_old_executables(&install_dir, &asset.name)?;
 
+        self.instance
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .proxy_version = Some(latest.version.clone());
+
         // Launch the proxy
-        let _ = app.emit("updater:status", ProxyStatus::Launching);
+        let _ = app.emit_scoped("updater:status", ProxyStatus::Launching);
 
         // Update RPC to "Launching"
         if let Some(rpc) = app.try_state::<RpcManager>() {
             rpc.set_launching();
         }
 
-        self.launch_process(app, file_path, port).await?;
+        self.update_connection(&app, ConnectionEvent::ProxyStarting);
+        if let Err(e) = self
+            .launch_process(
+                app.clone(),
+                file_path,
+                port,
+                config.keep_proxy_on_exit,
+                config.proxy_priority,
+            )
+            .await
+        {
+            self.update_connection(&app, ConnectionEvent::ProxyStopped);
+            return Err(e);
+        }
+
+        if config.launch_minecraft {
+            run_post_launch_command(&app, &config.post_launch_command);
+        }
+
+        // First launches on Windows are often silently firewalled
+        let app_clone = app.clone();
+        tokio::spawn(async move {
+            let status = check_firewall(port).await;
+            if status != FirewallStatus::Unsupported {
+                let _ = app_clone.emit_scoped("firewall-status", status);
+            }
+        });
 
         Ok(())
     }
 
-    /// Launches the proxy process
+    /// Launches the proxy process.
+    ///
+    /// A `detached` proxy is not bound to the launcher's lifetime and doesn't
+    /// write to pipes the launcher owns, so it survives the launcher exiting.
+    /// Its logs are streamed over the control socket instead.
     async fn launch_process(
         &self,
         app: AppHandle,
         executable_path: PathBuf,
         port: u16,
+        detached: bool,
+        priority: ProxyPriority,
     ) -> Result<(), ProxyError> {
         let mut cmd = Command::new(&executable_path);
         cmd.arg("--port")
@@ -313,7 +1403,7 @@ impl ProxyManager {
             .current_dir(executable_path.parent().unwrap())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .stdin(Stdio::null());
+            .stdin(Stdio::piped());
 
         // Set environment variables for better encoding
         #[cfg(unix)]
@@ -329,10 +1419,59 @@ impl ProxyManager {
             cmd.creation_flags(CREATE_NO_WINDOW);
         }
 
+        if detached {
+            // Stays in the launcher's process group, so it's only ever
+            // signalled by pid (see `stop_pid`)
+            cmd.stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null());
+        } else {
+            // Make sure the proxy doesn't outlive the launcher if it gets killed
+            bind_to_launcher(&mut cmd);
+        }
+
+        // Something else on the port would pass the readiness probe below
+        if let Some(reason) = port_taken(port).await {
+            return Err(self.abort_launch(&app, reason).await);
+        }
+
         let mut child = cmd
             .spawn()
             .map_err(|e| ProxyError::ProcessError(e.to_string()))?;
 
+        if let Err(e) = set_process_priority(&child, priority) {
+            let msg = format!("[proxy] Failed to set proxy priority: {}", e);
+            eprintln!("{}", msg);
+            let _ = app.emit_scoped("log-message", msg);
+        }
+
+        if detached {
+            let pid = child
+                .id()
+                .ok_or_else(|| ProxyError::ProcessError("Failed to get proxy pid".to_string()))?;
+
+            // Dropping the handle doesn't kill the process; the runtime reaps it on exit
+            drop(child);
+
+            *self.adopted_pid.lock().await = Some(pid);
+            if let Err(reason) = self.wait_until_listening(port).await {
+                return Err(self.abort_launch(&app, reason).await);
+            }
+
+            let _ = app.emit_scoped("updater:hide", ());
+            self.track_unmanaged(app, pid, port).await;
+            return Ok(());
+        }
+
+        let guard = LifetimeGuard::attach(&child);
+        if guard.is_none() {
+            eprintln!("[proxy] Failed to bind proxy lifetime to the launcher");
+        }
+        *self.lifetime_guard.lock().await = guard;
+
+        // Keep stdin for console commands
+        *self.stdin.lock().await = child.stdin.take();
+
         // Get stdout and stderr
         let stdout = child
             .stdout
@@ -347,31 +1486,225 @@ impl ProxyManager {
         *self.process.lock().await = Some(child);
         *self.is_running.lock().await = true;
 
-        let _ = app.emit("updater:status", ProxyStatus::Launched);
-        let _ = app.emit("updater:hide", ());
-
-        // Clear "Launching" state - RPC goes back to "Idle" until user connects
-        if let Some(rpc) = app.try_state::<RpcManager>() {
-            rpc.set_in_launcher();
-        }
-
         // Spawn tasks to handle stdout and stderr
         let app_clone = app.clone();
         let is_running_clone = self.is_running.clone();
+        let output_tail = self.output_tail.clone();
+        output_tail
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
         tokio::spawn(async move {
-            Self::handle_output(app_clone, stdout, stderr, is_running_clone).await;
+            Self::handle_output(app_clone, stdout, stderr, is_running_clone, output_tail).await;
         });
 
         // Spawn control socket listener for user data
         let app_clone = app.clone();
         let is_running_clone = self.is_running.clone();
+        let control_writer_clone = self.control_writer.clone();
+        let reconnect = self.control_reconnect.clone();
         tokio::spawn(async move {
-            listen_control_socket(app_clone, is_running_clone).await;
+            listen_control_socket(
+                app_clone,
+                is_running_clone,
+                control_writer_clone,
+                reconnect,
+                false,
+            )
+            .await;
         });
 
+        if let Err(reason) = self.wait_until_listening(port).await {
+            return Err(self.abort_launch(&app, reason).await);
+        }
+
+        let _ = app.emit_scoped("updater:status", ProxyStatus::Launched { port });
+        let _ = app.emit_scoped("updater:hide", ());
+        self.update_connection(&app, ConnectionEvent::ProxyLaunched);
+        session_stats::reset(&app);
+        logs_window::on_proxy_launched(&app);
+
+        // Clear "Launching" state - RPC goes back to "Idle" until user connects
+        if let Some(rpc) = app.try_state::<RpcManager>() {
+            rpc.set_in_launcher();
+        }
+
         Ok(())
     }
 
+    /// Reaps a spawned proxy whose output closed. If it wasn't stopped
+    /// through the manager, reports a crash and returns why it exited.
+    async fn handle_child_exit(&self, app: &AppHandle) -> Option<ProxyErrorData> {
+        // `stop()` and failed launches take the child before killing it
+        let mut child = self.process.lock().await.take()?;
+        *self.lifetime_guard.lock().await = None;
+        *self.stdin.lock().await = None;
+
+        let status = tokio::time::timeout(std::time::Duration::from_secs(2), child.wait())
+            .await
+            .ok()
+            .and_then(|r| r.ok());
+        if status.is_some_and(|s| s.success()) {
+            return None;
+        }
+
+        self.report_crash(app, status);
+
+        #[cfg(unix)]
+        let signal = status.and_then(|s| std::os::unix::process::ExitStatusExt::signal(&s));
+        #[cfg(not(unix))]
+        let signal = None;
+
+        let stderr: Vec<String> = self
+            .output_tail
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .stderr
+            .iter()
+            .cloned()
+            .collect();
+        Some(Self::classify_exit(
+            status.and_then(|s| s.code()),
+            signal,
+            &stderr,
+        ))
+    }
+
+    /// Writes a crash bundle for an unexpected exit and emits `proxy-crashed`
+    fn report_crash(&self, app: &AppHandle, status: Option<std::process::ExitStatus>) {
+        telemetry::record_error("proxyCrash");
+        let logs = self.recent_output();
+        let proxy_version = self.instance_scope().proxy_version;
+        let app = app.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let config = get_config().await.ok().flatten().unwrap_or_default();
+            let report = CrashReport::new(
+                status,
+                proxy_version,
+                app.package_info().version.to_string(),
+                &config,
+                logs,
+            );
+
+            // Clicking the notification shows the report, or the logs
+            // without one
+            let route = match write_crash_report(&report) {
+                Ok(path) => {
+                    println!("[proxy] Crash report written to {}", path.display());
+                    let _ = app.emit_scoped(
+                        "proxy-crashed",
+                        ProxyCrashInfo {
+                            path: path.to_string_lossy().to_string(),
+                            exit_code: report.exit_code,
+                        },
+                    );
+                    Route::action(NotificationAction::ViewCrashReport {
+                        path: path.to_string_lossy().to_string(),
+                    })
+                }
+                Err(e) => {
+                    eprintln!("[proxy] Failed to write crash report: {}", e);
+                    Route::action(NotificationAction::OpenLogs)
+                }
+            };
+            notifications::notify_with(
+                &app,
+                Category::ProxyCrash,
+                route,
+                t!("notifications.proxyCrash.title"),
+                t!("notifications.proxyCrash.body"),
+            );
+        });
+    }
+
+    /// Waits until the freshly spawned proxy accepts connections on `port`.
+    ///
+    /// Fails early if the process exits (or is stopped) during startup, and
+    /// after `PROXY_READY_TIMEOUT_SECS` if it never starts listening.
+    async fn wait_until_listening(&self, port: u16) -> Result<(), String> {
+        let deadline =
+            tokio::time::Instant::now() + std::time::Duration::from_secs(PROXY_READY_TIMEOUT_SECS);
+
+        loop {
+            let connect = TcpStream::connect(("127.0.0.1", port));
+            let listening = matches!(
+                tokio::time::timeout(std::time::Duration::from_secs(1), connect).await,
+                Ok(Ok(_))
+            );
+
+            // Answering only counts while the proxy is alive; the port was
+            // free before it started, so then it's the proxy listening
+            if let Some(reason) = self.startup_exit_reason().await {
+                return Err(reason);
+            }
+            if listening {
+                return Ok(());
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(format!(
+                    "Proxy did not start listening on port {} within {}s",
+                    port, PROXY_READY_TIMEOUT_SECS
+                ));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        }
+    }
+
+    /// Describes why a starting proxy is no longer running, if it isn't
+    async fn startup_exit_reason(&self) -> Option<String> {
+        if let Some(pid) = *self.adopted_pid.lock().await {
+            return (!is_process_alive(pid)).then(|| "Proxy exited during startup".to_string());
+        }
+
+        match self.process.lock().await.as_mut() {
+            None => Some("Proxy was stopped during startup".to_string()),
+            Some(child) => match child.try_wait() {
+                Ok(Some(status)) => Some(format!("Proxy exited during startup ({})", status)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Kills a proxy that failed to become ready and reports the failure
+    /// along with its most recent stderr output
+    async fn abort_launch(&self, app: &AppHandle, reason: String) -> ProxyError {
+        if let Some(pid) = self.adopted_pid.lock().await.take() {
+            kill_process(pid);
+        }
+        if let Some(mut child) = self.process.lock().await.take() {
+            let _ = child.kill().await;
+        }
+        *self.lifetime_guard.lock().await = None;
+        *self.stdin.lock().await = None;
+        *self.is_running.lock().await = false;
+
+        // Give the output reader a moment to drain what the proxy wrote before dying
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        let stderr: Vec<String> = self
+            .output_tail
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .stderr
+            .iter()
+            .cloned()
+            .collect();
+
+        eprintln!("[proxy] Launch failed: {}", reason);
+        let _ = app.emit_scoped("updater:status", ProxyStatus::Error);
+        let _ = app.emit_scoped(
+            "proxy-launch-failed",
+            LaunchFailure {
+                reason: reason.clone(),
+                stderr,
+            },
+        );
+
+        ProxyError::LaunchFailed(reason)
+    }
+
     /// Parses a log line for error patterns and returns a ProxyErrorData if detected.
     /// Based on proxy's theme.js log format:
     ///   [i] - info, [✓] - success, [~] - warning, [X] - error
@@ -606,155 +1939,361 @@ impl ProxyManager {
         )
     }
 
+    /// Explains an unexpected proxy exit from its exit code, terminating
+    /// signal (Unix) and last stderr lines.
+    ///
+    /// Stderr is checked first since it is more specific, newest line first.
+    fn classify_exit(
+        exit_code: Option<i32>,
+        signal: Option<i32>,
+        stderr: &[String],
+    ) -> ProxyErrorData {
+        let exit_desc = match (exit_code, signal) {
+            (Some(code), _) => format!("exit code {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => "unknown exit status".to_string(),
+        };
+
+        let from_stderr = stderr.iter().rev().find_map(|line| {
+            let line_lower = line.to_lowercase();
+            if let Some(found) = Self::categorize_exit_line(&line_lower) {
+                return Some((found, line.clone()));
+            }
+            let found = Self::categorize_error(line, &line_lower);
+            (found.0 != "UNKNOWN").then(|| (found, line.clone()))
+        });
+
+        let ((code, category, title, suggestion, severity), original_message) = from_stderr
+            .unwrap_or_else(|| {
+                (
+                    Self::categorize_exit_status(exit_code, signal),
+                    format!("Proxy exited with {}", exit_desc),
+                )
+            });
+
+        ProxyErrorData {
+            code,
+            title,
+            message: format!("The proxy stopped unexpectedly ({}).", exit_desc),
+            suggestion,
+            severity,
+            category,
+            original_message,
+            context: Some("exit".to_string()),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Matches final stderr lines that explain why the process died
+    fn categorize_exit_line(
+        line_lower: &str,
+    ) -> Option<(String, ErrorCategory, String, String, ErrorSeverity)> {
+        if line_lower.contains("eaddrinuse") || line_lower.contains("address already in use") {
+            return Some((
+                "EADDRINUSE".to_string(),
+                ErrorCategory::Proxy,
+                "Port Already In Use".to_string(),
+                "The proxy port is already being used. Close other applications or change the port in settings.".to_string(),
+                ErrorSeverity::Error,
+            ));
+        }
+        if line_lower.contains("eacces") && line_lower.contains("listen") {
+            return Some((
+                "EACCES".to_string(),
+                ErrorCategory::Proxy,
+                "Port Not Allowed".to_string(),
+                "The proxy isn't allowed to use this port. Choose a port above 1024 in settings."
+                    .to_string(),
+                ErrorSeverity::Error,
+            ));
+        }
+        if line_lower.contains("unauthorized") || line_lower.contains("invalid token") {
+            return Some((
+                "AUTH_REJECTED".to_string(),
+                ErrorCategory::Authentication,
+                "Authentication Rejected".to_string(),
+                "The Duels+ API rejected your token. Sign out and sign in again in the launcher."
+                    .to_string(),
+                ErrorSeverity::Error,
+            ));
+        }
+        if line_lower.contains("error while loading shared libraries")
+            || line_lower.contains("cannot find module")
+            || line_lower.contains("module_not_found")
+            || line_lower.contains("glibc_")
+            || line_lower.contains("vcruntime")
+            || line_lower.contains(".dll was not found")
+        {
+            return Some((
+                "MISSING_DEPENDENCY".to_string(),
+                ErrorCategory::Proxy,
+                "Missing Dependency".to_string(),
+                "A library the proxy needs is missing. Update your system or reinstall the proxy from settings.".to_string(),
+                ErrorSeverity::Critical,
+            ));
+        }
+        None
+    }
+
+    /// Maps a bare exit code or signal to an error when stderr had nothing useful
+    fn categorize_exit_status(
+        exit_code: Option<i32>,
+        signal: Option<i32>,
+    ) -> (String, ErrorCategory, String, String, ErrorSeverity) {
+        // NTSTATUS codes show up as negative i32 exit codes on Windows
+        const STATUS_ACCESS_VIOLATION: i32 = 0xC0000005_u32 as i32;
+        const STATUS_DLL_NOT_FOUND: i32 = 0xC0000135_u32 as i32;
+
+        match (exit_code, signal) {
+            (Some(STATUS_DLL_NOT_FOUND), _) => (
+                "MISSING_DEPENDENCY".to_string(),
+                ErrorCategory::Proxy,
+                "Missing Dependency".to_string(),
+                "A library the proxy needs is missing. Install the latest Visual C++ Redistributable and try again.".to_string(),
+                ErrorSeverity::Critical,
+            ),
+            (Some(STATUS_ACCESS_VIOLATION), _) | (None, Some(11)) => (
+                "PROXY_CRASHED".to_string(),
+                ErrorCategory::Proxy,
+                "Proxy Crashed".to_string(),
+                "The proxy crashed. Restart it, and send the crash report if it keeps happening."
+                    .to_string(),
+                ErrorSeverity::Error,
+            ),
+            (None, Some(9)) => (
+                "PROXY_KILLED".to_string(),
+                ErrorCategory::Proxy,
+                "Proxy Was Killed".to_string(),
+                "The proxy was killed by the system, possibly because it ran out of memory. Close other programs and try again.".to_string(),
+                ErrorSeverity::Error,
+            ),
+            // Node exits with 134 (SIGABRT) when it runs out of heap
+            (Some(134), _) | (None, Some(6)) => (
+                "PROXY_ABORTED".to_string(),
+                ErrorCategory::Proxy,
+                "Proxy Aborted".to_string(),
+                "The proxy aborted, usually because it ran out of memory. Restart it and try again."
+                    .to_string(),
+                ErrorSeverity::Error,
+            ),
+            _ => (
+                "PROXY_EXITED".to_string(),
+                ErrorCategory::Proxy,
+                "Proxy Stopped Unexpectedly".to_string(),
+                "Restart the proxy. If it keeps stopping, check the logs or send the crash report."
+                    .to_string(),
+                ErrorSeverity::Error,
+            ),
+        }
+    }
+
     /// Handles stdout and stderr from the proxy process
     async fn handle_output(
         app: AppHandle,
         stdout: impl tokio::io::AsyncRead + Unpin,
         stderr: impl tokio::io::AsyncRead + Unpin,
         is_running: Arc<Mutex<bool>>,
+        output_tail: Arc<std::sync::Mutex<OutputTail>>,
     ) {
-        let stdout_reader = BufReader::new(stdout);
-        let stderr_reader = BufReader::new(stderr);
-
-        let mut stdout_lines = stdout_reader.lines();
-        let mut stderr_lines = stderr_reader.lines();
+        // Read raw bytes so non UTF-8 output is decoded instead of ending the stream
+        let mut stdout_reader = BufReader::new(stdout);
+        let mut stderr_reader = BufReader::new(stderr);
+        let mut stdout_buf = Vec::new();
+        let mut stderr_buf = Vec::new();
 
         // Track if we've emitted an error to avoid duplicates
         let mut error_emitted = false;
 
         loop {
             tokio::select! {
-                result = stdout_lines.next_line() => {
+                result = stdout_reader.read_until(b'\n', &mut stdout_buf) => {
                     match result {
-                        Ok(Some(line)) => {
-                            let line = Self::fix_encoding(&line);
+                        Ok(0) => break,
+                        Ok(_) => {
+                            let line = Self::decode_line(&stdout_buf);
+                            stdout_buf.clear();
+                            output_tail
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner())
+                                .push(&line, false);
 
                             // Emit log message and print to console
                             if !line.contains("ExperimentalWarning") && !line.contains("--trace-warnings") {
                                 println!("[proxy] {}", line);
-                                let _ = app.emit("log-message", line.clone());
+                                let _ = app.emit_scoped("log-message", line.clone());
 
                                 // Check for error patterns and emit proxy-error event
                                 if !error_emitted {
                                     if let Some(error_data) = Self::parse_error_from_log(&line) {
-                                        let _ = app.emit("proxy-error", error_data);
+                                        let _ = app.emit_scoped("proxy-error", error_data);
                                         error_emitted = true;
                                     }
                                 }
                             }
                         }
-                        Ok(None) => break,
                         Err(_) => break,
                     }
                 }
-                result = stderr_lines.next_line() => {
+                result = stderr_reader.read_until(b'\n', &mut stderr_buf) => {
                     match result {
-                        Ok(Some(line)) => {
-                            let line = Self::fix_encoding(&line);
+                        Ok(0) => break,
+                        Ok(_) => {
+                            let line = Self::decode_line(&stderr_buf);
+                            stderr_buf.clear();
+
+                            output_tail
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner())
+                                .push(&line, true);
 
                             if !line.contains("ExperimentalWarning") && !line.contains("--trace-warnings") {
                                 eprintln!("[proxy:err] {}", line);
-                                let _ = app.emit("log-message", line.clone());
+                                let _ = app.emit_scoped("log-message", line.clone());
 
                                 // Check for error patterns in stderr too
                                 if !error_emitted {
                                     if let Some(error_data) = Self::parse_error_from_log(&line) {
-                                        let _ = app.emit("proxy-error", error_data);
+                                        let _ = app.emit_scoped("proxy-error", error_data);
                                         error_emitted = true;
                                     }
                                 }
                             }
                         }
-                        Ok(None) => break,
                         Err(_) => break,
                     }
                 }
             }
         }
 
-        *is_running.lock().await = false;
-        let _ = app.emit("updater:status", ProxyStatus::Error);
-        println!("[proxy] Proxy process exited");
-        let _ = app.emit("log-message", "Proxy process exited");
-
-        // Reset RPC to "In Launcher"
-        if let Some(rpc) = app.try_state::<RpcManager>() {
-            rpc.clear_activity();
-        }
-    }
-
-    /// Fixes encoding issues on Windows
-    fn fix_encoding(s: &str) -> String {
-        s.replace("\u{0393}\u{00a3}\u{00f4}", "*") // garbled checkmark -> *
-            .replace("\u{0393}\u{00a3}\u{00f9}", "x") // garbled x mark -> x
-            .replace("\u{0393}\u{00a3}\u{00e6}", "*") // garbled check variant -> *
-            .replace("\u{0393}\u{0102}\u{00b6}", "-") // garbled em dash -> -
-            .replace("\u{0393}\u{0102}\u{00aa}", "...") // garbled ellipsis -> ...
-            .replace("\u{0393}\u{0102}\u{00f4}", "-") // garbled en dash -> -
-            .replace("\u{0393}\u{0102}\u{0153}", "\"") // garbled left quote -> "
-            .replace("\u{0393}\u{0102}\u{009d}", "\"") // garbled right quote -> "
-            .replace("\u{0393}\u{201e}\u{00bf}", "*") // garbled bullet -> *
-            .replace("\u{0393}\u{0102}\u{00b3}", "*") // garbled bullet variant -> *
-            .replace("\u{00c2}\u{00a9}", "(c)") // garbled copyright -> (c)
-            .replace("✔", "*")
-            .replace("✓", "*")
-            .replace("✗", "x")
-            .replace("✘", "x")
-            .replace("—", "-")
-            .replace("–", "-")
-            .replace("…", "...")
-            .replace("\u{201c}", "\"") // left double quote
-            .replace("\u{201d}", "\"") // right double quote
-            .replace("\u{2018}", "'") // left single quote
-            .replace("\u{2019}", "'") // right single quote
-            .replace("●", "*")
-            .replace("•", "*")
-            .replace("©", "(c)")
-    }
-
-    /// Stops the proxy process
-    pub async fn stop(&self) -> Result<(), ProxyError> {
-        let mut process_guard = self.process.lock().await;
-
-        if let Some(mut child) = process_guard.take() {
-            // Try graceful shutdown via control socket first (works on all platforms)
-            if let Some(lock_data) = read_lock_file() {
-                if let Some(control_port) = lock_data.control_port {
-                    if send_shutdown_command(control_port).await {
-                        // Graceful shutdown initiated, wait for process to exit
-                        let _ =
-                            tokio::time::timeout(std::time::Duration::from_secs(5), child.wait())
-                                .await;
-                        *self.is_running.lock().await = false;
-                        return Ok(());
-                    }
-                }
+        let exit_error = match app.try_state::<ProxyManager>() {
+            Some(proxy) => proxy.handle_child_exit(&app).await,
+            None => None,
+        };
+        if let Some(error_data) = &exit_error {
+            if !error_emitted {
+                let _ = app.emit_scoped("proxy-error", error_data.clone());
             }
+        }
+        mark_process_exited(
+            &app,
+            &is_running,
+            exit_error.as_ref().map(|e| e.title.as_str()),
+        )
+        .await;
+    }
 
-            // Fallback: signal-based shutdown
-            #[cfg(unix)]
-            {
-                use nix::sys::signal::{kill, Signal};
-                use nix::unistd::Pid;
+    /// Decodes one raw output line from the proxy.
+    ///
+    /// Output is expected to be UTF-8. Anything else is decoded with the
+    /// system codepage on Windows (OEM first, as used for console output,
+    /// then ANSI), falling back to lossy UTF-8.
+    fn decode_line(bytes: &[u8]) -> String {
+        let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+        let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+
+        if let Ok(line) = std::str::from_utf8(bytes) {
+            return line.to_string();
+        }
 
-                if let Some(pid) = child.id() {
-                    let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
-                }
+        match system_encoding() {
+            Some(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+            None => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+
+    /// Winds everything down for the launcher quitting.
+    ///
+    /// Cancels an in-flight download, then either detaches the proxy (when
+    /// `keepProxyOnExit` is set) or stops it. The whole sequence is bounded by
+    /// `SHUTDOWN_ALL_TIMEOUT_SECS`; whatever is still running after that is
+    /// left to the lifetime binding to clean up.
+    pub async fn shutdown_all(&self) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        let deadline =
+            tokio::time::Instant::now() + std::time::Duration::from_secs(SHUTDOWN_ALL_TIMEOUT_SECS);
+
+        if self.is_downloading() {
+            self.download_cancel.store(true, Ordering::SeqCst);
+            // The download loop notices on its next chunk
+            while self.is_downloading() && tokio::time::Instant::now() < deadline {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
             }
+            report.download_cancelled = !self.is_downloading();
+        }
+
+        // Let a launch past the download settle so `stop` can take the child
+        while self.is_starting() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+
+        let keep_running = get_config()
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|c| c.keep_proxy_on_exit);
+        if keep_running && self.detach().await.is_ok() {
+            report.kept_running = true;
+            return report;
+        }
 
-            #[cfg(windows)]
-            {
-                let _ = child.kill().await;
+        match tokio::time::timeout_at(deadline, self.stop()).await {
+            Ok(Ok(outcome)) => report.proxy = Some(outcome),
+            Ok(Err(ProxyError::NotRunning)) => {}
+            // e.g. `Busy` with a launch still going at the deadline
+            Ok(Err(e)) => {
+                eprintln!("[proxy] Failed to stop the proxy on exit: {}", e);
+                report.timed_out = true;
             }
+            Err(_) => report.timed_out = true,
+        }
+        report.timed_out |= self.is_downloading() || self.is_starting();
 
-            // Wait for process to exit
-            let _ = tokio::time::timeout(std::time::Duration::from_secs(5), child.wait()).await;
+        report
+    }
 
-            *self.is_running.lock().await = false;
-            Ok(())
+    /// Stops the proxy process.
+    ///
+    /// Tries a graceful shutdown over the control socket first, then SIGTERM
+    /// (Unix only), and always escalates to SIGKILL/`TerminateProcess` once the
+    /// configured `proxyShutdownTimeoutSecs` elapses. Returns which path
+    /// actually stopped the process.
+    pub async fn stop(&self) -> Result<StopOutcome, ProxyError> {
+        self.ensure_settled()?;
+
+        let timeout = shutdown_timeout(
+            get_config()
+                .await
+                .ok()
+                .flatten()
+                .map(|c| c.proxy_shutdown_timeout_secs),
+        );
+
+        let outcome = if let Some(pid) = self.adopted_pid.lock().await.take() {
+            if request_graceful_shutdown().await && wait_for_pid_exit(pid, timeout).await {
+                StopOutcome::Graceful
+            } else {
+                stop_pid(pid, timeout).await
+            }
         } else {
-            Err(ProxyError::NotRunning)
-        }
+            let mut child = self
+                .process
+                .lock()
+                .await
+                .take()
+                .ok_or(ProxyError::NotRunning)?;
+            stop_child(&mut child, timeout).await
+        };
+
+        *self.lifetime_guard.lock().await = None;
+        *self.stdin.lock().await = None;
+        *self.is_running.lock().await = false;
+
+        println!("[proxy] Proxy stopped ({})", outcome.describe());
+        Ok(outcome)
     }
 }
 
@@ -767,6 +2306,30 @@ impl Default for ProxyManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::proxy::models::GameEventKind;
+
+    #[test]
+    fn test_lock_file_without_control_socket() {
+        let data: LockFileData =
+            serde_json::from_str(r#"{"pid":1,"port":25565,"controlPort":25570}"#).unwrap();
+        assert_eq!(data.control_port, Some(25570));
+        assert!(data.control_socket.is_none());
+        assert!(data.has_control_channel());
+    }
+
+    #[test]
+    fn test_lock_file_with_control_socket() {
+        let data: LockFileData = serde_json::from_str(
+            r#"{"pid":1,"port":25565,"controlSocket":"/tmp/duelsplus-control.sock"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data.control_socket.as_deref(),
+            Some("/tmp/duelsplus-control.sock")
+        );
+        assert!(data.control_port.is_none());
+        assert!(data.has_control_channel());
+    }
 
     #[tokio::test]
     async fn test_proxy_manager_stop_when_not_running() {
@@ -776,4 +2339,427 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ProxyError::NotRunning));
     }
+
+    #[tokio::test]
+    async fn test_proxy_manager_busy_while_starting() {
+        let manager = ProxyManager::new();
+        manager.starting.store(true, Ordering::SeqCst);
+
+        assert!(matches!(manager.stop().await, Err(ProxyError::Busy)));
+        assert!(matches!(
+            manager.send_control_command(&ControlCommand::Ping).await,
+            Err(ProxyError::Busy)
+        ));
+        assert!(matches!(
+            manager.send_stdin("help").await,
+            Err(ProxyError::Busy)
+        ));
+    }
+
+    #[test]
+    fn test_flag_guard_clears_on_drop() {
+        let flag = AtomicBool::new(true);
+        drop(FlagGuard(&flag));
+        assert!(!flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_classify_exit_from_stderr() {
+        let stderr = vec![
+            "[i] Starting proxy".to_string(),
+            "Error: listen EADDRINUSE: address already in use 0.0.0.0:25565".to_string(),
+        ];
+        let error = ProxyManager::classify_exit(Some(1), None, &stderr);
+        assert_eq!(error.code, "EADDRINUSE");
+        assert_eq!(error.context.as_deref(), Some("exit"));
+
+        let stderr =
+            vec!["./proxy: error while loading shared libraries: libstdc++.so.6".to_string()];
+        let error = ProxyManager::classify_exit(Some(127), None, &stderr);
+        assert_eq!(error.code, "MISSING_DEPENDENCY");
+
+        let stderr = vec!["API responded with 401 Unauthorized".to_string()];
+        let error = ProxyManager::classify_exit(Some(1), None, &stderr);
+        assert_eq!(error.code, "AUTH_REJECTED");
+    }
+
+    #[test]
+    fn test_classify_exit_from_status() {
+        let error = ProxyManager::classify_exit(None, Some(9), &[]);
+        assert_eq!(error.code, "PROXY_KILLED");
+        assert!(error.message.contains("signal 9"));
+
+        let error = ProxyManager::classify_exit(Some(0xC0000135_u32 as i32), None, &[]);
+        assert_eq!(error.code, "MISSING_DEPENDENCY");
+
+        let error = ProxyManager::classify_exit(Some(1), None, &["noise".to_string()]);
+        assert_eq!(error.code, "PROXY_EXITED");
+        assert!(error.message.contains("exit code 1"));
+    }
+
+    #[test]
+    fn test_proxy_synced_keys_exist_in_config() {
+        let config = serde_json::to_value(crate::config::models::Config::default()).unwrap();
+        for key in PROXY_SYNCED_KEYS {
+            assert!(config.get(*key).is_some(), "unknown config key {}", key);
+        }
+    }
+
+    #[test]
+    fn test_decode_line() {
+        assert_eq!(ProxyManager::decode_line(b"[i] ready\r\n"), "[i] ready");
+        assert_eq!(
+            ProxyManager::decode_line("\u{feff}[✓] done — ok\n".as_bytes()),
+            "[✓] done — ok"
+        );
+
+        // Invalid UTF-8 must not be dropped or end the stream
+        let decoded = ProxyManager::decode_line(b"caf\xe9\n");
+        assert!(decoded.starts_with("caf"));
+        assert_eq!(decoded.chars().count(), 4);
+    }
+
+    #[test]
+    fn test_parse_proxy_port() {
+        assert_eq!(parse_proxy_port("25565").unwrap(), 25565);
+        assert_eq!(parse_proxy_port(" 8080 ").unwrap(), 8080);
+        assert!(matches!(
+            parse_proxy_port("0"),
+            Err(ProxyError::InvalidPort(_))
+        ));
+        assert!(parse_proxy_port("65536").is_err());
+        assert!(parse_proxy_port("abc").is_err());
+        assert!(parse_proxy_port("").is_err());
+    }
+
+    #[test]
+    fn test_shutdown_timeout_is_clamped() {
+        let secs = |configured| shutdown_timeout(configured).as_secs();
+        assert_eq!(secs(None), DEFAULT_SHUTDOWN_TIMEOUT_SECS);
+        assert_eq!(secs(Some(2)), 2);
+        assert_eq!(secs(Some(600)), MAX_SHUTDOWN_TIMEOUT_SECS);
+        // Both waits and the force kill fit in the time allowed on exit
+        assert!(2 * secs(Some(u64::MAX)) < SHUTDOWN_ALL_TIMEOUT_SECS);
+    }
+
+    #[test]
+    fn test_control_message_user_data() {
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"user_data","ign":"Player","uuid":"abc-123"}"#)
+                .unwrap();
+
+        match msg {
+            ControlMessage::UserData { ign, uuid } => {
+                assert_eq!(ign, "Player");
+                assert_eq!(uuid, "abc-123");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_message_game_mode_full() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{"type":"game_mode","mode":"DUELS_CLASSIC_DUEL","map":"Arena","gametype":"DUELS","lobbyname":null}"#,
+        )
+        .unwrap();
+
+        match msg {
+            ControlMessage::GameMode {
+                mode,
+                map,
+                gametype,
+                lobbyname,
+                ends_at,
+            } => {
+                assert_eq!(mode.as_deref(), Some("DUELS_CLASSIC_DUEL"));
+                assert_eq!(map.as_deref(), Some("Arena"));
+                assert_eq!(gametype.as_deref(), Some("DUELS"));
+                assert_eq!(lobbyname, None);
+                assert_eq!(ends_at, None);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_message_game_mode_lobby() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{"type":"game_mode","mode":null,"gametype":"DUELS","lobbyname":"duelslobby1"}"#,
+        )
+        .unwrap();
+
+        match msg {
+            ControlMessage::GameMode {
+                mode,
+                map,
+                gametype,
+                lobbyname,
+                ..
+            } => {
+                assert_eq!(mode, None);
+                assert_eq!(map, None);
+                assert_eq!(gametype.as_deref(), Some("DUELS"));
+                assert_eq!(lobbyname.as_deref(), Some("duelslobby1"));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_message_game_mode_timed() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{"type":"game_mode","mode":"DUELS_BRIDGE_DUEL","gametype":"DUELS","ends_at":1700000900}"#,
+        )
+        .unwrap();
+
+        match msg {
+            ControlMessage::GameMode { ends_at, .. } => {
+                assert_eq!(ends_at, Some(1700000900));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_message_game_mode_legacy_fields_only() {
+        // Older proxies only send mode/map
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"game_mode","mode":"DUELS_SUMO_DUEL","map":"Ring"}"#)
+                .unwrap();
+
+        match msg {
+            ControlMessage::GameMode {
+                gametype,
+                lobbyname,
+                ..
+            } => {
+                assert_eq!(gametype, None);
+                assert_eq!(lobbyname, None);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_message_disconnect() {
+        let msg: ControlMessage = serde_json::from_str(r#"{"type":"disconnect"}"#).unwrap();
+        assert!(matches!(msg, ControlMessage::Disconnect));
+    }
+
+    #[test]
+    fn test_control_message_party() {
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"party","id":"party-1","size":2,"max":8}"#).unwrap();
+        assert!(matches!(
+            msg,
+            ControlMessage::Party { id: Some(ref id), size: 2, max: 8 } if id == "party-1"
+        ));
+
+        let msg: ControlMessage = serde_json::from_str(r#"{"type":"party","id":null}"#).unwrap();
+        assert!(matches!(msg, ControlMessage::Party { id: None, .. }));
+    }
+
+    #[test]
+    fn test_control_message_spectating() {
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"spectating","target":"DUELS_SUMO_DUEL"}"#).unwrap();
+        assert!(matches!(
+            msg,
+            ControlMessage::Spectating { target: Some(ref target) } if target == "DUELS_SUMO_DUEL"
+        ));
+
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"spectating","target":null}"#).unwrap();
+        assert!(matches!(msg, ControlMessage::Spectating { target: None }));
+    }
+
+    #[test]
+    fn test_control_message_hello() {
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"hello","protocol_version":2}"#).unwrap();
+        assert!(matches!(
+            msg,
+            ControlMessage::Hello {
+                protocol_version: 2
+            }
+        ));
+
+        let hello = serde_json::to_value(ControlCommand::Hello {
+            protocol_version: CONTROL_PROTOCOL_VERSION,
+        })
+        .unwrap();
+        assert_eq!(hello["type"], "hello");
+        assert_eq!(hello["protocol_version"], CONTROL_PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_control_message_type() {
+        assert_eq!(
+            control_message_type(r#"{"type":"something_new","x":1}"#).as_deref(),
+            Some("something_new")
+        );
+        assert_eq!(control_message_type("not json"), None);
+    }
+
+    #[test]
+    fn test_control_message_game_event() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{"type":"game_event","event":"win","mode":"duels_bridge_duel","map":"Urban"}"#,
+        )
+        .unwrap();
+        match msg {
+            ControlMessage::GameEvent(event) => {
+                assert_eq!(event.event, GameEventKind::Win);
+                assert_eq!(event.mode.as_deref(), Some("duels_bridge_duel"));
+                assert_eq!(event.map.as_deref(), Some("Urban"));
+            }
+            _ => panic!("Expected GameEvent"),
+        }
+
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"game_event","event":"game_start"}"#).unwrap();
+        match msg {
+            ControlMessage::GameEvent(event) => {
+                assert_eq!(event.event, GameEventKind::GameStart);
+                assert!(event.mode.is_none());
+                assert!(event.map.is_none());
+            }
+            _ => panic!("Expected GameEvent"),
+        }
+    }
+
+    #[test]
+    fn test_control_message_friend_online() {
+        let msg: ControlMessage =
+            serde_json::from_str(r#"{"type":"friend_online","name":"Steve"}"#).unwrap();
+        match msg {
+            ControlMessage::FriendOnline { name } => assert_eq!(name, "Steve"),
+            _ => panic!("Expected FriendOnline"),
+        }
+    }
+
+    #[test]
+    fn test_control_message_opponent() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{"type":"opponent","name":"Steve","uuid":null,"wins":120,"losses":40}"#,
+        )
+        .unwrap();
+        match msg {
+            ControlMessage::Opponent(opponent) => {
+                assert_eq!(opponent.name, "Steve");
+                assert_eq!(opponent.wins, Some(120));
+                assert_eq!(opponent.winstreak, None);
+            }
+            _ => panic!("Expected Opponent"),
+        }
+    }
+
+    #[test]
+    fn test_control_message_metrics() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{"type":"metrics","activeConnections":1,"packetsPerSec":42.5,"bytesProxied":1024,"pingMs":37}"#,
+        )
+        .unwrap();
+        match msg {
+            ControlMessage::Metrics(metrics) => {
+                assert_eq!(metrics.active_connections, 1);
+                assert_eq!(metrics.packets_per_sec, 42.5);
+                assert_eq!(metrics.bytes_proxied, 1024);
+                assert_eq!(metrics.ping_ms, Some(37));
+            }
+            _ => panic!("Expected Metrics"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_metrics_answers_waiters() {
+        let manager = ProxyManager::new();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        manager.metrics_waiters.lock().unwrap().push(tx);
+
+        manager.resolve_metrics(&ProxyMetrics {
+            active_connections: 2,
+            ..Default::default()
+        });
+
+        assert_eq!(rx.await.unwrap().active_connections, 2);
+        assert!(manager.metrics_waiters.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_control_message_pong() {
+        let msg: ControlMessage = serde_json::from_str(r#"{"type":"pong"}"#).unwrap();
+        assert!(matches!(msg, ControlMessage::Pong));
+    }
+
+    #[test]
+    fn test_control_message_proxy_error() {
+        let msg: ControlMessage = serde_json::from_str(
+            r#"{
+                "type": "proxy_error",
+                "code": "ECONNRESET",
+                "title": "Connection Reset",
+                "message": "read ECONNRESET",
+                "suggestion": "Try reconnecting.",
+                "severity": "warning",
+                "category": "network",
+                "originalMessage": "Error: read ECONNRESET",
+                "context": "hypixel_connection",
+                "timestamp": 1700000000000
+            }"#,
+        )
+        .unwrap();
+
+        match msg {
+            ControlMessage::ProxyError {
+                code,
+                severity,
+                category,
+                original_message,
+                context,
+                timestamp,
+                ..
+            } => {
+                assert_eq!(code, "ECONNRESET");
+                assert!(matches!(severity, ErrorSeverity::Warning));
+                assert!(matches!(category, ErrorCategory::Network));
+                assert_eq!(original_message, "Error: read ECONNRESET");
+                assert_eq!(context.as_deref(), Some("hypixel_connection"));
+                assert_eq!(timestamp, 1700000000000);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_control_message_unknown_type() {
+        let result = serde_json::from_str::<ControlMessage>(r#"{"type":"something_new"}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_control_command_serialization() {
+        let ping = serde_json::to_value(ControlCommand::Ping).unwrap();
+        assert_eq!(ping, serde_json::json!({ "type": "ping" }));
+
+        let set = serde_json::to_value(ControlCommand::SetSetting {
+            key: "autoGG".to_string(),
+            value: serde_json::json!(true),
+        })
+        .unwrap();
+        assert_eq!(
+            set,
+            serde_json::json!({ "type": "set_setting", "key": "autoGG", "value": true })
+        );
+
+        let join = serde_json::to_value(ControlCommand::JoinParty {
+            party_id: "party-1".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            join,
+            serde_json::json!({ "type": "join_party", "party_id": "party-1" })
+        );
+    }
 }
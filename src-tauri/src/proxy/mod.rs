@@ -2,9 +2,17 @@
 //!
 //! This module handles downloading, updating, and running the Duels+ proxy executable.
 
+pub mod addons;
+pub mod connection;
+pub mod crash;
 pub mod download;
 pub mod error;
+pub mod events;
+pub mod firewall;
 pub mod manager;
 pub mod models;
+pub mod preflight;
+pub mod process;
+pub mod transport;
 
 pub use manager::ProxyManager;
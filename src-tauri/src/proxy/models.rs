@@ -25,6 +25,36 @@ pub struct Asset {
     pub url: String,
 }
 
+/// Optional proxy feature module from the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Addon {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Latest version
+    pub version: String,
+    #[serde(default)]
+    pub changelog: String,
+    pub asset: Asset,
+}
+
+/// An addon and its state, as returned by `list_addons`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// `None` if the addon is installed but no longer offered
+    pub latest_version: Option<String>,
+    /// `None` if the addon isn't installed
+    pub installed_version: Option<String>,
+    /// Whether the proxy loads it, following `disabledAddons`
+    pub enabled: bool,
+    pub update_available: bool,
+}
+
 /// Download progress information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -42,10 +72,106 @@ pub enum ProxyStatus {
     Checking,
     Downloading { version: String },
     Launching,
-    Launched,
+    Launched { port: u16 },
     Error,
 }
 
+/// Where the player's connection through the proxy currently stands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionState {
+    /// The proxy is not running
+    #[default]
+    Stopped,
+    ProxyStarting,
+    /// The proxy is up but no Minecraft client has connected yet
+    WaitingForClient,
+    /// A client is connected (in a lobby or limbo)
+    ClientConnected,
+    InGame,
+    /// The client left Hypixel while the proxy kept running
+    Disconnected,
+}
+
+/// A running proxy found at startup that this launcher does not own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanProxyInfo {
+    pub pid: u32,
+    pub port: u16,
+}
+
+/// Payload of the `proxy-launch-failed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchFailure {
+    pub reason: String,
+    /// Last lines the proxy wrote to stderr before failing
+    pub stderr: Vec<String>,
+}
+
+/// What `ProxyManager::shutdown_all` did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShutdownReport {
+    /// An in-flight proxy download was cancelled
+    pub download_cancelled: bool,
+    /// How the proxy was stopped, if one was running
+    pub proxy: Option<StopOutcome>,
+    /// The proxy was left running because `keepProxyOnExit` is set
+    pub kept_running: bool,
+    /// Shutdown didn't finish within the time limit
+    pub timed_out: bool,
+}
+
+/// Payload of the `proxy-crashed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyCrashInfo {
+    /// Path of the crash bundle on disk
+    pub path: String,
+    pub exit_code: Option<i32>,
+}
+
+/// Payload of the `control-protocol-mismatch` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolMismatch {
+    pub launcher_version: u32,
+    pub proxy_version: u32,
+}
+
+/// Payload of the `minecraft-launched` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinecraftLaunchInfo {
+    pub pid: u32,
+    pub command: String,
+}
+
+/// How a proxy process was stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StopOutcome {
+    /// Exited after the control socket shutdown command
+    Graceful,
+    /// Exited after SIGTERM
+    Terminated,
+    /// Force killed after the shutdown timeout elapsed
+    Killed,
+}
+
+impl StopOutcome {
+    /// Short human readable description for logs
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StopOutcome::Graceful => "graceful shutdown",
+            StopOutcome::Terminated => "terminated",
+            StopOutcome::Killed => "force killed after timeout",
+        }
+    }
+}
+
 /// User data extracted from proxy logs for RPC
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -54,6 +180,75 @@ pub struct RpcUserData {
     pub uuid: String,
 }
 
+/// Proxy-side network statistics, payload of the `proxy-metrics` event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyMetrics {
+    pub active_connections: u32,
+    pub packets_per_sec: f64,
+    /// Total bytes forwarded in both directions since the proxy started
+    pub bytes_proxied: u64,
+    /// Round trip to Hypixel, `None` while no server connection is open
+    pub ping_ms: Option<u32>,
+}
+
+/// Kind of in-game event reported by the proxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEventKind {
+    Kill,
+    Death,
+    Win,
+    Loss,
+    GameStart,
+    GameEnd,
+    /// The player scored, e.g. in the Bridge
+    Goal,
+    /// An opponent scored
+    GoalConceded,
+    /// The player broke an opponent's bed, in BedWars
+    BedBroken,
+    /// An opponent broke the player's bed
+    BedLost,
+}
+
+/// Payload of the `game-event` event, relayed as-is from the control socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameEvent {
+    pub event: GameEventKind,
+    pub mode: Option<String>,
+    pub map: Option<String>,
+    /// The other player in it: who was killed, who killed the player, or
+    /// who scored or broke a bed
+    #[serde(default)]
+    pub player: Option<String>,
+    /// Round of the match from 1, for modes played in rounds
+    #[serde(default)]
+    pub round: Option<u32>,
+    /// Whether a kill or death decided the match
+    #[serde(default)]
+    pub final_blow: bool,
+}
+
+/// The player's opponent in the current duel, as looked up by the proxy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Opponent {
+    pub name: String,
+    pub uuid: Option<String>,
+    /// Duels stats; `None` when the proxy couldn't look them up, e.g. for a
+    /// nicked player
+    #[serde(default)]
+    pub wins: Option<u32>,
+    #[serde(default)]
+    pub losses: Option<u32>,
+    #[serde(default)]
+    pub winstreak: Option<u32>,
+    #[serde(default)]
+    pub best_winstreak: Option<u32>,
+}
+
 /// Error severity levels
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -99,3 +294,27 @@ pub struct ProxyErrorData {
     /// Timestamp when the error occurred
     pub timestamp: u64,
 }
+
+/// Command sent from the launcher to the proxy over the control socket.
+///
+/// Serialized as a single JSON line, e.g. `{"type":"set_setting","key":"autoGG","value":true}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Announces the launcher's control protocol version, answered by the
+    /// proxy with its own `hello`
+    Hello { protocol_version: u32 },
+    /// Heartbeat, answered by the proxy with a `pong` message
+    Ping,
+    /// Asks the proxy to stream its log lines as `log` messages
+    SubscribeLogs,
+    /// Asks the proxy for a `metrics` message
+    GetMetrics,
+    /// A Discord "Ask to Join" request for this party was accepted
+    JoinParty { party_id: String },
+    /// Changes a proxy setting at runtime
+    SetSetting {
+        key: String,
+        value: serde_json::Value,
+    },
+}
@@ -0,0 +1,263 @@
+//! Pre-flight checks for launching the proxy.
+//!
+//! Walks through what `check_and_launch` depends on without downloading or
+//! spawning anything, so the UI can point at the exact problem before the
+//! user hits Launch.
+
+use super::download::{
+    fetch_releases, find_latest_release, find_platform_asset, get_install_dir, get_platform_tag,
+    is_file_valid,
+};
+use super::manager::parse_proxy_port;
+use crate::config::manager::get_config;
+use serde::{Deserialize, Serialize};
+
+/// What a single check covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreflightCheckId {
+    Platform,
+    InstallDir,
+    Port,
+    Api,
+    Binary,
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreflightStatus {
+    Passed,
+    /// Launch will still work, e.g. the binary just needs downloading
+    Warning,
+    Failed,
+    /// Not checked because an earlier check it depends on failed
+    Skipped,
+}
+
+/// A single pre-flight check result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub id: PreflightCheckId,
+    pub status: PreflightStatus,
+    /// Human-readable explanation shown next to the check
+    pub message: String,
+}
+
+impl PreflightCheck {
+    fn new(id: PreflightCheckId, status: PreflightStatus, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Result of `validate_launch_environment`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    /// True when no check failed
+    pub ready: bool,
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    fn from_checks(checks: Vec<PreflightCheck>) -> Self {
+        let ready = checks.iter().all(|c| c.status != PreflightStatus::Failed);
+        Self { ready, checks }
+    }
+}
+
+/// Checks everything a launch needs, in the order `check_and_launch` needs it.
+///
+/// # Arguments
+/// * `port` - Port to check, defaults to the configured proxy port
+/// * `proxy_running` - Whether a proxy is already running (its port is then expected to be taken)
+///
+/// # Returns
+/// One entry per check; the report is `ready` if none of them failed
+pub async fn validate_launch_environment(
+    port: Option<u16>,
+    proxy_running: bool,
+) -> PreflightReport {
+    use PreflightCheckId as Id;
+    use PreflightStatus as Status;
+
+    let config = get_config().await.ok().flatten().unwrap_or_default();
+    let mut checks = Vec::new();
+
+    let platform_tag = match get_platform_tag() {
+        Ok(tag) => {
+            checks.push(PreflightCheck::new(
+                Id::Platform,
+                Status::Passed,
+                tag.clone(),
+            ));
+            Some(tag)
+        }
+        Err(e) => {
+            checks.push(PreflightCheck::new(
+                Id::Platform,
+                Status::Failed,
+                e.to_string(),
+            ));
+            None
+        }
+    };
+
+    let install_dir = match get_install_dir().and_then(|dir| {
+        check_writable(&dir)?;
+        Ok(dir)
+    }) {
+        Ok(dir) => {
+            checks.push(PreflightCheck::new(
+                Id::InstallDir,
+                Status::Passed,
+                dir.display().to_string(),
+            ));
+            Some(dir)
+        }
+        Err(e) => {
+            checks.push(PreflightCheck::new(
+                Id::InstallDir,
+                Status::Failed,
+                format!("Install directory is not writable: {}", e),
+            ));
+            None
+        }
+    };
+
+    checks.push(if proxy_running {
+        PreflightCheck::new(Id::Port, Status::Skipped, "Proxy is already running")
+    } else {
+        match port
+            .map(Ok)
+            .unwrap_or_else(|| parse_proxy_port(&config.proxy_port))
+        {
+            Ok(port) => check_port(port),
+            Err(e) => PreflightCheck::new(Id::Port, Status::Failed, e.to_string()),
+        }
+    });
+
+    let release = match fetch_releases(config.receive_beta_releases).await {
+        Ok(releases) => {
+            checks.push(PreflightCheck::new(
+                Id::Api,
+                Status::Passed,
+                "Release server is reachable",
+            ));
+            Some(releases)
+        }
+        Err(e) => {
+            checks.push(PreflightCheck::new(
+                Id::Api,
+                Status::Failed,
+                format!("Couldn't reach the release server: {}", e),
+            ));
+            None
+        }
+    };
+
+    checks.push(match (platform_tag, install_dir, release) {
+        (Some(tag), Some(dir), Some(releases)) => {
+            match find_latest_release(&releases)
+                .and_then(|latest| Ok((latest, find_platform_asset(latest, &tag)?)))
+            {
+                Ok((latest, asset)) if is_file_valid(&dir.join(&asset.name)) => {
+                    PreflightCheck::new(
+                        Id::Binary,
+                        Status::Passed,
+                        format!("Version {} is installed", latest.version),
+                    )
+                }
+                Ok((latest, _)) => PreflightCheck::new(
+                    Id::Binary,
+                    Status::Warning,
+                    format!("Version {} will be downloaded on launch", latest.version),
+                ),
+                Err(e) => PreflightCheck::new(Id::Binary, Status::Failed, e.to_string()),
+            }
+        }
+        _ => PreflightCheck::new(
+            Id::Binary,
+            Status::Skipped,
+            "Can't check the proxy binary until the checks above pass",
+        ),
+    });
+
+    PreflightReport::from_checks(checks)
+}
+
+/// Creates the directory if needed and writes (then removes) a probe file in it
+fn check_writable(dir: &std::path::Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".write-test");
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(&probe)
+}
+
+/// Checks that nothing else is listening on the proxy port
+pub(super) fn check_port(port: u16) -> PreflightCheck {
+    match std::net::TcpListener::bind(("0.0.0.0", port)) {
+        Ok(_) => PreflightCheck::new(
+            PreflightCheckId::Port,
+            PreflightStatus::Passed,
+            format!("Port {} is free", port),
+        ),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => PreflightCheck::new(
+            PreflightCheckId::Port,
+            PreflightStatus::Failed,
+            format!("Port {} is already in use by another program", port),
+        ),
+        Err(e) => PreflightCheck::new(
+            PreflightCheckId::Port,
+            PreflightStatus::Failed,
+            format!("Port {} can't be used: {}", port, e),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_port_in_use() {
+        let listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let check = check_port(port);
+        assert_eq!(check.status, PreflightStatus::Failed);
+
+        drop(listener);
+        assert_eq!(check_port(port).status, PreflightStatus::Passed);
+    }
+
+    #[test]
+    fn test_report_ready_ignores_warnings() {
+        let report = PreflightReport::from_checks(vec![
+            PreflightCheck::new(PreflightCheckId::Platform, PreflightStatus::Passed, ""),
+            PreflightCheck::new(PreflightCheckId::Binary, PreflightStatus::Warning, ""),
+            PreflightCheck::new(PreflightCheckId::Port, PreflightStatus::Skipped, ""),
+        ]);
+        assert!(report.ready);
+
+        let report = PreflightReport::from_checks(vec![PreflightCheck::new(
+            PreflightCheckId::Api,
+            PreflightStatus::Failed,
+            "",
+        )]);
+        assert!(!report.ready);
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let dir = std::env::temp_dir().join(format!("duelsplus-preflight-{}", std::process::id()));
+        assert!(check_writable(&dir).is_ok());
+        assert!(!dir.join(".write-test").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
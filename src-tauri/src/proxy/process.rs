@@ -0,0 +1,389 @@
+//! Platform helpers for proxy process lifetime management.
+//!
+//! Covers binding a spawned proxy to the launcher's lifetime, as well as
+//! inspecting and signalling proxy processes by pid for instances the
+//! launcher did not spawn itself (e.g. one left running after a launcher
+//! crash), where no `Child` handle is available. Such a pid is checked
+//! against the executable it runs before it's trusted, and only children
+//! the launcher put in their own process group are signalled as a group.
+
+use crate::config::models::ProxyPriority;
+use std::path::{Path, PathBuf};
+use tokio::process::{Child, Command};
+
+/// Prepares the proxy command so the child cannot outlive the launcher.
+///
+/// On Unix the proxy gets its own process group (so the whole tree can be
+/// signalled at once), and on Linux it additionally receives SIGKILL when
+/// the launcher dies. Windows uses a Job Object instead, see [`LifetimeGuard`].
+#[cfg(unix)]
+pub fn bind_to_launcher(cmd: &mut Command) {
+    cmd.process_group(0);
+
+    #[cfg(target_os = "linux")]
+    unsafe {
+        // Runs in the forked child before exec; prctl is async-signal-safe
+        cmd.pre_exec(|| {
+            nix::sys::prctl::set_pdeathsig(nix::sys::signal::Signal::SIGKILL)
+                .map_err(std::io::Error::from)
+        });
+    }
+}
+
+/// Prepares the proxy command so the child cannot outlive the launcher.
+///
+/// Nothing to do before spawning on Windows, see [`LifetimeGuard`].
+#[cfg(windows)]
+pub fn bind_to_launcher(_cmd: &mut Command) {}
+
+/// Keeps a spawned proxy tied to the launcher process.
+///
+/// On Windows this owns a Job Object created with kill-on-close; the handle
+/// is closed by the OS when the launcher exits for any reason, which
+/// terminates the proxy. On Unix the binding is done at spawn time by
+/// [`bind_to_launcher`] and this guard holds nothing.
+pub struct LifetimeGuard {
+    #[cfg(windows)]
+    job: windows_sys::Win32::Foundation::HANDLE,
+}
+
+// The job handle is only used for CloseHandle, which is thread-safe
+#[cfg(windows)]
+unsafe impl Send for LifetimeGuard {}
+#[cfg(windows)]
+unsafe impl Sync for LifetimeGuard {}
+
+impl LifetimeGuard {
+    /// Binds a freshly spawned child to the launcher's lifetime
+    #[cfg(unix)]
+    pub fn attach(_child: &Child) -> Option<Self> {
+        Some(Self {})
+    }
+
+    /// Binds a freshly spawned child to the launcher's lifetime
+    #[cfg(windows)]
+    pub fn attach(child: &Child) -> Option<Self> {
+        use windows_sys::Win32::Foundation::CloseHandle;
+        use windows_sys::Win32::System::JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+            SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+            JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+        };
+
+        let process = child.raw_handle()?;
+
+        unsafe {
+            let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+            if job.is_null() {
+                return None;
+            }
+
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+            info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+
+            let configured = SetInformationJobObject(
+                job,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const std::ffi::c_void,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            );
+            if configured == 0 || AssignProcessToJobObject(job, process as _) == 0 {
+                CloseHandle(job);
+                return None;
+            }
+
+            Some(Self { job })
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for LifetimeGuard {
+    fn drop(&mut self) {
+        unsafe {
+            windows_sys::Win32::Foundation::CloseHandle(self.job);
+        }
+    }
+}
+
+/// Checks whether a process with the given pid is alive
+#[cfg(unix)]
+pub fn is_process_alive(pid: u32) -> bool {
+    use nix::errno::Errno;
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    // Signal 0 only performs permission/existence checks
+    match kill(Pid::from_raw(pid as i32), None) {
+        Ok(()) => true,
+        Err(Errno::EPERM) => true,
+        Err(_) => false,
+    }
+}
+
+/// Checks whether a process with the given pid is alive
+#[cfg(windows)]
+pub fn is_process_alive(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return false;
+        }
+
+        let mut exit_code: u32 = 0;
+        let ok = GetExitCodeProcess(handle, &mut exit_code);
+        CloseHandle(handle);
+
+        ok != 0 && exit_code == STILL_ACTIVE as u32
+    }
+}
+
+/// Sends a signal to a single process
+#[cfg(unix)]
+fn signal_process(pid: u32, signal: nix::sys::signal::Signal) {
+    let _ = nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid as i32), signal);
+}
+
+/// Sends a signal to the process group a proxy leads, falling back to the
+/// pid alone.
+///
+/// Only for children spawned through [`bind_to_launcher`]: any other pid
+/// may share its group with unrelated processes.
+#[cfg(unix)]
+fn signal_group(pid: u32, signal: nix::sys::signal::Signal) {
+    use nix::sys::signal::{kill, killpg};
+    use nix::unistd::Pid;
+
+    let pid = Pid::from_raw(pid as i32);
+    if killpg(pid, signal).is_err() {
+        let _ = kill(pid, signal);
+    }
+}
+
+/// Asks a process to terminate with SIGTERM
+#[cfg(unix)]
+pub fn terminate_process(pid: u32) {
+    signal_process(pid, nix::sys::signal::Signal::SIGTERM);
+}
+
+/// Force kills a process (SIGKILL on Unix)
+#[cfg(unix)]
+pub fn kill_process(pid: u32) {
+    signal_process(pid, nix::sys::signal::Signal::SIGKILL);
+}
+
+/// Asks a proxy spawned through [`bind_to_launcher`] and its process group
+/// to terminate with SIGTERM
+#[cfg(unix)]
+pub fn terminate_process_group(pid: u32) {
+    signal_group(pid, nix::sys::signal::Signal::SIGTERM);
+}
+
+/// Force kills a proxy spawned through [`bind_to_launcher`] along with its
+/// process group (SIGKILL on Unix)
+#[cfg(unix)]
+pub fn kill_process_group(pid: u32) {
+    signal_group(pid, nix::sys::signal::Signal::SIGKILL);
+}
+
+/// Force kills a proxy spawned through [`bind_to_launcher`]; its job object
+/// takes down the rest of the tree on Windows
+#[cfg(windows)]
+pub fn kill_process_group(pid: u32) {
+    kill_process(pid);
+}
+
+/// Force kills a process (`TerminateProcess` on Windows)
+#[cfg(windows)]
+pub fn kill_process(pid: u32) {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if handle.is_null() {
+            return;
+        }
+        TerminateProcess(handle, 1);
+        CloseHandle(handle);
+    }
+}
+
+/// Gets the path of the executable a process runs, if it can be read
+#[cfg(target_os = "linux")]
+pub fn process_executable(pid: u32) -> Option<PathBuf> {
+    std::fs::read_link(format!("/proc/{}/exe", pid)).ok()
+}
+
+/// Gets the path of the executable a process runs, if it can be read
+#[cfg(target_os = "macos")]
+pub fn process_executable(pid: u32) -> Option<PathBuf> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut buf = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    // SAFETY: the buffer is writable for the size passed
+    let len = unsafe { libc::proc_pidpath(pid as i32, buf.as_mut_ptr().cast(), buf.len() as u32) };
+    if len <= 0 {
+        return None;
+    }
+    buf.truncate(len as usize);
+    Some(PathBuf::from(std::ffi::OsStr::from_bytes(&buf)))
+}
+
+/// Gets the path of the executable a process runs, if it can be read
+#[cfg(windows)]
+pub fn process_executable(pid: u32) -> Option<PathBuf> {
+    use std::os::windows::ffi::OsStringExt;
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+
+        let mut buf = [0u16; 1024];
+        let mut len = buf.len() as u32;
+        let ok = QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, buf.as_mut_ptr(), &mut len);
+        CloseHandle(handle);
+
+        (ok != 0).then(|| PathBuf::from(std::ffi::OsString::from_wide(&buf[..len as usize])))
+    }
+}
+
+/// Whether a process runs an executable from `dir`.
+///
+/// A pid read back from a file may have been reused by an unrelated
+/// process since (e.g. after a reboot), so liveness alone doesn't prove
+/// it's still the process that was recorded.
+pub fn runs_from(pid: u32, dir: &Path) -> bool {
+    let Some(executable) = process_executable(pid) else {
+        return false;
+    };
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    executable.starts_with(&dir)
+}
+
+/// Applies the configured scheduling priority to a spawned proxy.
+///
+/// Uses the nice value on Unix. Raising priority above normal usually needs
+/// elevated privileges there, so failures are returned for logging only.
+#[cfg(unix)]
+pub fn set_process_priority(child: &Child, priority: ProxyPriority) -> std::io::Result<()> {
+    let nice = match priority {
+        ProxyPriority::Low => 10,
+        ProxyPriority::Normal => return Ok(()),
+        ProxyPriority::High => -5,
+    };
+    let pid = child
+        .id()
+        .ok_or_else(|| std::io::Error::other("Process has already exited"))?;
+
+    // The `which` argument type differs between libc targets
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS as _, pid as libc::id_t, nice) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Applies the configured scheduling priority to a spawned proxy.
+///
+/// Uses the process priority class on Windows.
+#[cfg(windows)]
+pub fn set_process_priority(child: &Child, priority: ProxyPriority) -> std::io::Result<()> {
+    use windows_sys::Win32::System::Threading::{
+        SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+    };
+
+    let class = match priority {
+        ProxyPriority::Low => BELOW_NORMAL_PRIORITY_CLASS,
+        ProxyPriority::Normal => return Ok(()),
+        ProxyPriority::High => ABOVE_NORMAL_PRIORITY_CLASS,
+    };
+    let handle = child
+        .raw_handle()
+        .ok_or_else(|| std::io::Error::other("Process has already exited"))?;
+
+    if unsafe { SetPriorityClass(handle as _, class) } == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Starts an external program fully detached from the launcher.
+///
+/// Used for the post-launch hook: the program gets its own process group
+/// (no console or job on Windows), no stdio, and is not bound to the
+/// launcher's lifetime. If `program` isn't an existing file it is run
+/// through the platform shell so a full command line can be configured.
+/// macOS `.app` bundles are opened with `open`.
+pub fn spawn_detached(program: &str) -> std::io::Result<u32> {
+    let path = std::path::Path::new(program);
+
+    let mut cmd = if cfg!(target_os = "macos") && path.extension().is_some_and(|e| e == "app") {
+        let mut cmd = Command::new("open");
+        cmd.arg("-a").arg(path);
+        cmd
+    } else if path.is_file() {
+        let mut cmd = Command::new(path);
+        if let Some(parent) = path.parent() {
+            cmd.current_dir(parent);
+        }
+        cmd
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(program);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(program);
+        cmd
+    };
+
+    cmd.stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    #[cfg(windows)]
+    {
+        const DETACHED_PROCESS: u32 = 0x00000008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+        cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+
+    let child = cmd.spawn()?;
+    // Dropping the handle leaves the program running; the runtime reaps it
+    child
+        .id()
+        .ok_or_else(|| std::io::Error::other("Program exited before it could be tracked"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_process_is_alive() {
+        assert!(is_process_alive(std::process::id()));
+    }
+
+    #[test]
+    fn test_runs_from() {
+        let exe = std::env::current_exe().unwrap().canonicalize().unwrap();
+        let dir = exe.parent().unwrap();
+        assert!(runs_from(std::process::id(), dir));
+        assert!(!runs_from(std::process::id(), &dir.join("elsewhere")));
+    }
+}
@@ -0,0 +1,121 @@
+//! Control channel transports.
+//!
+//! Newer proxy builds advertise a Unix domain socket (a named pipe on
+//! Windows) in the lock file's `controlSocket` field, which only the current
+//! user can reach. Older builds only listen on a TCP `controlPort` bound to
+//! 127.0.0.1, which stays supported as a fallback.
+
+use tokio::io::{AsyncRead, AsyncWrite, WriteHalf};
+use tokio::net::TcpStream;
+
+/// Any bidirectional stream the control protocol can run over
+pub trait ControlIo: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> ControlIo for T {}
+
+/// A connected control channel, regardless of transport
+pub type ControlStream = Box<dyn ControlIo>;
+
+/// Write half of a control channel, shared for sending commands
+pub type ControlWriter = WriteHalf<ControlStream>;
+
+/// Connects to the proxy's control channel.
+///
+/// The local socket is tried first when one is advertised; if it is missing
+/// or refuses the connection, the TCP port is used instead.
+///
+/// # Arguments
+/// * `socket` - Socket path (Unix) or pipe name (Windows) from the lock file
+/// * `port` - TCP control port from the lock file
+///
+/// # Returns
+/// The connected stream, or `None` if no transport could be reached
+pub async fn connect(socket: Option<&str>, port: Option<u16>) -> Option<ControlStream> {
+    if let Some(socket) = socket {
+        if let Ok(stream) = connect_local(socket).await {
+            return Some(stream);
+        }
+    }
+
+    let stream = TcpStream::connect(("127.0.0.1", port?)).await.ok()?;
+    Some(Box::new(stream))
+}
+
+/// Connects to a Unix domain socket, refusing sockets owned by another user
+#[cfg(unix)]
+async fn connect_local(path: &str) -> std::io::Result<ControlStream> {
+    use std::os::unix::fs::MetadataExt;
+
+    let owner = std::fs::metadata(path)?.uid();
+    // SAFETY: getuid has no preconditions and cannot fail
+    if owner != unsafe { libc::getuid() } {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "control socket is owned by another user",
+        ));
+    }
+
+    let stream = tokio::net::UnixStream::connect(path).await?;
+    Ok(Box::new(stream))
+}
+
+/// Connects to a named pipe, waiting briefly while all instances are busy
+#[cfg(windows)]
+async fn connect_local(name: &str) -> std::io::Result<ControlStream> {
+    use tokio::net::windows::named_pipe::ClientOptions;
+
+    /// Win32 ERROR_PIPE_BUSY
+    const PIPE_BUSY: i32 = 231;
+
+    for _ in 0..5 {
+        match ClientOptions::new().open(name) {
+            Ok(client) => return Ok(Box::new(client)),
+            Err(e) if e.raw_os_error() == Some(PIPE_BUSY) => {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(std::io::Error::new(
+        std::io::ErrorKind::TimedOut,
+        "control pipe stayed busy",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connect_without_transport() {
+        assert!(connect(None, None).await.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connect_prefers_unix_socket() {
+        let dir = std::env::temp_dir().join(format!("duelsplus-ctl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("control.sock");
+        let _ = std::fs::remove_file(&path);
+
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let accept = tokio::spawn(async move { listener.accept().await.is_ok() });
+
+        let stream = connect(path.to_str(), None).await;
+        assert!(stream.is_some());
+        assert!(accept.await.unwrap());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_connect_falls_back_to_tcp() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let stream = connect(Some("/nonexistent/duelsplus-control.sock"), Some(port)).await;
+        assert!(stream.is_some());
+    }
+}
@@ -0,0 +1,96 @@
+//! Player avatars for the Discord small image.
+//!
+//! Discord only loads images from URLs, so the activity keeps pointing at
+//! mc-heads.net. The avatar is downloaded to `avatars/` in the cache
+//! directory first: a successful download (or a recent cached copy) means the URL is
+//! known to work, and the small image is skipped instead of showing a broken
+//! image when the service is down.
+
+use crate::cache::{self, CacheCategory};
+use crate::mc::mojang;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How long a cached avatar is trusted before it is downloaded again
+const CACHE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Timeout for downloading an avatar
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// URL Discord loads the avatar from
+pub(crate) fn avatar_url(uuid: &str) -> String {
+    format!("https://mc-heads.net/avatar/{}/64.png", uuid)
+}
+
+/// Gets the cache path for a player's avatar
+fn cache_path(uuid: &str) -> Option<PathBuf> {
+    // UUIDs come from the proxy; keep anything else out of the path, and
+    // cache dashed and undashed UUIDs under the same name
+    let uuid = mojang::dashed(uuid)?;
+    cache::dir(CacheCategory::Avatars)
+        .ok()
+        .map(|dir| dir.join(format!("{}.png", uuid)))
+}
+
+/// Whether a cached file is recent enough to skip downloading
+fn is_fresh(path: &std::path::Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CACHE_MAX_AGE)
+}
+
+/// Makes sure the player's avatar is cached, downloading it if missing or
+/// stale.
+///
+/// # Returns
+/// `true` if the avatar is available, i.e. Discord can be pointed at it
+pub(crate) async fn ensure_cached(uuid: &str) -> bool {
+    let Some(path) = cache_path(uuid) else {
+        return false;
+    };
+    if is_fresh(&path) {
+        return true;
+    }
+
+    match download(uuid).await {
+        Ok(bytes) => {
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            if let Err(e) = std::fs::write(&path, bytes) {
+                eprintln!("[rpc] Failed to cache avatar: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("[rpc] Failed to fetch avatar: {}", e);
+            false
+        }
+    }
+}
+
+/// Downloads the avatar image
+async fn download(uuid: &str) -> Result<Vec<u8>, reqwest::Error> {
+    let response = reqwest::Client::new()
+        .get(avatar_url(uuid))
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_path_rejects_unsafe_uuids() {
+        assert!(cache_path("../../etc/passwd").is_none());
+        assert!(cache_path("").is_none());
+        let path = cache_path("069a79f4-44e9-4726-a5be-fca90e38aaf5").unwrap();
+        assert!(path.ends_with("cache/avatars/069a79f4-44e9-4726-a5be-fca90e38aaf5.png"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Translations for the text shown in the Discord activity.
+//!
+//! Game, mode and location names are proper nouns on Hypixel and stay in
+//! English; only the surrounding phrases are translated. Templates use `{}`
+//! as the placeholder for the formatted name.
+
+/// Phrases used to build the Discord activity
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct RpcStrings {
+    pub launcher: &'static str,
+    pub launcher_dev: &'static str,
+    pub idle: &'static str,
+    pub launching: &'static str,
+    pub in_launcher: &'static str,
+    pub browsing_stats: &'static str,
+    /// "Signed in as {}", where `{}` is the Duels+ username
+    pub signed_in_as: &'static str,
+    pub afk: &'static str,
+    pub playing_on_hypixel: &'static str,
+    pub in_limbo: &'static str,
+    pub in_skyblock: &'static str,
+    pub in_hypixel_lobby: &'static str,
+    /// "In {}", where `{}` is a SkyBlock location
+    pub in_location: &'static str,
+    /// "Playing {}", where `{}` is a game mode
+    pub playing: &'static str,
+    /// "In {} Lobby", where `{}` is a gametype
+    pub in_lobby: &'static str,
+    /// "Spectating {}", where `{}` is a game mode
+    pub spectating: &'static str,
+    /// "{} win streak", where `{}` is the number of wins
+    pub win_streak: &'static str,
+    /// "Session: {wins}W {losses}L"
+    pub session_record: &'static str,
+    /// "Map: {}", where `{}` is the map name
+    pub map: &'static str,
+    pub view_stats: &'static str,
+    pub get_duelsplus: &'static str,
+}
+
+impl RpcStrings {
+    /// Fills a template's `{}` placeholder
+    pub fn fill(template: &str, value: &str) -> String {
+        template.replacen("{}", value, 1)
+    }
+}
+
+const EN: RpcStrings = RpcStrings {
+    launcher: "Duels+ Launcher",
+    launcher_dev: "Launcher (dev build)",
+    idle: "Idle",
+    launching: "Launching",
+    in_launcher: "In Launcher",
+    browsing_stats: "Browsing stats",
+    signed_in_as: "Signed in as {}",
+    afk: "AFK",
+    playing_on_hypixel: "Playing on Hypixel",
+    in_limbo: "In Limbo",
+    in_skyblock: "In SkyBlock",
+    in_hypixel_lobby: "In Hypixel Lobby",
+    in_location: "In {}",
+    playing: "Playing {}",
+    in_lobby: "In {} Lobby",
+    spectating: "Spectating {}",
+    win_streak: "{} win streak",
+    session_record: "Session: {wins}W {losses}L",
+    map: "Map: {}",
+    view_stats: "View my stats",
+    get_duelsplus: "Get Duels+",
+};
+
+const DE: RpcStrings = RpcStrings {
+    launcher: "Duels+ Launcher",
+    launcher_dev: "Launcher (Dev-Build)",
+    idle: "Inaktiv",
+    launching: "Wird gestartet",
+    in_launcher: "Im Launcher",
+    browsing_stats: "Sieht Statistiken an",
+    signed_in_as: "Angemeldet als {}",
+    afk: "AFK",
+    playing_on_hypixel: "Spielt auf Hypixel",
+    in_limbo: "Im Limbo",
+    in_skyblock: "In SkyBlock",
+    in_hypixel_lobby: "In der Hypixel-Lobby",
+    in_location: "In {}",
+    playing: "Spielt {}",
+    in_lobby: "In der {}-Lobby",
+    spectating: "Schaut {} zu",
+    win_streak: "{} Siege in Folge",
+    session_record: "Sitzung: {wins}S {losses}N",
+    map: "Karte: {}",
+    view_stats: "Meine Statistiken",
+    get_duelsplus: "Duels+ holen",
+};
+
+const ES: RpcStrings = RpcStrings {
+    launcher: "Duels+ Launcher",
+    launcher_dev: "Launcher (versión de desarrollo)",
+    idle: "Inactivo",
+    launching: "Iniciando",
+    in_launcher: "En el launcher",
+    browsing_stats: "Viendo estadísticas",
+    signed_in_as: "Conectado como {}",
+    afk: "AFK",
+    playing_on_hypixel: "Jugando en Hypixel",
+    in_limbo: "En Limbo",
+    in_skyblock: "En SkyBlock",
+    in_hypixel_lobby: "En el lobby de Hypixel",
+    in_location: "En {}",
+    playing: "Jugando {}",
+    in_lobby: "En el lobby de {}",
+    spectating: "Observando {}",
+    win_streak: "Racha de {} victorias",
+    session_record: "Sesión: {wins}V {losses}D",
+    map: "Mapa: {}",
+    view_stats: "Ver mis estadísticas",
+    get_duelsplus: "Obtener Duels+",
+};
+
+const FR: RpcStrings = RpcStrings {
+    launcher: "Duels+ Launcher",
+    launcher_dev: "Launcher (version de dev)",
+    idle: "Inactif",
+    launching: "Lancement",
+    in_launcher: "Dans le launcher",
+    browsing_stats: "Consulte les stats",
+    signed_in_as: "Connecté en tant que {}",
+    afk: "AFK",
+    playing_on_hypixel: "Joue sur Hypixel",
+    in_limbo: "Dans les Limbes",
+    in_skyblock: "Sur SkyBlock",
+    in_hypixel_lobby: "Dans le lobby Hypixel",
+    in_location: "Dans {}",
+    playing: "Joue à {}",
+    in_lobby: "Dans le lobby {}",
+    spectating: "Regarde {}",
+    win_streak: "{} victoires d'affilée",
+    session_record: "Session : {wins}V {losses}D",
+    map: "Carte : {}",
+    view_stats: "Voir mes stats",
+    get_duelsplus: "Obtenir Duels+",
+};
+
+const PT: RpcStrings = RpcStrings {
+    launcher: "Duels+ Launcher",
+    launcher_dev: "Launcher (versão de desenvolvimento)",
+    idle: "Inativo",
+    launching: "Iniciando",
+    in_launcher: "No launcher",
+    browsing_stats: "Vendo estatísticas",
+    signed_in_as: "Conectado como {}",
+    afk: "AFK",
+    playing_on_hypixel: "Jogando no Hypixel",
+    in_limbo: "No Limbo",
+    in_skyblock: "No SkyBlock",
+    in_hypixel_lobby: "No lobby do Hypixel",
+    in_location: "Em {}",
+    playing: "Jogando {}",
+    in_lobby: "No lobby de {}",
+    spectating: "Assistindo {}",
+    win_streak: "{} vitórias seguidas",
+    session_record: "Sessão: {wins}V {losses}D",
+    map: "Mapa: {}",
+    view_stats: "Ver minhas estatísticas",
+    get_duelsplus: "Baixar Duels+",
+};
+
+/// Locales with a translation, by primary language subtag
+const LOCALES: &[(&str, &RpcStrings)] = &[
+    ("en", &EN),
+    ("de", &DE),
+    ("es", &ES),
+    ("fr", &FR),
+    ("pt", &PT),
+];
+
+/// Returns the phrases for a locale such as `de` or `pt-BR`, falling back
+/// to English for unknown or empty locales
+pub(crate) fn strings_for(locale: &str) -> &'static RpcStrings {
+    let language = locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    LOCALES
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map(|(_, strings)| *strings)
+        .unwrap_or(&EN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings_for_region_and_case() {
+        assert_eq!(strings_for("de"), &DE);
+        assert_eq!(strings_for("pt-BR"), &PT);
+        assert_eq!(strings_for("FR_fr"), &FR);
+    }
+
+    #[test]
+    fn test_strings_for_falls_back_to_english() {
+        assert_eq!(strings_for(""), &EN);
+        assert_eq!(strings_for("xx"), &EN);
+    }
+
+    #[test]
+    fn test_templates_have_placeholders() {
+        for (_, strings) in LOCALES {
+            for template in [
+                strings.in_location,
+                strings.playing,
+                strings.in_lobby,
+                strings.spectating,
+                strings.signed_in_as,
+                strings.win_streak,
+                strings.map,
+            ] {
+                assert!(template.contains("{}"), "missing placeholder: {}", template);
+            }
+            assert!(strings.session_record.contains("{wins}"));
+            assert!(strings.session_record.contains("{losses}"));
+        }
+        assert_eq!(RpcStrings::fill(EN.in_lobby, "Duels"), "In Duels Lobby");
+    }
+}
@@ -0,0 +1,276 @@
+//! Discord IPC connection that picks which Discord client to talk to.
+//!
+//! Discord, Discord PTB and Discord Canary each open their own
+//! `discord-ipc-N` pipe, numbered in start order. `DiscordIpcClient` always
+//! takes the first pipe it finds, so with several clients running the
+//! presence can end up on the wrong one. `PipeClient` handshakes with each
+//! pipe in turn, reads which client answered from its `READY` payload and
+//! keeps the preferred one, falling back to the first client found.
+
+use crate::config::models::DiscordClient;
+use discord_rich_presence::DiscordIpc;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[cfg(unix)]
+type Socket = std::os::unix::net::UnixStream;
+#[cfg(windows)]
+type Socket = std::fs::File;
+
+/// Number of `discord-ipc-N` pipes Discord clients may open
+const MAX_PIPES: u32 = 10;
+
+/// Environment variables naming the directory the pipes live in
+#[cfg(unix)]
+const ENV_KEYS: [&str; 4] = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"];
+
+/// Subdirectories used by sandboxed (Flatpak, Snap) installs
+#[cfg(unix)]
+const APP_SUBPATHS: [&str; 4] = [
+    "",
+    "app/com.discordapp.Discord/",
+    "snap.discord-canary/",
+    "snap.discord/",
+];
+
+/// Pipe paths to try, in the order Discord assigns them
+#[cfg(unix)]
+fn pipe_paths() -> Vec<PathBuf> {
+    let base = ENV_KEYS
+        .iter()
+        .find_map(|key| std::env::var(key).ok())
+        .map(PathBuf::from)
+        .unwrap_or_default();
+
+    let mut paths = Vec::new();
+    for i in 0..MAX_PIPES {
+        for subpath in APP_SUBPATHS {
+            paths.push(base.join(subpath).join(format!("discord-ipc-{}", i)));
+        }
+    }
+    paths
+}
+
+/// Pipe paths to try, in the order Discord assigns them
+#[cfg(windows)]
+fn pipe_paths() -> Vec<PathBuf> {
+    (0..MAX_PIPES)
+        .map(|i| PathBuf::from(format!(r"\\?\pipe\discord-ipc-{}", i)))
+        .collect()
+}
+
+#[cfg(unix)]
+fn open(path: &Path) -> std::io::Result<Socket> {
+    Socket::connect(path)
+}
+
+#[cfg(windows)]
+fn open(path: &Path) -> std::io::Result<Socket> {
+    use std::os::windows::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new().access_mode(0x3).open(path)
+}
+
+#[cfg(unix)]
+fn wait_readable(socket: &Socket, timeout: Duration) -> Result<bool> {
+    use std::os::fd::AsRawFd;
+
+    let mut fd = libc::pollfd {
+        fd: socket.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+    // SAFETY: `fd` is one valid pollfd for the duration of the call
+    match unsafe { libc::poll(&mut fd, 1, ms) } {
+        -1 => Err(std::io::Error::last_os_error().into()),
+        // A hang-up is readable too, the read then reports it
+        ready => Ok(ready > 0),
+    }
+}
+
+#[cfg(windows)]
+fn wait_readable(socket: &Socket, timeout: Duration) -> Result<bool> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::System::Pipes::PeekNamedPipe;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        let mut available = 0u32;
+        // SAFETY: the handle is an open pipe, and only the count is written
+        let ok = unsafe {
+            PeekNamedPipe(
+                socket.as_raw_handle(),
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                &mut available,
+                std::ptr::null_mut(),
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        if available > 0 {
+            return Ok(true);
+        }
+        if std::time::Instant::now() >= deadline {
+            return Ok(false);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Tells which Discord client sent a `READY` payload from its API endpoint
+/// (`//discord.com/api`, `//ptb.discord.com/api`, ...)
+pub(crate) fn client_from_ready(ready: &Value) -> Option<DiscordClient> {
+    let endpoint = ready["data"]["config"]["api_endpoint"].as_str()?;
+    Some(if endpoint.contains("ptb.") {
+        DiscordClient::Ptb
+    } else if endpoint.contains("canary.") {
+        DiscordClient::Canary
+    } else {
+        DiscordClient::Stable
+    })
+}
+
+/// IPC client connecting to the preferred Discord client
+#[derive(Debug)]
+pub(crate) struct PipeClient {
+    client_id: String,
+    preferred: DiscordClient,
+    socket: Option<Socket>,
+}
+
+impl PipeClient {
+    pub fn new(client_id: &str, preferred: DiscordClient) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            preferred,
+            socket: None,
+        }
+    }
+
+    /// Handshakes on the current socket, returning which client answered
+    fn handshake(&mut self) -> Result<Option<DiscordClient>> {
+        self.send(
+            json!({
+                "v": 1,
+                "client_id": self.client_id,
+            }),
+            0,
+        )?;
+        let (_, ready) = self.recv()?;
+        Ok(client_from_ready(&ready))
+    }
+
+    /// Waits up to `timeout` for something to read, so a reader can stop
+    /// in between instead of blocking on an idle pipe
+    pub fn wait_readable(&mut self, timeout: Duration) -> Result<bool> {
+        wait_readable(self.socket()?, timeout)
+    }
+
+    fn socket(&mut self) -> Result<&mut Socket> {
+        self.socket.as_mut().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "Couldn't retrieve the Discord IPC socket",
+            )
+            .into()
+        })
+    }
+}
+
+impl DiscordIpc for PipeClient {
+    fn connect_ipc(&mut self) -> Result<()> {
+        let mut fallback = None;
+
+        for path in pipe_paths() {
+            let Ok(socket) = open(&path) else {
+                continue;
+            };
+            self.socket = Some(socket);
+
+            match self.handshake() {
+                Ok(client)
+                    if self.preferred == DiscordClient::Auto || client == Some(self.preferred) =>
+                {
+                    return Ok(());
+                }
+                // Keep the first client that answers in case the preferred
+                // one isn't running
+                Ok(_) if fallback.is_none() => fallback = self.socket.take(),
+                _ => self.socket = None,
+            }
+        }
+
+        self.socket = fallback;
+        if self.socket.is_some() {
+            Ok(())
+        } else {
+            Err("Couldn't connect to the Discord IPC socket".into())
+        }
+    }
+
+    /// The handshake already happened while choosing the pipe
+    fn send_handshake(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        self.socket()?.write_all(data)?;
+        Ok(())
+    }
+
+    fn read(&mut self, buffer: &mut [u8]) -> Result<()> {
+        self.socket()?.read_exact(buffer)?;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let _ = self.send(json!({}), 2);
+        if let Some(mut socket) = self.socket.take() {
+            socket.flush()?;
+        }
+        Ok(())
+    }
+
+    fn get_client_id(&self) -> &String {
+        &self.client_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_from_ready() {
+        let ready = |endpoint: &str| {
+            json!({
+                "cmd": "DISPATCH",
+                "evt": "READY",
+                "data": { "config": { "api_endpoint": endpoint } },
+            })
+        };
+        assert_eq!(
+            client_from_ready(&ready("//discord.com/api")),
+            Some(DiscordClient::Stable)
+        );
+        assert_eq!(
+            client_from_ready(&ready("//ptb.discord.com/api")),
+            Some(DiscordClient::Ptb)
+        );
+        assert_eq!(
+            client_from_ready(&ready("//canary.discord.com/api")),
+            Some(DiscordClient::Canary)
+        );
+        assert_eq!(client_from_ready(&json!({ "evt": "READY" })), None);
+    }
+}
@@ -0,0 +1,179 @@
+//! Discord "Ask to Join" support.
+//!
+//! While the player is in a Hypixel party (reported by the proxy), the
+//! activity carries a join secret naming that party. When a friend's request
+//! is accepted, Discord hands the secret to the friend's launcher as an
+//! `ACTIVITY_JOIN` event, which relays the party id to its own proxy so the
+//! party invite can be arranged in game.
+//!
+//! `discord-rich-presence` has no event API, and the worker's client is
+//! never read from, so events are received on a second IPC connection that
+//! only subscribes to `ACTIVITY_JOIN`.
+
+use super::ipc::PipeClient;
+use crate::config::models::DiscordClient;
+use discord_rich_presence::DiscordIpc;
+use serde_json::json;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Prefix marking join secrets generated by the launcher
+const JOIN_SECRET_PREFIX: &str = "duelsplus-party:";
+
+/// Delay between attempts to (re)open the event connection
+const LISTENER_RETRY_SECS: u64 = 10;
+
+/// How often the listener checks whether to stop while the connection is
+/// idle
+const LISTENER_POLL: Duration = Duration::from_millis(500);
+
+/// Hypixel party the player is in, as reported by the proxy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Party {
+    pub id: String,
+    pub size: u32,
+    pub max: u32,
+}
+
+/// Builds the join secret advertised for a party
+pub(crate) fn join_secret(party_id: &str) -> String {
+    format!("{}{}", JOIN_SECRET_PREFIX, party_id)
+}
+
+/// Extracts the party id from a join secret, ignoring foreign secrets
+pub(crate) fn parse_join_secret(secret: &str) -> Option<&str> {
+    secret
+        .strip_prefix(JOIN_SECRET_PREFIX)
+        .filter(|id| !id.is_empty())
+}
+
+/// Called with the party id of every accepted join
+pub(crate) type JoinHandler = Arc<dyn Fn(String) + Send + Sync>;
+
+/// The thread listening for `ACTIVITY_JOIN` events
+pub(crate) struct Listener {
+    stop: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl Listener {
+    /// Stops the thread, which closes its connection within
+    /// [`LISTENER_POLL`], and waits for it
+    pub(crate) fn stop(self) {
+        drop(self.stop);
+        let _ = self.thread.join();
+    }
+}
+
+/// Spawns the thread that listens for `ACTIVITY_JOIN` events, until
+/// [`Listener::stop`].
+///
+/// `current_target` returns the Discord application to listen as and the
+/// Discord client to listen on, or `None` while RPC is disabled; the
+/// connection is reopened whenever it changes.
+pub(crate) fn spawn_listener<F>(
+    current_target: F,
+    handler: Arc<Mutex<Option<JoinHandler>>>,
+) -> Listener
+where
+    F: Fn() -> Option<(String, DiscordClient)> + Send + 'static,
+{
+    let (stop, stopped) = mpsc::channel();
+    let thread = thread::spawn(move || loop {
+        if let Some(target) = current_target() {
+            if let Ok(mut client) = subscribe(&target) {
+                listen(&mut client, &target, &current_target, &handler, &stopped);
+                let _ = client.close();
+            }
+        }
+
+        match stopped.recv_timeout(Duration::from_secs(LISTENER_RETRY_SECS)) {
+            Err(RecvTimeoutError::Timeout) => {}
+            _ => return,
+        }
+    });
+    Listener { stop, thread }
+}
+
+/// Whether [`Listener::stop`] was called
+fn should_stop(stopped: &Receiver<()>) -> bool {
+    !matches!(stopped.try_recv(), Err(TryRecvError::Empty))
+}
+
+/// Opens an event connection subscribed to `ACTIVITY_JOIN`
+fn subscribe(
+    (client_id, discord_client): &(String, DiscordClient),
+) -> Result<PipeClient, Box<dyn std::error::Error>> {
+    let mut client = PipeClient::new(client_id, *discord_client);
+    client.connect()?;
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string();
+    client.send(
+        json!({
+            "cmd": "SUBSCRIBE",
+            "evt": "ACTIVITY_JOIN",
+            "nonce": nonce,
+        }),
+        1,
+    )?;
+    Ok(client)
+}
+
+/// Dispatches join events until the connection breaks, no longer matches
+/// the application and client to listen on, or the listener stops
+fn listen(
+    client: &mut PipeClient,
+    target: &(String, DiscordClient),
+    current_target: &impl Fn() -> Option<(String, DiscordClient)>,
+    handler: &Arc<Mutex<Option<JoinHandler>>>,
+    stopped: &Receiver<()>,
+) {
+    loop {
+        if should_stop(stopped) || current_target().as_ref() != Some(target) {
+            return;
+        }
+        match client.wait_readable(LISTENER_POLL) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(_) => return,
+        }
+        let Ok((_, data)) = client.recv() else {
+            return;
+        };
+
+        if data["cmd"] != "DISPATCH" || data["evt"] != "ACTIVITY_JOIN" {
+            continue;
+        }
+        let Some(party_id) = data["data"]["secret"].as_str().and_then(parse_join_secret) else {
+            continue;
+        };
+
+        let handler = handler.lock().unwrap().clone();
+        if let Some(handler) = handler {
+            handler(party_id.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_secret_round_trip() {
+        let secret = join_secret("abc-123");
+        assert_ne!(secret, "abc-123");
+        assert_eq!(parse_join_secret(&secret), Some("abc-123"));
+    }
+
+    #[test]
+    fn test_parse_join_secret_rejects_foreign_secrets() {
+        assert_eq!(parse_join_secret("abc-123"), None);
+        assert_eq!(parse_join_secret(JOIN_SECRET_PREFIX), None);
+    }
+}
@@ -1,18 +1,66 @@
 //! Discord RPC manager for activity presence.
 //!
 //! Manages the Discord Rich Presence connection and activity updates.
-//! Uses a background thread to handle the Discord IPC connection since
-//! the discord-rich-presence crate uses blocking I/O.
-
-use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
-use std::sync::mpsc::{self, Sender};
+//! Runs as an async task on the Tauri runtime; since the
+//! discord-rich-presence crate uses blocking I/O, every IPC call is moved
+//! onto the blocking pool.
+
+use super::avatar;
+use super::i18n::{self, RpcStrings};
+use super::ipc::PipeClient;
+use super::join::{self, JoinHandler, Party};
+use super::names;
+use super::session::{SessionSummary, SessionTracker};
+use crate::config::models::{DiscordClient, RpcTimerMode};
+use discord_rich_presence::{activity, DiscordIpc};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::async_runtime::JoinHandle;
+use tokio::sync::mpsc::{self, UnboundedSender};
 
 /// Discord Application Client ID for Duels+
 const CLIENT_ID: &str = "1391866803889770526";
 
+/// Delay before the first retry when Discord isn't reachable
+const RECONNECT_INITIAL_SECS: u64 = 2;
+
+/// Upper bound for the reconnect backoff
+const RECONNECT_MAX_SECS: u64 = 60;
+
+/// Minimum time between two activity updates sent to Discord
+const ACTIVITY_UPDATE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long `shutdown_and_wait` waits for the worker to close the connection
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often an idle lobby session is checked for AFK
+const AFK_CHECK_SECS: u64 = 30;
+
+/// How long `preview` shows its sample game before restoring the real activity
+const PREVIEW_DURATION: Duration = Duration::from_secs(30);
+
+/// Sample game shown by a preview when no mode is given
+const PREVIEW_MODE: &str = "DUELS_CLASSIC_DUEL";
+const PREVIEW_MAP: &str = "Arena";
+const PREVIEW_GAMETYPE: &str = "DUELS";
+
+/// Placeholder mode while the game is starting
+const LAUNCHING_MODE: &str = "Launching";
+
+/// Website linked by the "Get Duels+" activity button
+const WEBSITE_URL: &str = "https://duelsplus.com";
+
+/// Player stats page linked by the "View my stats" button, followed by the IGN
+const STATS_URL_BASE: &str = "https://plancke.io/hypixel/player/stats/";
+
+/// Artwork asset keys shown as the large image while playing a gametype
+const GAMETYPE_IMAGES: &[(&str, &str)] = &[
+    ("DUELS", "game-duels"),
+    ("BEDWARS", "game-bedwars"),
+    ("SKYWARS", "game-skywars"),
+];
+
 /// Valid Discord RPC image asset keys
 pub const VALID_IMAGE_KEYS: &[&str] = &[
     "logo-emerald",
@@ -33,35 +81,15 @@ pub const VALID_IMAGE_KEYS: &[&str] = &[
     "nerd",
 ];
 
-/// Commands sent to the RPC worker thread
+/// Commands sent to the RPC worker task
 #[derive(Debug, Clone)]
 enum RpcCommand {
     /// Connect to Discord
     Connect,
     /// Disconnect from Discord
     Disconnect,
-    /// Set activity to "In Launcher"
-    SetInLauncher,
-    /// Set activity to "Launching"
-    SetLaunching,
-    /// Set activity to "Playing" (basic, no user data)
-    SetPlaying,
-    /// Update user data (ign, uuid)
-    SetUserData {
-        ign: Option<String>,
-        uuid: Option<String>,
-    },
-    /// Update game mode
-    SetGameMode {
-        mode: Option<String>,
-        map: Option<String>,
-        gametype: Option<String>,
-        lobbyname: Option<String>,
-    },
-    /// User disconnected from Hypixel
-    SetDisconnected,
-    /// Clear activity (reset to base)
-    ClearActivity,
+    /// Merge player/game state changes in one step
+    ApplyStateUpdate(StatePatch),
     /// Update anonymization settings
     SetAnonymization {
         anonymize_profile: bool,
@@ -69,16 +97,303 @@ enum RpcCommand {
     },
     /// Set custom image asset key
     SetImage { image_key: String },
-    /// Shutdown the RPC thread
+    /// Show or hide the activity buttons
+    SetButtons { enabled: bool },
+    /// Show or hide the session win streak / record
+    SetShowStreak { enabled: bool },
+    /// Show or hide the Duels+ profile while in the launcher
+    SetShowLauncherProfile { enabled: bool },
+    /// The session stats changed
+    SessionStats {
+        wins: u32,
+        losses: u32,
+        win_streak: u32,
+    },
+    /// The avatar download for a player finished (internal)
+    AvatarChecked { uuid: String, available: bool },
+    /// Change what the elapsed timer counts
+    SetTimerMode { mode: RpcTimerMode },
+    /// Switch to another Discord application, reconnecting if needed
+    SetClientId { client_id: Option<String> },
+    /// Switch to another Discord client (stable, PTB, Canary), reconnecting if needed
+    SetDiscordClient { discord_client: DiscordClient },
+    /// Change how long a lobby session may idle before showing AFK
+    SetAfkTimeout { timeout_secs: u64 },
+    /// Player activity (e.g. a game event) that resets the AFK timer
+    Activity,
+    /// Show a frontend-supplied activity instead of the automatic one
+    /// (`None` returns to automatic updates)
+    SetCustomActivity { activity: Option<CustomActivity> },
+    /// Update the Hypixel party offered through "Ask to Join"
+    SetParty { party: Option<Party> },
+    /// Start (`Some` mode) or stop spectating a game or watching a replay
+    SetSpectating { target: Option<String> },
+    /// Show a sample game for `PREVIEW_DURATION` (`None` ends the preview)
+    SetPreview { preview: Option<RpcPreview> },
+    /// Change the language of the activity text
+    SetLocale { locale: String },
+    /// Shutdown the RPC worker
     Shutdown,
+    /// The worker's wait timed out (internal, never sent)
+    Tick,
+}
+
+/// Where the player is, as far as the activity is concerned
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Location {
+    /// Not in a game or lobby
+    Nowhere,
+    /// The game is starting
+    Launching,
+    /// In a game or lobby, as reported by the proxy
+    Game {
+        mode: Option<String>,
+        map: Option<String>,
+        gametype: Option<String>,
+        lobbyname: Option<String>,
+        /// Expected end of a timed game, as a unix timestamp
+        ends_at: Option<i64>,
+    },
+}
+
+/// Signed-in Duels+ user and what they are doing in the launcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LauncherProfile {
+    username: String,
+    browsing_stats: bool,
+}
+
+/// Changes to the player and game state.
+///
+/// The worker merges a patch in one step, so updates that belong together
+/// (e.g. "playing" plus the player's name) never show up half-applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct StatePatch {
+    /// Connected to Hypixel, or back in the launcher
+    is_playing: Option<bool>,
+    /// Forget the player before applying `ign` and `uuid`
+    clear_player: bool,
+    /// What the user is doing in the launcher; `None` keeps the current value
+    launcher_profile: Option<LauncherProfile>,
+    /// New player name and UUID; `None` keeps the current value
+    ign: Option<String>,
+    uuid: Option<String>,
+    /// New location; `None` keeps the current one
+    location: Option<Location>,
+    /// Leave the party and stop spectating
+    clear_session: bool,
+    /// Push the activity; `false` waits for the data that follows
+    refresh: bool,
+}
+
+impl StatePatch {
+    fn in_launcher() -> Self {
+        Self {
+            is_playing: Some(false),
+            location: Some(Location::Nowhere),
+            refresh: true,
+            ..Default::default()
+        }
+    }
+
+    /// In the launcher as a signed-in Duels+ user
+    fn in_launcher_with_profile(profile: LauncherProfile) -> Self {
+        Self {
+            launcher_profile: Some(profile),
+            ..Self::in_launcher()
+        }
+    }
+
+    fn launching() -> Self {
+        Self {
+            is_playing: Some(false),
+            location: Some(Location::Launching),
+            refresh: true,
+            ..Default::default()
+        }
+    }
+
+    /// A new game session; only pushed once the player is known
+    fn playing(ign: Option<String>, uuid: Option<String>) -> Self {
+        Self {
+            is_playing: Some(true),
+            refresh: ign.is_some() || uuid.is_some(),
+            ign,
+            uuid,
+            location: Some(Location::Nowhere),
+            ..Default::default()
+        }
+    }
+
+    /// The proxy reported the player; keeps any game mode that arrived first
+    fn user_data(ign: Option<String>, uuid: Option<String>) -> Self {
+        Self {
+            is_playing: Some(true),
+            ign,
+            uuid,
+            refresh: true,
+            ..Default::default()
+        }
+    }
+
+    fn game_mode(location: Location) -> Self {
+        Self {
+            location: Some(location),
+            refresh: true,
+            ..Default::default()
+        }
+    }
+
+    /// Left Hypixel, keeping the player for the next connection
+    fn disconnected() -> Self {
+        Self {
+            is_playing: Some(false),
+            location: Some(Location::Nowhere),
+            clear_session: true,
+            refresh: true,
+            ..Default::default()
+        }
+    }
+
+    /// Back to the base activity, forgetting the player
+    fn cleared() -> Self {
+        Self {
+            clear_player: true,
+            ..Self::disconnected()
+        }
+    }
 }
 
+/// Maximum length of the details and state lines
+const MAX_TEXT_LEN: usize = 128;
+
+/// Maximum length of a button label
+const MAX_BUTTON_LABEL_LEN: usize = 32;
+
+/// Sample game shown by a preview, rendered with the user's real settings
+/// (privacy, image, buttons, language) so they can check how it looks.
+/// Missing fields use a Classic Duel on Arena.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPreview {
+    pub mode: Option<String>,
+    pub map: Option<String>,
+    pub gametype: Option<String>,
+    /// Player name to show, defaults to the signed-in player
+    pub ign: Option<String>,
+}
+
+/// A button on a custom activity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomButton {
+    pub label: String,
+    pub url: String,
+}
+
+/// Activity supplied by the frontend through `rpc_set_activity`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomActivity {
+    pub details: Option<String>,
+    pub state: Option<String>,
+    /// Asset key from `VALID_IMAGE_KEYS` or an https URL
+    pub large_image: Option<String>,
+    pub large_text: Option<String>,
+    /// Asset key from `VALID_IMAGE_KEYS` or an https URL
+    pub small_image: Option<String>,
+    pub small_text: Option<String>,
+    #[serde(default)]
+    pub buttons: Vec<CustomButton>,
+    /// Unix timestamps (seconds) for the elapsed/remaining timer
+    pub start_timestamp: Option<i64>,
+    pub end_timestamp: Option<i64>,
+}
+
+impl CustomActivity {
+    /// Checks the activity against Discord's limits
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, text) in [("details", &self.details), ("state", &self.state)] {
+            if let Some(text) = text {
+                let len = text.chars().count();
+                if !(2..=MAX_TEXT_LEN).contains(&len) {
+                    return Err(format!(
+                        "Activity {} must be 2-{} characters",
+                        name, MAX_TEXT_LEN
+                    ));
+                }
+            }
+        }
+
+        for image in [&self.large_image, &self.small_image].into_iter().flatten() {
+            if !VALID_IMAGE_KEYS.contains(&image.as_str()) && !image.starts_with("https://") {
+                return Err(format!(
+                    "Invalid image '{}', expected an asset key or https URL",
+                    image
+                ));
+            }
+        }
+
+        if self.buttons.len() > 2 {
+            return Err("An activity can have at most 2 buttons".to_string());
+        }
+        for button in &self.buttons {
+            if button.label.is_empty() || button.label.chars().count() > MAX_BUTTON_LABEL_LEN {
+                return Err(format!(
+                    "Button labels must be 1-{} characters",
+                    MAX_BUTTON_LABEL_LEN
+                ));
+            }
+            if !button.url.starts_with("https://") && !button.url.starts_with("http://") {
+                return Err(format!("Invalid button URL '{}'", button.url));
+            }
+        }
+
+        if let (Some(start), Some(end)) = (self.start_timestamp, self.end_timestamp) {
+            if end < start {
+                return Err("Activity end timestamp is before its start".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Connection state reported through `rpc-status` events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcConnection {
+    Connected,
+    #[default]
+    Disconnected,
+    /// Discord couldn't be reached (e.g. it isn't running)
+    Failed,
+    /// Connected, but updates are being held back by the rate limit
+    RateLimited,
+}
+
+/// Payload of the `rpc-status` event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStatus {
+    pub status: RpcConnection,
+    /// Human-readable explanation, e.g. "Discord not detected"
+    pub reason: Option<String>,
+}
+
+/// Called with every change of the connection status
+pub(crate) type StatusHandler = Arc<dyn Fn(RpcStatus) + Send + Sync>;
+
 /// Discord RPC state
 #[derive(Debug, Clone, Default)]
 struct RpcState {
     connected: bool,
     enabled: bool,
     start_timestamp: i64,
+    /// When the current game started, `None` outside of games
+    game_start_timestamp: Option<i64>,
+    /// When the current game is expected to end, for timed modes
+    game_end_timestamp: Option<i64>,
+    timer_mode: RpcTimerMode,
     current_ign: Option<String>,
     current_uuid: Option<String>,
     current_mode: Option<String>,
@@ -92,13 +407,338 @@ struct RpcState {
     anonymize_location: bool,
     /// Custom image asset key (None = use default based on dev mode)
     custom_image: Option<String>,
+    /// Whether to show the stats and website buttons
+    show_buttons: bool,
+    /// Custom Discord application ID (None = `CLIENT_ID`)
+    client_id: Option<String>,
+    /// Discord client to connect to when several are running
+    discord_client: DiscordClient,
+    /// Seconds idle in a lobby before showing AFK (0 = never)
+    afk_timeout_secs: u64,
+    /// Last player activity seen, as a unix timestamp
+    last_activity: i64,
+    /// Whether the activity currently shows AFK
+    afk: bool,
+    /// Frontend-supplied activity overriding the automatic one
+    custom_activity: Option<CustomActivity>,
+    /// Hypixel party the player is in, offered through "Ask to Join"
+    party: Option<Party>,
+    /// Mode being spectated or replayed, if any
+    spectating: Option<String>,
+    /// Sample game shown instead of the real state until `preview_until`
+    preview: Option<RpcPreview>,
+    preview_until: Option<Instant>,
+    /// Language of the activity text (empty = English)
+    locale: String,
+    /// Connection status last reported to the frontend
+    status: RpcStatus,
+    /// Whether to show the session win streak / record while playing Duels
+    show_streak: bool,
+    /// Whether to show the Duels+ profile while in the launcher
+    show_launcher_profile: bool,
+    /// Signed-in Duels+ user, shown in the launcher if enabled
+    launcher_profile: Option<LauncherProfile>,
+    /// Games won and lost since the proxy launched
+    session_wins: u32,
+    session_losses: u32,
+    /// Consecutive wins, reset by a loss
+    win_streak: u32,
+    /// Player whose avatar is known to load, so it can be the small image
+    avatar_uuid: Option<String>,
+    /// Set once the manager shuts down, so the join listener stops reconnecting
+    shut_down: bool,
+    /// Time in game and per mode, for the session recap
+    session: SessionTracker,
+}
+
+/// Serializable snapshot of the RPC state, returned by `rpc_get_state`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStateSnapshot {
+    pub connected: bool,
+    pub enabled: bool,
+    pub ign: Option<String>,
+    pub uuid: Option<String>,
+    pub mode: Option<String>,
+    pub map: Option<String>,
+    pub gametype: Option<String>,
+    pub in_lobby: bool,
+    pub is_playing: bool,
+    /// Mode being spectated or replayed, if any
+    pub spectating: Option<String>,
+    pub anonymize_profile: bool,
+    pub anonymize_location: bool,
+    pub custom_image: Option<String>,
+    pub show_buttons: bool,
+    pub timer_mode: RpcTimerMode,
+    pub locale: String,
+    /// Launcher start, as a unix timestamp
+    pub session_start: i64,
+    /// Start of the current game, if in one
+    pub game_start: Option<i64>,
+    /// Expected end of the current game, for timed modes
+    pub game_end: Option<i64>,
+    /// Details line as currently shown on Discord
+    pub details: String,
+    /// State line as currently shown on Discord
+    pub state: Option<String>,
+    pub show_streak: bool,
+    pub show_launcher_profile: bool,
+    pub session_wins: u32,
+    pub session_losses: u32,
+    pub win_streak: u32,
+    /// Start of the elapsed timer as currently shown on Discord
+    pub timer_start: i64,
+    /// Custom activity overriding the automatic one, if set
+    pub custom_activity: Option<CustomActivity>,
+    /// Whether a preview is shown instead of the real activity
+    pub previewing: bool,
+    pub status: RpcStatus,
+}
+
+/// Text and images shown in the Discord activity, derived from `RpcState`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ActivityContent {
+    details: String,
+    large_image: String,
+    large_text: String,
+    /// Player avatar URL, hidden when the profile is anonymized
+    small_image: Option<String>,
+    /// Player IGN, hidden when the profile is anonymized
+    small_text: Option<String>,
+    /// Activity buttons as (label, url), at most two
+    buttons: Vec<(String, String)>,
+    /// Start of the elapsed timer, as a unix timestamp
+    start_timestamp: i64,
+    /// End of a timed game; Discord then counts down instead of up
+    end_timestamp: Option<i64>,
+    /// Second line, e.g. the session win streak
+    state: Option<String>,
+    /// Party offered through "Ask to Join"; Discord doesn't allow buttons
+    /// alongside a join secret, so `buttons` is empty when this is set
+    party: Option<Party>,
+}
+
+impl RpcState {
+    /// Merges a state patch.
+    ///
+    /// # Returns
+    /// The UUID of a newly seen player, whose avatar still has to be checked
+    fn apply(&mut self, patch: StatePatch, now: i64) -> Option<String> {
+        if let Some(is_playing) = patch.is_playing {
+            self.is_playing = is_playing;
+            if is_playing {
+                self.touch(now);
+                // The real mode comes from the proxy's game mode message
+                if self.current_mode.as_deref() == Some(LAUNCHING_MODE) {
+                    self.current_mode = None;
+                    self.game_start_timestamp = None;
+                }
+            }
+        }
+
+        if patch.clear_player {
+            self.current_ign = None;
+            self.current_uuid = None;
+            self.launcher_profile = None;
+        }
+        if patch.launcher_profile.is_some() {
+            self.launcher_profile = patch.launcher_profile;
+        }
+        if patch.ign.is_some() {
+            self.current_ign = patch.ign;
+        }
+        let mut new_uuid = None;
+        if let Some(uuid) = patch.uuid {
+            if self.avatar_uuid.as_ref() != Some(&uuid) {
+                self.avatar_uuid = None;
+                new_uuid = Some(uuid.clone());
+            }
+            self.current_uuid = Some(uuid);
+        }
+
+        match patch.location {
+            Some(Location::Nowhere) => {
+                self.current_mode = None;
+                self.current_map = None;
+                self.current_gametype = None;
+                self.in_lobby = false;
+                self.game_start_timestamp = None;
+                self.game_end_timestamp = None;
+            }
+            Some(Location::Launching) => {
+                self.current_mode = Some(LAUNCHING_MODE.to_string());
+                self.current_map = None;
+                self.game_start_timestamp = None;
+                self.game_end_timestamp = None;
+            }
+            Some(Location::Game {
+                mode,
+                map,
+                gametype,
+                lobbyname,
+                ends_at,
+            }) => {
+                self.apply_game_mode(mode, map, gametype, lobbyname, now);
+                // A countdown only makes sense for a game still running
+                self.game_end_timestamp =
+                    ends_at.filter(|&end| end > now && self.game_start_timestamp.is_some());
+            }
+            None => {}
+        }
+
+        if patch.clear_session {
+            self.party = None;
+            self.spectating = None;
+        }
+
+        let mode = self
+            .current_mode
+            .as_deref()
+            .filter(|mode| *mode != LAUNCHING_MODE);
+        self.session.observe(self.is_playing, mode, now);
+
+        new_uuid
+    }
+
+    /// Updates the connection status (and the `connected` flag with it)
+    fn set_status(&mut self, status: RpcConnection, reason: Option<&str>) {
+        self.connected = matches!(
+            status,
+            RpcConnection::Connected | RpcConnection::RateLimited
+        );
+        self.status = RpcStatus {
+            status,
+            reason: reason.map(str::to_string),
+        };
+    }
+
+    /// Records a game mode update, restarting the game timer when a new game
+    /// (a different mode or map) begins and clearing it outside of games
+    fn apply_game_mode(
+        &mut self,
+        mode: Option<String>,
+        map: Option<String>,
+        gametype: Option<String>,
+        lobbyname: Option<String>,
+        now: i64,
+    ) {
+        self.touch(now);
+
+        if mode.is_none() {
+            self.game_start_timestamp = None;
+        } else if self.game_start_timestamp.is_none()
+            || mode != self.current_mode
+            || map != self.current_map
+        {
+            self.game_start_timestamp = Some(now);
+        }
+
+        self.current_mode = mode;
+        self.current_map = map;
+        self.current_gametype = gametype;
+        self.in_lobby = lobbyname.is_some();
+    }
+
+    /// Sets the session's record and win streak
+    fn set_session_stats(&mut self, wins: u32, losses: u32, win_streak: u32) {
+        self.session_wins = wins;
+        self.session_losses = losses;
+        self.win_streak = win_streak;
+    }
+
+    /// Records player activity, leaving AFK
+    fn touch(&mut self, now: i64) {
+        self.last_activity = now;
+        self.afk = false;
+    }
+
+    /// Whether the player is idle somewhere that can turn into AFK
+    fn watches_afk(&self) -> bool {
+        self.afk_timeout_secs > 0 && self.is_playing && self.current_mode.is_none() && !self.afk
+    }
+
+    /// Enters AFK once the idle timeout has passed; returns true if it did
+    fn update_afk(&mut self, now: i64) -> bool {
+        if self.watches_afk() && now - self.last_activity >= self.afk_timeout_secs as i64 {
+            self.afk = true;
+            return true;
+        }
+        false
+    }
+
+    /// Time left on the running preview, if any
+    fn preview_remaining(&self, now: Instant) -> Option<Duration> {
+        self.preview_until
+            .filter(|_| self.preview.is_some())
+            .map(|until| until.saturating_duration_since(now))
+    }
+
+    /// The state to render: the preview's sample game while it runs,
+    /// otherwise `None` for the real state
+    fn previewed(&self, now: Instant) -> Option<RpcState> {
+        if self.preview_remaining(now)?.is_zero() {
+            return None;
+        }
+        let preview = self.preview.as_ref()?;
+
+        let mut state = self.clone();
+        state.preview = None;
+        state.preview_until = None;
+        state.custom_activity = None;
+        state.is_playing = true;
+        state.afk = false;
+        state.in_lobby = false;
+        state.spectating = None;
+        state.current_mode = Some(preview.mode.clone().unwrap_or(PREVIEW_MODE.to_string()));
+        state.current_map = Some(preview.map.clone().unwrap_or(PREVIEW_MAP.to_string()));
+        state.current_gametype = Some(
+            preview
+                .gametype
+                .clone()
+                .unwrap_or(PREVIEW_GAMETYPE.to_string()),
+        );
+        if preview.ign.is_some() {
+            state.current_ign = preview.ign.clone();
+        }
+        state.game_start_timestamp = state.game_start_timestamp.or(Some(unix_now()));
+        state.game_end_timestamp = None;
+        Some(state)
+    }
+
+    /// Timestamp the elapsed timer counts from under the current timer mode
+    fn timer_start(&self) -> i64 {
+        match (self.timer_mode, self.game_start_timestamp) {
+            (RpcTimerMode::PerGame, Some(game_start)) => game_start,
+            _ => self.start_timestamp,
+        }
+    }
+}
+
+/// Current unix time in seconds
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
 }
 
-/// Manager for Discord Rich Presence
+/// Manager for Discord Rich Presence.
+///
+/// Updates are handled by an async worker task. The state stays behind a
+/// `std::sync::Mutex` because setters are called from both sync and async
+/// contexts and the lock is never held across an `.await` or IPC call.
 pub struct RpcManager {
-    sender: Mutex<Option<Sender<RpcCommand>>>,
+    sender: Mutex<Option<UnboundedSender<RpcCommand>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    /// Thread receiving "Ask to Join" events
+    join_listener: Mutex<Option<join::Listener>>,
     state: Arc<Mutex<RpcState>>,
     is_dev: Arc<Mutex<bool>>,
+    /// Receives accepted "Ask to Join" requests
+    join_handler: Arc<Mutex<Option<JoinHandler>>>,
+    /// Receives connection status changes
+    status_handler: Arc<Mutex<Option<StatusHandler>>>,
 }
 
 impl RpcManager {
@@ -111,6 +751,9 @@ impl RpcManager {
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs() as i64,
+            game_start_timestamp: None,
+            game_end_timestamp: None,
+            timer_mode: RpcTimerMode::Session,
             current_ign: None,
             current_uuid: None,
             current_mode: None,
@@ -121,12 +764,38 @@ impl RpcManager {
             anonymize_profile: false,
             anonymize_location: false,
             custom_image: None,
+            show_buttons: true,
+            client_id: None,
+            discord_client: DiscordClient::Auto,
+            afk_timeout_secs: 0,
+            last_activity: 0,
+            afk: false,
+            custom_activity: None,
+            party: None,
+            spectating: None,
+            preview: None,
+            preview_until: None,
+            locale: String::new(),
+            status: RpcStatus::default(),
+            show_streak: true,
+            show_launcher_profile: false,
+            launcher_profile: None,
+            session_wins: 0,
+            session_losses: 0,
+            win_streak: 0,
+            avatar_uuid: None,
+            shut_down: false,
+            session: SessionTracker::default(),
         }));
 
         Self {
             sender: Mutex::new(None),
+            worker: Mutex::new(None),
+            join_listener: Mutex::new(None),
             state,
             is_dev: Arc::new(Mutex::new(is_dev)),
+            join_handler: Arc::new(Mutex::new(None)),
+            status_handler: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -135,552 +804,555 @@ impl RpcManager {
         *self.is_dev.lock().unwrap() = is_dev;
     }
 
-    /// Starts the RPC worker thread
+    /// Starts the RPC worker task
     pub fn start(&self) {
         let mut sender_lock = self.sender.lock().unwrap();
         if sender_lock.is_some() {
             return; // Already started
         }
 
-        let (tx, rx) = mpsc::channel::<RpcCommand>();
+        let (tx, mut rx) = mpsc::unbounded_channel::<RpcCommand>();
+        let avatar_tx = tx.clone();
         let state = self.state.clone();
         let is_dev = self.is_dev.clone();
+        let status_handler = self.status_handler.clone();
+
+        // The join listener blocks on its IPC connection for as long as it
+        // is open, so it keeps a dedicated thread
+        let listener_state = self.state.clone();
+        let listener = join::spawn_listener(
+            move || {
+                let s = listener_state.lock().unwrap();
+                (s.enabled && !s.shut_down).then(|| {
+                    (
+                        s.client_id.clone().unwrap_or_else(|| CLIENT_ID.to_string()),
+                        s.discord_client,
+                    )
+                })
+            },
+            self.join_handler.clone(),
+        );
+        *self.join_listener.lock().unwrap() = Some(listener);
 
-        thread::spawn(move || {
-            let mut client: Option<DiscordIpcClient> = None;
+        let worker = tauri::async_runtime::spawn(async move {
+            let mut client: Option<PipeClient> = None;
             let mut should_run = true;
 
-            // Helper closure to attempt connection to Discord
-            // Returns true if connected (either already or newly)
-            let try_connect =
-                |client: &mut Option<DiscordIpcClient>, state: &Arc<Mutex<RpcState>>| -> bool {
-                    if client.is_some() {
-                        return true;
-                    }
+            let initial_delay = Duration::from_secs(RECONNECT_INITIAL_SECS);
+            let mut retry_delay = initial_delay;
 
-                    // Check if RPC is enabled before attempting connection
-                    let enabled = {
-                        let s = state.lock().unwrap();
-                        s.enabled
-                    };
-                    if !enabled {
-                        return false;
-                    }
+            // Commands only mark the activity as stale; it is pushed at most
+            // once per ACTIVITY_UPDATE_INTERVAL so bursts of control messages
+            // collapse into a single update carrying the latest state
+            let mut pending = false;
+            let mut last_push: Option<Instant> = None;
+            let mut last_status = RpcStatus::default();
 
-                    match DiscordIpcClient::new(CLIENT_ID) {
-                        Ok(mut c) => {
-                            if c.connect().is_ok() {
-                                let mut s = state.lock().unwrap();
-                                s.connected = true;
-                                *client = Some(c);
-                                true
-                            } else {
-                                false
-                            }
-                        }
-                        Err(_) => false,
-                    }
+            while should_run {
+                // While enabled but not connected, wake up periodically to
+                // retry instead of waiting for the next command. While idle
+                // in a lobby, wake up to check whether the player went AFK.
+                // A stale activity wakes up once it may be pushed again.
+                let (waiting, watch_afk, preview_in) = {
+                    let s = state.lock().unwrap();
+                    (
+                        client.is_none() && s.enabled,
+                        s.watches_afk(),
+                        s.preview_remaining(Instant::now()),
+                    )
+                };
+                let push_in = pending.then(|| {
+                    last_push.map_or(Duration::ZERO, |t| {
+                        ACTIVITY_UPDATE_INTERVAL.saturating_sub(t.elapsed())
+                    })
+                });
+                let timeout = if waiting {
+                    Some(retry_delay)
+                } else {
+                    [
+                        watch_afk.then(|| Duration::from_secs(AFK_CHECK_SECS)),
+                        push_in,
+                        preview_in,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .min()
                 };
 
-            while should_run {
-                let command = match rx.recv() {
-                    Ok(cmd) => cmd,
-                    Err(_) => break, // Channel closed
+                let command = match timeout {
+                    Some(timeout) => match tokio::time::timeout(timeout, rx.recv()).await {
+                        Ok(Some(cmd)) => cmd,
+                        Ok(None) => break, // Channel closed
+                        Err(_) => RpcCommand::Tick,
+                    },
+                    None => match rx.recv().await {
+                        Some(cmd) => cmd,
+                        None => break, // Channel closed
+                    },
                 };
 
+                let timed_out = matches!(command, RpcCommand::Tick);
                 match command {
                     RpcCommand::Connect => {
                         if client.is_none() {
-                            if try_connect(&mut client, &state) {
-                                // Set initial activity after successful connection
-                                let s = state.lock().unwrap();
-                                if let Some(ref mut c) = client {
-                                    let dev = *is_dev.lock().unwrap();
-                                    Self::set_activity_internal(c, &s, dev);
-                                }
-                            }
+                            // Set initial activity after successful connection
+                            pending = true;
                         }
                     }
                     RpcCommand::Disconnect => {
-                        if let Some(mut c) = client.take() {
-                            let _ = c.close();
+                        pending = false;
+                        if client.is_some() {
+                            client = Self::with_client(client, Self::close_client).await;
+                        }
+                        let mut s = state.lock().unwrap();
+                        s.set_status(RpcConnection::Disconnected, None);
+                    }
+                    RpcCommand::ApplyStateUpdate(patch) => {
+                        let refresh = patch.refresh;
+                        let (new_uuid, new_mode) = {
                             let mut s = state.lock().unwrap();
-                            s.connected = false;
+                            let previous_mode = s.current_mode.clone();
+                            let new_uuid = s.apply(patch, unix_now());
+                            // SkyBlock modes are locations, named separately
+                            let new_mode = s.current_mode.clone().filter(|mode| {
+                                previous_mode.as_ref() != Some(mode)
+                                    && mode != LAUNCHING_MODE
+                                    && s.current_gametype.as_deref() != Some("SKYBLOCK")
+                            });
+                            (new_uuid, new_mode)
+                        };
+                        if let Some(mode) = new_mode {
+                            names::note_mode(&mode);
+                        }
+                        if let Some(uuid) = new_uuid {
+                            // Fetch the new player's avatar in the background;
+                            // the small image stays hidden until it loads
+                            let tx = avatar_tx.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let available = avatar::ensure_cached(&uuid).await;
+                                let _ = tx.send(RpcCommand::AvatarChecked { uuid, available });
+                            });
+                        }
+                        if refresh {
+                            // Try to connect if not connected, then set activity
+                            pending = true;
                         }
                     }
-                    RpcCommand::SetInLauncher => {
+                    RpcCommand::SetAnonymization {
+                        anonymize_profile,
+                        anonymize_location,
+                    } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.is_playing = false;
-                            s.current_mode = None;
-                            s.current_map = None;
+                            s.anonymize_profile = anonymize_profile;
+                            s.anonymize_location = anonymize_location;
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
-                        }
+                        pending = true;
                     }
-                    RpcCommand::SetLaunching => {
+                    RpcCommand::SetImage { image_key } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.is_playing = false;
-                            s.current_mode = Some("Launching".to_string());
+                            s.custom_image = Some(image_key);
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
+                        pending = true;
+                    }
+                    RpcCommand::SetButtons { enabled } => {
+                        {
+                            let mut s = state.lock().unwrap();
+                            s.show_buttons = enabled;
                         }
+                        // Try to connect if not connected, then set activity
+                        pending = true;
                     }
-                    RpcCommand::SetPlaying => {
-                        // Just mark as playing, don't update activity yet
-                        // Activity will update when user data or game mode arrives
+                    RpcCommand::AvatarChecked { uuid, available } => {
                         let mut s = state.lock().unwrap();
-                        s.is_playing = true;
-                        s.current_mode = None;
+                        // Ignore results for a player that has since changed
+                        if available && s.current_uuid.as_ref() == Some(&uuid) {
+                            s.avatar_uuid = Some(uuid);
+                            pending = true;
+                        }
                     }
-                    RpcCommand::SetUserData { ign, uuid } => {
+                    RpcCommand::SetShowStreak { enabled } => {
                         {
                             let mut s = state.lock().unwrap();
-                            // When we receive user data, user is actually connected
-                            s.is_playing = true;
-                            // Clear any "Launching" state - real mode will come from game_mode message
-                            s.current_mode = None;
-                            s.current_map = None;
-                            if ign.is_some() {
-                                s.current_ign = ign;
-                            }
-                            if uuid.is_some() {
-                                s.current_uuid = uuid;
-                            }
+                            s.show_streak = enabled;
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
+                        pending = true;
+                    }
+                    RpcCommand::SetShowLauncherProfile { enabled } => {
+                        {
+                            let mut s = state.lock().unwrap();
+                            s.show_launcher_profile = enabled;
                         }
+                        // Try to connect if not connected, then set activity
+                        pending = true;
                     }
-                    RpcCommand::SetGameMode {
-                        mode,
-                        map,
-                        gametype,
-                        lobbyname,
+                    RpcCommand::SessionStats {
+                        wins,
+                        losses,
+                        win_streak,
                     } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.current_mode = mode;
-                            s.current_map = map;
-                            s.current_gametype = gametype;
-                            s.in_lobby = lobbyname.is_some();
+                            s.set_session_stats(wins, losses, win_streak);
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
-                        }
+                        pending = true;
                     }
-                    RpcCommand::SetDisconnected => {
-                        // User disconnected from Hypixel - clear playing state but keep user info
+                    RpcCommand::SetTimerMode { mode } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.is_playing = false;
-                            s.current_mode = None;
-                            s.current_map = None;
-                            s.current_gametype = None;
-                            s.in_lobby = false;
+                            s.timer_mode = mode;
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
+                        pending = true;
+                    }
+                    RpcCommand::SetClientId { client_id } => {
+                        {
+                            let mut s = state.lock().unwrap();
+                            s.client_id = client_id;
+                        }
+                        // The app ID is fixed per IPC connection, so reconnect
+                        if client.is_some() {
+                            client = Self::with_client(client, |client| {
+                                if let Some(c) = client.as_mut() {
+                                    let _ = c.clear_activity();
+                                }
+                                Self::close_client(client);
+                            })
+                            .await;
+                            state.lock().unwrap().set_status(
+                                RpcConnection::Disconnected,
+                                Some("Switching Discord application"),
+                            );
                         }
+                        pending = true;
                     }
-                    RpcCommand::ClearActivity => {
+                    RpcCommand::SetDiscordClient { discord_client } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.current_ign = None;
-                            s.current_uuid = None;
-                            s.current_mode = None;
-                            s.current_map = None;
-                            s.current_gametype = None;
-                            s.in_lobby = false;
-                            s.is_playing = false;
+                            s.discord_client = discord_client;
                         }
-                        // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
+                        // The pipe is chosen when connecting, so reconnect
+                        if client.is_some() {
+                            client = Self::with_client(client, |client| {
+                                if let Some(c) = client.as_mut() {
+                                    let _ = c.clear_activity();
+                                }
+                                Self::close_client(client);
+                            })
+                            .await;
+                            state.lock().unwrap().set_status(
+                                RpcConnection::Disconnected,
+                                Some("Switching Discord client"),
+                            );
                         }
+                        pending = true;
                     }
-                    RpcCommand::SetAnonymization {
-                        anonymize_profile,
-                        anonymize_location,
-                    } => {
+                    RpcCommand::SetAfkTimeout { timeout_secs } => {
+                        let changed = {
+                            let mut s = state.lock().unwrap();
+                            s.afk_timeout_secs = timeout_secs;
+                            // Re-evaluate under the new timeout
+                            let was_afk = s.afk;
+                            s.afk = false;
+                            s.update_afk(unix_now());
+                            was_afk != s.afk
+                        };
+                        if changed {
+                            pending = true;
+                        }
+                    }
+                    RpcCommand::Activity => {
+                        let was_afk = {
+                            let mut s = state.lock().unwrap();
+                            let was_afk = s.afk;
+                            s.touch(unix_now());
+                            was_afk
+                        };
+                        if was_afk {
+                            pending = true;
+                        }
+                    }
+                    RpcCommand::SetCustomActivity { activity } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.anonymize_profile = anonymize_profile;
-                            s.anonymize_location = anonymize_location;
+                            s.custom_activity = activity;
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
+                        pending = true;
+                    }
+                    RpcCommand::SetParty { party } => {
+                        let changed = {
+                            let mut s = state.lock().unwrap();
+                            let changed = s.party != party;
+                            s.party = party;
+                            changed
+                        };
+                        if changed {
+                            pending = true;
                         }
                     }
-                    RpcCommand::SetImage { image_key } => {
+                    RpcCommand::SetSpectating { target } => {
+                        let mut s = state.lock().unwrap();
+                        if s.spectating != target {
+                            s.spectating = target;
+                            pending = true;
+                        }
+                    }
+                    RpcCommand::SetPreview { preview } => {
+                        let mut s = state.lock().unwrap();
+                        s.preview_until =
+                            preview.as_ref().map(|_| Instant::now() + PREVIEW_DURATION);
+                        s.preview = preview;
+                        pending = true;
+                    }
+                    RpcCommand::SetLocale { locale } => {
                         {
                             let mut s = state.lock().unwrap();
-                            s.custom_image = Some(image_key);
+                            s.locale = locale;
                         }
                         // Try to connect if not connected, then set activity
-                        try_connect(&mut client, &state);
-                        let s = state.lock().unwrap();
-                        if let Some(ref mut c) = client {
-                            let dev = *is_dev.lock().unwrap();
-                            Self::set_activity_internal(c, &s, dev);
-                        }
+                        pending = true;
                     }
                     RpcCommand::Shutdown => {
-                        if let Some(mut c) = client.take() {
-                            let _ = c.close();
-                        }
+                        // Clear first so the presence disappears without
+                        // waiting for Discord to notice the closed pipe
+                        client = Self::with_client(client, |client| {
+                            if let Some(c) = client.as_mut() {
+                                let _ = c.clear_activity();
+                            }
+                            Self::close_client(client);
+                        })
+                        .await;
+                        state
+                            .lock()
+                            .unwrap()
+                            .set_status(RpcConnection::Disconnected, None);
                         should_run = false;
                     }
+                    RpcCommand::Tick => {
+                        let mut s = state.lock().unwrap();
+                        if s.update_afk(unix_now()) {
+                            pending = true;
+                        }
+                        // Restore the real activity once the preview is over
+                        if s.preview_remaining(Instant::now()) == Some(Duration::ZERO) {
+                            s.preview = None;
+                            s.preview_until = None;
+                            pending = true;
+                        }
+                    }
+                }
+
+                // A reconnect attempt is due regardless of the rate limit,
+                // and pushes the current activity if it succeeds
+                let retrying = waiting && timed_out;
+                let due = last_push.is_none_or(|t| t.elapsed() >= ACTIVITY_UPDATE_INTERVAL);
+                if should_run && (retrying || (pending && due)) {
+                    let state = state.clone();
+                    let dev = *is_dev.lock().unwrap();
+                    client =
+                        Self::with_client(client, move |client| Self::refresh(client, &state, dev))
+                            .await;
+                    last_push = Some(Instant::now());
+                    pending = false;
+                } else if pending && client.is_some() {
+                    state.lock().unwrap().set_status(
+                        RpcConnection::RateLimited,
+                        Some("Waiting to send the next activity update"),
+                    );
+                }
+
+                let status = state.lock().unwrap().status.clone();
+                if status != last_status {
+                    let handler = status_handler.lock().unwrap().clone();
+                    if let Some(handler) = handler {
+                        handler(status.clone());
+                    }
+                    last_status = status;
+                }
+
+                if client.is_some() {
+                    retry_delay = initial_delay;
+                } else if retrying {
+                    retry_delay = (retry_delay * 2).min(Duration::from_secs(RECONNECT_MAX_SECS));
                 }
             }
         });
 
         *sender_lock = Some(tx);
+        *self.worker.lock().unwrap() = Some(worker);
     }
 
-    /// Formats a SkyBlock location/mode to be human readable
-    fn format_skyblock_location(mode: &str) -> String {
-        match mode {
-            "hub" => "SkyBlock Hub".to_string(),
-            "dynamic" => "SkyBlock Island".to_string(),
-            "farming_1" => "SkyBlock Farm".to_string(),
-            "mining_1" => "SkyBlock Deep Caverns".to_string(),
-            "mining_2" => "SkyBlock Dwarven Mines".to_string(),
-            "mining_3" => "SkyBlock Crystal Hollows".to_string(),
-            "combat_1" => "SkyBlock Spider's Den".to_string(),
-            "combat_2" => "SkyBlock Blazing Fortress".to_string(),
-            "combat_3" => "SkyBlock The End".to_string(),
-            "foraging_1" => "SkyBlock Park".to_string(),
-            "dungeon_hub" => "SkyBlock Dungeon Hub".to_string(),
-            "dungeon" => "SkyBlock Dungeons".to_string(),
-            "crimson_isle" => "SkyBlock Crimson Isle".to_string(),
-            "rift" => "SkyBlock Rift".to_string(),
-            "garden" => "SkyBlock Garden".to_string(),
-            "kuudra_normal" => "SkyBlock Kuudra (Basic)".to_string(),
-            "kuudra_hot" => "SkyBlock Kuudra (Hot)".to_string(),
-            "kuudra_burning" => "SkyBlock Kuudra (Burning)".to_string(),
-            "kuudra_fiery" => "SkyBlock Kuudra (Fiery)".to_string(),
-            "kuudra_infernal" => "SkyBlock Kuudra (Infernal)".to_string(),
-            "instanced" => "SkyBlock Instance".to_string(),
-            "dark_auction" => "SkyBlock Dark Auction".to_string(),
-            "winter" => "SkyBlock Jerry's Workshop".to_string(),
-            // Fallback: clean up the string
-            _ => {
-                let cleaned = mode.replace('_', " ");
-                let mut result = String::new();
-                for (i, word) in cleaned.split_whitespace().enumerate() {
-                    if i > 0 {
-                        result.push(' ');
-                    }
-                    let mut chars = word.chars();
-                    if let Some(first) = chars.next() {
-                        result.push_str(&first.to_uppercase().to_string());
-                        result.push_str(&chars.as_str().to_lowercase());
-                    }
-                }
-                format!("SkyBlock {}", result)
-            }
+    /// Attempts to connect to Discord if not connected.
+    ///
+    /// Returns true if connected (either already or newly).
+    fn try_connect(client: &mut Option<PipeClient>, state: &Mutex<RpcState>) -> bool {
+        if client.is_some() {
+            return true;
         }
-    }
 
-    /// Formats a gametype string to be human readable for lobby display
-    fn format_gametype(gametype: &str) -> String {
-        match gametype {
-            "DUELS" => "Duels".to_string(),
-            "BEDWARS" => "BedWars".to_string(),
-            "SKYWARS" => "SkyWars".to_string(),
-            "ARCADE" => "Arcade".to_string(),
-            "MURDER_MYSTERY" => "Murder Mystery".to_string(),
-            "BUILD_BATTLE" => "Build Battle".to_string(),
-            "HOUSING" => "Housing".to_string(),
-            "SURVIVAL_GAMES" => "Blitz SG".to_string(),
-            "SUPER_SMASH" => "Smash Heroes".to_string(),
-            "WALLS3" => "Mega Walls".to_string(),
-            "MCGO" => "Cops and Crims".to_string(),
-            "UHC" => "UHC".to_string(),
-            "SPEED_UHC" => "Speed UHC".to_string(),
-            "TNTGAMES" => "TNT Games".to_string(),
-            "BATTLEGROUND" => "Warlords".to_string(),
-            "PIT" => "The Pit".to_string(),
-            "PROTOTYPE" => "Prototype".to_string(),
-            "SKYBLOCK" => "SkyBlock".to_string(),
-            "WOOL_GAMES" => "Wool Wars".to_string(),
-            "PAINTBALL" => "Paintball".to_string(),
-            "QUAKECRAFT" => "Quake".to_string(),
-            "VAMPIREZ" => "VampireZ".to_string(),
-            "WALLS" => "The Walls".to_string(),
-            "ARENA" => "Arena Brawl".to_string(),
-            "LEGACY" => "Classic Games".to_string(),
-            "SMP" => "SMP".to_string(),
-            "LIMBO" => "Limbo".to_string(),
-            "MAIN" => "Main".to_string(),
-            "TOURNAMENT" => "Tournament".to_string(),
-            "REPLAY" => "Replay".to_string(),
-            // Fallback: title case the gametype
-            _ => gametype
-                .split('_')
-                .map(|word| {
-                    let mut chars = word.chars();
-                    match chars.next() {
-                        None => String::new(),
-                        Some(first) => {
-                            first.to_uppercase().to_string() + &chars.as_str().to_lowercase()
-                        }
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" "),
+        // Check if RPC is enabled before attempting connection
+        let (enabled, client_id, discord_client) = {
+            let s = state.lock().unwrap();
+            (s.enabled, s.client_id.clone(), s.discord_client)
+        };
+        if !enabled {
+            return false;
+        }
+
+        let mut c = PipeClient::new(client_id.as_deref().unwrap_or(CLIENT_ID), discord_client);
+        if c.connect().is_ok() {
+            let mut s = state.lock().unwrap();
+            s.set_status(RpcConnection::Connected, None);
+            *client = Some(c);
+            true
+        } else {
+            let mut s = state.lock().unwrap();
+            s.set_status(RpcConnection::Failed, Some("Discord not detected"));
+            false
         }
     }
 
-    /// Formats a game mode string to be human readable for the "Playing X" display
-    fn format_mode(mode: &str) -> String {
-        // Map of internal mode names to display names
-        match mode {
-            // === DUELS - Solo modes (1v1) ===
-            "DUELS_COMBO_DUEL" => "Combo Duel".to_string(),
-            "DUELS_CLASSIC_DUEL" => "Classic Duel".to_string(),
-            "DUELS_POTION_DUEL" => "NoDebuff Duel".to_string(),
-            "DUELS_BOXING_DUEL" => "Boxing Duel".to_string(),
-            "DUELS_BOW_DUEL" => "Bow Duel".to_string(),
-            "DUELS_SUMO_DUEL" => "Sumo Duel".to_string(),
-            "DUELS_OP_DUEL" => "OP Duel".to_string(),
-            "DUELS_UHC_DUEL" => "UHC Duel".to_string(),
-            "DUELS_BRIDGE_DUEL" => "Bridge Duel".to_string(),
-            "DUELS_SW_DUEL" => "SkyWars Duel".to_string(),
-            "DUELS_MW_DUEL" => "MegaWalls Duel".to_string(),
-            "DUELS_BLITZ_DUEL" => "Blitz Duel".to_string(),
-            "DUELS_PARKOUR_DUEL" => "Parkour Duel".to_string(),
-            "DUELS_BOWSPLEEF_DUEL" => "Bow Spleef Duel".to_string(),
-            "DUELS_SPLEEF_DUEL" => "Spleef Duel".to_string(),
-            "DUELS_QUAKE_DUEL" => "Quake Duel".to_string(),
-            // === DUELS - Doubles modes (2v2) ===
-            "DUELS_CLASSIC_DOUBLES" => "Classic Doubles".to_string(),
-            "DUELS_OP_DOUBLES" => "OP Doubles".to_string(),
-            "DUELS_UHC_DOUBLES" => "UHC Doubles".to_string(),
-            "DUELS_BRIDGE_DOUBLES" => "Bridge Doubles".to_string(),
-            "DUELS_SW_DOUBLES" => "SkyWars Doubles".to_string(),
-            "DUELS_MW_DOUBLES" => "MegaWalls Doubles".to_string(),
-            // === DUELS - Bridge team modes ===
-            "DUELS_BRIDGE_THREES" => "Bridge 3v3".to_string(),
-            "DUELS_BRIDGE_FOUR" => "Bridge 4v4".to_string(),
-            "DUELS_BRIDGE_2V2V2V2" => "Bridge 2v2v2v2".to_string(),
-            "DUELS_BRIDGE_3V3V3V3" => "Bridge 3v3v3v3".to_string(),
-            "DUELS_CAPTURE_THREES" => "CTF 3v3".to_string(),
-            // === DUELS - Party/Arena modes ===
-            "DUELS_DUEL_ARENA" => "Arena".to_string(),
-            "DUELS_DISASTERS" => "Disasters".to_string(),
-            "DUELS_PARKOUR_EIGHT" => "Parkour".to_string(),
-
-            // === BEDWARS ===
-            "BEDWARS_TWO_ONE_DUELS" => "BedWars Duel".to_string(),
-            "BEDWARS_TWO_ONE_DUELS_RUSH" => "BedWars Rush Duel".to_string(),
-            "BEDWARS_EIGHT_ONE" => "BedWars Solo".to_string(),
-            "BEDWARS_EIGHT_TWO" => "BedWars Doubles".to_string(),
-            "BEDWARS_FOUR_THREE" => "BedWars 3v3v3v3".to_string(),
-            "BEDWARS_FOUR_FOUR" => "BedWars 4v4v4v4".to_string(),
-            "BEDWARS_TWO_FOUR" => "BedWars 4v4".to_string(),
-            "BEDWARS_CASTLE" => "BedWars Castle".to_string(),
-            "BEDWARS_EIGHT_ONE_RUSH" => "BedWars Rush Solo".to_string(),
-            "BEDWARS_EIGHT_TWO_RUSH" => "BedWars Rush Doubles".to_string(),
-            "BEDWARS_FOUR_FOUR_RUSH" => "BedWars Rush 4v4v4v4".to_string(),
-            "BEDWARS_EIGHT_ONE_ULTIMATE" => "BedWars Ultimate Solo".to_string(),
-            "BEDWARS_EIGHT_TWO_ULTIMATE" => "BedWars Ultimate Doubles".to_string(),
-            "BEDWARS_FOUR_FOUR_ULTIMATE" => "BedWars Ultimate 4v4v4v4".to_string(),
-            "BEDWARS_EIGHT_ONE_ARMED" => "BedWars Armed Solo".to_string(),
-            "BEDWARS_EIGHT_TWO_ARMED" => "BedWars Armed Doubles".to_string(),
-            "BEDWARS_FOUR_FOUR_ARMED" => "BedWars Armed 4v4v4v4".to_string(),
-            "BEDWARS_EIGHT_ONE_LUCKY" => "BedWars Lucky Solo".to_string(),
-            "BEDWARS_EIGHT_TWO_LUCKY" => "BedWars Lucky Doubles".to_string(),
-            "BEDWARS_FOUR_FOUR_LUCKY" => "BedWars Lucky 4v4v4v4".to_string(),
-            "BEDWARS_EIGHT_ONE_VOIDLESS" => "BedWars Voidless Solo".to_string(),
-            "BEDWARS_EIGHT_TWO_VOIDLESS" => "BedWars Voidless Doubles".to_string(),
-            "BEDWARS_FOUR_FOUR_VOIDLESS" => "BedWars Voidless 4v4v4v4".to_string(),
-            "BEDWARS_PRACTICE" => "BedWars Practice".to_string(),
-
-            // === SKYWARS ===
-            "solo_normal" => "SkyWars Solo Normal".to_string(),
-            "solo_insane" => "SkyWars Solo Insane".to_string(),
-            "teams_normal" => "SkyWars Teams Normal".to_string(),
-            "teams_insane" => "SkyWars Teams Insane".to_string(),
-            "ranked_normal" => "SkyWars Ranked".to_string(),
-            "mega_normal" => "SkyWars Mega".to_string(),
-            "mega_doubles" => "SkyWars Mega Doubles".to_string(),
-            "solo_insane_lucky" => "SkyWars Lucky Solo".to_string(),
-            "teams_insane_lucky" => "SkyWars Lucky Teams".to_string(),
-            "solo_insane_slime" => "SkyWars Slime Solo".to_string(),
-            "teams_insane_slime" => "SkyWars Slime Teams".to_string(),
-            "solo_insane_rush" => "SkyWars Rush Solo".to_string(),
-            "teams_insane_rush" => "SkyWars Rush Teams".to_string(),
-            "solo_insane_tnt_madness" => "SkyWars TNT Madness Solo".to_string(),
-            "teams_insane_tnt_madness" => "SkyWars TNT Madness Teams".to_string(),
-
-            // === MURDER MYSTERY ===
-            "MURDER_CLASSIC" => "Murder Mystery Classic".to_string(),
-            "MURDER_DOUBLE_UP" => "Murder Mystery Double Up".to_string(),
-            "MURDER_ASSASSINS" => "Murder Mystery Assassins".to_string(),
-            "MURDER_INFECTION" => "Murder Mystery Infection".to_string(),
-            "MURDER_SHOWDOWN" => "Murder Mystery Showdown".to_string(),
-
-            // === ARCADE ===
-            "PARTY" => "Party Games".to_string(),
-            "HOLE_IN_THE_WALL" => "Hole in the Wall".to_string(),
-            "FARM_HUNT" => "Farm Hunt".to_string(),
-            "SOCCER" => "Football".to_string(),
-            "BOUNTY_HUNTERS" => "Bounty Hunters".to_string(),
-            "MINI_WALLS" => "Mini Walls".to_string(),
-            "HIDE_AND_SEEK_PROP_HUNT" => "Prop Hunt".to_string(),
-            "HIDE_AND_SEEK_PARTY_POOPER" => "Party Pooper".to_string(),
-            "ZOMBIES_DEAD_END" => "Zombies Dead End".to_string(),
-            "ZOMBIES_BAD_BLOOD" => "Zombies Bad Blood".to_string(),
-            "ZOMBIES_ALIEN_ARCADIUM" => "Zombies Alien Arcadium".to_string(),
-            "PIXEL_PAINTERS" => "Pixel Painters".to_string(),
-            "THROW_OUT" => "Throw Out".to_string(),
-            "ENDER_SPLEEF" => "Ender Spleef".to_string(),
-            "STARWARS" => "Galaxy Wars".to_string(),
-            "DRAGON_WARS" => "Dragon Wars".to_string(),
-            "BLOCKING_DEAD" => "Blocking Dead".to_string(),
-            "CAPTURE_THE_WOOL" => "Capture the Wool".to_string(),
-            "PVP_CTW" => "Capture the Wool".to_string(),
-            "EASTER_SIMULATOR" => "Easter Simulator".to_string(),
-            "SCUBA_SIMULATOR" => "Scuba Simulator".to_string(),
-            "HALLOWEEN_SIMULATOR" => "Halloween Simulator".to_string(),
-            "GRINCH_SIMULATOR_V2" => "Grinch Simulator".to_string(),
-            "SANTA_SIMULATOR" => "Santa Simulator".to_string(),
-            "HYPIXEL_SAYS" => "Hypixel Says".to_string(),
-            "CREEPER_ATTACK" => "Creeper Attack".to_string(),
-            "SIMON_SAYS" => "Simon Says".to_string(),
-            "SANTA_SAYS" => "Santa Says".to_string(),
-            "DAY_ONE" => "Day One".to_string(),
-
-            // === WOOL GAMES ===
-            "WOOL_WARS_TWO_FOUR" => "Wool Wars".to_string(),
-            "SHEEP_WARS" => "Sheep Wars".to_string(),
-
-            // === UHC ===
-            "SOLO" => "UHC Solo".to_string(),
-            "TEAMS" => "UHC Teams".to_string(),
-
-            // === THE PIT ===
-            "PIT" => "The Pit".to_string(),
-
-            // === BUILD BATTLE ===
-            "BUILD_BATTLE_SOLO_NORMAL" => "Build Battle Solo".to_string(),
-            "BUILD_BATTLE_TEAMS_NORMAL" => "Build Battle Teams".to_string(),
-            "BUILD_BATTLE_SOLO_PRO" => "Build Battle Pro".to_string(),
-            "BUILD_BATTLE_GUESS_THE_BUILD" => "Guess the Build".to_string(),
-            "BUILD_BATTLE_SOLO_NORMAL_LATEST" => "Build Battle Solo".to_string(),
-            "BUILD_BATTLE_TEAMS_NORMAL_LATEST" => "Build Battle Teams".to_string(),
-
-            // === SKYBLOCK ===
-            "dynamic" => "SkyBlock".to_string(),
-            "hub" => "SkyBlock Hub".to_string(),
-            "farming_1" => "SkyBlock Farming".to_string(),
-            "mining_1" => "SkyBlock Deep Caverns".to_string(),
-            "mining_2" => "SkyBlock Dwarven Mines".to_string(),
-            "mining_3" => "SkyBlock Crystal Hollows".to_string(),
-            "combat_1" => "SkyBlock Spider's Den".to_string(),
-            "combat_2" => "SkyBlock Blazing Fortress".to_string(),
-            "combat_3" => "SkyBlock The End".to_string(),
-            "foraging_1" => "SkyBlock Park".to_string(),
-            "dungeon_hub" => "SkyBlock Dungeon Hub".to_string(),
-            "dungeon" => "SkyBlock Dungeons".to_string(),
-            "crimson_isle" => "SkyBlock Crimson Isle".to_string(),
-            "rift" => "SkyBlock Rift".to_string(),
-            "garden" => "SkyBlock Garden".to_string(),
-            "kuudra_normal" => "Kuudra Basic".to_string(),
-            "kuudra_hot" => "Kuudra Hot".to_string(),
-            "kuudra_burning" => "Kuudra Burning".to_string(),
-            "kuudra_fiery" => "Kuudra Fiery".to_string(),
-            "kuudra_infernal" => "Kuudra Infernal".to_string(),
-            "instanced" => "SkyBlock Instanced".to_string(),
-            "dark_auction" => "SkyBlock Dark Auction".to_string(),
-            "winter" => "SkyBlock Jerry's Workshop".to_string(),
-
-            // === MEGA WALLS ===
-            "standard" => "Mega Walls".to_string(),
-            "face_off" => "Mega Walls Face Off".to_string(),
-
-            // === COPS AND CRIMS ===
-            "normal" => "Cops and Crims".to_string(),
-            "deathmatch" => "Cops and Crims Deathmatch".to_string(),
-            "normal_party" => "Cops and Crims Party".to_string(),
-
-            // === TNT GAMES ===
-            "TNTRUN" => "TNT Run".to_string(),
-            "PVPRUN" => "PVP Run".to_string(),
-            "BOWSPLEEF" => "Bow Spleef".to_string(),
-            "TNTAG" => "TNT Tag".to_string(),
-            "CAPTURE" => "TNT Wizards".to_string(),
-
-            // === WARLORDS ===
-            "ctf_mini" => "Warlords CTF".to_string(),
-            "domination" => "Warlords Domination".to_string(),
-            "team_deathmatch" => "Warlords TDM".to_string(),
-
-            // === SMASH HEROES ===
-            "1v1_normal" => "Smash 1v1".to_string(),
-            "2v2_normal" => "Smash 2v2".to_string(),
-
-            // Fallback: clean up the string
-            _ => {
-                let cleaned = mode
-                    .strip_prefix("DUELS_")
-                    .or_else(|| mode.strip_prefix("BEDWARS_"))
-                    .or_else(|| mode.strip_prefix("SKYWARS_"))
-                    .or_else(|| mode.strip_prefix("MURDER_"))
-                    .or_else(|| mode.strip_prefix("BUILD_BATTLE_"))
-                    .unwrap_or(mode);
-                cleaned
-                    .split('_')
-                    .filter(|&w| w != "DUEL" && w != "DUELS")
-                    .map(|word| {
-                        let mut chars = word.chars();
-                        match chars.next() {
-                            None => String::new(),
-                            Some(first) => {
-                                first.to_uppercase().to_string() + &chars.as_str().to_lowercase()
-                            }
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" ")
+    /// Connects if needed and pushes the current activity.
+    ///
+    /// A failed update means Discord went away (e.g. it was restarted), so
+    /// the client is dropped and the worker's retry loop takes over.
+    fn refresh(client: &mut Option<PipeClient>, state: &Mutex<RpcState>, is_dev: bool) {
+        if !Self::try_connect(client, state) {
+            return;
+        }
+        let updated = {
+            let s = state.lock().unwrap();
+            client
+                .as_mut()
+                .is_some_and(|c| Self::set_activity_internal(c, &s, is_dev))
+        };
+        let mut s = state.lock().unwrap();
+        if updated {
+            s.set_status(RpcConnection::Connected, None);
+        } else {
+            Self::close_client(client);
+            s.set_status(
+                RpcConnection::Disconnected,
+                Some("Lost connection to Discord"),
+            );
+        }
+    }
+
+    /// Closes and drops the client, if any
+    fn close_client(client: &mut Option<PipeClient>) {
+        if let Some(mut c) = client.take() {
+            let _ = c.close();
+        }
+    }
+
+    /// Runs blocking IPC work on the client off the async runtime, handing
+    /// the client to the blocking pool and back
+    async fn with_client<F>(client: Option<PipeClient>, f: F) -> Option<PipeClient>
+    where
+        F: FnOnce(&mut Option<PipeClient>) + Send + 'static,
+    {
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut client = client;
+            f(&mut client);
+            client
+        })
+        .await
+        .unwrap_or(None)
+    }
+
+    /// Formats a SkyBlock location/mode to be human readable
+    fn format_skyblock_location(mode: &str) -> String {
+        if let Some(name) = names::current().skyblock_locations.get(mode) {
+            return name.clone();
+        }
+
+        // Fallback: clean up the string
+        let cleaned = mode.replace('_', " ");
+        let mut result = String::new();
+        for (i, word) in cleaned.split_whitespace().enumerate() {
+            if i > 0 {
+                result.push(' ');
+            }
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                result.push_str(&first.to_uppercase().to_string());
+                result.push_str(&chars.as_str().to_lowercase());
             }
         }
+        format!("SkyBlock {}", result)
     }
 
-    /// Sets the Discord activity based on current state
-    fn set_activity_internal(client: &mut DiscordIpcClient, state: &RpcState, is_dev: bool) {
+    /// Returns the artwork asset key for a gametype, if it has one
+    fn gametype_image(gametype: &str) -> Option<&'static str> {
+        GAMETYPE_IMAGES
+            .iter()
+            .find(|(key, _)| *key == gametype)
+            .map(|(_, image)| *image)
+    }
+
+    /// Formats a gametype string to be human readable for lobby display
+    fn format_gametype(gametype: &str) -> String {
+        if let Some(name) = names::current().gametypes.get(gametype) {
+            return name.clone();
+        }
+
+        // Fallback: title case the gametype
+        gametype
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => {
+                        first.to_uppercase().to_string() + &chars.as_str().to_lowercase()
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Formats a game mode string to be human readable for the "Playing X" display
+    fn format_mode(mode: &str) -> String {
+        if let Some(name) = names::current().modes.get(mode) {
+            return name.clone();
+        }
+
+        // Fallback: clean up the string
+        let cleaned = mode
+            .strip_prefix("DUELS_")
+            .or_else(|| mode.strip_prefix("BEDWARS_"))
+            .or_else(|| mode.strip_prefix("SKYWARS_"))
+            .or_else(|| mode.strip_prefix("MURDER_"))
+            .or_else(|| mode.strip_prefix("BUILD_BATTLE_"))
+            .unwrap_or(mode);
+        cleaned
+            .split('_')
+            .filter(|&w| w != "DUEL" && w != "DUELS")
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => {
+                        first.to_uppercase().to_string() + &chars.as_str().to_lowercase()
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Builds the activity content for the current state, applying the
+    /// profile and location anonymization settings
+    fn build_activity(state: &RpcState, is_dev: bool) -> ActivityContent {
+        let strings = i18n::strings_for(&state.locale);
+
         // Use custom image if set, otherwise fall back to default based on dev mode
         let large_image = state.custom_image.as_deref().unwrap_or(if is_dev {
             "logo-v1-purple"
@@ -688,11 +1360,18 @@ impl RpcManager {
             "logo-v1"
         });
         let large_text = if is_dev {
-            "Launcher (dev build)"
+            strings.launcher_dev
         } else {
-            "Duels+ Launcher"
+            strings.launcher
         };
 
+        // The Duels+ profile replaces the generic idle state, if enabled
+        let launcher_profile = state.launcher_profile.as_ref().filter(|_| {
+            state.show_launcher_profile
+                && !state.is_playing
+                && state.current_mode.as_deref() != Some(LAUNCHING_MODE)
+        });
+
         // Format game mode
         let formatted_mode: Option<String> = if state.is_playing {
             state.current_mode.as_ref().map(|m| Self::format_mode(m))
@@ -702,74 +1381,277 @@ impl RpcManager {
 
         // Determine details text based on current activity
         let details: String = if state.is_playing {
+            if state.afk {
+                strings.afk.to_string()
             // If location is anonymized, show generic text
-            if state.anonymize_location {
-                "Playing on Hypixel".to_string()
+            } else if state.anonymize_location {
+                strings.playing_on_hypixel.to_string()
+            // Spectators and replay viewers aren't playing themselves
+            } else if let Some(ref target) = state.spectating {
+                RpcStrings::fill(strings.spectating, &Self::format_mode(target))
             // Special handling for Limbo
             } else if state.current_gametype.as_deref() == Some("LIMBO") {
-                "In Limbo".to_string()
+                strings.in_limbo.to_string()
             // Special handling for SkyBlock - modes are locations, not games
             } else if state.current_gametype.as_deref() == Some("SKYBLOCK") {
                 if let Some(ref mode) = state.current_mode {
                     // SkyBlock location - "In SkyBlock Hub", "In SkyBlock Dungeons", etc.
-                    format!("In {}", Self::format_skyblock_location(mode))
+                    RpcStrings::fill(strings.in_location, &Self::format_skyblock_location(mode))
                 } else {
-                    "In SkyBlock".to_string()
+                    strings.in_skyblock.to_string()
                 }
             } else if let Some(ref mode) = formatted_mode {
                 // Playing with a known game mode - "Playing Combo Duel"
-                format!("Playing {}", mode)
+                RpcStrings::fill(strings.playing, mode)
             } else if state.in_lobby {
                 // In a specific game lobby - format based on gametype
                 if let Some(ref gametype) = state.current_gametype {
-                    format!("In {} Lobby", Self::format_gametype(gametype))
+                    RpcStrings::fill(strings.in_lobby, &Self::format_gametype(gametype))
                 } else {
-                    "In Hypixel Lobby".to_string()
+                    strings.in_hypixel_lobby.to_string()
                 }
             } else {
                 // Connected to Hypixel but no specific location
-                "In Hypixel Lobby".to_string()
+                strings.in_hypixel_lobby.to_string()
+            }
+        } else if state.current_mode.as_deref() == Some(LAUNCHING_MODE) {
+            strings.launching.to_string()
+        } else if let Some(profile) = launcher_profile {
+            if profile.browsing_stats {
+                strings.browsing_stats.to_string()
+            } else {
+                strings.in_launcher.to_string()
             }
-        } else if state.current_mode.as_deref() == Some("Launching") {
-            "Launching".to_string()
         } else {
-            "Idle".to_string()
+            strings.idle.to_string()
         };
 
-        let mut activity_builder = activity::Activity::new()
-            .details(&details)
-            .timestamps(activity::Timestamps::new().start(state.start_timestamp));
-
-        // Build assets - need to store avatar_url to extend its lifetime
-        // Only show profile (avatar/IGN) if not anonymized
-        let avatar_url: String;
-        let assets = if !state.anonymize_profile {
-            if let (Some(ign), Some(uuid)) = (&state.current_ign, &state.current_uuid) {
-                avatar_url = format!("https://mc-heads.net/avatar/{}/64.png", uuid);
-                activity::Assets::new()
-                    .large_image(large_image)
-                    .large_text(large_text)
-                    .small_image(&avatar_url)
-                    .small_text(ign)
+        // Gametype artwork gives away the location, so it follows location anonymization
+        let artwork = match (&state.current_gametype, state.is_playing) {
+            (Some(gametype), true) if !state.anonymize_location => {
+                Self::gametype_image(gametype).map(|image| (image, Self::format_gametype(gametype)))
+            }
+            _ => None,
+        };
+
+        let (large_image, large_text, small_image, small_text) = match artwork {
+            // The launcher logo moves to the small image next to the artwork
+            Some((image, gametype)) => (
+                image.to_string(),
+                gametype,
+                Some(large_image.to_string()),
+                Some(large_text.to_string()),
+            ),
+            None => {
+                // Only show profile (avatar/IGN) if not anonymized, and only
+                // once the avatar is known to load
+                let (small_image, small_text) = match (&state.current_ign, &state.current_uuid) {
+                    (Some(ign), Some(uuid))
+                        if !state.anonymize_profile && state.avatar_uuid.as_ref() == Some(uuid) =>
+                    {
+                        (Some(avatar::avatar_url(uuid)), Some(ign.clone()))
+                    }
+                    _ => (None, None),
+                };
+                (
+                    large_image.to_string(),
+                    large_text.to_string(),
+                    small_image,
+                    small_text,
+                )
+            }
+        };
+
+        // The streak gives away the game, so it follows location anonymization
+        let in_duels = state.current_gametype.as_deref() == Some("DUELS");
+        let state_line = if state.show_streak
+            && state.is_playing
+            && state.spectating.is_none()
+            && in_duels
+            && !state.afk
+            && !state.anonymize_location
+        {
+            if state.win_streak >= 2 {
+                Some(RpcStrings::fill(
+                    strings.win_streak,
+                    &state.win_streak.to_string(),
+                ))
+            } else if state.session_wins + state.session_losses > 0 {
+                Some(
+                    strings
+                        .session_record
+                        .replacen("{wins}", &state.session_wins.to_string(), 1)
+                        .replacen("{losses}", &state.session_losses.to_string(), 1),
+                )
             } else {
-                activity::Assets::new()
-                    .large_image(large_image)
-                    .large_text(large_text)
+                None
             }
         } else {
-            activity::Assets::new()
-                .large_image(large_image)
-                .large_text(large_text)
+            None
         };
 
-        activity_builder = activity_builder.assets(assets);
+        // Otherwise show the map of the game being played, which like the
+        // mode follows location anonymization
+        let shows_mode = formatted_mode.is_some()
+            && state.spectating.is_none()
+            && !matches!(
+                state.current_gametype.as_deref(),
+                Some("LIMBO") | Some("SKYBLOCK")
+            );
+        let state_line = state_line.or_else(|| {
+            state
+                .current_map
+                .as_deref()
+                .filter(|_| shows_mode && !state.afk && !state.anonymize_location)
+                .map(|map| RpcStrings::fill(strings.map, map))
+        });
+
+        // The username names the user, so it follows profile anonymization
+        let state_line = state_line.or_else(|| {
+            launcher_profile
+                .filter(|_| !state.anonymize_profile)
+                .map(|profile| RpcStrings::fill(strings.signed_in_as, &profile.username))
+        });
+
+        // Joining reveals who the player is, so it follows profile anonymization
+        let party = state
+            .party
+            .clone()
+            .filter(|_| state.is_playing && !state.anonymize_profile);
+
+        let mut buttons = Vec::new();
+        if state.show_buttons && party.is_none() {
+            // The stats link names the player, so it follows profile anonymization
+            if let (Some(ign), false) = (&state.current_ign, state.anonymize_profile) {
+                buttons.push((
+                    strings.view_stats.to_string(),
+                    format!("{}{}", STATS_URL_BASE, ign),
+                ));
+            }
+            buttons.push((strings.get_duelsplus.to_string(), WEBSITE_URL.to_string()));
+        }
+
+        ActivityContent {
+            details,
+            large_image,
+            large_text,
+            small_image,
+            small_text,
+            buttons,
+            start_timestamp: state.timer_start(),
+            end_timestamp: state
+                .game_end_timestamp
+                .filter(|_| state.is_playing && state.game_start_timestamp.is_some()),
+            state: state_line,
+            party,
+        }
+    }
+
+    /// Sets the Discord activity based on current state.
+    ///
+    /// Returns `false` if Discord rejected the update, which usually means
+    /// the IPC pipe is broken.
+    fn set_activity_internal(client: &mut PipeClient, state: &RpcState, is_dev: bool) -> bool {
+        let previewed = state.previewed(Instant::now());
+        let state = previewed.as_ref().unwrap_or(state);
+
+        if let Some(ref custom) = state.custom_activity {
+            return Self::set_custom_activity_internal(client, custom);
+        }
+
+        let content = Self::build_activity(state, is_dev);
+
+        let mut assets = activity::Assets::new()
+            .large_image(&content.large_image)
+            .large_text(&content.large_text);
+        if let (Some(image), Some(text)) = (&content.small_image, &content.small_text) {
+            assets = assets.small_image(image).small_text(text);
+        }
+
+        let join_secret = content.party.as_ref().map(|p| join::join_secret(&p.id));
+
+        let mut timestamps = activity::Timestamps::new().start(content.start_timestamp);
+        if let Some(end) = content.end_timestamp {
+            timestamps = timestamps.end(end);
+        }
+
+        let mut activity_builder = activity::Activity::new()
+            .details(&content.details)
+            .timestamps(timestamps)
+            .assets(assets);
+        if let Some(ref state_line) = content.state {
+            activity_builder = activity_builder.state(state_line);
+        }
+        if let (Some(party), Some(secret)) = (&content.party, &join_secret) {
+            activity_builder = activity_builder
+                .party(
+                    activity::Party::new()
+                        .id(&party.id)
+                        .size([party.size as i32, party.max as i32]),
+                )
+                .secrets(activity::Secrets::new().join(secret));
+        } else if !content.buttons.is_empty() {
+            activity_builder = activity_builder.buttons(
+                content
+                    .buttons
+                    .iter()
+                    .map(|(label, url)| activity::Button::new(label, url))
+                    .collect(),
+            );
+        }
 
         // Clear existing activity first to ensure Duels+ takes priority
         // over any other application's activity
         let _ = client.clear_activity();
 
-        // Set the new activity - ignore errors as Discord might not be running
-        let _ = client.set_activity(activity_builder);
+        client.set_activity(activity_builder).is_ok()
+    }
+
+    /// Sets a frontend-supplied activity as-is
+    fn set_custom_activity_internal(client: &mut PipeClient, custom: &CustomActivity) -> bool {
+        let mut assets = activity::Assets::new();
+        if let Some(ref image) = custom.large_image {
+            assets = assets.large_image(image);
+        }
+        if let Some(ref text) = custom.large_text {
+            assets = assets.large_text(text);
+        }
+        if let Some(ref image) = custom.small_image {
+            assets = assets.small_image(image);
+        }
+        if let Some(ref text) = custom.small_text {
+            assets = assets.small_text(text);
+        }
+
+        let mut timestamps = activity::Timestamps::new();
+        if let Some(start) = custom.start_timestamp {
+            timestamps = timestamps.start(start);
+        }
+        if let Some(end) = custom.end_timestamp {
+            timestamps = timestamps.end(end);
+        }
+
+        let mut activity_builder = activity::Activity::new()
+            .assets(assets)
+            .timestamps(timestamps);
+        if let Some(ref details) = custom.details {
+            activity_builder = activity_builder.details(details);
+        }
+        if let Some(ref state) = custom.state {
+            activity_builder = activity_builder.state(state);
+        }
+        if !custom.buttons.is_empty() {
+            activity_builder = activity_builder.buttons(
+                custom
+                    .buttons
+                    .iter()
+                    .map(|b| activity::Button::new(&b.label, &b.url))
+                    .collect(),
+            );
+        }
+
+        let _ = client.clear_activity();
+        client.set_activity(activity_builder).is_ok()
     }
 
     /// Sends a command to the RPC worker
@@ -797,53 +1679,177 @@ impl RpcManager {
         self.send(RpcCommand::Disconnect);
     }
 
+    /// Drops and reopens the Discord connection, e.g. after sleep, when the
+    /// pipe is dead but hasn't failed yet
+    pub fn reconnect(&self) {
+        self.disconnect();
+        self.connect();
+    }
+
     /// Sets activity to "In Launcher"
     pub fn set_in_launcher(&self) {
-        self.send(RpcCommand::SetInLauncher);
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::in_launcher()));
+    }
+
+    /// Sets activity to "In Launcher" for a signed-in Duels+ user.
+    ///
+    /// Only shown if enabled with `set_show_launcher_profile`; the username
+    /// is hidden when the profile is anonymized.
+    ///
+    /// # Arguments
+    /// * `username` - The Duels+ username, e.g. from `get_user`
+    /// * `browsing_stats` - Whether the stats page is open
+    pub fn set_in_launcher_with_profile(&self, username: String, browsing_stats: bool) {
+        let profile = LauncherProfile {
+            username,
+            browsing_stats,
+        };
+        self.send(RpcCommand::ApplyStateUpdate(
+            StatePatch::in_launcher_with_profile(profile),
+        ));
     }
 
     /// Sets activity to "Launching"
     pub fn set_launching(&self) {
-        self.send(RpcCommand::SetLaunching);
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::launching()));
     }
 
     /// Sets activity to "Playing" (basic)
     pub fn set_playing(&self, ign: Option<String>, uuid: Option<String>) {
-        self.send(RpcCommand::SetPlaying);
-        if ign.is_some() || uuid.is_some() {
-            self.send(RpcCommand::SetUserData { ign, uuid });
-        }
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::playing(ign, uuid)));
     }
 
     /// Updates user data (ign, uuid) for RPC display
     pub fn set_user_data(&self, ign: Option<String>, uuid: Option<String>) {
-        self.send(RpcCommand::SetUserData { ign, uuid });
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::user_data(
+            ign, uuid,
+        )));
     }
 
     /// Updates game mode for RPC display
+    ///
+    /// `ends_at` is the expected end of a timed game (unix seconds), shown as
+    /// a countdown.
     pub fn set_game_mode(
         &self,
         mode: Option<String>,
         map: Option<String>,
         gametype: Option<String>,
         lobbyname: Option<String>,
+        ends_at: Option<i64>,
     ) {
-        self.send(RpcCommand::SetGameMode {
+        let location = Location::Game {
             mode,
             map,
             gametype,
             lobbyname,
-        });
+            ends_at,
+        };
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::game_mode(
+            location,
+        )));
     }
 
     /// Called when user disconnects from Hypixel, clears playing state but keeps user data
     pub fn set_disconnected(&self) {
-        self.send(RpcCommand::SetDisconnected);
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::disconnected()));
+    }
+
+    /// Shows a custom activity until `clear_custom_activity` is called.
+    ///
+    /// Automatic updates keep tracking state in the background and take over
+    /// again once the custom activity is cleared.
+    ///
+    /// # Returns
+    /// * `Err(String)` if the activity exceeds Discord's limits
+    pub fn set_custom_activity(&self, activity: CustomActivity) -> Result<(), String> {
+        activity.validate()?;
+        self.state.lock().unwrap().custom_activity = Some(activity.clone());
+        self.send(RpcCommand::SetCustomActivity {
+            activity: Some(activity),
+        });
+        Ok(())
+    }
+
+    /// Returns to automatic activity updates
+    pub fn clear_custom_activity(&self) {
+        self.state.lock().unwrap().custom_activity = None;
+        self.send(RpcCommand::SetCustomActivity { activity: None });
+    }
+
+    /// Updates the Hypixel party offered through "Ask to Join".
+    ///
+    /// # Arguments
+    /// * `party_id` - Party id as reported by the proxy, `None` when not in a party
+    /// * `size` - Current number of members
+    /// * `max` - Maximum number of members
+    pub fn set_party(&self, party_id: Option<String>, size: u32, max: u32) {
+        let party = party_id.filter(|id| !id.is_empty()).map(|id| {
+            let size = size.max(1);
+            Party {
+                id,
+                size,
+                max: max.max(size),
+            }
+        });
+        self.send(RpcCommand::SetParty { party });
+    }
+
+    /// Shows that the player is spectating a game or watching a replay.
+    ///
+    /// # Arguments
+    /// * `target` - Mode being watched, `None` once the player stops
+    pub fn set_spectating(&self, target: Option<String>) {
+        let target = target.filter(|t| !t.is_empty());
+        self.send(RpcCommand::SetSpectating { target });
+    }
+
+    /// Shows a sample game on Discord for 30 seconds, then restores the
+    /// real activity.
+    ///
+    /// # Returns
+    /// * `Err(String)` if Rich Presence is disabled
+    pub fn preview(&self, preview: RpcPreview) -> Result<(), String> {
+        if !self.is_enabled() {
+            return Err("Discord Rich Presence is disabled".to_string());
+        }
+        self.send(RpcCommand::SetPreview {
+            preview: Some(preview),
+        });
+        Ok(())
+    }
+
+    /// Changes the language of the activity text; unknown locales fall back
+    /// to English
+    pub fn set_locale(&self, locale: &str) {
+        // Applied immediately so it also takes effect before `start`
+        self.state.lock().unwrap().locale = locale.to_string();
+        self.send(RpcCommand::SetLocale {
+            locale: locale.to_string(),
+        });
+    }
+
+    /// Sets the callback run with every connection status change, replacing
+    /// any previous one
+    pub fn on_status<F>(&self, handler: F)
+    where
+        F: Fn(RpcStatus) + Send + Sync + 'static,
+    {
+        *self.status_handler.lock().unwrap() = Some(Arc::new(handler));
+    }
+
+    /// Sets the callback run with the party id of every accepted
+    /// "Ask to Join" request, replacing any previous one
+    pub fn on_join<F>(&self, handler: F)
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        *self.join_handler.lock().unwrap() = Some(Arc::new(handler));
     }
 
     /// Clears user data and resets to base activity
     pub fn clear_activity(&self) {
-        self.send(RpcCommand::ClearActivity);
+        self.send(RpcCommand::ApplyStateUpdate(StatePatch::cleared()));
     }
 
     /// Enables or disables RPC
@@ -880,19 +1886,13 @@ impl RpcManager {
     /// Sets whether to anonymize profile (IGN/avatar) in RPC
     pub fn set_anonymize_profile(&self, anonymize: bool) {
         let current_location = self.state.lock().unwrap().anonymize_location;
-        self.send(RpcCommand::SetAnonymization {
-            anonymize_profile: anonymize,
-            anonymize_location: current_location,
-        });
+        self.set_anonymization(anonymize, current_location);
     }
 
     /// Sets whether to anonymize location/game mode in RPC
     pub fn set_anonymize_location(&self, anonymize: bool) {
         let current_profile = self.state.lock().unwrap().anonymize_profile;
-        self.send(RpcCommand::SetAnonymization {
-            anonymize_profile: current_profile,
-            anonymize_location: anonymize,
-        });
+        self.set_anonymization(current_profile, anonymize);
     }
 
     /// Sets the RPC image asset key.
@@ -921,11 +1921,170 @@ impl RpcManager {
         Ok(())
     }
 
+    /// Shows or hides the "View my stats" and "Get Duels+" activity buttons
+    pub fn set_buttons(&self, enabled: bool) {
+        {
+            let mut s = self.state.lock().unwrap();
+            s.show_buttons = enabled;
+        }
+        self.send(RpcCommand::SetButtons { enabled });
+    }
+
+    /// Shows or hides the session win streak / record while playing Duels
+    pub fn set_show_streak(&self, enabled: bool) {
+        {
+            let mut s = self.state.lock().unwrap();
+            s.show_streak = enabled;
+        }
+        self.send(RpcCommand::SetShowStreak { enabled });
+    }
+
+    /// Shows or hides the Duels+ username while in the launcher, instead of
+    /// the generic idle state
+    pub fn set_show_launcher_profile(&self, enabled: bool) {
+        {
+            let mut s = self.state.lock().unwrap();
+            s.show_launcher_profile = enabled;
+        }
+        self.send(RpcCommand::SetShowLauncherProfile { enabled });
+    }
+
+    /// Sets the session's record and win streak, shown while playing Duels
+    pub fn set_session_stats(&self, wins: u32, losses: u32, win_streak: u32) {
+        self.send(RpcCommand::SessionStats {
+            wins,
+            losses,
+            win_streak,
+        });
+    }
+
+    /// Sets whether the elapsed timer counts the session or the current game
+    pub fn set_timer_mode(&self, mode: RpcTimerMode) {
+        {
+            let mut s = self.state.lock().unwrap();
+            s.timer_mode = mode;
+        }
+        self.send(RpcCommand::SetTimerMode { mode });
+    }
+
+    /// Sets a custom Discord application ID, reconnecting under it if it changed.
+    ///
+    /// # Arguments
+    /// * `client_id` - Numeric Discord application ID; empty restores the default
+    ///
+    /// # Returns
+    /// * `Ok(())` if the ID is valid and was set
+    /// * `Err(String)` if the ID is not a Discord application ID
+    pub fn set_client_id(&self, client_id: &str) -> Result<(), String> {
+        let client_id = client_id.trim();
+        let client_id = if client_id.is_empty() {
+            None
+        } else if client_id.chars().all(|c| c.is_ascii_digit()) {
+            Some(client_id.to_string())
+        } else {
+            return Err(format!("Invalid Discord application ID '{}'", client_id));
+        };
+
+        {
+            let mut s = self.state.lock().unwrap();
+            if s.client_id == client_id {
+                return Ok(());
+            }
+            s.client_id = client_id.clone();
+        }
+        self.send(RpcCommand::SetClientId { client_id });
+        Ok(())
+    }
+
+    /// Sets which Discord client to show the activity on when several
+    /// (stable, PTB, Canary) are running, reconnecting if it changed
+    pub fn set_discord_client(&self, discord_client: DiscordClient) {
+        {
+            let mut s = self.state.lock().unwrap();
+            if s.discord_client == discord_client {
+                return;
+            }
+            s.discord_client = discord_client;
+        }
+        self.send(RpcCommand::SetDiscordClient { discord_client });
+    }
+
+    /// Sets how many minutes the player may idle in a lobby before the
+    /// activity shows AFK; 0 disables AFK detection
+    pub fn set_afk_timeout(&self, minutes: u64) {
+        let timeout_secs = minutes.saturating_mul(60);
+        {
+            let mut s = self.state.lock().unwrap();
+            s.afk_timeout_secs = timeout_secs;
+        }
+        self.send(RpcCommand::SetAfkTimeout { timeout_secs });
+    }
+
+    /// Resets the AFK timer after player activity seen outside game mode updates
+    pub fn note_activity(&self) {
+        self.send(RpcCommand::Activity);
+    }
+
     /// Returns the list of valid RPC image keys
     pub fn get_valid_image_keys() -> &'static [&'static str] {
         VALID_IMAGE_KEYS
     }
 
+    /// Returns a snapshot of the current state, including what the activity shows
+    pub fn get_state(&self) -> RpcStateSnapshot {
+        let s = self.state.lock().unwrap();
+        let previewed = s.previewed(Instant::now());
+        let content = Self::build_activity(
+            previewed.as_ref().unwrap_or(&s),
+            *self.is_dev.lock().unwrap(),
+        );
+
+        RpcStateSnapshot {
+            connected: s.connected,
+            enabled: s.enabled,
+            ign: s.current_ign.clone(),
+            uuid: s.current_uuid.clone(),
+            mode: s.current_mode.clone(),
+            map: s.current_map.clone(),
+            gametype: s.current_gametype.clone(),
+            in_lobby: s.in_lobby,
+            is_playing: s.is_playing,
+            spectating: s.spectating.clone(),
+            anonymize_profile: s.anonymize_profile,
+            anonymize_location: s.anonymize_location,
+            custom_image: s.custom_image.clone(),
+            show_buttons: s.show_buttons,
+            timer_mode: s.timer_mode,
+            locale: s.locale.clone(),
+            session_start: s.start_timestamp,
+            game_start: s.game_start_timestamp,
+            game_end: content.end_timestamp,
+            details: content.details,
+            state: content.state,
+            show_streak: s.show_streak,
+            show_launcher_profile: s.show_launcher_profile,
+            session_wins: s.session_wins,
+            session_losses: s.session_losses,
+            win_streak: s.win_streak,
+            timer_start: content.start_timestamp,
+            custom_activity: s.custom_activity.clone(),
+            previewing: previewed.is_some(),
+            status: s.status.clone(),
+        }
+    }
+
+    /// Summarizes the launcher session so far: total time, time in game
+    /// versus in the launcher, time per game mode and the win/loss record
+    pub fn get_session_summary(&self) -> SessionSummary {
+        let s = self.state.lock().unwrap();
+        SessionSummary {
+            wins: s.session_wins,
+            losses: s.session_losses,
+            ..s.session
+                .summary(s.start_timestamp, unix_now(), Self::format_mode)
+        }
+    }
+
     /// Returns whether RPC is connected
     #[allow(dead_code)]
     pub fn is_connected(&self) -> bool {
@@ -934,8 +2093,34 @@ impl RpcManager {
 
     /// Shuts down the RPC manager
     pub fn shutdown(&self) {
+        // Also runs on drop, so don't panic on a poisoned lock
+        if let Ok(mut s) = self.state.lock() {
+            s.shut_down = true;
+        }
+        // Dropping the listener tells its thread to stop without waiting
+        if let Ok(mut listener) = self.join_listener.lock() {
+            listener.take();
+        }
         self.send(RpcCommand::Shutdown);
     }
+
+    /// Shuts down the worker and the join listener and waits (up to
+    /// `SHUTDOWN_TIMEOUT`) for them to close their Discord connections, so
+    /// the activity disappears right away
+    pub async fn shutdown_and_wait(&self) {
+        let listener = self.join_listener.lock().unwrap().take();
+        self.shutdown();
+        let worker = self.worker.lock().unwrap().take();
+        let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, async {
+            if let Some(worker) = worker {
+                let _ = worker.await;
+            }
+            if let Some(listener) = listener {
+                let _ = tauri::async_runtime::spawn_blocking(move || listener.stop()).await;
+            }
+        })
+        .await;
+    }
 }
 
 impl Drop for RpcManager {
@@ -943,3 +2128,695 @@ impl Drop for RpcManager {
         self.shutdown();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playing_state(anonymize_profile: bool, anonymize_location: bool) -> RpcState {
+        RpcState {
+            is_playing: true,
+            current_ign: Some("Steve".to_string()),
+            current_uuid: Some("abc123".to_string()),
+            current_mode: Some("DUELS_SUMO_DUEL".to_string()),
+            current_map: Some("Ice".to_string()),
+            avatar_uuid: Some("abc123".to_string()),
+            anonymize_profile,
+            anonymize_location,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_activity_not_anonymized() {
+        let content = RpcManager::build_activity(&playing_state(false, false), false);
+        assert_eq!(content.details, "Playing Sumo Duel");
+        assert_eq!(content.small_text.as_deref(), Some("Steve"));
+        assert_eq!(
+            content.small_image.as_deref(),
+            Some("https://mc-heads.net/avatar/abc123/64.png")
+        );
+    }
+
+    #[test]
+    fn test_activity_avatar_unavailable() {
+        let mut state = playing_state(false, false);
+        state.avatar_uuid = None;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.small_image, None);
+        assert_eq!(content.small_text, None);
+        assert_eq!(content.details, "Playing Sumo Duel");
+    }
+
+    #[test]
+    fn test_activity_profile_anonymized() {
+        let content = RpcManager::build_activity(&playing_state(true, false), false);
+        assert_eq!(content.details, "Playing Sumo Duel");
+        assert!(content.small_text.is_none());
+        assert!(content.small_image.is_none());
+    }
+
+    #[test]
+    fn test_activity_location_anonymized() {
+        let content = RpcManager::build_activity(&playing_state(false, true), false);
+        assert_eq!(content.details, "Playing on Hypixel");
+        assert_eq!(content.small_text.as_deref(), Some("Steve"));
+    }
+
+    #[test]
+    fn test_activity_fully_anonymized() {
+        let content = RpcManager::build_activity(&playing_state(true, true), false);
+        assert_eq!(content.details, "Playing on Hypixel");
+        assert!(content.small_text.is_none());
+        assert!(content.small_image.is_none());
+        assert!(!content.details.contains("Steve"));
+    }
+
+    #[test]
+    fn test_activity_location_anonymized_hides_lobby() {
+        let mut state = playing_state(false, true);
+        state.current_mode = None;
+        state.current_gametype = Some("DUELS".to_string());
+        state.in_lobby = true;
+        assert_eq!(
+            RpcManager::build_activity(&state, false).details,
+            "Playing on Hypixel"
+        );
+
+        state.anonymize_location = false;
+        assert_eq!(
+            RpcManager::build_activity(&state, false).details,
+            "In Duels Lobby"
+        );
+    }
+
+    #[test]
+    fn test_activity_gametype_artwork() {
+        let mut state = playing_state(false, false);
+        state.current_gametype = Some("BEDWARS".to_string());
+
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.large_image, "game-bedwars");
+        assert_eq!(content.large_text, "BedWars");
+        assert_eq!(content.small_image.as_deref(), Some("logo-v1"));
+        assert_eq!(content.small_text.as_deref(), Some("Duels+ Launcher"));
+    }
+
+    #[test]
+    fn test_activity_gametype_artwork_fallback() {
+        let mut state = playing_state(false, false);
+        state.current_gametype = Some("HOUSING".to_string());
+        assert_eq!(
+            RpcManager::build_activity(&state, false).large_image,
+            "logo-v1"
+        );
+
+        // Location anonymization hides the gametype artwork too
+        state.current_gametype = Some("DUELS".to_string());
+        state.anonymize_location = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.large_image, "logo-v1");
+        assert_eq!(content.small_text.as_deref(), Some("Steve"));
+
+        // Not playing shows the logo
+        state.anonymize_location = false;
+        state.is_playing = false;
+        assert_eq!(
+            RpcManager::build_activity(&state, false).large_image,
+            "logo-v1"
+        );
+    }
+
+    #[test]
+    fn test_activity_party_replaces_buttons() {
+        let mut state = playing_state(false, false);
+        state.show_buttons = true;
+        state.party = Some(Party {
+            id: "party-1".to_string(),
+            size: 2,
+            max: 8,
+        });
+
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.party, state.party);
+        assert!(content.buttons.is_empty());
+
+        // Anonymized profiles can't be joined
+        state.anonymize_profile = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert!(content.party.is_none());
+        assert!(!content.buttons.is_empty());
+    }
+
+    #[test]
+    fn test_activity_localized() {
+        let mut state = playing_state(false, false);
+        state.locale = "de-DE".to_string();
+        state.current_mode = None;
+        state.current_gametype = Some("DUELS".to_string());
+        state.in_lobby = true;
+        assert_eq!(
+            RpcManager::build_activity(&state, false).details,
+            "In der Duels-Lobby"
+        );
+
+        state.is_playing = false;
+        assert_eq!(RpcManager::build_activity(&state, false).details, "Inaktiv");
+
+        // Unknown locales fall back to English
+        state.locale = "xx".to_string();
+        assert_eq!(RpcManager::build_activity(&state, false).details, "Idle");
+    }
+
+    #[test]
+    fn test_patch_playing_with_user_data() {
+        let mut state = RpcState::default();
+        let patch = StatePatch::playing(Some("Steve".to_string()), Some("abc123".to_string()));
+        assert!(patch.refresh);
+        let new_uuid = state.apply(patch, 100);
+
+        assert!(state.is_playing);
+        assert_eq!(state.current_ign.as_deref(), Some("Steve"));
+        assert_eq!(new_uuid.as_deref(), Some("abc123"));
+
+        // Without the player there is nothing to show yet
+        assert!(!StatePatch::playing(None, None).refresh);
+    }
+
+    #[test]
+    fn test_patch_game_mode_before_user_data() {
+        let mut state = RpcState::default();
+        state.apply(StatePatch::launching(), 100);
+        state.apply(
+            StatePatch::game_mode(Location::Game {
+                mode: Some("DUELS_SUMO_DUEL".to_string()),
+                map: Some("Ice".to_string()),
+                gametype: Some("DUELS".to_string()),
+                lobbyname: None,
+                ends_at: None,
+            }),
+            110,
+        );
+        state.apply(StatePatch::user_data(Some("Steve".to_string()), None), 120);
+
+        // User data arriving late doesn't wipe the game mode
+        assert_eq!(state.current_mode.as_deref(), Some("DUELS_SUMO_DUEL"));
+        assert_eq!(state.current_map.as_deref(), Some("Ice"));
+        assert_eq!(state.game_start_timestamp, Some(110));
+    }
+
+    #[test]
+    fn test_patch_user_data_clears_launching() {
+        let mut state = RpcState::default();
+        state.apply(StatePatch::launching(), 100);
+        assert_eq!(state.current_mode.as_deref(), Some(LAUNCHING_MODE));
+
+        state.apply(StatePatch::user_data(Some("Steve".to_string()), None), 110);
+        assert!(state.is_playing);
+        assert_eq!(state.current_mode, None);
+    }
+
+    #[test]
+    fn test_patch_disconnected_and_cleared() {
+        let mut state = playing_state(false, false);
+        state.spectating = Some("DUELS_SUMO_DUEL".to_string());
+        state.apply(StatePatch::disconnected(), 100);
+        assert!(!state.is_playing);
+        assert_eq!(state.current_mode, None);
+        assert_eq!(state.spectating, None);
+        assert_eq!(state.current_ign.as_deref(), Some("Steve"));
+
+        state.apply(StatePatch::cleared(), 110);
+        assert_eq!(state.current_ign, None);
+        assert_eq!(state.current_uuid, None);
+    }
+
+    #[test]
+    fn test_patches_feed_session_tracker() {
+        let mut state = RpcState::default();
+        state.apply(StatePatch::launching(), 100);
+        state.apply(StatePatch::user_data(Some("Steve".to_string()), None), 110);
+        state.apply(
+            StatePatch::game_mode(Location::Game {
+                mode: Some("DUELS_SUMO_DUEL".to_string()),
+                map: None,
+                gametype: Some("DUELS".to_string()),
+                lobbyname: None,
+                ends_at: None,
+            }),
+            120,
+        );
+        state.apply(StatePatch::disconnected(), 180);
+
+        let summary = state.session.summary(100, 200, RpcManager::format_mode);
+        assert_eq!(summary.in_game_secs, 70);
+        assert_eq!(summary.in_launcher_secs, 30);
+        assert_eq!(summary.modes.len(), 1);
+        assert_eq!(summary.modes[0].name, "Sumo Duel");
+        assert_eq!(summary.modes[0].secs, 60);
+    }
+
+    #[test]
+    fn test_activity_launcher_profile() {
+        let mut state = RpcState::default();
+        state.apply(
+            StatePatch::in_launcher_with_profile(LauncherProfile {
+                username: "steve".to_string(),
+                browsing_stats: true,
+            }),
+            100,
+        );
+        // Off by default
+        assert_eq!(RpcManager::build_activity(&state, false).details, "Idle");
+
+        state.show_launcher_profile = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.details, "Browsing stats");
+        assert_eq!(content.state.as_deref(), Some("Signed in as steve"));
+
+        // The proxy stopping keeps the profile
+        state.apply(StatePatch::in_launcher(), 110);
+        state.anonymize_profile = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.details, "Browsing stats");
+        assert_eq!(content.state, None);
+
+        state.apply(StatePatch::cleared(), 120);
+        assert_eq!(RpcManager::build_activity(&state, false).details, "Idle");
+    }
+
+    #[test]
+    fn test_preview_uses_real_settings() {
+        let now = Instant::now();
+        let mut state = RpcState {
+            current_ign: Some("Steve".to_string()),
+            anonymize_location: true,
+            preview: Some(RpcPreview::default()),
+            preview_until: Some(now + PREVIEW_DURATION),
+            ..Default::default()
+        };
+
+        let previewed = state.previewed(now).unwrap();
+        assert_eq!(previewed.current_mode.as_deref(), Some(PREVIEW_MODE));
+        let content = RpcManager::build_activity(&previewed, false);
+        // Privacy settings apply to the preview too
+        assert_eq!(content.details, "Playing on Hypixel");
+
+        state.anonymize_location = false;
+        let content = RpcManager::build_activity(&state.previewed(now).unwrap(), false);
+        assert_eq!(content.details, "Playing Classic Duel");
+        assert_eq!(content.state.as_deref(), Some("Map: Arena"));
+    }
+
+    #[test]
+    fn test_preview_expires() {
+        let now = Instant::now();
+        let state = RpcState {
+            preview: Some(RpcPreview::default()),
+            preview_until: Some(now),
+            ..Default::default()
+        };
+        assert_eq!(state.preview_remaining(now), Some(Duration::ZERO));
+        assert!(state.previewed(now).is_none());
+        assert!(RpcState::default().previewed(now).is_none());
+    }
+
+    #[test]
+    fn test_activity_spectating() {
+        let mut state = playing_state(false, false);
+        state.spectating = Some("DUELS_SUMO_DUEL".to_string());
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.details, "Spectating Sumo Duel");
+        // Neither the map nor the player's own stats apply
+        assert_eq!(content.state, None);
+
+        state.anonymize_location = true;
+        assert_eq!(
+            RpcManager::build_activity(&state, false).details,
+            "Playing on Hypixel"
+        );
+    }
+
+    #[test]
+    fn test_activity_map_with_mode() {
+        let state = playing_state(false, false);
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.details, "Playing Sumo Duel");
+        assert_eq!(content.state.as_deref(), Some("Map: Ice"));
+
+        // The map gives away the location
+        let content = RpcManager::build_activity(&playing_state(false, true), false);
+        assert_eq!(content.state, None);
+    }
+
+    #[test]
+    fn test_activity_mode_without_map() {
+        let mut state = playing_state(false, false);
+        state.current_map = None;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.details, "Playing Sumo Duel");
+        assert_eq!(content.state, None);
+    }
+
+    #[test]
+    fn test_activity_lobby_has_no_map() {
+        let mut state = playing_state(false, false);
+        state.current_mode = None;
+        state.current_gametype = Some("DUELS".to_string());
+        state.in_lobby = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(content.details, "In Duels Lobby");
+        assert_eq!(content.state, None);
+    }
+
+    #[test]
+    fn test_activity_streak_takes_precedence_over_map() {
+        let mut state = playing_state(false, false);
+        state.show_streak = true;
+        state.current_gametype = Some("DUELS".to_string());
+        state.set_session_stats(2, 0, 2);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).state.as_deref(),
+            Some("2 win streak")
+        );
+    }
+
+    #[test]
+    fn test_activity_win_streak() {
+        let mut state = playing_state(false, false);
+        state.current_map = None;
+        state.show_streak = true;
+        state.current_gametype = Some("DUELS".to_string());
+        assert_eq!(RpcManager::build_activity(&state, false).state, None);
+
+        state.set_session_stats(1, 0, 1);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).state.as_deref(),
+            Some("Session: 1W 0L")
+        );
+
+        state.set_session_stats(3, 0, 3);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).state.as_deref(),
+            Some("3 win streak")
+        );
+
+        state.set_session_stats(3, 1, 0);
+        assert_eq!(state.win_streak, 0);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).state.as_deref(),
+            Some("Session: 3W 1L")
+        );
+
+        // Hidden outside Duels, when anonymized and when turned off
+        state.anonymize_location = true;
+        assert_eq!(RpcManager::build_activity(&state, false).state, None);
+        state.anonymize_location = false;
+        state.show_streak = false;
+        assert_eq!(RpcManager::build_activity(&state, false).state, None);
+        state.show_streak = true;
+        state.current_gametype = Some("BEDWARS".to_string());
+        assert_eq!(RpcManager::build_activity(&state, false).state, None);
+    }
+
+    #[test]
+    fn test_activity_buttons() {
+        let mut state = playing_state(false, false);
+        state.show_buttons = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(
+            content.buttons,
+            vec![
+                (
+                    "View my stats".to_string(),
+                    "https://plancke.io/hypixel/player/stats/Steve".to_string()
+                ),
+                ("Get Duels+".to_string(), WEBSITE_URL.to_string()),
+            ]
+        );
+
+        // Anonymized profiles keep only the website button
+        state.anonymize_profile = true;
+        let content = RpcManager::build_activity(&state, false);
+        assert_eq!(
+            content.buttons,
+            vec![("Get Duels+".to_string(), WEBSITE_URL.to_string())]
+        );
+
+        state.show_buttons = false;
+        assert!(RpcManager::build_activity(&state, false).buttons.is_empty());
+    }
+
+    #[test]
+    fn test_activity_end_timestamp() {
+        let mut state = RpcState {
+            start_timestamp: 100,
+            is_playing: true,
+            ..Default::default()
+        };
+        state.apply_game_mode(
+            Some("DUELS_BRIDGE_DUEL".to_string()),
+            None,
+            Some("DUELS".to_string()),
+            None,
+            200,
+        );
+        state.game_end_timestamp = Some(1100);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).end_timestamp,
+            Some(1100)
+        );
+
+        // Leaving the game drops the countdown
+        state.apply_game_mode(
+            None,
+            None,
+            Some("DUELS".to_string()),
+            Some("duelslobby1".to_string()),
+            300,
+        );
+        assert_eq!(
+            RpcManager::build_activity(&state, false).end_timestamp,
+            None
+        );
+    }
+
+    #[test]
+    fn test_per_game_timer() {
+        let mut state = RpcState {
+            start_timestamp: 100,
+            timer_mode: RpcTimerMode::PerGame,
+            is_playing: true,
+            ..Default::default()
+        };
+        let duel = || Some("DUELS_SUMO_DUEL".to_string());
+        let map = || Some("Ice".to_string());
+
+        state.apply_game_mode(
+            None,
+            None,
+            Some("DUELS".to_string()),
+            Some("duelslobby1".to_string()),
+            150,
+        );
+        assert_eq!(state.timer_start(), 100);
+
+        state.apply_game_mode(duel(), map(), Some("DUELS".to_string()), None, 200);
+        assert_eq!(state.timer_start(), 200);
+
+        // Repeated updates for the same game keep the timer running
+        state.apply_game_mode(duel(), map(), Some("DUELS".to_string()), None, 250);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).start_timestamp,
+            200
+        );
+
+        // Back in a lobby shows session time again
+        state.apply_game_mode(
+            None,
+            None,
+            Some("DUELS".to_string()),
+            Some("duelslobby1".to_string()),
+            300,
+        );
+        assert_eq!(state.timer_start(), 100);
+
+        state.apply_game_mode(
+            duel(),
+            Some("Sandstone".to_string()),
+            Some("DUELS".to_string()),
+            None,
+            400,
+        );
+        assert_eq!(state.timer_start(), 400);
+    }
+
+    #[test]
+    fn test_session_timer_ignores_games() {
+        let mut state = RpcState {
+            start_timestamp: 100,
+            ..Default::default()
+        };
+        state.apply_game_mode(Some("DUELS_SUMO_DUEL".to_string()), None, None, None, 200);
+        assert_eq!(state.timer_start(), 100);
+    }
+
+    #[test]
+    fn test_get_state_snapshot() {
+        let manager = RpcManager::new(false);
+        manager.set_anonymize_location(true);
+
+        let snapshot = manager.get_state();
+        assert!(!snapshot.connected);
+        assert!(snapshot.enabled);
+        assert!(snapshot.anonymize_location);
+        assert_eq!(snapshot.details, "Idle");
+        assert_eq!(snapshot.timer_start, snapshot.session_start);
+
+        let value = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(value["timerMode"], "session");
+        assert!(value["gameStart"].is_null());
+    }
+
+    #[test]
+    fn test_set_client_id_validation() {
+        let manager = RpcManager::new(false);
+        assert!(manager.set_client_id("1391866803889770526").is_ok());
+        assert_eq!(
+            manager.state.lock().unwrap().client_id.as_deref(),
+            Some("1391866803889770526")
+        );
+
+        assert!(manager.set_client_id("my-app").is_err());
+        assert!(manager.state.lock().unwrap().client_id.is_some());
+
+        assert!(manager.set_client_id("").is_ok());
+        assert!(manager.state.lock().unwrap().client_id.is_none());
+    }
+
+    #[test]
+    fn test_afk_in_lobby() {
+        let mut state = RpcState {
+            afk_timeout_secs: 600,
+            ..Default::default()
+        };
+        state.is_playing = true;
+        state.apply_game_mode(
+            None,
+            None,
+            Some("DUELS".to_string()),
+            Some("duelslobby1".to_string()),
+            1000,
+        );
+
+        assert!(!state.update_afk(1599));
+        assert!(state.update_afk(1600));
+        assert_eq!(RpcManager::build_activity(&state, false).details, "AFK");
+
+        // A new game mode update counts as activity
+        state.apply_game_mode(
+            Some("DUELS_SUMO_DUEL".to_string()),
+            None,
+            Some("DUELS".to_string()),
+            None,
+            1700,
+        );
+        assert!(!state.afk);
+        assert_eq!(
+            RpcManager::build_activity(&state, false).details,
+            "Playing Sumo Duel"
+        );
+    }
+
+    #[test]
+    fn test_afk_not_in_game_or_disabled() {
+        let mut state = playing_state(false, false);
+        state.afk_timeout_secs = 600;
+        state.touch(0);
+        // Mid-game stays as is
+        assert!(!state.update_afk(10_000));
+
+        state.current_mode = None;
+        state.afk_timeout_secs = 0;
+        assert!(!state.update_afk(10_000));
+    }
+
+    #[test]
+    fn test_custom_activity_validation() {
+        let valid = CustomActivity {
+            details: Some("Streaming Duels".to_string()),
+            state: Some("Road to 1000 wins".to_string()),
+            large_image: Some("logo-v1".to_string()),
+            small_image: Some("https://example.com/icon.png".to_string()),
+            buttons: vec![CustomButton {
+                label: "Watch".to_string(),
+                url: "https://twitch.tv/example".to_string(),
+            }],
+            start_timestamp: Some(100),
+            end_timestamp: Some(200),
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+
+        let mut short = valid.clone();
+        short.details = Some("x".to_string());
+        assert!(short.validate().is_err());
+
+        let mut bad_image = valid.clone();
+        bad_image.large_image = Some("not-an-asset".to_string());
+        assert!(bad_image.validate().is_err());
+
+        let mut too_many_buttons = valid.clone();
+        too_many_buttons.buttons = vec![valid.buttons[0].clone(); 3];
+        assert!(too_many_buttons.validate().is_err());
+
+        let mut bad_url = valid.clone();
+        bad_url.buttons[0].url = "javascript:alert(1)".to_string();
+        assert!(bad_url.validate().is_err());
+
+        let mut backwards = valid;
+        backwards.end_timestamp = Some(50);
+        assert!(backwards.validate().is_err());
+    }
+
+    #[test]
+    fn test_custom_activity_set_and_clear() {
+        let manager = RpcManager::new(false);
+        let activity = CustomActivity {
+            details: Some("Custom".to_string()),
+            ..Default::default()
+        };
+
+        assert!(manager.set_custom_activity(activity.clone()).is_ok());
+        assert_eq!(manager.get_state().custom_activity, Some(activity));
+
+        manager.clear_custom_activity();
+        assert!(manager.get_state().custom_activity.is_none());
+    }
+
+    #[test]
+    fn test_set_status_tracks_connected() {
+        let mut state = RpcState::default();
+        state.set_status(RpcConnection::RateLimited, Some("busy"));
+        assert!(state.connected);
+        assert_eq!(state.status.reason.as_deref(), Some("busy"));
+
+        state.set_status(RpcConnection::Failed, Some("Discord not detected"));
+        assert!(!state.connected);
+        assert_eq!(
+            serde_json::to_value(&state.status).unwrap(),
+            serde_json::json!({ "status": "failed", "reason": "Discord not detected" })
+        );
+    }
+
+    #[test]
+    fn test_anonymize_setters_update_state() {
+        let manager = RpcManager::new(false);
+        manager.set_anonymize_profile(true);
+        manager.set_anonymize_location(true);
+
+        let state = manager.state.lock().unwrap();
+        assert!(state.anonymize_profile);
+        assert!(state.anonymize_location);
+    }
+}
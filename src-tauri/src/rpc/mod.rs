@@ -3,6 +3,13 @@
 //! This module handles Discord RPC for showing user activity status
 //! in their Discord profile while using the launcher or playing.
 
+mod avatar;
+mod i18n;
+mod ipc;
+mod join;
 mod manager;
+pub mod names;
+mod session;
 
-pub use manager::RpcManager;
+pub use manager::{CustomActivity, RpcManager, RpcPreview, RpcStateSnapshot};
+pub use session::SessionSummary;
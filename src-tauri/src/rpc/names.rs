@@ -0,0 +1,238 @@
+//! Display names for Hypixel modes, gametypes and SkyBlock locations.
+//!
+//! The defaults ship embedded in the binary (`names.json`). They are layered
+//! with a list refreshed from the API, so new modes show up without a
+//! launcher release, and finally with `rpc-names.json` in the config
+//! directory (e.g. `~/.config/duelsplus/` on Linux), which always wins. Every layer only needs the entries it changes.
+//!
+//! Modes missing from every layer are remembered so contributors can submit
+//! names for them, and reported to the API if the user opted in.
+
+use crate::config;
+use crate::utils::{self, AppDir};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Display names bundled with the launcher
+const EMBEDDED_NAMES: &str = include_str!("names.json");
+
+/// Endpoint serving the latest display names
+const NAMES_API_URL: &str = "https://duelsplus.com/api/rpc-names";
+
+/// Endpoint collecting modes without a display name
+const UNKNOWN_MODES_API_URL: &str = "https://duelsplus.com/api/rpc-names/unknown";
+
+/// Name of the user override file in the config directory
+const OVERRIDE_FILE: &str = "rpc-names.json";
+
+/// Display names keyed by their Hypixel identifiers
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DisplayNames {
+    /// Game modes, e.g. `DUELS_SUMO_DUEL` → "Sumo Duel"
+    #[serde(default)]
+    pub modes: HashMap<String, String>,
+    /// Gametypes, e.g. `BEDWARS` → "BedWars"
+    #[serde(default)]
+    pub gametypes: HashMap<String, String>,
+    /// SkyBlock modes, e.g. `hub` → "SkyBlock Hub"
+    #[serde(default)]
+    pub skyblock_locations: HashMap<String, String>,
+}
+
+impl DisplayNames {
+    /// Parses the embedded defaults
+    fn embedded() -> Self {
+        serde_json::from_str(EMBEDDED_NAMES).expect("embedded names.json is valid")
+    }
+
+    /// Adds or replaces entries from another layer
+    fn merge(&mut self, other: DisplayNames) {
+        self.modes.extend(other.modes);
+        self.gametypes.extend(other.gametypes);
+        self.skyblock_locations.extend(other.skyblock_locations);
+    }
+
+    /// Builds the effective names from the defaults, an optional API layer
+    /// and the user override file
+    fn layered(remote: Option<DisplayNames>) -> Self {
+        let mut names = Self::embedded();
+        if let Some(remote) = remote {
+            names.merge(remote);
+        }
+        if let Some(local) = read_override() {
+            names.merge(local);
+        }
+        names
+    }
+}
+
+/// Names currently in use - initialized on first access
+static NAMES: OnceLock<RwLock<Arc<DisplayNames>>> = OnceLock::new();
+
+fn store() -> &'static RwLock<Arc<DisplayNames>> {
+    NAMES.get_or_init(|| RwLock::new(Arc::new(DisplayNames::layered(None))))
+}
+
+/// Returns the display names currently in use
+pub(crate) fn current() -> Arc<DisplayNames> {
+    store().read().unwrap().clone()
+}
+
+/// A game mode seen without a display name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnknownMode {
+    pub mode: String,
+    /// Games played in the mode this session
+    pub count: u32,
+    /// When the mode was first seen, as a unix timestamp
+    pub first_seen: i64,
+}
+
+/// Modes seen this session without a display name
+static UNKNOWN_MODES: Mutex<Vec<UnknownMode>> = Mutex::new(Vec::new());
+
+/// Records a game in `mode` if it has no display name.
+///
+/// The first time a mode is seen it is also reported to the API when
+/// `reportUnknownModes` is enabled. Must be called from the async runtime.
+pub(crate) fn note_mode(mode: &str) {
+    if current().modes.contains_key(mode) {
+        return;
+    }
+
+    if !record_unknown(mode) {
+        return;
+    }
+    let mode = mode.to_string();
+    tauri::async_runtime::spawn(async move {
+        let opted_in = config::manager::get_config()
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|c| c.report_unknown_modes);
+        if opted_in {
+            if let Err(e) = report_unknown(&mode).await {
+                eprintln!("[rpc] Failed to report unknown mode {}: {}", mode, e);
+            }
+        }
+    });
+}
+
+/// Counts a game in an unknown mode; returns true the first time it is seen
+fn record_unknown(mode: &str) -> bool {
+    let mut unknown = UNKNOWN_MODES.lock().unwrap();
+    if let Some(entry) = unknown.iter_mut().find(|u| u.mode == mode) {
+        entry.count += 1;
+        return false;
+    }
+    unknown.push(UnknownMode {
+        mode: mode.to_string(),
+        count: 1,
+        first_seen: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64,
+    });
+    true
+}
+
+/// Returns the modes seen this session without a display name, most played first
+pub fn unknown_modes() -> Vec<UnknownMode> {
+    let mut unknown = UNKNOWN_MODES.lock().unwrap().clone();
+    unknown.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.mode.cmp(&b.mode)));
+    unknown
+}
+
+/// Sends a mode without a display name to the API
+async fn report_unknown(mode: &str) -> Result<(), reqwest::Error> {
+    reqwest::Client::new()
+        .post(UNKNOWN_MODES_API_URL)
+        .json(&serde_json::json!({ "mode": mode }))
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Gets the path to the user override file
+fn override_path() -> Option<PathBuf> {
+    utils::get_app_path(AppDir::Config, OVERRIDE_FILE).ok()
+}
+
+/// Reads the user override file, ignoring it if missing or malformed
+fn read_override() -> Option<DisplayNames> {
+    let content = std::fs::read_to_string(override_path()?).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(names) => Some(names),
+        Err(e) => {
+            eprintln!("[rpc] Ignoring invalid {}: {}", OVERRIDE_FILE, e);
+            None
+        }
+    }
+}
+
+/// Fetches the latest display names from the API and applies them.
+///
+/// On failure the names in use are kept.
+pub async fn refresh() -> Result<(), String> {
+    let remote = reqwest::Client::new()
+        .get(NAMES_API_URL)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json::<DisplayNames>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let names = Arc::new(DisplayNames::layered(Some(remote)));
+    *store().write().unwrap() = names;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_names_parse() {
+        let names = DisplayNames::embedded();
+        assert_eq!(names.modes["DUELS_SUMO_DUEL"], "Sumo Duel");
+        assert_eq!(names.gametypes["BEDWARS"], "BedWars");
+        assert_eq!(names.skyblock_locations["hub"], "SkyBlock Hub");
+    }
+
+    #[test]
+    fn test_record_unknown_counts_games() {
+        assert!(record_unknown("DUELS_TEST_UNKNOWN_DUEL"));
+        assert!(!record_unknown("DUELS_TEST_UNKNOWN_DUEL"));
+
+        let entry = unknown_modes()
+            .into_iter()
+            .find(|u| u.mode == "DUELS_TEST_UNKNOWN_DUEL")
+            .unwrap();
+        assert_eq!(entry.count, 2);
+    }
+
+    #[test]
+    fn test_merge_overrides_and_extends() {
+        let mut names = DisplayNames::embedded();
+        let layer: DisplayNames = serde_json::from_str(
+            r#"{"modes":{"DUELS_SUMO_DUEL":"Sumo","DUELS_NEW_DUEL":"New Duel"}}"#,
+        )
+        .unwrap();
+        names.merge(layer);
+
+        assert_eq!(names.modes["DUELS_SUMO_DUEL"], "Sumo");
+        assert_eq!(names.modes["DUELS_NEW_DUEL"], "New Duel");
+        // Untouched sections keep their defaults
+        assert_eq!(names.gametypes["BEDWARS"], "BedWars");
+    }
+}
@@ -0,0 +1,155 @@
+//! Time tracking for the end-of-session recap.
+//!
+//! The RPC state already follows where the player is, so the tracker is fed
+//! from every state change: time connected to Hypixel counts as in game, the
+//! rest of the session as in the launcher, and time in a game mode is added
+//! up per mode.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Accumulated play time, updated as the player moves around
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionTracker {
+    /// Seconds connected to Hypixel, not counting the current stretch
+    playing_secs: i64,
+    /// Since when the player has been connected, if they are
+    playing_since: Option<i64>,
+    /// Seconds per game mode, not counting the current game
+    mode_secs: HashMap<String, i64>,
+    /// Mode being played and since when
+    current_mode: Option<(String, i64)>,
+}
+
+/// Time spent in one game mode
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeTime {
+    pub mode: String,
+    /// Display name, e.g. "Sumo Duel"
+    pub name: String,
+    pub secs: i64,
+}
+
+/// Summary of the launcher session, for the recap screen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    /// Launcher start, as a unix timestamp
+    pub session_start: i64,
+    pub total_secs: i64,
+    /// Time connected to Hypixel through the proxy
+    pub in_game_secs: i64,
+    pub in_launcher_secs: i64,
+    /// Time per game mode, longest first
+    pub modes: Vec<ModeTime>,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl SessionTracker {
+    /// Records where the player is now; `mode` is `None` outside of games
+    pub fn observe(&mut self, playing: bool, mode: Option<&str>, now: i64) {
+        match (playing, self.playing_since) {
+            (true, None) => self.playing_since = Some(now),
+            (false, Some(since)) => {
+                self.playing_secs += (now - since).max(0);
+                self.playing_since = None;
+            }
+            _ => {}
+        }
+
+        let mode = mode.filter(|_| playing);
+        if self.current_mode.as_ref().map(|(m, _)| m.as_str()) == mode {
+            return;
+        }
+        if let Some((previous, since)) = self.current_mode.take() {
+            *self.mode_secs.entry(previous).or_default() += (now - since).max(0);
+        }
+        self.current_mode = mode.map(|m| (m.to_string(), now));
+    }
+
+    /// Summarizes the session up to `now`, naming modes with `format_mode`
+    pub fn summary(
+        &self,
+        session_start: i64,
+        now: i64,
+        format_mode: impl Fn(&str) -> String,
+    ) -> SessionSummary {
+        let total_secs = (now - session_start).max(0);
+        let in_game_secs = (self.playing_secs
+            + self.playing_since.map_or(0, |since| (now - since).max(0)))
+        .min(total_secs);
+
+        let mut mode_secs = self.mode_secs.clone();
+        if let Some((mode, since)) = &self.current_mode {
+            *mode_secs.entry(mode.clone()).or_default() += (now - since).max(0);
+        }
+        let mut modes: Vec<ModeTime> = mode_secs
+            .into_iter()
+            .map(|(mode, secs)| ModeTime {
+                name: format_mode(&mode),
+                mode,
+                secs,
+            })
+            .collect();
+        modes.sort_by(|a, b| b.secs.cmp(&a.secs).then_with(|| a.mode.cmp(&b.mode)));
+
+        SessionSummary {
+            session_start,
+            total_secs,
+            in_game_secs,
+            in_launcher_secs: total_secs - in_game_secs,
+            modes,
+            wins: 0,
+            losses: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_times() {
+        let mut tracker = SessionTracker::default();
+        tracker.observe(false, None, 100);
+        tracker.observe(true, None, 200);
+        tracker.observe(true, Some("DUELS_SUMO_DUEL"), 250);
+        tracker.observe(true, Some("DUELS_CLASSIC_DUEL"), 300);
+        tracker.observe(true, None, 400);
+        tracker.observe(true, Some("DUELS_SUMO_DUEL"), 450);
+        tracker.observe(false, None, 500);
+
+        let summary = tracker.summary(0, 600, |m| m.to_lowercase());
+        assert_eq!(summary.total_secs, 600);
+        assert_eq!(summary.in_game_secs, 300);
+        assert_eq!(summary.in_launcher_secs, 300);
+        assert_eq!(
+            summary.modes,
+            vec![
+                ModeTime {
+                    mode: "DUELS_CLASSIC_DUEL".to_string(),
+                    name: "duels_classic_duel".to_string(),
+                    secs: 100,
+                },
+                ModeTime {
+                    mode: "DUELS_SUMO_DUEL".to_string(),
+                    name: "duels_sumo_duel".to_string(),
+                    secs: 100,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_includes_current_game() {
+        let mut tracker = SessionTracker::default();
+        tracker.observe(true, Some("DUELS_SUMO_DUEL"), 100);
+
+        let summary = tracker.summary(0, 160, |m| m.to_string());
+        assert_eq!(summary.in_game_secs, 60);
+        assert_eq!(summary.modes[0].secs, 60);
+    }
+}
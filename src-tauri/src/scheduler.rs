@@ -0,0 +1,219 @@
+//! Periodic background tasks.
+//!
+//! Tasks register with a name, an interval and a jitter. Each runs on its
+//! own loop, waiting the interval plus a random delay of up to the jitter
+//! between runs, so launchers started together don't all hit the API at
+//! once. The first run is one wait after registering.
+//!
+//! `run_task_now` runs a task early, mostly for debugging; its next run is
+//! then a full wait later.
+
+use futures_util::future::BoxFuture;
+use serde::Serialize;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tokio::sync::Notify;
+
+type TaskFn = Arc<dyn Fn(AppHandle) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// A registered task and what it last did
+struct Task {
+    name: &'static str,
+    interval: Duration,
+    jitter: Duration,
+    run_now: Notify,
+    state: Mutex<TaskState>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TaskState {
+    running: bool,
+    runs: u64,
+    /// Unix time in milliseconds
+    last_run: Option<u64>,
+    last_duration_ms: Option<u64>,
+    last_error: Option<String>,
+    /// Unix time in milliseconds
+    next_run: Option<u64>,
+}
+
+/// A scheduled task, as returned by `list_scheduled_tasks`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTask {
+    pub name: String,
+    pub interval_secs: u64,
+    pub jitter_secs: u64,
+    pub running: bool,
+    pub runs: u64,
+    /// When the last run started, as unix time in milliseconds
+    pub last_run: Option<u64>,
+    pub last_duration_ms: Option<u64>,
+    /// Why the last run failed, `None` if it succeeded
+    pub last_error: Option<String>,
+    /// When the next run is due, as unix time in milliseconds
+    pub next_run: Option<u64>,
+}
+
+/// Runs registered tasks periodically, managed by Tauri
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: Mutex<Vec<Arc<Task>>>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a task and starts its loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Unique name, used by `run_task_now`
+    /// * `interval` - Time between runs
+    /// * `jitter` - Longest random delay added to each wait
+    /// * `run` - The task; failures are logged and kept for
+    ///   `list_scheduled_tasks`
+    pub fn register<F, Fut>(
+        &self,
+        app: &AppHandle,
+        name: &'static str,
+        interval: Duration,
+        jitter: Duration,
+        run: F,
+    ) where
+        F: Fn(AppHandle) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let task = Arc::new(Task {
+            name,
+            interval,
+            jitter,
+            run_now: Notify::new(),
+            state: Mutex::new(TaskState::default()),
+        });
+        let run: TaskFn = Arc::new(move |app| Box::pin(run(app)));
+        {
+            let mut tasks = self.tasks.lock().unwrap();
+            if tasks.iter().any(|t| t.name == name) {
+                eprintln!("[scheduler] Task {} is already registered", name);
+                return;
+            }
+            tasks.push(task.clone());
+        }
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let wait = task.interval + random_delay(task.jitter);
+                task.state.lock().unwrap().next_run = Some(unix_millis() + wait.as_millis() as u64);
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = task.run_now.notified() => {}
+                }
+                run_task(&task, &run, &app).await;
+            }
+        });
+    }
+
+    /// Gets the registered tasks and their last runs
+    pub fn list(&self) -> Vec<ScheduledTask> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|task| {
+                let state = task.state.lock().unwrap().clone();
+                ScheduledTask {
+                    name: task.name.to_string(),
+                    interval_secs: task.interval.as_secs(),
+                    jitter_secs: task.jitter.as_secs(),
+                    running: state.running,
+                    runs: state.runs,
+                    last_run: state.last_run,
+                    last_duration_ms: state.last_duration_ms,
+                    last_error: state.last_error,
+                    next_run: state.next_run,
+                }
+            })
+            .collect()
+    }
+
+    /// Runs a task now instead of at its next run
+    pub fn run_now(&self, name: &str) -> Result<(), String> {
+        let tasks = self.tasks.lock().unwrap();
+        let task = tasks
+            .iter()
+            .find(|task| task.name == name)
+            .ok_or_else(|| format!("No scheduled task named {}", name))?;
+        if task.state.lock().unwrap().running {
+            return Err(format!("{} is already running", name));
+        }
+        task.run_now.notify_one();
+        Ok(())
+    }
+}
+
+async fn run_task(task: &Task, run: &TaskFn, app: &AppHandle) {
+    {
+        let mut state = task.state.lock().unwrap();
+        state.running = true;
+        state.next_run = None;
+        state.last_run = Some(unix_millis());
+    }
+    let started = Instant::now();
+    let result = run(app.clone()).await;
+    if let Err(e) = &result {
+        eprintln!("[scheduler] {} failed: {}", task.name, e);
+    }
+
+    let mut state = task.state.lock().unwrap();
+    state.running = false;
+    state.runs += 1;
+    state.last_duration_ms = Some(started.elapsed().as_millis() as u64);
+    state.last_error = result.err();
+}
+
+/// Picks a random delay of up to `max`
+fn random_delay(max: Duration) -> Duration {
+    let max_ms = max.as_millis() as u64;
+    if max_ms == 0 {
+        return Duration::ZERO;
+    }
+    // Freshly keyed for every call, which is random enough for jitter
+    let random = RandomState::new().hash_one(unix_millis());
+    Duration::from_millis(random % (max_ms + 1))
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_delay_stays_in_range() {
+        assert_eq!(random_delay(Duration::ZERO), Duration::ZERO);
+        let max = Duration::from_secs(5);
+        for _ in 0..100 {
+            assert!(random_delay(max) <= max);
+        }
+    }
+
+    #[test]
+    fn test_run_now_unknown_task() {
+        let scheduler = Scheduler::new();
+        assert!(scheduler.run_now("nope").is_err());
+        assert!(scheduler.list().is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+//! Encryption for secrets kept in the config, like the Hypixel API key.
+//!
+//! The config gets copied around (exported with diagnostics, synced,
+//! pasted into support threads), so secrets in it are sealed with
+//! ChaCha20-Poly1305 under a random key kept in `secret.key` in the data
+//! directory. A sealed value has the form
+//! `chacha20poly1305$<nonce>$<ciphertext>`, both in hex. Without the key
+//! file, e.g. on another computer, the secret has to be entered again.
+
+use crate::utils::{self, from_hex, to_hex, AppDir};
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::path::Path;
+use std::sync::Mutex;
+
+const SCHEME: &str = "chacha20poly1305";
+
+/// Name of the key file in the data directory
+const KEY_FILE: &str = "secret.key";
+
+const KEY_LEN: usize = 32;
+
+/// Serializes creating the key file
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Reads the key, creating it the first time
+fn load_key() -> Result<LessSafeKey, String> {
+    let _lock = LOCK.lock().unwrap();
+    let path = utils::get_app_path(AppDir::Data, KEY_FILE)?;
+    let bytes = match std::fs::read_to_string(&path) {
+        Ok(hex) => from_hex(hex.trim())
+            .filter(|bytes| bytes.len() == KEY_LEN)
+            .ok_or_else(|| format!("{} is corrupted", KEY_FILE))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => create_key(&path)?,
+        Err(e) => return Err(e.to_string()),
+    };
+    key_from(&bytes)
+}
+
+fn create_key(path: &Path) -> Result<Vec<u8>, String> {
+    let mut bytes = vec![0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "Failed to generate a key".to_string())?;
+    utils::write_private(path, to_hex(&bytes))?;
+    Ok(bytes)
+}
+
+fn key_from(bytes: &[u8]) -> Result<LessSafeKey, String> {
+    UnboundKey::new(&CHACHA20_POLY1305, bytes)
+        .map(LessSafeKey::new)
+        .map_err(|_| "Invalid key".to_string())
+}
+
+/// Seals a secret to store in the config
+pub(crate) fn seal(secret: &str) -> Result<String, String> {
+    seal_with(&load_key()?, secret)
+}
+
+/// Opens a secret sealed by [`seal`]
+pub(crate) fn open(sealed: &str) -> Result<String, String> {
+    open_with(&load_key()?, sealed)
+}
+
+fn seal_with(key: &LessSafeKey, secret: &str) -> Result<String, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "Failed to generate a nonce".to_string())?;
+    let mut data = secret.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "Failed to encrypt".to_string())?;
+    Ok(format!("{}${}${}", SCHEME, to_hex(&nonce), to_hex(&data)))
+}
+
+fn open_with(key: &LessSafeKey, sealed: &str) -> Result<String, String> {
+    let invalid = || "The saved secret can't be read, enter it again".to_string();
+    let parts: Vec<&str> = sealed.split('$').collect();
+    let [SCHEME, nonce, data] = parts[..] else {
+        return Err(invalid());
+    };
+    let nonce = from_hex(nonce)
+        .and_then(|nonce| aead::Nonce::try_assume_unique_for_key(&nonce).ok())
+        .ok_or_else(invalid)?;
+    let mut data = from_hex(data).ok_or_else(invalid)?;
+    let plain = key
+        .open_in_place(nonce, Aad::empty(), &mut data)
+        .map_err(|_| invalid())?;
+    String::from_utf8(plain.to_vec()).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = key_from(&[7u8; KEY_LEN]).unwrap();
+        let sealed = seal_with(&key, "0f1e2d3c-api-key").unwrap();
+        assert!(sealed.starts_with("chacha20poly1305$"));
+        assert!(!sealed.contains("api-key"));
+        assert_eq!(open_with(&key, &sealed).unwrap(), "0f1e2d3c-api-key");
+
+        // Sealing twice gives different values
+        assert_ne!(seal_with(&key, "0f1e2d3c-api-key").unwrap(), sealed);
+    }
+
+    #[test]
+    fn test_open_rejects_other_keys_and_garbage() {
+        let key = key_from(&[7u8; KEY_LEN]).unwrap();
+        let other = key_from(&[8u8; KEY_LEN]).unwrap();
+        let sealed = seal_with(&key, "secret").unwrap();
+        assert!(open_with(&other, &sealed).is_err());
+        assert!(open_with(&key, "secret").is_err());
+        assert!(open_with(&key, "chacha20poly1305$zz$00").is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Whether Hypixel and Mojang's session servers are up.
+//!
+//! When joining fails, it's usually one of them rather than the proxy, and
+//! `get_service_status` tells which: Hypixel is checked with a connect to
+//! its Minecraft port and its public status page, the session server with
+//! a request it answers even for nobody. Results are kept for
+//! [`MAX_AGE`], since a failed login tends to ask several times.
+
+use crate::ping::{self, HYPIXEL_HOST, MINECRAFT_PORT};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Hypixel's status page, in the Statuspage API format
+const HYPIXEL_STATUS_URL: &str = "https://status.hypixel.net/api/v2/status.json";
+
+/// Answers 204 for a player who hasn't joined, so it's up if it answers
+const SESSION_SERVER_URL: &str =
+    "https://sessionserver.mojang.com/session/minecraft/hasJoined?username=Notch&serverId=0";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a status is reused
+const MAX_AGE: Duration = Duration::from_secs(30);
+
+static LAST: Mutex<Option<(Instant, ServiceStatus)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Service {
+    Hypixel,
+    MojangSession,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServiceState {
+    Up,
+    /// Reachable, but reporting problems
+    Degraded,
+    Down,
+}
+
+/// One service's check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceCheck {
+    pub service: Service,
+    pub state: ServiceState,
+    /// What the service reports about itself, or why it's down
+    pub detail: Option<String>,
+}
+
+/// Every check, as returned by `get_service_status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatus {
+    pub services: Vec<ServiceCheck>,
+    /// A sentence for the user when something's wrong, `None` when all is up
+    pub summary: Option<String>,
+    /// Unix time in seconds
+    pub checked_at: i64,
+}
+
+/// Checks every service, or returns the last check if it's recent
+pub(crate) async fn get(refresh: bool) -> ServiceStatus {
+    if !refresh {
+        let last = LAST.lock().unwrap().clone();
+        if let Some((_, status)) = last.filter(|(at, _)| at.elapsed() < MAX_AGE) {
+            return status;
+        }
+    }
+
+    let client = reqwest::Client::new();
+    let (hypixel, session) = tokio::join!(check_hypixel(&client), check_session_server(&client));
+    let services = vec![hypixel, session];
+    let status = ServiceStatus {
+        summary: summarize(&services),
+        services,
+        checked_at: chrono::Utc::now().timestamp(),
+    };
+    *LAST.lock().unwrap() = Some((Instant::now(), status.clone()));
+    status
+}
+
+async fn check_hypixel(client: &reqwest::Client) -> ServiceCheck {
+    let (reachable, reported) = tokio::join!(
+        ping::connect_time(HYPIXEL_HOST, MINECRAFT_PORT),
+        hypixel_status_page(client)
+    );
+    let (state, detail) = hypixel_state(reachable.is_some(), reported);
+    ServiceCheck {
+        service: Service::Hypixel,
+        state,
+        detail,
+    }
+}
+
+/// Gets the status page's indicator ("none", "minor", "major" or
+/// "critical") and description, `None` if it can't be read
+async fn hypixel_status_page(client: &reqwest::Client) -> Option<(String, String)> {
+    let body: Value = client
+        .get(HYPIXEL_STATUS_URL)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    let status = body.get("status")?;
+    Some((
+        status.get("indicator")?.as_str()?.to_string(),
+        status
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    ))
+}
+
+fn hypixel_state(
+    reachable: bool,
+    reported: Option<(String, String)>,
+) -> (ServiceState, Option<String>) {
+    let description = reported
+        .as_ref()
+        .map(|(_, description)| description.clone())
+        .filter(|description| !description.is_empty());
+    if !reachable {
+        let detail = description.unwrap_or_else(|| format!("{} didn't answer", HYPIXEL_HOST));
+        return (ServiceState::Down, Some(detail));
+    }
+    match reported.as_ref().map(|(indicator, _)| indicator.as_str()) {
+        None | Some("none") => (ServiceState::Up, description),
+        Some(_) => (ServiceState::Degraded, description),
+    }
+}
+
+async fn check_session_server(client: &reqwest::Client) -> ServiceCheck {
+    let response = client
+        .get(SESSION_SERVER_URL)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await;
+    let (state, detail) = match response {
+        Ok(response) if response.status().is_server_error() => (
+            ServiceState::Down,
+            Some(format!("Answered {}", response.status())),
+        ),
+        Ok(_) => (ServiceState::Up, None),
+        Err(e) if e.is_timeout() => (ServiceState::Down, Some("Timed out".to_string())),
+        Err(e) => (ServiceState::Down, Some(e.to_string())),
+    };
+    ServiceCheck {
+        service: Service::MojangSession,
+        state,
+        detail,
+    }
+}
+
+fn summarize(services: &[ServiceCheck]) -> Option<String> {
+    let state = |service| {
+        services
+            .iter()
+            .find(|check| check.service == service)
+            .map(|check| check.state)
+    };
+    match (state(Service::Hypixel), state(Service::MojangSession)) {
+        (Some(ServiceState::Down), Some(ServiceState::Down)) => Some(
+            "Neither Hypixel nor Mojang can be reached. Check your internet connection."
+                .to_string(),
+        ),
+        (Some(ServiceState::Down), _) => Some("Hypixel appears to be down.".to_string()),
+        (_, Some(ServiceState::Down)) => Some(
+            "Mojang's session servers appear to be down, so logging in to Hypixel may fail."
+                .to_string(),
+        ),
+        (Some(ServiceState::Degraded), _) => {
+            Some("Hypixel is reporting problems right now.".to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(service: Service, state: ServiceState) -> ServiceCheck {
+        ServiceCheck {
+            service,
+            state,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_hypixel_state() {
+        let reported = |indicator: &str, description: &str| {
+            Some((indicator.to_string(), description.to_string()))
+        };
+        assert_eq!(
+            hypixel_state(true, reported("none", "All Systems Operational")).0,
+            ServiceState::Up
+        );
+        assert_eq!(
+            hypixel_state(true, reported("major", "Partial System Outage")),
+            (
+                ServiceState::Degraded,
+                Some("Partial System Outage".to_string())
+            )
+        );
+        assert_eq!(hypixel_state(true, None).0, ServiceState::Up);
+        assert_eq!(hypixel_state(false, None).0, ServiceState::Down);
+    }
+
+    #[test]
+    fn test_summarize() {
+        use ServiceState::*;
+        let summary = |hypixel, session| {
+            summarize(&[
+                check(Service::Hypixel, hypixel),
+                check(Service::MojangSession, session),
+            ])
+        };
+        assert_eq!(summary(Up, Up), None);
+        assert_eq!(
+            summary(Down, Up).as_deref(),
+            Some("Hypixel appears to be down.")
+        );
+        assert!(summary(Up, Down).unwrap().starts_with("Mojang"));
+        assert!(summary(Down, Down).unwrap().contains("internet"));
+        assert!(summary(Degraded, Up).is_some());
+    }
+}
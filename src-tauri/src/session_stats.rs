@@ -0,0 +1,146 @@
+//! Wins, losses, kills and deaths since the proxy launched.
+//!
+//! Counted from the proxy's game events, and reset when the proxy launches
+//! or with `reset_session_stats`. Every change is emitted as
+//! `session-stats-updated` and passed on to Discord Rich Presence, which
+//! shows the record and win streak, to notifications, which announce win
+//! streak milestones, and to OBS overlays (see [`crate::obs`]).
+
+use crate::notifications;
+use crate::obs;
+use crate::proxy::models::GameEventKind;
+use crate::rpc::RpcManager;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+static STATS: Mutex<SessionStats> = Mutex::new(SessionStats::new());
+
+/// Stats of the session, as returned by `get_session_stats` and emitted as
+/// `session-stats-updated`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStats {
+    pub wins: u32,
+    pub losses: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    /// Consecutive wins, reset by a loss
+    pub win_streak: u32,
+    pub best_win_streak: u32,
+    /// Unix time the session started, in seconds; 0 until the proxy first
+    /// launches
+    pub started_at: i64,
+}
+
+impl SessionStats {
+    const fn new() -> Self {
+        Self {
+            wins: 0,
+            losses: 0,
+            kills: 0,
+            deaths: 0,
+            win_streak: 0,
+            best_win_streak: 0,
+            started_at: 0,
+        }
+    }
+
+    /// Counts a game event, returning whether anything changed
+    fn apply(&mut self, kind: GameEventKind) -> bool {
+        match kind {
+            GameEventKind::Win => {
+                self.wins += 1;
+                self.win_streak += 1;
+                self.best_win_streak = self.best_win_streak.max(self.win_streak);
+            }
+            GameEventKind::Loss => {
+                self.losses += 1;
+                self.win_streak = 0;
+            }
+            GameEventKind::Kill => self.kills += 1,
+            GameEventKind::Death => self.deaths += 1,
+            GameEventKind::GameStart
+            | GameEventKind::GameEnd
+            | GameEventKind::Goal
+            | GameEventKind::GoalConceded
+            | GameEventKind::BedBroken
+            | GameEventKind::BedLost => return false,
+        }
+        true
+    }
+}
+
+/// Gets the stats of the current session
+pub(crate) fn get() -> SessionStats {
+    *STATS.lock().unwrap()
+}
+
+/// Starts a new session, e.g. when the proxy launches
+pub(crate) fn reset(app: &AppHandle) {
+    let stats = {
+        let mut stats = STATS.lock().unwrap();
+        *stats = SessionStats {
+            started_at: chrono::Utc::now().timestamp(),
+            ..SessionStats::new()
+        };
+        *stats
+    };
+    publish(app, &stats);
+}
+
+/// Counts a game event from the proxy
+pub(crate) fn on_game_event(app: &AppHandle, kind: GameEventKind) {
+    let stats = {
+        let mut stats = STATS.lock().unwrap();
+        if !stats.apply(kind) {
+            return;
+        }
+        *stats
+    };
+    if kind == GameEventKind::Win {
+        notifications::on_win_streak(app, stats.win_streak);
+    }
+    publish(app, &stats);
+}
+
+fn publish(app: &AppHandle, stats: &SessionStats) {
+    if let Some(rpc) = app.try_state::<RpcManager>() {
+        rpc.set_session_stats(stats.wins, stats.losses, stats.win_streak);
+    }
+    let _ = app.emit("session-stats-updated", stats);
+    obs::refresh(app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let mut stats = SessionStats::new();
+        assert!(!stats.apply(GameEventKind::GameStart));
+        for kind in [
+            GameEventKind::Kill,
+            GameEventKind::Win,
+            GameEventKind::Win,
+            GameEventKind::Death,
+            GameEventKind::Loss,
+            GameEventKind::Win,
+        ] {
+            assert!(stats.apply(kind));
+        }
+        assert_eq!(
+            stats,
+            SessionStats {
+                wins: 3,
+                losses: 1,
+                kills: 1,
+                deaths: 1,
+                win_streak: 1,
+                best_win_streak: 2,
+                started_at: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Startup ordering and timings.
+//!
+//! Once the window is set up, startup work runs as parallel tasks: loading
+//! the config, reading the token, checking the network and API, and
+//! prefetching proxy releases. Discord is connected only after the first
+//! frame (or [`FIRST_FRAME_TIMEOUT`], for a window that starts hidden), so
+//! it doesn't compete with showing the launcher.
+//!
+//! Each step records when it finished, relative to the process starting.
+//! Once startup is done they're emitted as `startup-timings`, so
+//! regressions are measurable.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// How long to wait for the first frame before connecting Discord anyway
+const FIRST_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// When `run_app` started
+static STARTED: OnceLock<Instant> = OnceLock::new();
+
+/// Steps finished so far, in order
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+
+static FIRST_FRAME_SHOWN: AtomicBool = AtomicBool::new(false);
+static FIRST_FRAME: Notify = Notify::const_new();
+
+/// A startup step and when it finished
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    /// e.g. `config`, `firstFrame` or `ready`
+    pub name: &'static str,
+    /// Milliseconds since the process started
+    pub ms: u64,
+}
+
+/// Payload of the `startup-timings` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupTimings {
+    pub phases: Vec<StartupPhase>,
+    /// When the last step finished
+    pub total_ms: u64,
+}
+
+impl StartupTimings {
+    fn new(phases: Vec<StartupPhase>) -> Self {
+        let total_ms = phases.iter().map(|p| p.ms).max().unwrap_or_default();
+        Self { phases, total_ms }
+    }
+}
+
+/// Starts the clock, to call first thing in `run_app`
+pub(crate) fn begin() {
+    STARTED.get_or_init(Instant::now);
+}
+
+/// Records that a startup step finished
+pub(crate) fn mark(name: &'static str) {
+    let ms = STARTED.get_or_init(Instant::now).elapsed().as_millis() as u64;
+    PHASES.lock().unwrap().push(StartupPhase { name, ms });
+}
+
+/// Records the first frame, to call when the main window finishes loading
+pub(crate) fn on_first_frame() {
+    if !FIRST_FRAME_SHOWN.swap(true, Ordering::SeqCst) {
+        mark("firstFrame");
+        FIRST_FRAME.notify_waiters();
+    }
+}
+
+/// Waits until the first frame, or [`FIRST_FRAME_TIMEOUT`] at most
+pub(crate) async fn first_frame() {
+    let notified = FIRST_FRAME.notified();
+    tokio::pin!(notified);
+    // Registered before checking, so a frame in between isn't missed
+    notified.as_mut().enable();
+    if FIRST_FRAME_SHOWN.load(Ordering::SeqCst) {
+        return;
+    }
+    let _ = tokio::time::timeout(FIRST_FRAME_TIMEOUT, notified).await;
+}
+
+/// Gets the steps finished so far
+pub(crate) fn timings() -> StartupTimings {
+    StartupTimings::new(PHASES.lock().unwrap().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_is_last_phase() {
+        let timings = StartupTimings::new(vec![
+            StartupPhase {
+                name: "config",
+                ms: 12,
+            },
+            StartupPhase {
+                name: "releases",
+                ms: 840,
+            },
+            StartupPhase {
+                name: "ready",
+                ms: 95,
+            },
+        ]);
+        assert_eq!(timings.total_ms, 840);
+        assert_eq!(StartupTimings::new(Vec::new()).total_ms, 0);
+    }
+}
@@ -0,0 +1,126 @@
+//! UI state kept by the backend.
+//!
+//! Webview storage is wiped when the webview is reset or reinstalled, so
+//! state worth keeping (the last opened page, dismissed banners, onboarding
+//! progress) is also stored in `app-state.json` in the data directory, via
+//! `get_app_state` and `set_app_state`. Values are any JSON, keyed by name.
+
+use crate::utils::{self, AppDir};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Name of the state file in the data directory
+const STATE_FILE: &str = "app-state.json";
+
+/// Longest key accepted, to keep the file from filling with junk
+const MAX_KEY_LEN: usize = 64;
+
+/// State loaded from the state file, `None` until first used
+static STATE: Mutex<Option<StateStore>> = Mutex::new(None);
+
+/// Values by key, and the file they're saved to
+#[derive(Debug)]
+struct StateStore {
+    path: PathBuf,
+    values: Map<String, Value>,
+}
+
+impl StateStore {
+    /// Loads the state file, starting empty if it's missing or malformed
+    fn open(path: PathBuf) -> Self {
+        let values = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(values) => Some(values),
+                Err(e) => {
+                    eprintln!("[state] Ignoring invalid {}: {}", STATE_FILE, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self { path, values }
+    }
+
+    fn get(&self, key: &str) -> Option<Value> {
+        self.values.get(key).cloned()
+    }
+
+    /// Sets a value, or removes it if it's `null`, and saves the file
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(format!("Keys must be 1 to {} characters", MAX_KEY_LEN));
+        }
+        if value.is_null() {
+            self.values.remove(key);
+        } else {
+            self.values.insert(key.to_string(), value);
+        }
+        self.save()
+    }
+
+    /// Writes the file through a temporary one, so a crash mid-write
+    /// doesn't lose everything
+    fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(&self.values).map_err(|e| e.to_string())?;
+        let temp = self.path.with_extension("json.tmp");
+        std::fs::write(&temp, content).map_err(|e| e.to_string())?;
+        std::fs::rename(&temp, &self.path).map_err(|e| e.to_string())
+    }
+}
+
+fn with_store<T>(f: impl FnOnce(&mut StateStore) -> T) -> Result<T, String> {
+    let mut state = STATE.lock().unwrap();
+    if state.is_none() {
+        *state = Some(StateStore::open(utils::get_app_path(
+            AppDir::Data,
+            STATE_FILE,
+        )?));
+    }
+    Ok(f(state.as_mut().unwrap()))
+}
+
+/// Gets a stored value, `None` if it was never set
+pub(crate) fn get(key: &str) -> Result<Option<Value>, String> {
+    with_store(|store| store.get(key))
+}
+
+/// Stores a value, or removes it if it's `null`
+pub(crate) fn set(key: &str, value: Value) -> Result<(), String> {
+    with_store(|store| store.set(key, value))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_set_persists_and_null_removes() {
+        let dir = std::env::temp_dir().join(format!("duelsplus-state-test-{}", std::process::id()));
+        let path = dir.join(STATE_FILE);
+
+        let mut store = StateStore::open(path.clone());
+        assert_eq!(store.get("activeTab"), None);
+        store.set("activeTab", json!("stats")).unwrap();
+        store.set("dismissedBanners", json!(["beta"])).unwrap();
+        store.set("dismissedBanners", Value::Null).unwrap();
+
+        let reopened = StateStore::open(path);
+        assert_eq!(reopened.get("activeTab"), Some(json!("stats")));
+        assert_eq!(reopened.get("dismissedBanners"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_rejects_bad_keys() {
+        let path = std::env::temp_dir().join("duelsplus-state-unused.json");
+        let mut store = StateStore::open(path);
+        assert!(store.set("", json!(1)).is_err());
+        assert!(store.set(&"k".repeat(MAX_KEY_LEN + 1), json!(1)).is_err());
+    }
+}
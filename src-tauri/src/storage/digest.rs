@@ -0,0 +1,284 @@
+//! Daily and weekly summaries of the match history.
+//!
+//! `get_stats_digest` sums up the matches of today or this week so far, in
+//! local time with weeks starting on Monday: the games and win rate, the
+//! best mode and the longest win streak.
+//!
+//! The `stats-digest` task checks hourly for a new week. On Mondays, with
+//! `notifyWeeklyDigest` on, it sends a recap of the week before, once: the
+//! week it last recapped is kept in the app state (see
+//! [`crate::state_store`]), so restarting doesn't send it again.
+
+use super::matches::{self, MatchRecord, MatchResult};
+use crate::i18n::t;
+use crate::notifications::{self, Category};
+use crate::state_store;
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// App state key of the Monday starting the last week recapped
+const RECAPPED_KEY: &str = "weeklyDigestRecapped";
+
+/// Games a mode needs to be the best mode, so one lucky win doesn't make it;
+/// if no mode has that many, all of them count
+const BEST_MODE_MIN_GAMES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DigestPeriod {
+    Day,
+    Week,
+}
+
+impl DigestPeriod {
+    /// First and last day of the period `today` is in
+    fn days(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            DigestPeriod::Day => (today, today),
+            DigestPeriod::Week => {
+                let monday = today.week(Weekday::Mon).first_day();
+                (monday, monday + Days::new(6))
+            }
+        }
+    }
+}
+
+/// How a mode went
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeSummary {
+    pub mode: String,
+    pub games: u32,
+    pub wins: u32,
+    /// Wins over games, from 0 to 1
+    pub win_rate: f64,
+}
+
+/// A summary of one day or week, as returned by `get_stats_digest`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsDigest {
+    pub period: DigestPeriod,
+    /// Unix time the period started, in seconds
+    pub start: i64,
+    /// Unix time it ends, exclusive
+    pub end: i64,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    /// Wins over games, from 0 to 1; `None` without games
+    pub win_rate: Option<f64>,
+    /// The mode with the best win rate, `None` without games
+    pub best_mode: Option<ModeSummary>,
+    /// Most wins in a row, where losses end a streak and draws don't
+    pub longest_streak: u32,
+}
+
+/// Unix time local midnight starts `date`
+fn midnight(date: NaiveDate) -> i64 {
+    date.and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .map_or_else(
+            || date.and_time(NaiveTime::MIN).and_utc().timestamp(),
+            |time| time.timestamp(),
+        )
+}
+
+/// Sums up the matches started between `start` and `end`
+fn summarize(period: DigestPeriod, start: i64, end: i64, matches: &[MatchRecord]) -> StatsDigest {
+    let mut digest = StatsDigest {
+        period,
+        start,
+        end,
+        games: 0,
+        wins: 0,
+        losses: 0,
+        draws: 0,
+        kills: 0,
+        deaths: 0,
+        win_rate: None,
+        best_mode: None,
+        longest_streak: 0,
+    };
+    let mut streak = 0;
+    let mut modes: HashMap<&str, (u32, u32)> = HashMap::new();
+    for record in matches
+        .iter()
+        .filter(|record| (start..end).contains(&record.started_at))
+    {
+        digest.games += 1;
+        digest.kills += record.kills;
+        digest.deaths += record.deaths;
+        let won = record.result == MatchResult::Win;
+        match record.result {
+            MatchResult::Win => {
+                digest.wins += 1;
+                streak += 1;
+                digest.longest_streak = digest.longest_streak.max(streak);
+            }
+            MatchResult::Loss => {
+                digest.losses += 1;
+                streak = 0;
+            }
+            MatchResult::Draw => digest.draws += 1,
+        }
+        if let Some(mode) = &record.mode {
+            let (games, wins) = modes.entry(mode).or_default();
+            *games += 1;
+            *wins += won as u32;
+        }
+    }
+
+    let rate = |wins: u32, games: u32| wins as f64 / games as f64;
+    digest.win_rate = (digest.games > 0).then(|| rate(digest.wins, digest.games));
+    let enough = modes
+        .values()
+        .any(|(games, _)| *games >= BEST_MODE_MIN_GAMES);
+    digest.best_mode = modes
+        .into_iter()
+        .filter(|(_, (games, _))| !enough || *games >= BEST_MODE_MIN_GAMES)
+        .map(|(mode, (games, wins))| ModeSummary {
+            mode: mode.to_string(),
+            games,
+            wins,
+            win_rate: rate(wins, games),
+        })
+        .max_by(|a, b| {
+            a.win_rate
+                .total_cmp(&b.win_rate)
+                .then(a.games.cmp(&b.games))
+                // Any order, as long as it's always the same
+                .then(b.mode.cmp(&a.mode))
+        });
+    digest
+}
+
+fn digest_of(period: DigestPeriod, day: NaiveDate) -> Result<StatsDigest, String> {
+    let (first, last) = period.days(day);
+    Ok(summarize(
+        period,
+        midnight(first),
+        midnight(last + Days::new(1)),
+        &matches::all()?,
+    ))
+}
+
+/// Sums up today's or this week's matches so far
+pub(crate) fn digest(period: DigestPeriod) -> Result<StatsDigest, String> {
+    digest_of(period, Local::now().date_naive())
+}
+
+/// Sends last week's recap on Mondays, for the scheduler
+pub(crate) async fn check_weekly(app: AppHandle) -> Result<(), String> {
+    let today = Local::now().date_naive();
+    if today.weekday() != Weekday::Mon {
+        return Ok(());
+    }
+    let last_week = today - Days::new(7);
+    let recapped = last_week.to_string();
+    let last_recapped = state_store::get(RECAPPED_KEY)?;
+    if last_recapped.as_ref().and_then(|value| value.as_str()) == Some(recapped.as_str()) {
+        return Ok(());
+    }
+    state_store::set(RECAPPED_KEY, serde_json::json!(recapped))?;
+
+    let digest = digest_of(DigestPeriod::Week, last_week)?;
+    if let Some(win_rate) = digest.win_rate {
+        notifications::notify(
+            &app,
+            Category::WeeklyDigest,
+            t!("notifications.weeklyDigest.title"),
+            t!(
+                "notifications.weeklyDigest.body",
+                games = digest.games,
+                rate = (win_rate * 100.0).round(),
+                streak = digest.longest_streak
+            ),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(started_at: i64, mode: &str, result: MatchResult) -> MatchRecord {
+        MatchRecord {
+            id: started_at as u64,
+            mode: Some(mode.to_string()),
+            map: None,
+            result,
+            started_at,
+            duration_secs: 60,
+            kills: 1,
+            deaths: (result == MatchResult::Loss) as u32,
+            opponents: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        use MatchResult::*;
+        let matches: Vec<MatchRecord> = [
+            ("DUELS_SUMO_DUEL", Win),
+            ("DUELS_SUMO_DUEL", Win),
+            ("DUELS_SUMO_DUEL", Draw),
+            ("DUELS_SUMO_DUEL", Win),
+            ("DUELS_BRIDGE_DUEL", Loss),
+            ("DUELS_BRIDGE_DUEL", Win),
+            ("DUELS_BRIDGE_DUEL", Win),
+            // A perfect record, but too few games to count
+            ("DUELS_UHC_DUEL", Win),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (mode, result))| record(100 + i as i64, mode, *result))
+        .chain([record(50, "DUELS_UHC_DUEL", Loss)])
+        .collect();
+
+        let digest = summarize(DigestPeriod::Day, 100, 200, &matches);
+        assert_eq!(digest.games, 8);
+        assert_eq!((digest.wins, digest.losses, digest.draws), (6, 1, 1));
+        assert_eq!(digest.win_rate, Some(0.75));
+        // The draw doesn't end the first streak
+        assert_eq!(digest.longest_streak, 3);
+        let best = digest.best_mode.unwrap();
+        assert_eq!(best.mode, "DUELS_SUMO_DUEL");
+        assert_eq!((best.games, best.wins), (4, 3));
+    }
+
+    #[test]
+    fn test_summarize_without_games() {
+        let digest = summarize(DigestPeriod::Week, 0, 100, &[]);
+        assert_eq!(digest.games, 0);
+        assert_eq!(digest.win_rate, None);
+        assert_eq!(digest.best_mode, None);
+
+        // With few games, the best of them still counts
+        let digest = summarize(
+            DigestPeriod::Day,
+            0,
+            100,
+            &[record(1, "DUELS_UHC_DUEL", MatchResult::Win)],
+        );
+        assert_eq!(digest.best_mode.unwrap().mode, "DUELS_UHC_DUEL");
+    }
+
+    #[test]
+    fn test_period_days() {
+        // A Wednesday
+        let day = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        assert_eq!(DigestPeriod::Day.days(day), (day, day));
+        let (monday, sunday) = DigestPeriod::Week.days(day);
+        assert_eq!(monday, NaiveDate::from_ymd_opt(2026, 10, 12).unwrap());
+        assert_eq!(sunday, NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+        assert_eq!(DigestPeriod::Week.days(monday).0, monday);
+    }
+}
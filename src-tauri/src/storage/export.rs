@@ -0,0 +1,269 @@
+//! Exports the match history and stat snapshots for spreadsheets and other
+//! tools, through `export_stats`.
+//!
+//! The JSON export is one file:
+//!
+//! ```text
+//! {
+//!   "schemaVersion": 1,
+//!   "exportedAt": <unix seconds>,
+//!   "matches": [<match>, ...],
+//!   "snapshots": [{ "takenAt": <unix seconds>, "stats": { "<stat>": <number>, ... } }, ...]
+//! }
+//! ```
+//!
+//! where a match has the fields of [`MatchRecord`]: `id`, `mode`, `map`,
+//! `result` (`win`, `loss` or `draw`), `startedAt` (unix seconds),
+//! `durationSecs`, `kills`, `deaths` and `opponents`.
+//!
+//! The CSV export is two files with a header row each: the matches at the
+//! given path, with the columns in [`MATCH_COLUMNS`] and the opponents
+//! separated by `;`, and the snapshots next to it with `-snapshots` added to
+//! the name, with `takenAt`, `takenAtUtc` and then one column per stat,
+//! empty where a snapshot doesn't have it. The `*Utc` columns repeat the
+//! time as RFC 3339, which spreadsheets read as a date. Both lists are
+//! oldest first.
+
+use super::matches::{self, MatchRecord, MatchResult};
+use super::snapshots::{self, StatSnapshot};
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Version of the export's schema, bumped when a field or column changes
+const SCHEMA_VERSION: u32 = 1;
+
+/// Columns of the matches CSV
+pub(crate) const MATCH_COLUMNS: [&str; 10] = [
+    "id",
+    "startedAt",
+    "startedAtUtc",
+    "mode",
+    "map",
+    "result",
+    "durationSecs",
+    "kills",
+    "deaths",
+    "opponents",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// What `export_stats` wrote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsExport {
+    /// Files written, the matches first
+    pub files: Vec<String>,
+    pub matches: usize,
+    pub snapshots: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExport<'a> {
+    schema_version: u32,
+    exported_at: i64,
+    matches: &'a [MatchRecord],
+    snapshots: &'a [StatSnapshot],
+}
+
+/// Gets where an export is saved when no path is given
+pub(crate) fn default_path(format: ExportFormat) -> Result<PathBuf, String> {
+    utils::get_export_path(&format!(
+        "duelsplus-stats-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ))
+}
+
+/// Gets where the snapshots CSV goes, next to the matches one
+fn snapshots_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}-snapshots.csv", stem))
+}
+
+/// Writes the match history and snapshots to `path`
+pub(crate) fn export(format: ExportFormat, path: &Path) -> Result<StatsExport, String> {
+    let matches = matches::all()?;
+    let snapshots = snapshots::all()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut files = vec![path.to_path_buf()];
+    match format {
+        ExportFormat::Json => {
+            let export = JsonExport {
+                schema_version: SCHEMA_VERSION,
+                exported_at: chrono::Utc::now().timestamp(),
+                matches: &matches,
+                snapshots: &snapshots,
+            };
+            let content = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+            std::fs::write(path, content).map_err(|e| e.to_string())?;
+        }
+        ExportFormat::Csv => {
+            let snapshots_path = snapshots_path(path);
+            std::fs::write(path, matches_csv(&matches)).map_err(|e| e.to_string())?;
+            std::fs::write(&snapshots_path, snapshots_csv(&snapshots))
+                .map_err(|e| e.to_string())?;
+            files.push(snapshots_path);
+        }
+    }
+
+    Ok(StatsExport {
+        files: files
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect(),
+        matches: matches.len(),
+        snapshots: snapshots.len(),
+    })
+}
+
+fn utc(at: i64) -> String {
+    chrono::DateTime::from_timestamp(at, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn matches_csv(matches: &[MatchRecord]) -> String {
+    let mut csv = row(MATCH_COLUMNS.iter().map(|column| column.to_string()));
+    for record in matches {
+        let result = match record.result {
+            MatchResult::Win => "win",
+            MatchResult::Loss => "loss",
+            MatchResult::Draw => "draw",
+        };
+        csv.push_str(&row([
+            record.id.to_string(),
+            record.started_at.to_string(),
+            utc(record.started_at),
+            record.mode.clone().unwrap_or_default(),
+            record.map.clone().unwrap_or_default(),
+            result.to_string(),
+            record.duration_secs.to_string(),
+            record.kills.to_string(),
+            record.deaths.to_string(),
+            record.opponents.join(";"),
+        ]));
+    }
+    csv
+}
+
+fn snapshots_csv(snapshots: &[StatSnapshot]) -> String {
+    // Every stat any snapshot has, as the API adds stats over time
+    let stats: BTreeSet<&String> = snapshots
+        .iter()
+        .flat_map(|snapshot| snapshot.stats.keys())
+        .collect();
+    let mut csv = row(["takenAt".to_string(), "takenAtUtc".to_string()]
+        .into_iter()
+        .chain(stats.iter().map(|stat| stat.to_string())));
+    for snapshot in snapshots {
+        csv.push_str(&row([
+            snapshot.taken_at.to_string(),
+            utc(snapshot.taken_at),
+        ]
+        .into_iter()
+        .chain(stats.iter().map(|stat| {
+            snapshot
+                .stats
+                .get(*stat)
+                .map(f64::to_string)
+                .unwrap_or_default()
+        }))));
+    }
+    csv
+}
+
+/// Joins fields into a CSV line, quoting the ones that need it
+fn row(fields: impl IntoIterator<Item = String>) -> String {
+    let mut line = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_matches_csv() {
+        let csv = matches_csv(&[MatchRecord {
+            id: 1,
+            mode: Some("DUELS_SUMO_DUEL".to_string()),
+            map: Some("Fort, \"Royale\"".to_string()),
+            result: MatchResult::Win,
+            started_at: 1_700_000_000,
+            duration_secs: 95,
+            kills: 1,
+            deaths: 0,
+            opponents: vec!["Steve".to_string(), "Alex".to_string()],
+        }]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], MATCH_COLUMNS.join(","));
+        assert_eq!(
+            lines[1],
+            "1,1700000000,2023-11-14T22:13:20+00:00,DUELS_SUMO_DUEL,\
+             \"Fort, \"\"Royale\"\"\",win,95,1,0,Steve;Alex"
+        );
+    }
+
+    #[test]
+    fn test_snapshots_csv() {
+        let snapshot = |taken_at, stats: &[(&str, f64)]| StatSnapshot {
+            taken_at,
+            stats: stats
+                .iter()
+                .map(|(name, value)| (name.to_string(), *value))
+                .collect::<BTreeMap<_, _>>(),
+        };
+        let csv = snapshots_csv(&[
+            snapshot(0, &[("wins", 10.0)]),
+            snapshot(86_400, &[("wins", 12.0), ("winRate", 0.5)]),
+        ]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "takenAt,takenAtUtc,winRate,wins");
+        assert_eq!(lines[1], "0,1970-01-01T00:00:00+00:00,,10");
+        assert_eq!(lines[2], "86400,1970-01-02T00:00:00+00:00,0.5,12");
+    }
+
+    #[test]
+    fn test_snapshots_path() {
+        assert_eq!(
+            snapshots_path(Path::new("exports/stats.csv")),
+            Path::new("exports/stats-snapshots.csv")
+        );
+    }
+}
@@ -0,0 +1,510 @@
+//! Match history, built from the proxy's game events.
+//!
+//! A match starts with `game_start` and is recorded on `game_end`, with the
+//! mode and map, the result (a draw if neither `win` nor `loss` came), how
+//! long it took, the kills and deaths, and the opponents the proxy
+//! reported. `get_match_history` returns the recorded matches newest first,
+//! filtered and a page at a time. What happened during the match is saved
+//! apart, see [`super::timelines`].
+//!
+//! Matches are rows of the `matches` table, with their opponents in
+//! `match_opponents`; filtering and paging run in SQL.
+
+use super::timelines::{self, Recorder};
+use super::{with_database, Database};
+use crate::proxy::models::{GameEvent, GameEventKind, Opponent};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{named_params, Connection, Row, ToSql};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Matches per page of `get_match_history`
+pub(crate) const PAGE_SIZE: usize = 25;
+
+/// Columns of `matches`, in the order [`read_match`] expects
+const COLUMNS: &str = "id, mode, map, result, started_at, duration_secs, kills, deaths";
+
+/// Conditions of [`MatchFilter`], with its fields as named parameters
+const FILTER: &str = "(:mode IS NULL OR mode = :mode)
+    AND (:result IS NULL OR result = :result)
+    AND (:opponent IS NULL OR EXISTS (
+        SELECT 1 FROM match_opponents
+        WHERE match_id = matches.id AND instr(lower(name), lower(:opponent)) > 0
+    ))
+    AND (:since IS NULL OR started_at >= :since)
+    AND (:until IS NULL OR started_at < :until)";
+
+/// The match being played, if any
+static CURRENT: Mutex<Option<CurrentMatch>> = Mutex::new(None);
+
+/// How a match ended for the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl MatchResult {
+    fn as_str(self) -> &'static str {
+        match self {
+            MatchResult::Win => "win",
+            MatchResult::Loss => "loss",
+            MatchResult::Draw => "draw",
+        }
+    }
+}
+
+impl ToSql for MatchResult {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for MatchResult {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "win" => Ok(MatchResult::Win),
+            "loss" => Ok(MatchResult::Loss),
+            "draw" => Ok(MatchResult::Draw),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// A finished match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchRecord {
+    /// Increasing from 1, in the order matches were played
+    pub id: u64,
+    pub mode: Option<String>,
+    pub map: Option<String>,
+    pub result: MatchResult,
+    /// Unix time the match started, in seconds
+    pub started_at: i64,
+    pub duration_secs: u64,
+    pub kills: u32,
+    pub deaths: u32,
+    /// Names of the opponents, as reported by the proxy
+    #[serde(default)]
+    pub opponents: Vec<String>,
+}
+
+/// Which matches `get_match_history` returns; every field is optional
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchFilter {
+    pub mode: Option<String>,
+    pub result: Option<MatchResult>,
+    /// Part of an opponent's name, ignoring case
+    pub opponent: Option<String>,
+    /// Unix time in seconds, inclusive
+    pub since: Option<i64>,
+    /// Unix time in seconds, exclusive
+    pub until: Option<i64>,
+}
+
+impl MatchFilter {
+    /// Binds the filter to the parameters of [`FILTER`]
+    fn params(&self) -> Vec<(&'static str, &dyn ToSql)> {
+        named_params! {
+            ":mode": self.mode,
+            ":result": self.result,
+            ":opponent": self.opponent,
+            ":since": self.since,
+            ":until": self.until,
+        }
+        .to_vec()
+    }
+}
+
+/// A page of the match history, as returned by `get_match_history`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchHistoryPage {
+    /// Newest first
+    pub matches: Vec<MatchRecord>,
+    /// Matches passing the filter, on every page
+    pub total: usize,
+    /// From 0
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// Reads a row of [`COLUMNS`], without the opponents
+fn read_match(row: &Row) -> rusqlite::Result<MatchRecord> {
+    Ok(MatchRecord {
+        id: row.get::<_, i64>(0)? as u64,
+        mode: row.get(1)?,
+        map: row.get(2)?,
+        result: row.get(3)?,
+        started_at: row.get(4)?,
+        duration_secs: row.get::<_, i64>(5)? as u64,
+        kills: row.get(6)?,
+        deaths: row.get(7)?,
+        opponents: Vec::new(),
+    })
+}
+
+/// Fills in the opponents of matches read by [`read_match`]
+fn read_opponents(conn: &Connection, records: &mut [MatchRecord]) -> rusqlite::Result<()> {
+    let mut statement = conn
+        .prepare_cached("SELECT name FROM match_opponents WHERE match_id = ?1 ORDER BY position")?;
+    for record in records {
+        record.opponents = statement
+            .query_map([record.id as i64], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+    }
+    Ok(())
+}
+
+/// Saves the match `finish` makes with the next ID
+fn insert(
+    database: &mut Database,
+    finish: impl FnOnce(u64) -> MatchRecord,
+) -> rusqlite::Result<MatchRecord> {
+    let tx = database.conn.transaction()?;
+    let id: i64 = tx.query_row("SELECT COALESCE(MAX(id), 0) + 1 FROM matches", [], |row| {
+        row.get(0)
+    })?;
+    let record = finish(id as u64);
+    tx.execute(
+        &format!(
+            "INSERT INTO matches ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            COLUMNS
+        ),
+        rusqlite::params![
+            id,
+            record.mode,
+            record.map,
+            record.result,
+            record.started_at,
+            record.duration_secs as i64,
+            record.kills,
+            record.deaths,
+        ],
+    )?;
+    for (position, name) in record.opponents.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO match_opponents (match_id, position, name) VALUES (?1, ?2, ?3)",
+            rusqlite::params![id, position as i64, name],
+        )?;
+    }
+    tx.commit()?;
+    Ok(record)
+}
+
+/// Gets a page of the matches passing `filter`, newest first
+fn page(
+    conn: &Connection,
+    filter: &MatchFilter,
+    page: usize,
+) -> rusqlite::Result<MatchHistoryPage> {
+    let params = filter.params();
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM matches WHERE {}", FILTER),
+        params.as_slice(),
+        |row| row.get(0),
+    )?;
+
+    let limit = PAGE_SIZE as i64;
+    let offset = i64::try_from(page.saturating_mul(PAGE_SIZE)).unwrap_or(i64::MAX);
+    let mut params = params;
+    params.push((":limit", &limit));
+    params.push((":offset", &offset));
+    let mut matches = conn
+        .prepare(&format!(
+            "SELECT {} FROM matches WHERE {} ORDER BY id DESC LIMIT :limit OFFSET :offset",
+            COLUMNS, FILTER
+        ))?
+        .query_map(params.as_slice(), read_match)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    read_opponents(conn, &mut matches)?;
+
+    Ok(MatchHistoryPage {
+        matches,
+        total: total as usize,
+        page,
+        page_size: PAGE_SIZE,
+    })
+}
+
+/// A match in progress
+#[derive(Debug)]
+struct CurrentMatch {
+    mode: Option<String>,
+    map: Option<String>,
+    started_at: i64,
+    started: Instant,
+    result: Option<MatchResult>,
+    kills: u32,
+    deaths: u32,
+    opponents: Vec<String>,
+    timeline: Recorder,
+}
+
+impl CurrentMatch {
+    fn start(event: &GameEvent) -> Self {
+        Self {
+            mode: event.mode.clone(),
+            map: event.map.clone(),
+            started_at: chrono::Utc::now().timestamp(),
+            started: Instant::now(),
+            result: None,
+            kills: 0,
+            deaths: 0,
+            opponents: Vec::new(),
+            timeline: Recorder::default(),
+        }
+    }
+
+    fn finish(self, id: u64) -> MatchRecord {
+        MatchRecord {
+            id,
+            mode: self.mode,
+            map: self.map,
+            result: self.result.unwrap_or(MatchResult::Draw),
+            started_at: self.started_at,
+            duration_secs: self.started.elapsed().as_secs(),
+            kills: self.kills,
+            deaths: self.deaths,
+            opponents: self.opponents,
+        }
+    }
+}
+
+/// Applies a game event to the current match, returning the match if
+/// it's over
+fn apply(current: &mut Option<CurrentMatch>, event: &GameEvent) -> Option<CurrentMatch> {
+    match event.event {
+        GameEventKind::GameStart => {
+            // A match without `game_end` still counts if it had a result
+            let previous = current.take().filter(|previous| previous.result.is_some());
+            *current = Some(CurrentMatch::start(event));
+            previous
+        }
+        GameEventKind::GameEnd => current.take(),
+        kind => {
+            let current = current.as_mut()?;
+            current.timeline.record(event, current.started.elapsed());
+            match kind {
+                GameEventKind::Win => current.result = Some(MatchResult::Win),
+                GameEventKind::Loss => current.result = Some(MatchResult::Loss),
+                GameEventKind::Kill => current.kills += 1,
+                GameEventKind::Death => current.deaths += 1,
+                GameEventKind::GameStart
+                | GameEventKind::GameEnd
+                | GameEventKind::Goal
+                | GameEventKind::GoalConceded
+                | GameEventKind::BedBroken
+                | GameEventKind::BedLost => {}
+            }
+            None
+        }
+    }
+}
+
+/// Tracks a game event from the proxy, returning the match it finished.
+///
+/// Nothing is recorded while the database is from a newer launcher.
+pub(crate) fn on_game_event(event: &GameEvent) -> Option<MatchRecord> {
+    let mut finished = apply(&mut CURRENT.lock().unwrap(), event)?;
+    let events = std::mem::take(&mut finished.timeline).into_events();
+    let record = with_database(|database| {
+        if database.read_only {
+            return Ok(None);
+        }
+        insert(database, |id| finished.finish(id)).map(Some)
+    })
+    .unwrap_or_else(|e| {
+        eprintln!("[storage] Failed to record a match: {}", e);
+        None
+    })?;
+    if let Err(e) = timelines::save(record.id, events) {
+        eprintln!(
+            "[storage] Failed to save the timeline of match {}: {}",
+            record.id, e
+        );
+    }
+    Some(record)
+}
+
+/// Adds an opponent reported by the proxy to the current match
+pub(crate) fn on_opponent(opponent: &Opponent) {
+    if let Some(current) = CURRENT.lock().unwrap().as_mut() {
+        if !current.opponents.contains(&opponent.name) {
+            current.opponents.push(opponent.name.clone());
+        }
+    }
+}
+
+/// Drops the match in progress, to call when the proxy stops
+pub(crate) fn abandon() {
+    CURRENT.lock().unwrap().take();
+}
+
+/// Gets a page of recorded matches passing `filter`, newest first
+pub(crate) fn history(
+    filter: &MatchFilter,
+    page_number: usize,
+) -> Result<MatchHistoryPage, String> {
+    with_database(|database| page(&database.conn, filter, page_number))
+}
+
+/// Gets every recorded match, oldest first
+pub(crate) fn all() -> Result<Vec<MatchRecord>, String> {
+    with_database(|database| {
+        let conn = &database.conn;
+        let mut records = conn
+            .prepare(&format!("SELECT {} FROM matches ORDER BY id", COLUMNS))?
+            .query_map([], read_match)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        read_opponents(conn, &mut records)?;
+        Ok(records)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: GameEventKind) -> GameEvent {
+        GameEvent {
+            event: kind,
+            mode: Some("DUELS_BRIDGE_DUEL".to_string()),
+            map: Some("Urban".to_string()),
+            player: None,
+            round: None,
+            final_blow: false,
+        }
+    }
+
+    fn record(id: u64, result: MatchResult, opponent: &str) -> MatchRecord {
+        MatchRecord {
+            id,
+            mode: Some(
+                if id.is_multiple_of(2) {
+                    "DUELS_SUMO_DUEL"
+                } else {
+                    "DUELS_BRIDGE_DUEL"
+                }
+                .to_string(),
+            ),
+            map: None,
+            result,
+            started_at: 1_700_000_000 + id as i64 * 60,
+            duration_secs: 60,
+            kills: 0,
+            deaths: 0,
+            opponents: vec![opponent.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_events_make_a_match() {
+        let mut current = None;
+        assert!(apply(&mut current, &event(GameEventKind::Kill)).is_none());
+        assert!(apply(&mut current, &event(GameEventKind::GameStart)).is_none());
+        apply(&mut current, &event(GameEventKind::Kill));
+        apply(&mut current, &event(GameEventKind::Kill));
+        apply(&mut current, &event(GameEventKind::Death));
+        apply(&mut current, &event(GameEventKind::Win));
+
+        let finished = apply(&mut current, &event(GameEventKind::GameEnd))
+            .unwrap()
+            .finish(7);
+        assert!(current.is_none());
+        assert_eq!(finished.id, 7);
+        assert_eq!(finished.result, MatchResult::Win);
+        assert_eq!((finished.kills, finished.deaths), (2, 1));
+        assert_eq!(finished.map.as_deref(), Some("Urban"));
+
+        // No result is a draw
+        apply(&mut current, &event(GameEventKind::GameStart));
+        let finished = apply(&mut current, &event(GameEventKind::GameEnd)).unwrap();
+        assert_eq!(finished.finish(8).result, MatchResult::Draw);
+    }
+
+    #[test]
+    fn test_timeline_is_kept_with_the_match() {
+        let mut current = None;
+        apply(&mut current, &event(GameEventKind::GameStart));
+        apply(&mut current, &event(GameEventKind::Goal));
+        apply(&mut current, &event(GameEventKind::Kill));
+        apply(&mut current, &event(GameEventKind::Win));
+        let finished = apply(&mut current, &event(GameEventKind::GameEnd)).unwrap();
+        let kinds: Vec<GameEventKind> = finished
+            .timeline
+            .into_events()
+            .iter()
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(kinds, [GameEventKind::Goal, GameEventKind::Kill]);
+    }
+
+    #[test]
+    fn test_missing_game_end() {
+        let mut current = None;
+        apply(&mut current, &event(GameEventKind::GameStart));
+        // Left before the end, nothing to record
+        assert!(apply(&mut current, &event(GameEventKind::GameStart)).is_none());
+        apply(&mut current, &event(GameEventKind::Loss));
+        let finished = apply(&mut current, &event(GameEventKind::GameStart)).unwrap();
+        assert_eq!(finished.finish(1).result, MatchResult::Loss);
+        assert!(current.is_some());
+    }
+
+    #[test]
+    fn test_page_and_filter() {
+        let mut database = Database::in_memory();
+        for id in 1..=30u64 {
+            let result = if id.is_multiple_of(3) {
+                MatchResult::Loss
+            } else {
+                MatchResult::Win
+            };
+            let opponent = if id == 5 { "Technoblade" } else { "Steve" };
+            let inserted = insert(&mut database, |id| record(id, result, opponent)).unwrap();
+            assert_eq!(inserted.id, id);
+        }
+        let page =
+            |filter: &MatchFilter, number| super::page(&database.conn, filter, number).unwrap();
+
+        let first = page(&MatchFilter::default(), 0);
+        assert_eq!(first.total, 30);
+        assert_eq!(first.matches.len(), PAGE_SIZE);
+        assert_eq!(first.matches[0].id, 30);
+        assert_eq!(first.matches[0].opponents, ["Steve"]);
+        let second = page(&MatchFilter::default(), 1);
+        assert_eq!(second.matches.len(), 30 - PAGE_SIZE);
+        assert!(page(&MatchFilter::default(), 9).matches.is_empty());
+
+        let losses = MatchFilter {
+            result: Some(MatchResult::Loss),
+            ..Default::default()
+        };
+        assert_eq!(page(&losses, 0).total, 10);
+
+        let sumo_losses = MatchFilter {
+            mode: Some("DUELS_SUMO_DUEL".to_string()),
+            ..losses
+        };
+        assert_eq!(page(&sumo_losses, 0).total, 5);
+
+        let techno = MatchFilter {
+            opponent: Some("techno".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(page(&techno, 0).matches[0].id, 5);
+
+        let window = MatchFilter {
+            since: Some(record(10, MatchResult::Win, "").started_at),
+            until: Some(record(20, MatchResult::Win, "").started_at),
+            ..Default::default()
+        };
+        assert_eq!(page(&window, 0).total, 10);
+    }
+}
@@ -0,0 +1,154 @@
+//! Local database of the player's games.
+//!
+//! Matches, their timelines and stat snapshots are kept in a SQLite
+//! database, `history.db` in the data directory. Its schema changes through
+//! migrations, SQL scripts applied in order; the database's `user_version`
+//! counts the ones it has had. Opening a database written by an older
+//! launcher runs the missing migrations in one transaction; a database from
+//! a newer launcher is read as is and never written, so going back a
+//! version doesn't lose history.
+
+pub(crate) mod digest;
+pub(crate) mod export;
+pub(crate) mod matches;
+pub(crate) mod snapshots;
+pub(crate) mod timelines;
+
+use crate::utils::{self, AppDir};
+use rusqlite::Connection;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Name of the database in the data directory
+const DATABASE_FILE: &str = "history.db";
+
+/// Schema migrations, in order
+const MIGRATIONS: &[&str] = &[
+    // 1: matches and their opponents
+    "CREATE TABLE matches (
+        id INTEGER PRIMARY KEY,
+        mode TEXT,
+        map TEXT,
+        result TEXT NOT NULL,
+        started_at INTEGER NOT NULL,
+        duration_secs INTEGER NOT NULL,
+        kills INTEGER NOT NULL,
+        deaths INTEGER NOT NULL
+    );
+    CREATE INDEX matches_started_at ON matches (started_at);
+    CREATE INDEX matches_mode ON matches (mode);
+    CREATE TABLE match_opponents (
+        match_id INTEGER NOT NULL REFERENCES matches (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        PRIMARY KEY (match_id, position)
+    );
+    CREATE INDEX match_opponents_name ON match_opponents (name);",
+    // 2: stat snapshots, with the stats as a JSON object
+    "CREATE TABLE stat_snapshots (
+        id INTEGER PRIMARY KEY,
+        taken_at INTEGER NOT NULL,
+        stats TEXT NOT NULL
+    );
+    CREATE INDEX stat_snapshots_taken_at ON stat_snapshots (taken_at);",
+    // 3: match timelines, with the events as a JSON array
+    "CREATE TABLE match_timelines (
+        match_id INTEGER PRIMARY KEY REFERENCES matches (id) ON DELETE CASCADE,
+        events TEXT NOT NULL
+    );",
+];
+
+/// The database, opened the first time it's needed
+static DATABASE: Mutex<Option<Database>> = Mutex::new(None);
+
+/// An open database, migrated to the current schema
+pub(crate) struct Database {
+    pub conn: Connection,
+    /// Whether the database is from a newer launcher, and mustn't be written
+    pub read_only: bool,
+}
+
+impl Database {
+    fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let conn = Connection::open(path).map_err(|e| e.to_string())?;
+        Self::migrate(conn, MIGRATIONS).map_err(|e| format!("Invalid {}: {}", DATABASE_FILE, e))
+    }
+
+    /// Runs the migrations `conn` hasn't had yet
+    fn migrate(mut conn: Connection, migrations: &[&str]) -> rusqlite::Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let version =
+            conn.pragma_query_value(None, "user_version", |row| row.get::<_, i64>(0))? as usize;
+        if version < migrations.len() {
+            println!(
+                "[storage] Migrating {} from version {} to {}",
+                DATABASE_FILE,
+                version,
+                migrations.len()
+            );
+            let tx = conn.transaction()?;
+            for migration in &migrations[version..] {
+                tx.execute_batch(migration)?;
+            }
+            tx.pragma_update(None, "user_version", migrations.len() as i64)?;
+            tx.commit()?;
+        }
+        Ok(Self {
+            conn,
+            read_only: version > migrations.len(),
+        })
+    }
+
+    /// Opens an empty database in memory, for tests
+    #[cfg(test)]
+    pub(crate) fn in_memory() -> Self {
+        Self::migrate(Connection::open_in_memory().unwrap(), MIGRATIONS).unwrap()
+    }
+}
+
+/// Runs `f` on the database, opening it first if needed
+pub(crate) fn with_database<T>(
+    f: impl FnOnce(&mut Database) -> rusqlite::Result<T>,
+) -> Result<T, String> {
+    let mut database = DATABASE.lock().unwrap();
+    if database.is_none() {
+        let path = utils::get_app_path(AppDir::Data, DATABASE_FILE)?;
+        *database = Some(Database::open(&path)?);
+    }
+    f(database.as_mut().unwrap()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_migrations() {
+        let conn = Connection::open_in_memory().unwrap();
+        let database = Database::migrate(conn, &["CREATE TABLE games (player TEXT);"]).unwrap();
+        assert!(!database.read_only);
+        database
+            .conn
+            .execute("INSERT INTO games (player) VALUES ('Steve')", [])
+            .unwrap();
+
+        // Version 2 renamed `player` to `name`
+        let migrations = [
+            "CREATE TABLE games (player TEXT);",
+            "ALTER TABLE games RENAME COLUMN player TO name;",
+        ];
+        let database = Database::migrate(database.conn, &migrations).unwrap();
+        let name: String = database
+            .conn
+            .query_row("SELECT name FROM games", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "Steve");
+
+        // An older launcher leaves the newer database alone
+        let database = Database::migrate(database.conn, &migrations[..1]).unwrap();
+        assert!(database.read_only);
+    }
+}
@@ -0,0 +1,261 @@
+//! Snapshots of the player's Duels+ stats, for progress graphs.
+//!
+//! The API only has the current stats, so they're saved to the
+//! `stat_snapshots` table of the database about once a day while the
+//! launcher runs, and whenever `take_stats_snapshot` is called.
+//! `get_stats_trend` turns the snapshots into a time series for one stat:
+//! any number the API returns, like `wins` or `winRate`, or one of the
+//! derived [`GAMES`] and [`GAMES_PER_DAY`].
+
+use super::with_database;
+use crate::auth;
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How old the last snapshot must be for the scheduled one to take
+/// another, a bit under a day so it doesn't drift later each day
+const SNAPSHOT_INTERVAL_SECS: i64 = 20 * 60 * 60;
+
+/// Derived stat: wins plus losses
+pub(crate) const GAMES: &str = "games";
+
+/// Derived stat: games played per day between snapshots
+pub(crate) const GAMES_PER_DAY: &str = "gamesPerDay";
+
+/// The stats at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatSnapshot {
+    /// Unix time in seconds
+    pub taken_at: i64,
+    /// Every number in the API's stats, by name
+    pub stats: BTreeMap<String, f64>,
+}
+
+impl StatSnapshot {
+    fn get(&self, stat: &str) -> Option<f64> {
+        match (self.stats.get(stat), stat) {
+            (Some(value), _) => Some(*value),
+            (None, GAMES) => Some(self.stats.get("wins")? + self.stats.get("losses")?),
+            _ => None,
+        }
+    }
+}
+
+/// How far back `get_stats_trend` goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrendRange {
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl TrendRange {
+    /// Earliest time included, `None` for everything
+    fn since(self, now: i64) -> Option<i64> {
+        let days = match self {
+            TrendRange::Week => 7,
+            TrendRange::Month => 30,
+            TrendRange::Year => 365,
+            TrendRange::All => return None,
+        };
+        Some(now - days * 24 * 60 * 60)
+    }
+}
+
+/// A value of a stat at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendPoint {
+    /// Unix time in seconds
+    pub at: i64,
+    pub value: f64,
+}
+
+/// Reads a row of `taken_at, stats`
+fn read_snapshot(row: &Row) -> rusqlite::Result<StatSnapshot> {
+    let stats: String = row.get(1)?;
+    Ok(StatSnapshot {
+        taken_at: row.get(0)?,
+        stats: serde_json::from_str(&stats).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, e.into())
+        })?,
+    })
+}
+
+/// Takes a snapshot of the stats.
+///
+/// Unless `force` is set, it's skipped if there's a recent snapshot or
+/// nobody is signed in, returning `None`.
+pub(crate) async fn snapshot(force: bool) -> Result<Option<StatSnapshot>, String> {
+    let now = chrono::Utc::now().timestamp();
+    if !force {
+        let last: Option<i64> = with_database(|database| {
+            database
+                .conn
+                .query_row("SELECT MAX(taken_at) FROM stat_snapshots", [], |row| {
+                    row.get(0)
+                })
+        })?;
+        if last.is_some_and(|last| now - last < SNAPSHOT_INTERVAL_SECS) {
+            return Ok(None);
+        }
+    }
+
+    let token = match auth::token::get_token().await.map_err(|e| e.to_string())? {
+        Some(token) => token,
+        None if !force => return Ok(None),
+        None => return Err(crate::i18n::t!("errors.noToken")),
+    };
+    let response = auth::api::get_stats(&token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let stats = match response.stats {
+        Some(stats) if response.success => numbers(&stats),
+        _ => {
+            return Err(response
+                .message
+                .unwrap_or_else(|| "The API returned no stats".to_string()))
+        }
+    };
+
+    let snapshot = StatSnapshot {
+        taken_at: now,
+        stats,
+    };
+    let stats = serde_json::to_string(&snapshot.stats).map_err(|e| e.to_string())?;
+    with_database(|database| {
+        if !database.read_only {
+            database.conn.execute(
+                "INSERT INTO stat_snapshots (taken_at, stats) VALUES (?1, ?2)",
+                params![snapshot.taken_at, stats],
+            )?;
+        }
+        Ok(())
+    })?;
+    Ok(Some(snapshot))
+}
+
+/// Gets every snapshot, oldest first
+pub(crate) fn all() -> Result<Vec<StatSnapshot>, String> {
+    with_database(|database| {
+        database
+            .conn
+            .prepare("SELECT taken_at, stats FROM stat_snapshots ORDER BY taken_at, id")?
+            .query_map([], read_snapshot)?
+            .collect()
+    })
+}
+
+/// Gets a stat from the snapshots in `range`, oldest first
+pub(crate) fn trend(stat: &str, range: TrendRange) -> Result<Vec<TrendPoint>, String> {
+    let snapshots = all()?;
+    Ok(series(
+        &snapshots,
+        stat,
+        range.since(chrono::Utc::now().timestamp()),
+    ))
+}
+
+fn series(snapshots: &[StatSnapshot], stat: &str, since: Option<i64>) -> Vec<TrendPoint> {
+    let in_range = |at: i64| since.is_none_or(|since| at >= since);
+    if stat == GAMES_PER_DAY {
+        return snapshots
+            .windows(2)
+            .filter(|pair| in_range(pair[1].taken_at))
+            .filter_map(|pair| {
+                let days = (pair[1].taken_at - pair[0].taken_at) as f64 / (24.0 * 60.0 * 60.0);
+                let games = pair[1].get(GAMES)? - pair[0].get(GAMES)?;
+                (days > 0.0).then(|| TrendPoint {
+                    at: pair[1].taken_at,
+                    value: games.max(0.0) / days,
+                })
+            })
+            .collect();
+    }
+    snapshots
+        .iter()
+        .filter(|snapshot| in_range(snapshot.taken_at))
+        .filter_map(|snapshot| {
+            Some(TrendPoint {
+                at: snapshot.taken_at,
+                value: snapshot.get(stat)?,
+            })
+        })
+        .collect()
+}
+
+/// Gets the top-level numbers of the API's stats
+fn numbers(stats: &serde_json::Value) -> BTreeMap<String, f64> {
+    stats
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| Some((name.clone(), value.as_f64()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn snapshot(day: i64, wins: f64, losses: f64) -> StatSnapshot {
+        StatSnapshot {
+            taken_at: day * DAY,
+            stats: numbers(&json!({
+                "wins": wins,
+                "losses": losses,
+                "winRate": wins / (wins + losses),
+                "rank": "gold",
+            })),
+        }
+    }
+
+    #[test]
+    fn test_series() {
+        let snapshots = [
+            snapshot(1, 10.0, 10.0),
+            snapshot(2, 16.0, 14.0),
+            snapshot(4, 30.0, 20.0),
+        ];
+
+        let wins = series(&snapshots, "wins", None);
+        assert_eq!(
+            wins,
+            vec![
+                TrendPoint {
+                    at: DAY,
+                    value: 10.0
+                },
+                TrendPoint {
+                    at: 2 * DAY,
+                    value: 16.0
+                },
+                TrendPoint {
+                    at: 4 * DAY,
+                    value: 30.0
+                },
+            ]
+        );
+        assert_eq!(series(&snapshots, GAMES, Some(2 * DAY))[0].value, 30.0);
+        assert!(series(&snapshots, "rank", None).is_empty());
+
+        let per_day = series(&snapshots, GAMES_PER_DAY, None);
+        assert_eq!(per_day.len(), 2);
+        assert_eq!(per_day[0].value, 10.0);
+        // 20 games over two days
+        assert_eq!(per_day[1].value, 10.0);
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(TrendRange::Week.since(10 * DAY), Some(3 * DAY));
+        assert_eq!(TrendRange::All.since(10 * DAY), None);
+    }
+}
@@ -0,0 +1,229 @@
+//! What happened during each match, for a round-by-round breakdown.
+//!
+//! While a match is played, its kills, deaths, goals and beds are kept with
+//! when they happened and in which round. When the match is recorded (see
+//! [`super::matches`]), they're saved to the `match_timelines` table under
+//! its ID, apart from the match itself so pages of the history stay small.
+//! `get_match_timeline` returns them with a summary of each round.
+//!
+//! Rounds come from the proxy when it knows them; otherwise every goal ends
+//! a round, as in the Bridge, and modes without goals are one round.
+
+use super::with_database;
+use crate::proxy::models::{GameEvent, GameEventKind};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Something that happened in a match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineEvent {
+    /// Milliseconds since the match started
+    pub at_ms: u64,
+    /// A kill, death, goal or bed
+    pub kind: GameEventKind,
+    /// The other player in it, as reported by the proxy
+    pub player: Option<String>,
+    /// From 1
+    pub round: u32,
+    /// Whether this decided the match
+    #[serde(default)]
+    pub final_blow: bool,
+}
+
+/// How one round went
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundSummary {
+    pub round: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub goals: u32,
+    pub goals_conceded: u32,
+    pub beds_broken: u32,
+    pub beds_lost: u32,
+    /// When its last event happened, in milliseconds since the match started
+    pub ended_at_ms: u64,
+}
+
+/// A match's timeline, as returned by `get_match_timeline`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchTimeline {
+    pub match_id: u64,
+    /// In the order they happened
+    pub events: Vec<TimelineEvent>,
+    /// Rounds with any events, first to last
+    pub rounds: Vec<RoundSummary>,
+}
+
+impl MatchTimeline {
+    fn new(match_id: u64, events: Vec<TimelineEvent>) -> Self {
+        let mut rounds: BTreeMap<u32, RoundSummary> = BTreeMap::new();
+        for event in &events {
+            let round = rounds.entry(event.round).or_insert_with(|| RoundSummary {
+                round: event.round,
+                ..Default::default()
+            });
+            round.ended_at_ms = round.ended_at_ms.max(event.at_ms);
+            match event.kind {
+                GameEventKind::Kill => round.kills += 1,
+                GameEventKind::Death => round.deaths += 1,
+                GameEventKind::Goal => round.goals += 1,
+                GameEventKind::GoalConceded => round.goals_conceded += 1,
+                GameEventKind::BedBroken => round.beds_broken += 1,
+                GameEventKind::BedLost => round.beds_lost += 1,
+                GameEventKind::Win
+                | GameEventKind::Loss
+                | GameEventKind::GameStart
+                | GameEventKind::GameEnd => {}
+            }
+        }
+        Self {
+            match_id,
+            events,
+            rounds: rounds.into_values().collect(),
+        }
+    }
+}
+
+/// Collects the timeline of the match being played
+#[derive(Debug, Default)]
+pub(crate) struct Recorder {
+    events: Vec<TimelineEvent>,
+    /// Goals either way so far, for modes the proxy doesn't number the
+    /// rounds of
+    goals: u32,
+}
+
+impl Recorder {
+    /// Adds an event, `at` after the match started. Only kills, deaths,
+    /// goals and beds are kept, the rest is in the match record.
+    pub(crate) fn record(&mut self, event: &GameEvent, at: Duration) {
+        let scored = match event.event {
+            GameEventKind::Goal | GameEventKind::GoalConceded => true,
+            GameEventKind::Kill
+            | GameEventKind::Death
+            | GameEventKind::BedBroken
+            | GameEventKind::BedLost => false,
+            GameEventKind::Win
+            | GameEventKind::Loss
+            | GameEventKind::GameStart
+            | GameEventKind::GameEnd => return,
+        };
+        self.events.push(TimelineEvent {
+            at_ms: at.as_millis() as u64,
+            kind: event.event,
+            player: event.player.clone(),
+            round: event.round.unwrap_or(self.goals + 1),
+            final_blow: event.final_blow,
+        });
+        if scored {
+            self.goals += 1;
+        }
+    }
+
+    pub(crate) fn into_events(self) -> Vec<TimelineEvent> {
+        self.events
+    }
+}
+
+/// Saves a recorded match's timeline, unless it's empty
+pub(crate) fn save(match_id: u64, events: Vec<TimelineEvent>) -> Result<(), String> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let events = serde_json::to_string(&events).map_err(|e| e.to_string())?;
+    with_database(|database| {
+        if !database.read_only {
+            database.conn.execute(
+                "INSERT OR REPLACE INTO match_timelines (match_id, events) VALUES (?1, ?2)",
+                params![match_id as i64, events],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Gets a match's timeline, `None` if it has none, e.g. it was recorded
+/// before timelines were
+pub(crate) fn timeline(match_id: u64) -> Result<Option<MatchTimeline>, String> {
+    let events: Option<String> = with_database(|database| {
+        database
+            .conn
+            .query_row(
+                "SELECT events FROM match_timelines WHERE match_id = ?1",
+                [match_id as i64],
+                |row| row.get(0),
+            )
+            .optional()
+    })?;
+    events
+        .map(|events| {
+            serde_json::from_str(&events)
+                .map(|events| MatchTimeline::new(match_id, events))
+                .map_err(|e| format!("Invalid timeline of match {}: {}", match_id, e))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: GameEventKind, round: Option<u32>) -> GameEvent {
+        GameEvent {
+            event: kind,
+            mode: None,
+            map: None,
+            player: Some("Steve".to_string()),
+            round,
+            final_blow: false,
+        }
+    }
+
+    #[test]
+    fn test_rounds_from_goals() {
+        let mut recorder = Recorder::default();
+        let secs = Duration::from_secs;
+        recorder.record(&event(GameEventKind::GameStart, None), secs(0));
+        recorder.record(&event(GameEventKind::Kill, None), secs(10));
+        recorder.record(&event(GameEventKind::Goal, None), secs(20));
+        recorder.record(&event(GameEventKind::Death, None), secs(30));
+        recorder.record(&event(GameEventKind::GoalConceded, None), secs(40));
+        recorder.record(&event(GameEventKind::Win, None), secs(50));
+
+        let events = recorder.into_events();
+        let rounds: Vec<u32> = events.iter().map(|e| e.round).collect();
+        assert_eq!(rounds, [1, 1, 2, 2]);
+        assert_eq!(events[1].at_ms, 20_000);
+
+        let timeline = MatchTimeline::new(3, events);
+        assert_eq!(timeline.rounds.len(), 2);
+        assert_eq!(
+            timeline.rounds[0],
+            RoundSummary {
+                round: 1,
+                kills: 1,
+                goals: 1,
+                ended_at_ms: 20_000,
+                ..Default::default()
+            }
+        );
+        assert_eq!(timeline.rounds[1].goals_conceded, 1);
+        assert_eq!(timeline.rounds[1].deaths, 1);
+    }
+
+    #[test]
+    fn test_rounds_from_the_proxy() {
+        let mut recorder = Recorder::default();
+        recorder.record(&event(GameEventKind::BedBroken, Some(1)), Duration::ZERO);
+        recorder.record(&event(GameEventKind::Goal, Some(4)), Duration::ZERO);
+        recorder.record(&event(GameEventKind::Kill, Some(4)), Duration::ZERO);
+        let timeline = MatchTimeline::new(1, recorder.into_events());
+        let rounds: Vec<u32> = timeline.rounds.iter().map(|r| r.round).collect();
+        assert_eq!(rounds, [1, 4]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Streamer mode.
+//!
+//! One switch to flip before going live. While `streamerMode` is on:
+//! - the Discord activity hides the profile and location, whatever the
+//!   `rpcAnonymize*` settings say
+//! - the player's IGN and UUID, the auth token and other secrets are
+//!   replaced in proxy events sent to the frontend, including log lines
+//! - notifications that show a username, such as friends coming online,
+//!   aren't shown
+//!
+//! Values to hide are remembered as they show up (e.g. when the proxy
+//! reports the player), whether or not streamer mode is on, so switching
+//! it on mid-session still hides them.
+
+use crate::diagnostics::Redactor;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+/// Replacement for hidden values
+const HIDDEN: &str = "[hidden]";
+
+/// Whether streamer mode is on, following the `streamerMode` config
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Values hidden while streamer mode is on, e.g. the player's IGN
+static VALUES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Whether streamer mode is on
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Turns streamer mode on or off, e.g. when the `streamerMode` config
+/// changes
+pub(crate) fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Gets the Discord privacy options to apply: both on in streamer mode,
+/// otherwise the configured ones
+pub(crate) fn rpc_privacy(profile: bool, location: bool) -> (bool, bool) {
+    let enabled = is_enabled();
+    (profile || enabled, location || enabled)
+}
+
+/// Remembers a value to hide while streamer mode is on
+pub(crate) fn hide(value: &str) {
+    let mut values = VALUES.write().unwrap();
+    // UUIDs show up both with and without dashes
+    for value in [value.to_string(), value.replace('-', "")] {
+        if !value.trim().is_empty() && !values.contains(&value) {
+            values.push(value);
+        }
+    }
+}
+
+/// Hides remembered values and secrets in text, if streamer mode is on
+pub(crate) fn scrub(text: &str) -> String {
+    if !is_enabled() {
+        return text.to_string();
+    }
+    scrub_with(text, &VALUES.read().unwrap())
+}
+
+/// Hides remembered values and secrets in every string of a JSON value, if
+/// streamer mode is on
+pub(crate) fn scrub_value(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = scrub(s),
+        Value::Array(items) => items.iter_mut().for_each(scrub_value),
+        Value::Object(map) => map.values_mut().for_each(scrub_value),
+        _ => {}
+    }
+}
+
+fn scrub_with(text: &str, values: &[String]) -> String {
+    static REDACTOR: OnceLock<Redactor> = OnceLock::new();
+    let text = values.iter().fold(text.to_string(), |text, value| {
+        replace_ignore_case(&text, value, HIDDEN)
+    });
+    // Webhook URLs, bearer tokens and the home directory, which usually
+    // has the user's name in it
+    REDACTOR.get_or_init(|| Redactor::new(None)).text(&text)
+}
+
+/// Replaces every occurrence of `needle`, ignoring ASCII case, since
+/// Minecraft names are matched case-insensitively
+fn replace_ignore_case(text: &str, needle: &str, with: &str) -> String {
+    let haystack = text.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in haystack.match_indices(&needle) {
+        result.push_str(&text[last..start]);
+        result.push_str(with);
+        last = start + needle.len();
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_with() {
+        let values = vec!["Steve".to_string(), "abc123secret".to_string()];
+        assert_eq!(
+            scrub_with("steve joined with token abc123secret", &values),
+            "[hidden] joined with token [hidden]"
+        );
+        assert_eq!(
+            scrub_with("Authorization: Bearer eyJhbGci", &values),
+            "Authorization: Bearer [redacted]"
+        );
+        assert_eq!(scrub_with("Nothing here", &[]), "Nothing here");
+    }
+
+    #[test]
+    fn test_replace_ignore_case() {
+        assert_eq!(replace_ignore_case("Steve, STEVE", "steve", "x"), "x, x");
+        assert_eq!(replace_ignore_case("Alex", "steve", "x"), "Alex");
+    }
+}
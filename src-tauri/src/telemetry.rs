@@ -0,0 +1,283 @@
+//! Opt-in, anonymous usage statistics.
+//!
+//! With `telemetry` on, the launcher counts how often it's started, which
+//! features are used and what kinds of errors happen, and uploads the
+//! counts along with the launcher and proxy versions, OS and architecture
+//! about once a day. Counts are only kept under fixed names, so nothing
+//! typed or received (IGNs, tokens, paths, messages) can end up in them.
+//!
+//! Until they're sent, counts are queued in `telemetry.json` in the data
+//! directory, so short sessions and time offline go out with the next
+//! upload. Nothing is counted or sent while `telemetry` is off, and turning
+//! it off deletes the queue. `get_telemetry_preview` returns exactly what
+//! the next upload would send.
+
+use crate::proxy::ProxyManager;
+use crate::utils::{self, AppDir};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// Endpoint collecting usage statistics
+const TELEMETRY_API_URL: &str = "https://duelsplus.com/api/telemetry";
+
+/// Name of the queue file in the data directory
+const QUEUE_FILE: &str = "telemetry.json";
+
+/// Whether telemetry is on, following the `telemetry` config
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Counts not uploaded yet, mirrored in the queue file
+static QUEUE: Mutex<Counts> = Mutex::new(Counts::new());
+
+/// What's been counted since the last upload
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Counts {
+    /// Unix time counting started, 0 while nothing is counted
+    pub since: i64,
+    /// Times the launcher was started
+    pub launches: u32,
+    /// Times each feature was used, e.g. `overlay`
+    pub features: BTreeMap<String, u32>,
+    /// Times each kind of error happened, e.g. `proxyCrash`
+    pub errors: BTreeMap<String, u32>,
+}
+
+impl Counts {
+    const fn new() -> Self {
+        Self {
+            since: 0,
+            launches: 0,
+            features: BTreeMap::new(),
+            errors: BTreeMap::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.launches == 0 && self.features.is_empty() && self.errors.is_empty()
+    }
+
+    /// Removes counts that were uploaded, keeping any added meanwhile
+    fn subtract(&mut self, sent: &Counts, now: i64) {
+        self.launches = self.launches.saturating_sub(sent.launches);
+        for (counts, sent) in [
+            (&mut self.features, &sent.features),
+            (&mut self.errors, &sent.errors),
+        ] {
+            for (name, sent) in sent {
+                if let Some(count) = counts.get_mut(name) {
+                    *count = count.saturating_sub(*sent);
+                }
+            }
+            counts.retain(|_, count| *count > 0);
+        }
+        self.since = if self.is_empty() { 0 } else { now };
+    }
+}
+
+/// What an upload sends, as returned by `get_telemetry_preview`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryReport {
+    pub launcher_version: String,
+    /// Version of the proxy run this session, if any
+    pub proxy_version: Option<String>,
+    pub os: &'static str,
+    pub arch: &'static str,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// Whether telemetry is on
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Turns telemetry on or off, e.g. when the `telemetry` config changes.
+///
+/// Turning it on picks up counts queued by earlier sessions; turning it off
+/// deletes them.
+pub(crate) fn set_enabled(enabled: bool) {
+    if ENABLED.swap(enabled, Ordering::SeqCst) == enabled {
+        return;
+    }
+    let mut queue = QUEUE.lock().unwrap();
+    if enabled {
+        *queue = read_queue().unwrap_or_default();
+    } else {
+        *queue = Counts::new();
+        if let Some(path) = queue_path() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Counts a launcher start
+pub(crate) fn record_launch() {
+    record(|counts| counts.launches += 1);
+}
+
+/// Counts a use of a feature
+pub(crate) fn record_feature(feature: &'static str) {
+    record(|counts| *counts.features.entry(feature.to_string()).or_default() += 1);
+}
+
+/// Counts an error of a kind, e.g. [`ProxyError::category`]
+///
+/// [`ProxyError::category`]: crate::proxy::error::ProxyError::category
+pub(crate) fn record_error(category: &'static str) {
+    record(|counts| *counts.errors.entry(category.to_string()).or_default() += 1);
+}
+
+fn record(change: impl FnOnce(&mut Counts)) {
+    if !is_enabled() {
+        return;
+    }
+    let mut queue = QUEUE.lock().unwrap();
+    change(&mut queue);
+    if queue.since == 0 {
+        queue.since = unix_secs();
+    }
+    if let Err(e) = write_queue(&queue) {
+        eprintln!("[telemetry] Failed to save the queue: {}", e);
+    }
+}
+
+/// Builds what the next upload would send
+pub(crate) fn preview(app: &AppHandle) -> TelemetryReport {
+    TelemetryReport {
+        launcher_version: app.package_info().version.to_string(),
+        proxy_version: app
+            .try_state::<ProxyManager>()
+            .and_then(|proxy| proxy.instance_scope().proxy_version),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        counts: QUEUE.lock().unwrap().clone(),
+    }
+}
+
+/// Uploads the queued counts, if telemetry is on and anything was counted.
+///
+/// On failure they stay queued for the next upload.
+pub(crate) async fn upload(app: AppHandle) -> Result<(), String> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let report = preview(&app);
+    if report.counts.is_empty() {
+        return Ok(());
+    }
+
+    reqwest::Client::new()
+        .post(TELEMETRY_API_URL)
+        .json(&report)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?;
+
+    let mut queue = QUEUE.lock().unwrap();
+    queue.subtract(&report.counts, unix_secs());
+    let result = if queue.is_empty() {
+        queue_path().map_or(Ok(()), |path| {
+            std::fs::remove_file(path).or_else(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Ok(()),
+                _ => Err(e.to_string()),
+            })
+        })
+    } else {
+        write_queue(&queue)
+    };
+    result.map_err(|e| format!("Failed to update the queue: {}", e))
+}
+
+fn queue_path() -> Option<PathBuf> {
+    utils::get_app_path(AppDir::Data, QUEUE_FILE).ok()
+}
+
+/// Reads the queue file, ignoring it if missing or malformed
+fn read_queue() -> Option<Counts> {
+    let content = std::fs::read_to_string(queue_path()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_queue(counts: &Counts) -> Result<(), String> {
+    let path = queue_path().ok_or("Failed to find the data directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string(counts).map_err(|e| e.to_string())?;
+    std::fs::write(path, content).map_err(|e| e.to_string())
+}
+
+fn unix_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(launches: u32, features: &[(&str, u32)]) -> Counts {
+        Counts {
+            since: 100,
+            launches,
+            features: features
+                .iter()
+                .map(|(name, count)| (name.to_string(), *count))
+                .collect(),
+            errors: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_subtract_keeps_counts_added_during_upload() {
+        let sent = counts(1, &[("overlay", 2)]);
+        let mut queue = counts(2, &[("overlay", 2), ("diagnostics", 1)]);
+        queue.subtract(&sent, 200);
+        assert_eq!(
+            queue,
+            Counts {
+                since: 200,
+                ..counts(1, &[("diagnostics", 1)])
+            }
+        );
+
+        queue.subtract(&queue.clone(), 300);
+        assert!(queue.is_empty());
+        assert_eq!(queue.since, 0);
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let report = TelemetryReport {
+            launcher_version: "1.0.0".to_string(),
+            proxy_version: None,
+            os: "linux",
+            arch: "x86_64",
+            counts: counts(1, &[("overlay", 1)]),
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "launcherVersion": "1.0.0",
+                "proxyVersion": null,
+                "os": "linux",
+                "arch": "x86_64",
+                "since": 100,
+                "launches": 1,
+                "features": { "overlay": 1 },
+                "errors": {},
+            })
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! System tray icon.
+//!
+//! With `minimizeToTray` enabled, closing the window only hides it; the
+//! proxy and Discord presence keep running and the tray menu brings the
+//! window back, starts or stops the proxy, or quits the launcher.
+//!
+//! In tray-only mode (`trayOnly` or `--tray-only`), the launcher runs as a
+//! background agent: the window isn't created on startup, only when it's
+//! opened from the tray or by a link, and closing it destroys it again. The
+//! proxy, Discord presence and background tasks keep running throughout.
+
+use crate::commands;
+use crate::proxy::ProxyManager;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, WebviewWindow, WebviewWindowBuilder};
+
+/// ID of the launcher's tray icon
+pub(crate) const TRAY_ID: &str = "main";
+
+/// Label of the launcher window
+pub(crate) const MAIN_WINDOW: &str = "main";
+
+/// Whether the launcher runs without a window until one is opened
+static TRAY_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Creates the tray icon and its menu
+pub(crate) fn create(app: &AppHandle) -> tauri::Result<()> {
+    let toggle = MenuItem::with_id(app, "toggle", "Show/Hide", true, None::<&str>)?;
+    let launch = MenuItem::with_id(app, "launch", "Launch Proxy", true, None::<&str>)?;
+    let stop = MenuItem::with_id(app, "stop", "Stop Proxy", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &toggle,
+            &PredefinedMenuItem::separator(app)?,
+            &launch,
+            &stop,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Duels+ Launcher")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Whether the tray icon exists, i.e. hiding the window leaves a way back
+pub(crate) fn is_available(app: &AppHandle) -> bool {
+    app.tray_by_id(TRAY_ID).is_some()
+}
+
+/// How the launcher window appears on startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StartMode {
+    /// Shown and focused
+    Normal,
+    /// Hidden, with the tray icon to bring it back
+    Tray,
+    /// Minimized to the taskbar, when there's no tray icon
+    Minimized,
+}
+
+impl StartMode {
+    /// Gets the start mode for a launcher that should start minimized or
+    /// not, depending on whether the tray icon exists
+    pub(crate) fn new(minimized: bool, tray_available: bool) -> Self {
+        match (minimized, tray_available) {
+            (false, _) => StartMode::Normal,
+            (true, true) => StartMode::Tray,
+            (true, false) => StartMode::Minimized,
+        }
+    }
+}
+
+/// Creates the launcher window on startup, unless the launcher runs
+/// tray-only and the tray icon exists to open it later
+pub(crate) fn start(app: &AppHandle, tray_only: bool) {
+    let tray_only = tray_only && is_available(app);
+    TRAY_ONLY.store(tray_only, Ordering::SeqCst);
+    if tray_only {
+        println!("[tray] Running in the background until opened");
+    } else if let Err(e) = main_window(app) {
+        eprintln!("[tray] Failed to create the launcher window: {}", e);
+    }
+}
+
+/// Whether the launcher runs tray-only, i.e. without a window until it's
+/// opened
+pub(crate) fn is_tray_only() -> bool {
+    TRAY_ONLY.load(Ordering::SeqCst)
+}
+
+/// Gets the launcher window, creating it (hidden) if there isn't one
+fn main_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        return Ok(window);
+    }
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == MAIN_WINDOW)
+        .cloned()
+        .unwrap_or_default();
+    WebviewWindowBuilder::from_config(app, &config)?.build()
+}
+
+/// Shows and focuses the launcher window, creating it in tray-only mode
+pub(crate) fn show_window(app: &AppHandle) {
+    match main_window(app) {
+        Ok(window) => {
+            let _ = window.unminimize();
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        Err(e) => eprintln!("[tray] Failed to create the launcher window: {}", e),
+    }
+}
+
+/// Shows the launcher window if hidden, hides it otherwise
+pub(crate) fn toggle_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        show_window(app);
+        return;
+    };
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+    } else {
+        show_window(app);
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id.as_ref() {
+        "toggle" => toggle_window(app),
+        "launch" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let Some(manager) = app.try_state::<ProxyManager>() else {
+                    return;
+                };
+                if manager.is_running().await {
+                    return;
+                }
+                // Failures are already reported to the logs by the command
+                let _ = commands::launch_proxy(app.clone(), manager, None).await;
+            });
+        }
+        "stop" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let Some(manager) = app.try_state::<ProxyManager>() else {
+                    return;
+                };
+                if !manager.is_running().await {
+                    return;
+                }
+                if let Err(e) = commands::stop_proxy(app.clone(), manager).await {
+                    eprintln!("[tray] Failed to stop proxy: {}", e);
+                }
+            });
+        }
+        "quit" => crate::shutdown_and_exit(app),
+        _ => {}
+    }
+}
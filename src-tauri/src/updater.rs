@@ -0,0 +1,285 @@
+//! Launcher self-updates.
+//!
+//! The updater plugin checks for and installs updates; this adds the policy
+//! around it:
+//! - `updateChannel` picks the stable or beta release channel
+//! - "Remind me later" sets `updateDeferredUntil`, skipping automatic
+//!   checks for a day
+//! - With `installUpdatesOnQuit`, a downloaded update waits until the
+//!   launcher quits instead of installing right away. It's also recorded
+//!   in the journal, so if the launcher is killed it's downloaded (and
+//!   verified) again on the next start (see [`crate::journal`])
+//!
+//! Emits `launcher-update-available` with the [`LauncherUpdate`] (including
+//! its release notes), `launcher-update-progress` while downloading and
+//! `launcher-update-ready` once downloaded, with whether it's installed yet.
+
+use crate::config;
+use crate::config::models::{Config, UpdateChannel};
+use crate::journal::{self, Operation};
+use crate::utils::{self, AppDir};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+/// Update manifest of the beta channel; stable uses the endpoint from
+/// `tauri.conf.json`
+const BETA_ENDPOINT: &str =
+    "https://github.com/duelsplus/launcher-tauri/releases/download/beta/latest.json";
+
+/// Name of a downloaded update waiting for the launcher to quit, in the
+/// cache directory
+const STAGED_FILE: &str = "launcher-update";
+
+/// How long "remind me later" skips automatic checks for
+const REMIND_LATER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// An available launcher update
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherUpdate {
+    pub version: String,
+    pub current_version: String,
+    /// Release notes, in Markdown
+    pub notes: Option<String>,
+    /// Release date, in RFC 3339 format
+    pub date: Option<String>,
+}
+
+impl From<&Update> for LauncherUpdate {
+    fn from(update: &Update) -> Self {
+        LauncherUpdate {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            notes: update.body.clone(),
+            date: update
+                .raw_json
+                .get("pub_date")
+                .and_then(|date| date.as_str())
+                .map(String::from),
+        }
+    }
+}
+
+/// Bytes downloaded so far, emitted as `launcher-update-progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    /// `None` if the server didn't send a size
+    pub total: Option<u64>,
+}
+
+/// Launcher update state, managed by Tauri
+#[derive(Default)]
+pub struct LauncherUpdater {
+    /// Found by the last check and not downloaded yet
+    available: Mutex<Option<Update>>,
+    /// Downloaded and waiting to be installed on quit
+    downloaded: Mutex<Option<(Update, Vec<u8>)>>,
+}
+
+impl LauncherUpdater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks the configured channel for an update.
+    ///
+    /// Automatic checks are skipped while "remind me later" is in effect,
+    /// manual ones aren't.
+    pub async fn check(
+        &self,
+        app: &AppHandle,
+        manual: bool,
+    ) -> Result<Option<LauncherUpdate>, String> {
+        let cfg = config::manager::get_config()
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+        if !manual && is_deferred(cfg.update_deferred_until, unix_now()) {
+            return Ok(None);
+        }
+
+        let update = fetch(app, &cfg).await?;
+        let info = update.as_ref().map(LauncherUpdate::from);
+        if let Some(info) = &info {
+            let _ = app.emit("launcher-update-available", info);
+        }
+        *self.available.lock().unwrap() = update;
+        Ok(info)
+    }
+
+    /// Downloads the update found by the last check, then installs it or
+    /// keeps it for when the launcher quits.
+    ///
+    /// # Returns
+    /// `true` if it's installed and takes effect on restart, `false` if it
+    /// installs on quit
+    pub async fn download(&self, app: &AppHandle) -> Result<bool, String> {
+        let update = self
+            .available
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "No launcher update to download".to_string())?;
+
+        let mut downloaded = 0;
+        let bytes = update
+            .download(
+                |chunk, total| {
+                    downloaded += chunk as u64;
+                    let _ = app.emit(
+                        "launcher-update-progress",
+                        UpdateProgress { downloaded, total },
+                    );
+                },
+                || {},
+            )
+            .await
+            .map_err(|e| format!("Failed to download the launcher update: {}", e))?;
+        self.available.lock().unwrap().take();
+
+        let install_on_quit = config::manager::get_config()
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|cfg| cfg.install_updates_on_quit);
+        if install_on_quit {
+            stage(&update.version, &bytes);
+            *self.downloaded.lock().unwrap() = Some((update, bytes));
+        } else {
+            update
+                .install(&bytes)
+                .map_err(|e| format!("Failed to install the launcher update: {}", e))?;
+        }
+
+        let installed = !install_on_quit;
+        let _ = app.emit("launcher-update-ready", installed);
+        Ok(installed)
+    }
+
+    /// Installs an update kept for when the launcher quits, to call while
+    /// shutting down
+    pub fn install_pending(&self) {
+        let pending = self.downloaded.lock().unwrap().take();
+        if let Some((update, bytes)) = pending {
+            println!("[updater] Installing launcher {} on quit", update.version);
+            if let Err(e) = update.install(&bytes) {
+                eprintln!("[updater] Failed to install the launcher update: {}", e);
+            }
+            unstage(&update.version);
+        }
+    }
+
+    /// Keeps an update saved by an earlier run for when the launcher quits,
+    /// if it's still the latest one.
+    ///
+    /// The saved file isn't trusted: the updater only checks signatures
+    /// while downloading, so the update is downloaded again and saved over
+    /// it instead.
+    ///
+    /// # Returns
+    /// `true` if it's kept, `false` if there's a different update (or none)
+    /// by now
+    pub async fn restage(&self, app: &AppHandle, version: &str) -> Result<bool, String> {
+        let cfg = config::manager::get_config()
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+        match fetch(app, &cfg).await? {
+            Some(update) if update.version == version => {
+                let bytes = update
+                    .download(|_, _| {}, || {})
+                    .await
+                    .map_err(|e| format!("Failed to download the launcher update: {}", e))?;
+                stage(version, &bytes);
+                println!("[updater] Launcher {} installs on quit", version);
+                *self.downloaded.lock().unwrap() = Some((update, bytes));
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Checks the configured channel for an update
+async fn fetch(app: &AppHandle, cfg: &Config) -> Result<Option<Update>, String> {
+    let mut builder = app.updater_builder();
+    if cfg.update_channel == UpdateChannel::Beta {
+        let endpoint = Url::parse(BETA_ENDPOINT).map_err(|e| e.to_string())?;
+        builder = builder
+            .endpoints(vec![endpoint])
+            .map_err(|e| e.to_string())?;
+    }
+    builder
+        .build()
+        .map_err(|e| e.to_string())?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for launcher updates: {}", e))
+}
+
+/// Saves a downloaded update until it's installed on quit
+fn stage(version: &str, bytes: &[u8]) {
+    let result = utils::get_app_path(AppDir::Cache, STAGED_FILE).and_then(|path| {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, bytes).map_err(|e| e.to_string())?;
+        journal::record(&Operation::StagedUpdate {
+            path,
+            version: version.to_string(),
+        });
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("[updater] Failed to save the launcher update: {}", e);
+    }
+}
+
+/// Deletes a saved update once it's installed
+fn unstage(version: &str) {
+    if let Ok(path) = utils::get_app_path(AppDir::Cache, STAGED_FILE) {
+        let _ = std::fs::remove_file(&path);
+        journal::remove(&Operation::StagedUpdate {
+            path,
+            version: version.to_string(),
+        });
+    }
+}
+
+/// Skips automatic update checks for a while ("remind me later")
+pub async fn defer() -> Result<(), String> {
+    let until = unix_now() + REMIND_LATER.as_secs();
+    config::manager::set_config_key("updateDeferredUntil", serde_json::json!(until))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Whether "remind me later" still skips automatic checks
+fn is_deferred(until: Option<u64>, now: u64) -> bool {
+    until.is_some_and(|until| now < until)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_deferred() {
+        assert!(!is_deferred(None, 1_000));
+        assert!(is_deferred(Some(2_000), 1_000));
+        assert!(!is_deferred(Some(1_000), 1_000));
+        assert!(!is_deferred(Some(500), 1_000));
+    }
+}
@@ -1,6 +1,29 @@
 //! Utility functions for common operations.
 
-use std::path::PathBuf;
+use directories::ProjectDirs;
+use std::path::{Path, PathBuf};
+
+/// Where a kind of launcher data is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppDir {
+    /// Settings, e.g. `~/.config/duelsplus` on Linux
+    Config,
+    /// The installed proxy and crash reports, e.g. `~/.local/share/duelsplus`
+    Data,
+    /// Files that can be downloaded again, e.g. `~/.cache/duelsplus`
+    Cache,
+}
+
+impl AppDir {
+    /// Where the same data was kept under `~/.duelsplus` before it moved
+    fn legacy(&self) -> Result<PathBuf, String> {
+        let root = get_app_root()?;
+        Ok(match self {
+            AppDir::Config | AppDir::Data => root,
+            AppDir::Cache => root.join("cache"),
+        })
+    }
+}
 
 /// Gets the home directory path for the current user.
 ///
@@ -23,8 +46,153 @@ pub fn get_home_dir() -> Result<PathBuf, String> {
 
 /// Gets the app root directory (~/.duelsplus).
 ///
+/// Launcher data has moved to the platform's directories (see
+/// [`get_app_dir`]); this is left for what's shared with the proxy, such as
+/// its lock file and the sign-in token.
+///
 /// Returns the app root directory path, or an error message if the home directory
 /// cannot be determined.
 pub fn get_app_root() -> Result<PathBuf, String> {
     Ok(get_home_dir()?.join(".duelsplus"))
 }
+
+/// Gets the platform's directory for a kind of launcher data.
+///
+/// Falls back to the old location under `~/.duelsplus` if the platform
+/// doesn't have one.
+pub fn get_app_dir(dir: AppDir) -> Result<PathBuf, String> {
+    let Some(dirs) = ProjectDirs::from("com", "duelsplus", "duelsplus") else {
+        return dir.legacy();
+    };
+    Ok(match dir {
+        AppDir::Config => dirs.config_dir().to_path_buf(),
+        AppDir::Data => dirs.data_dir().to_path_buf(),
+        AppDir::Cache => dirs.cache_dir().to_path_buf(),
+    })
+}
+
+/// Gets the path of a launcher file or directory, e.g. the installed proxy
+/// at `get_app_path(AppDir::Data, "proxy")`.
+///
+/// Anything not moved out of `~/.duelsplus` yet (see
+/// [`crate::migrate`]) is still used from there.
+pub fn get_app_path(dir: AppDir, name: &str) -> Result<PathBuf, String> {
+    let path = get_app_dir(dir)?.join(name);
+    let legacy = dir.legacy()?.join(name);
+    Ok(if !path.exists() && legacy.exists() {
+        legacy
+    } else {
+        path
+    })
+}
+
+/// Gets a path in the Downloads folder for a file the player exports,
+/// falling back to the data directory if there's no Downloads folder
+pub(crate) fn get_export_path(name: &str) -> Result<PathBuf, String> {
+    let downloads = get_home_dir()?.join("Downloads");
+    let dir = if downloads.is_dir() {
+        downloads
+    } else {
+        get_app_dir(AppDir::Data)?
+    };
+    Ok(dir.join(name))
+}
+
+/// Gets where a launcher file or directory used to be kept, before it moved
+/// to [`get_app_dir`]
+pub(crate) fn get_legacy_path(dir: AppDir, name: &str) -> Result<PathBuf, String> {
+    Ok(dir.legacy()?.join(name))
+}
+
+/// Builds a command that doesn't flash a console window
+#[cfg(windows)]
+pub fn hidden_command(program: &str) -> std::process::Command {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let mut cmd = std::process::Command::new(program);
+    cmd.creation_flags(CREATE_NO_WINDOW)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    cmd
+}
+
+/// Writes a file only the current user can read, for keys and secrets.
+///
+/// On Unix it's created with mode 0600, and an existing file is narrowed to
+/// that before it's written; on Windows the user's directories already keep
+/// other users out.
+pub(crate) fn write_private(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.write_all(contents.as_ref())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Generates a random token of `len` bytes, as hex
+pub(crate) fn random_token(len: usize) -> Result<String, String> {
+    use ring::rand::{SecureRandom, SystemRandom};
+
+    let mut bytes = vec![0u8; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "Failed to generate a random token".to_string())?;
+    Ok(to_hex(&bytes))
+}
+
+/// Compares secrets in time that doesn't depend on where they differ
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Encodes bytes as lowercase hex
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decodes hex, `None` if it isn't valid hex
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip() {
+        assert_eq!(to_hex(&[0, 15, 255]), "000fff");
+        assert_eq!(from_hex("000fff"), Some(vec![0, 15, 255]));
+        assert_eq!(from_hex("0f0"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secrets"));
+    }
+}
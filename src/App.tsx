@@ -4,13 +4,22 @@ import { OnboardingProvider, useOnboarding } from "@/lib/onboarding";
 import { Onboarding } from "@/components/onboarding";
 import { TabsProvider } from "@/lib/tabs";
 import { useEffect } from "react";
-import { showWindow } from "@/lib/window";
+import { invoke } from "@tauri-apps/api/core";
+import { showMinimized, showWindow } from "@/lib/window";
+import type { StartMode } from "@/types/window";
+import { UnlockAppDialog } from "@/components/dialogs/unlock-app";
 
 function Content() {
   const { isOnboarded, setOnboarded } = useOnboarding();
 
   useEffect(() => {
-    showWindow();
+    // Stay in the tray (or the taskbar) when started minimized
+    invoke<StartMode>("get_start_mode")
+      .catch((): StartMode => "normal")
+      .then((mode) => {
+        if (mode === "normal") showWindow();
+        else if (mode === "minimized") showMinimized();
+      });
 
     const handleContextMenu = (e: MouseEvent) => e.preventDefault();
     document.addEventListener("contextmenu", handleContextMenu);
@@ -20,11 +29,16 @@ function Content() {
     };
   }, []);
 
-  if (!isOnboarded) {
-    return <Onboarding open onFinish={() => setOnboarded(true)} />;
-  }
-
-  return <Shell />;
+  return (
+    <>
+      {isOnboarded ? (
+        <Shell />
+      ) : (
+        <Onboarding open onFinish={() => setOnboarded(true)} />
+      )}
+      <UnlockAppDialog />
+    </>
+  );
 }
 
 function App() {
@@ -0,0 +1,125 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "@/components/ui/dialog";
+import { Button } from "../ui/button";
+import { Input } from "../ui/input";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+
+interface AppPinDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  /** Whether a PIN is set now, which changing or removing it needs */
+  enabled: boolean;
+  onSaved: () => void;
+}
+
+const digits = (value: string) => value.replace(/\D/g, "");
+
+export function AppPinDialog({
+  open,
+  onOpenChange,
+  enabled,
+  onSaved,
+}: AppPinDialogProps) {
+  const [current, setCurrent] = useState("");
+  const [pin, setPin] = useState("");
+  const [confirm, setConfirm] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    if (!open) return;
+    setCurrent("");
+    setPin("");
+    setConfirm("");
+    setError(null);
+  }, [open]);
+
+  const save = async (next: string | null) => {
+    if (next !== null && next !== confirm) {
+      setError("The PINs don't match");
+      return;
+    }
+    setBusy(true);
+    try {
+      await invoke("set_app_pin", {
+        current: enabled ? current : null,
+        pin: next,
+      });
+      onSaved();
+      onOpenChange(false);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>{enabled ? "Change PIN" : "Set a PIN"}</DialogTitle>
+        </DialogHeader>
+
+        <section className="space-y-3 text-sm leading-relaxed text-muted-foreground">
+          <p>
+            The PIN is asked for before anyone signs out, switches accounts or
+            reads your token. Use 4 to 12 digits.
+          </p>
+          {enabled && (
+            <Input
+              type="password"
+              inputMode="numeric"
+              value={current}
+              onChange={(e) => setCurrent(digits(e.target.value))}
+              maxLength={12}
+              placeholder="Current PIN"
+            />
+          )}
+          <Input
+            type="password"
+            inputMode="numeric"
+            value={pin}
+            onChange={(e) => setPin(digits(e.target.value))}
+            maxLength={12}
+            placeholder="New PIN"
+          />
+          <Input
+            type="password"
+            inputMode="numeric"
+            value={confirm}
+            onChange={(e) => setConfirm(digits(e.target.value))}
+            maxLength={12}
+            placeholder="Confirm new PIN"
+          />
+          {error && <p className="text-xs text-rose-400">{error}</p>}
+        </section>
+
+        <DialogFooter>
+          {enabled && (
+            <Button
+              variant="outline"
+              disabled={!current || busy}
+              onClick={() => save(null)}
+            >
+              Remove PIN
+            </Button>
+          )}
+          <Button
+            variant="input"
+            disabled={pin.length < 4 || (enabled && !current) || busy}
+            onClick={() => save(pin)}
+          >
+            Save
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,71 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "@/components/ui/dialog";
+import { Button } from "../ui/button";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { useEffect, useState } from "react";
+import type { DeepLink } from "@/types/deeplink";
+
+type LaunchRequest = { port: number | null };
+
+//any page can open a duelsplus://launch link, so ask before launching
+export function LaunchLinkDialog() {
+  const [request, setRequest] = useState<LaunchRequest | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<DeepLink>("deep-link", async (event) => {
+      if (event.payload.kind !== "launch") return;
+      const running = await invoke<boolean>("get_proxy_status").catch(
+        () => false,
+      );
+      if (!running) setRequest({ port: event.payload.port });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handleLaunch = async () => {
+    const port = request?.port ?? null;
+    setRequest(null);
+    await invoke("launch_proxy", { port }).catch(console.error);
+  };
+
+  return (
+    <Dialog
+      open={request !== null}
+      onOpenChange={(open) => {
+        if (!open) setRequest(null);
+      }}
+    >
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Launch the proxy?</DialogTitle>
+        </DialogHeader>
+
+        <section className="space-y-3 text-sm leading-relaxed text-muted-foreground">
+          <p>
+            A link asked the launcher to start the proxy
+            {request?.port ? ` on port ${request.port}` : ""}. Only launch it
+            if you opened the link yourself.
+          </p>
+        </section>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => setRequest(null)}>
+            Cancel
+          </Button>
+          <Button variant="input" onClick={handleLaunch}>
+            Launch
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
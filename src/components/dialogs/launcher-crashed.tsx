@@ -0,0 +1,69 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "@/components/ui/dialog";
+import { Button } from "../ui/button";
+import { invoke } from "@tauri-apps/api/core";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
+import { useEffect, useState } from "react";
+
+type LauncherCrash = {
+  path: string;
+  message: string;
+};
+
+export function LauncherCrashedDialog() {
+  const [crash, setCrash] = useState<LauncherCrash | null>(null);
+
+  useEffect(() => {
+    invoke<LauncherCrash | null>("take_launcher_crash")
+      .then(setCrash)
+      .catch(() => setCrash(null));
+  }, []);
+
+  const handleOpenFolder = async () => {
+    if (crash) {
+      await revealItemInDir(crash.path).catch(console.error);
+    }
+    setCrash(null);
+  };
+
+  return (
+    <Dialog
+      open={crash !== null}
+      onOpenChange={(open) => {
+        if (!open) setCrash(null);
+      }}
+    >
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>The launcher crashed</DialogTitle>
+        </DialogHeader>
+
+        <section className="space-y-3 text-sm leading-relaxed text-muted-foreground">
+          <p>
+            The launcher closed unexpectedly last time. A crash report was
+            saved; attach it to a support ticket so we can look into it.
+          </p>
+          {crash?.message && (
+            <pre className="p-3 rounded-xl bg-muted/70 text-xs whitespace-pre-wrap break-all">
+              {crash.message}
+            </pre>
+          )}
+        </section>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => setCrash(null)}>
+            Dismiss
+          </Button>
+          <Button variant="input" onClick={handleOpenFolder}>
+            Open Crash Folder
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,75 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "@/components/ui/dialog";
+import { Button } from "../ui/button";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { useEffect, useState } from "react";
+import type { OrphanProxyInfo, ScopedEvent } from "@/types/proxy";
+
+export function OrphanProxyDialog() {
+  const [orphan, setOrphan] = useState<OrphanProxyInfo | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    //found during startup, usually before this listens
+    invoke<OrphanProxyInfo | null>("get_orphan_proxy")
+      .then(setOrphan)
+      .catch(() => {});
+
+    const unlisten = listen<ScopedEvent<OrphanProxyInfo>>(
+      "proxy-orphan-detected",
+      (event) => setOrphan(event.payload.payload),
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handleStop = async () => {
+    try {
+      await invoke("kill_orphan_proxy");
+      setOrphan(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <Dialog
+      open={orphan !== null}
+      onOpenChange={(open) => {
+        if (!open) setOrphan(null);
+      }}
+    >
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>A proxy is already running</DialogTitle>
+        </DialogHeader>
+
+        <section className="space-y-3 text-sm leading-relaxed text-muted-foreground">
+          <p>
+            A proxy from an earlier session is still running on port{" "}
+            {orphan?.port}, and the launcher can't control it. Stop it to
+            launch a new one on that port.
+          </p>
+          {error && <p className="text-xs text-rose-400">{error}</p>}
+        </section>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => setOrphan(null)}>
+            Leave Running
+          </Button>
+          <Button variant="input" onClick={handleStop}>
+            Stop It
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -5,10 +5,20 @@ import {
   DialogTitle,
   DialogFooter,
 } from "@/components/ui/dialog";
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { Button } from "../ui/button";
 import { ProxyError } from "@/types/proxy";
+import type { ServiceStatus } from "@/types/service-status";
 import { cn } from "@/lib/utils";
 
+//errors that may be hypixel or mojang being down rather than the proxy
+const SERVICE_CATEGORIES: ProxyError["category"][] = [
+  "network",
+  "authentication",
+  "hypixel",
+];
+
 interface ProxyErrorDialogProps {
   open: boolean;
   onOpenChange: (open: boolean) => void;
@@ -20,6 +30,18 @@ export function ProxyErrorDialog({
   onOpenChange,
   error,
 }: ProxyErrorDialogProps) {
+  const [outage, setOutage] = useState<string | null>(null);
+
+  useEffect(() => {
+    setOutage(null);
+    if (!open || !error || !SERVICE_CATEGORIES.includes(error.category)) {
+      return;
+    }
+    invoke<ServiceStatus>("get_service_status")
+      .then((status) => setOutage(status.summary))
+      .catch(() => {});
+  }, [open, error]);
+
   if (!error) return null;
   return (
     <Dialog open={open} onOpenChange={onOpenChange}>
@@ -30,8 +52,11 @@ export function ProxyErrorDialog({
 
         <section className="space-y-3 text-sm leading-relaxed text-muted-foreground">
           <p className="font-medium">
-            The proxy has crashed unexpectedly. Here's what we know:
+            {outage
+              ? "This doesn't look like a problem with the proxy:"
+              : "The proxy has crashed unexpectedly. Here's what we know:"}
           </p>
+          {outage && <p className="font-medium text-foreground">{outage}</p>}
           <p>{error.message}</p>
           <div>
             <Button
@@ -69,10 +69,7 @@ export function RestartPendingDialog({
 
   const handleRestart = async () => {
     setLoading(true);
-    const running = await invoke<boolean>("get_proxy_status");
-    if (running) {
-      await invoke("stop_proxy");
-    }
+    await invoke("shutdown_all");
     await relaunch();
   };
 
@@ -0,0 +1,88 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "@/components/ui/dialog";
+import { Button } from "../ui/button";
+import { Input } from "../ui/input";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+import { useAppLock } from "@/lib/applock";
+
+//shown when a PIN-guarded action finds the launcher locked
+export function UnlockAppDialog() {
+  const prompt = useAppLock((s) => s.prompt);
+  const finish = useAppLock((s) => s.finish);
+  const [pin, setPin] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    setPin("");
+    setError(null);
+  }, [prompt]);
+
+  const handleUnlock = async () => {
+    if (!pin || busy) return;
+    setBusy(true);
+    try {
+      await invoke("unlock_app", { pin });
+      finish(true);
+    } catch (err) {
+      setError(String(err));
+      setPin("");
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Dialog
+      open={prompt !== null}
+      onOpenChange={(open) => {
+        if (!open) finish(false);
+      }}
+    >
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Enter your PIN</DialogTitle>
+        </DialogHeader>
+
+        <form
+          className="space-y-3 text-sm leading-relaxed text-muted-foreground"
+          onSubmit={(e) => {
+            e.preventDefault();
+            handleUnlock();
+          }}
+        >
+          <p>The launcher is locked. Enter your PIN to continue.</p>
+          <Input
+            type="password"
+            inputMode="numeric"
+            autoFocus
+            value={pin}
+            onChange={(e) => setPin(e.target.value.replace(/\D/g, ""))}
+            maxLength={12}
+            placeholder="PIN"
+          />
+          {error && <p className="text-xs text-rose-400">{error}</p>}
+        </form>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => finish(false)}>
+            Cancel
+          </Button>
+          <Button
+            variant="input"
+            disabled={!pin || busy}
+            onClick={handleUnlock}
+          >
+            Unlock
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
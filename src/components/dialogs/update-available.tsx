@@ -0,0 +1,53 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "@/components/ui/dialog";
+import { Button } from "../ui/button";
+import { useUpdater } from "@/lib/updater";
+
+export function UpdateAvailableDialog() {
+  const status = useUpdater((s) => s.status);
+  const install = useUpdater((s) => s.install);
+  const remindLater = useUpdater((s) => s.remindLater);
+
+  const update = status.state === "available" ? status.update : null;
+
+  return (
+    <Dialog
+      open={update !== null}
+      onOpenChange={(open) => {
+        if (!open) remindLater();
+      }}
+    >
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Launcher Update Available</DialogTitle>
+        </DialogHeader>
+
+        <section className="space-y-3 text-sm leading-relaxed text-muted-foreground">
+          <p>
+            Version <strong>{update?.version}</strong> is available. You're on{" "}
+            {update?.currentVersion}.
+          </p>
+          {update?.notes && (
+            <pre className="max-h-60 overflow-y-auto p-3 rounded-xl bg-muted/70 text-xs whitespace-pre-wrap break-words font-sans">
+              {update.notes}
+            </pre>
+          )}
+        </section>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={remindLater}>
+            Remind Me Later
+          </Button>
+          <Button variant="input" onClick={install}>
+            Update Now
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,68 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { UsersIcon } from "@phosphor-icons/react";
+import clsx from "clsx";
+import type { FriendPresence } from "@/types/friends";
+
+//DUELS_SUMO_DUEL -> Sumo Duel
+function modeName(mode: string) {
+  return mode
+    .replace(/^DUELS_/i, "")
+    .toLowerCase()
+    .split("_")
+    .map((word) => word.charAt(0).toUpperCase() + word.slice(1))
+    .join(" ");
+}
+
+export function FriendsOnline() {
+  const [friends, setFriends] = useState<FriendPresence[]>([]);
+
+  useEffect(() => {
+    invoke<FriendPresence[]>("get_friends_presence")
+      .then(setFriends)
+      .catch(() => {});
+    const unlisten = listen<FriendPresence[]>(
+      "friends-presence-updated",
+      (event) => setFriends(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const online = friends.filter((friend) => friend.status !== "offline");
+  if (online.length === 0) return null;
+
+  return (
+    <div className="p-1.5 rounded-3xl bg-muted/70">
+      <div className="flex justify-between items-center gap-3 px-2 pt-1 mb-2">
+        <h2 className="text-xs font-bold tracking-widest uppercase text-muted-foreground/50">
+          Friends Online
+        </h2>
+        <UsersIcon weight="fill" className="size-3 text-muted-foreground/50" />
+      </div>
+      <div className="flex flex-wrap gap-1">
+        {online.map((friend) => (
+          <div
+            key={friend.id}
+            className="flex items-center gap-2 rounded-2xl bg-background px-3 py-1.5 text-sm"
+          >
+            <span
+              className={clsx(
+                "size-2 rounded-full",
+                friend.status === "inGame" ? "bg-amber-400" : "bg-emerald-400",
+              )}
+            />
+            {friend.username}
+            {friend.mode && (
+              <span className="text-xs text-muted-foreground">
+                {modeName(friend.mode)}
+              </span>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,25 @@
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { ThemeProvider } from "@/components/theme-provider";
+import { Logs } from "@/components/tabs/logs-tab";
+import { useLogs } from "@/lib/proxy-logs";
+
+/** Content of the separate logs window */
+export function LogsWindow() {
+  useEffect(() => {
+    //start with what was logged before the window opened
+    invoke<string[]>("get_recent_logs")
+      .then((lines) => {
+        useLogs.setState((state) => ({ logs: [...lines, ...state.logs] }));
+      })
+      .catch(console.error);
+  }, []);
+
+  return (
+    <ThemeProvider>
+      <div className="w-screen h-screen bg-background text-foreground overflow-hidden p-4">
+        <Logs detached />
+      </div>
+    </ThemeProvider>
+  );
+}
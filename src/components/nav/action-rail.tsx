@@ -4,11 +4,14 @@ import {
   HouseIcon,
   ListHeartIcon,
   ChartLineIcon,
+  ClockCounterClockwiseIcon,
+  MagnifyingGlassIcon,
   GearFineIcon,
   SpinnerIcon,
   DownloadSimpleIcon,
   ArrowsClockwiseIcon,
   WarningIcon,
+  TerminalWindowIcon,
 } from "@phosphor-icons/react";
 import type { IconWeight } from "@phosphor-icons/react";
 import clsx from "clsx";
@@ -21,16 +24,27 @@ import {
   TooltipTrigger,
 } from "@/components/ui/tooltip";
 import { Stats } from "../tabs/stats-tab";
+import { History } from "../tabs/history-tab";
+import { Lookup } from "../tabs/lookup-tab";
 import { Settings } from "../tabs/settings-tab";
 import { Logs } from "../tabs/logs-tab";
+import { Console } from "../tabs/console-tab";
 import { UserButton } from "./user-button";
 import { notify } from "@/lib/notification";
 import { DonateButton } from "./donate-button";
 import { SubscribeButton } from "./subscribe-button";
 import { getVersion } from "@tauri-apps/api/app";
+import { config as configApi } from "@/lib/config";
 
 interface ActionButtonProps {
-  icon: "home" | "logs" | "stats" | "settings";
+  icon:
+    | "home"
+    | "logs"
+    | "console"
+    | "stats"
+    | "history"
+    | "lookup"
+    | "settings";
   active?: boolean;
   onClick?: () => void;
 }
@@ -40,7 +54,10 @@ function ActionButton({ icon, active, onClick }: ActionButtonProps) {
   const iconsMap = {
     home: <HouseIcon weight={iconWeight} />,
     logs: <ListHeartIcon weight={iconWeight} />,
+    console: <TerminalWindowIcon weight={iconWeight} />,
     stats: <ChartLineIcon weight={iconWeight} />,
+    history: <ClockCounterClockwiseIcon weight={iconWeight} />,
+    lookup: <MagnifyingGlassIcon weight={iconWeight} />,
     settings: <GearFineIcon weight={iconWeight} />,
   };
 
@@ -139,6 +156,15 @@ function UpdateButton() {
 
 export function ActionRail() {
   const { activeTab, toggleTab } = useTabs();
+  const [consoleEnabled, setConsoleEnabled] = useState(false);
+
+  // Re-read on tab changes so toggling the setting applies after leaving settings
+  useEffect(() => {
+    configApi
+      .getValue("enableProxyConsole")
+      .then(setConsoleEnabled)
+      .catch(() => setConsoleEnabled(false));
+  }, [activeTab]);
 
   return (
     <>
@@ -154,11 +180,28 @@ export function ActionRail() {
             active={activeTab === "logs"}
             onClick={() => toggleTab("logs")}
           />
+          {consoleEnabled && (
+            <ActionButton
+              icon="console"
+              active={activeTab === "console"}
+              onClick={() => toggleTab("console")}
+            />
+          )}
           <ActionButton
             icon="stats"
             active={activeTab === "stats"}
             onClick={() => toggleTab("stats")}
           />
+          <ActionButton
+            icon="history"
+            active={activeTab === "history"}
+            onClick={() => toggleTab("history")}
+          />
+          <ActionButton
+            icon="lookup"
+            active={activeTab === "lookup"}
+            onClick={() => toggleTab("lookup")}
+          />
           <ActionButton
             icon="settings"
             active={activeTab === "settings"}
@@ -190,7 +233,10 @@ function Drawer() {
     >
       <div className="flex-1 overflow-y-auto p-6 pl-0">
         {activeTab === "logs" && <Logs />}
+        {activeTab === "console" && <Console />}
         {activeTab === "stats" && <Stats />}
+        {activeTab === "history" && <History />}
+        {activeTab === "lookup" && <Lookup />}
         {activeTab === "settings" && <Settings />}
       </div>
     </div>
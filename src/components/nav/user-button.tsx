@@ -17,6 +17,7 @@ import {
 import { cn } from "@/lib/utils";
 import { getToken, setToken } from "@/lib/token";
 import { useOnboarding } from "@/lib/onboarding";
+import { withUnlock } from "@/lib/applock";
 import { Skeleton } from "../ui/skeleton";
 import { User, Perm } from "@/lib/perm";
 import { Ripple } from "m3-ripple";
@@ -82,7 +83,7 @@ export function UserButton() {
       if (running) {
         await invoke("stop_proxy");
       }
-      await invoke("delete_token");
+      await withUnlock(() => invoke("delete_token"));
       setToken(null);
       setOnboarded(false);
     } catch (err) {
@@ -9,6 +9,7 @@ import { Titlebar } from "./titlebar";
 import { useTheme } from "@/components/theme-provider";
 import { setToken as setCachedToken } from "@/lib/token";
 import { startDiscordAuth } from "@/lib/discord";
+import { withUnlock } from "@/lib/applock";
 import { Ripple } from "m3-ripple";
 
 type OnboardingProps = {
@@ -106,7 +107,7 @@ export function Onboarding({ open, onFinish }: OnboardingProps) {
         token,
       });
       if (response?.success) {
-        await invoke("save_token", { token });
+        await withUnlock(() => invoke("save_token", { token }));
         setHasValidToken(true);
         setCachedToken(token);
         setStep("theme"); //switch to import once implemented
@@ -0,0 +1,67 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { ThemeProvider } from "@/components/theme-provider";
+import type { Opponent, OverlayData } from "@/types/overlay";
+
+function ratio(wins: number | null, losses: number | null) {
+  if (wins === null || losses === null) return "?";
+  return (losses === 0 ? wins : wins / losses).toFixed(2);
+}
+
+function OpponentStats({ opponent }: { opponent: Opponent }) {
+  return (
+    <div className="flex flex-col gap-0.5">
+      <span className="truncate font-semibold">{opponent.name}</span>
+      {opponent.wins === null ? (
+        <span className="text-xs text-muted-foreground">No stats (nicked?)</span>
+      ) : (
+        <span className="text-xs text-muted-foreground">
+          {opponent.wins}W · {opponent.losses ?? "?"}L · {ratio(opponent.wins, opponent.losses)}{" "}
+          WLR
+          {opponent.winstreak !== null && ` · ${opponent.winstreak} WS`}
+        </span>
+      )}
+    </div>
+  );
+}
+
+/** Content of the always-on-top overlay window */
+export function Overlay() {
+  const [data, setData] = useState<OverlayData | null>(null);
+
+  useEffect(() => {
+    // Only the card is drawn; the window itself is transparent
+    document.body.classList.add("bg-transparent");
+    invoke<OverlayData>("get_overlay_data").then(setData).catch(console.error);
+
+    const unlisten = listen<OverlayData>("overlay-update", (event) => setData(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <ThemeProvider>
+      <div
+        data-tauri-drag-region
+        className="flex h-screen cursor-move select-none flex-col justify-between gap-2 rounded-xl bg-background/85 p-3 text-sm text-foreground"
+      >
+        {data?.inGame && data.opponent ? (
+          <OpponentStats opponent={data.opponent} />
+        ) : (
+          <span data-tauri-drag-region className="text-muted-foreground">
+            {data?.inGame ? "Waiting for opponent..." : "Not in a game"}
+          </span>
+        )}
+        <div data-tauri-drag-region className="flex items-center justify-between text-xs">
+          <span className="text-muted-foreground">Session</span>
+          <span className="font-medium">
+            {data?.wins ?? 0}W · {data?.losses ?? 0}L · {ratio(data?.wins ?? 0, data?.losses ?? 0)}{" "}
+            WLR
+          </span>
+        </div>
+      </div>
+    </ThemeProvider>
+  );
+}
@@ -0,0 +1,89 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { WifiHighIcon, WifiSlashIcon } from "@phosphor-icons/react";
+import { Button } from "./ui/button";
+import {
+  Tooltip,
+  TooltipContent,
+  TooltipTrigger,
+} from "@/components/ui/tooltip";
+import { config as configApi } from "@/lib/config";
+import type { Config } from "@/types/config";
+import type { PingResult } from "@/types/ping";
+
+function color(ms: number) {
+  if (ms < 80) return "text-emerald-500 dark:text-emerald-400";
+  if (ms < 150) return "text-amber-500 dark:text-amber-400";
+  return "text-rose-500 dark:text-rose-400";
+}
+
+export function PingIndicator() {
+  const [enabled, setEnabled] = useState(false);
+  const [ping, setPing] = useState<PingResult | null>(null);
+  const [measuring, setMeasuring] = useState(false);
+
+  const measure = () => {
+    setMeasuring(true);
+    invoke<PingResult[]>("measure_ping")
+      .then(([hypixel]) => setPing(hypixel))
+      .catch(() => {})
+      .finally(() => setMeasuring(false));
+  };
+
+  useEffect(() => {
+    configApi
+      .getValue("pingIndicator")
+      .then(setEnabled)
+      .catch(() => {});
+    const unlistenConfig = listen<Partial<Config>>("config-changed", (event) => {
+      if (event.payload.pingIndicator !== undefined) {
+        setEnabled(event.payload.pingIndicator);
+      }
+    });
+    //only sampled while the indicator is on
+    const unlisten = listen<PingResult>("ping-updated", (event) => {
+      setEnabled(true);
+      setPing(event.payload);
+    });
+    return () => {
+      unlistenConfig.then((fn) => fn());
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  //first sample without waiting for the sampler
+  useEffect(() => {
+    if (enabled && !ping) measure();
+  }, [enabled]);
+
+  if (!enabled || !ping) return null;
+
+  const avg = ping.avgMs;
+  return (
+    <Tooltip>
+      <TooltipTrigger asChild>
+        <Button
+          size="pill-lg"
+          variant="muted"
+          className="z-10 gap-2 [&_svg:not([class*='size-'])]:size-6"
+          disabled={measuring}
+          onClick={measure}
+        >
+          {avg === null ? (
+            <WifiSlashIcon weight="fill" className="text-rose-500" />
+          ) : (
+            <WifiHighIcon weight="fill" className={color(avg)} />
+          )}
+          <span>{avg === null ? "Unreachable" : `${Math.round(avg)} ms`}</span>
+        </Button>
+      </TooltipTrigger>
+      <TooltipContent>
+        {avg === null
+          ? "Hypixel didn't answer"
+          : `Hypixel · min ${Math.round(ping.minMs ?? avg)} ms · jitter ${Math.round(ping.jitterMs ?? 0)} ms`}
+        {ping.lost > 0 && ` · ${ping.lost} lost`}
+      </TooltipContent>
+    </Tooltip>
+  );
+}
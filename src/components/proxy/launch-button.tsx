@@ -1,6 +1,7 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { WebviewWindow } from "@tauri-apps/api/webviewWindow";
 import {
   SpinnerIcon,
   CloudArrowDownIcon,
@@ -22,14 +23,18 @@ import { useLogs } from "@/lib/proxy-logs";
 import { useTabs } from "@/lib/tabs";
 import { config } from "@/lib/config";
 import { ProxyErrorDialog } from "../dialogs/proxy-error";
-import type { ProxyError } from "@/types/proxy";
+import type {
+  ConnectionState,
+  ProxyError,
+  ScopedEvent,
+} from "@/types/proxy";
 import { notify } from "@/lib/notification";
 
 type ProxyStatusEvent =
   | { status: "checking" }
   | { status: "downloading"; version: string }
   | { status: "launching" }
-  | { status: "launched" }
+  | { status: "launched"; port: number }
   | { status: "error" };
 
 type DownloadProgress = {
@@ -89,10 +94,10 @@ export function LaunchButton({ isBeta = false }: LaunchButtonProps) {
   }, []);
 
   useEffect(() => {
-    const unlistenStatus = listen<ProxyStatusEvent>(
+    const unlistenStatus = listen<ScopedEvent<ProxyStatusEvent>>(
       "updater:status",
       async (event) => {
-        const status = event.payload.status;
+        const status = event.payload.payload.status;
         //setStatusText(status);
 
         if (status === "checking") {
@@ -108,7 +113,9 @@ export function LaunchButton({ isBeta = false }: LaunchButtonProps) {
 
         if (status === "launched") {
           const cfg = await config.get();
-          if (cfg.openLogsOnLaunch && activeTab !== "logs") {
+          //the backend brings up the logs window instead, if it's open
+          const logsWindow = await WebviewWindow.getByLabel("logs");
+          if (cfg.openLogsOnLaunch && !logsWindow && activeTab !== "logs") {
             toggleTab("logs");
           }
           setState("running");
@@ -125,10 +132,10 @@ export function LaunchButton({ isBeta = false }: LaunchButtonProps) {
       },
     );
 
-    const unlistenProgress = listen<DownloadProgress>(
+    const unlistenProgress = listen<ScopedEvent<DownloadProgress>>(
       "updater:progress",
       (event) => {
-        setProgress(event.payload);
+        setProgress(event.payload.payload);
       },
     );
 
@@ -138,15 +145,33 @@ export function LaunchButton({ isBeta = false }: LaunchButtonProps) {
     };
   }, []);
 
+  // The proxy can also be stopped from the tray menu
   useEffect(() => {
-    const unlisten = listen<ProxyError>("proxy-error", (event) => {
-      const { severity } = event.payload;
+    const unlisten = listen<ScopedEvent<ConnectionState>>(
+      "connection-state-changed",
+      (event) => {
+        if (event.payload.payload === "stopped") {
+          setState((s) => (s === "error" ? s : "stopped"));
+          setBusy(false);
+        }
+      },
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<ScopedEvent<ProxyError>>("proxy-error", (event) => {
+      const error = event.payload.payload;
+      const { severity } = error;
       if (severity === "error" || severity === "critical") {
         notify({
           title: "A critical error occurred",
           body: "Check the launcher for details.",
         });
-        setProxyError(event.payload);
+        setProxyError(error);
       }
     });
 
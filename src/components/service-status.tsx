@@ -1,5 +1,6 @@
 import { useEffect, useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { WarningIcon, InfoIcon, SpinnerIcon } from "@phosphor-icons/react";
 
 import {
@@ -11,6 +12,7 @@ import {
 } from "@/components/ui/dialog";
 import { Button } from "./ui/button";
 import { getToken } from "@/lib/token";
+import type { NetworkStatus } from "@/types/network";
 
 type ApiResponse<T> = {
   success: boolean;
@@ -85,16 +87,32 @@ export default function ServiceStatus() {
   const [user, setUser] = useState<User | null>(null);
   const [messages, setMessages] = useState<StatusMessage[]>([]);
   const [open, setOpen] = useState(false);
+  const [network, setNetwork] = useState<NetworkStatus>("online");
 
   useEffect(() => {
+    invoke<NetworkStatus>("get_network_status")
+      .then(setNetwork)
+      .catch(() => {});
+    const unlisten = listen<NetworkStatus>("network-status", (event) => {
+      setNetwork(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Refetch once back online, in case the last attempt failed
+  useEffect(() => {
+    if (network !== "online") return;
     invoke<ApiResponse<User>>("get_user", {
       token: getToken(),
     })
       .then((u) => setUser(u.data))
       .catch(() => setUser(null));
-  }, []);
+  }, [network]);
 
   useEffect(() => {
+    if (network !== "online") return;
     const check = async () => {
       try {
         const { messages } = await invoke<StatusResponse>("get_status");
@@ -106,23 +124,36 @@ export default function ServiceStatus() {
     check();
     const interval = setInterval(check, 5 * 60 * 1000); //5min
     return () => clearInterval(interval);
-  }, []);
+  }, [network]);
 
   const sorted = useMemo(
     () => [...messages].sort((a, b) => PRIORITY[a.type] - PRIORITY[b.type]),
     [messages],
   );
   const notices = useMemo(() => {
-    if (!user?.isBanned) return sorted;
-    return [
-      {
-        type: "error" as StatusType,
+    const extra: StatusMessage[] = [];
+    if (network === "offline") {
+      extra.push({
+        type: "error",
+        message: "You're offline. Some features won't work until your connection is back.",
+        createdAt: new Date().toISOString(),
+      });
+    } else if (network === "apiDown") {
+      extra.push({
+        type: "warning",
+        message: "Duels+ services can't be reached right now. Your connection is fine.",
+        createdAt: new Date().toISOString(),
+      });
+    }
+    if (user?.isBanned) {
+      extra.push({
+        type: "error",
         message: "Your account was banned for breaching the Duels+ ToS.",
         createdAt: new Date().toISOString(),
-      },
-      ...sorted,
-    ];
-  }, [user, sorted]);
+      });
+    }
+    return [...extra, ...sorted];
+  }, [network, user, sorted]);
 
   const severity = useMemo<StatusType | null>(() => {
     switch (true) {
@@ -0,0 +1,105 @@
+import { useState } from "react";
+import { cn } from "@/lib/utils";
+
+interface SettingKeybindProps {
+  title: string;
+  description?: string;
+  /** Shortcut like `Ctrl+Shift+D`, empty if unbound */
+  value: string;
+  onChange: (value: string) => void;
+  /** Why the shortcut couldn't be registered */
+  error?: string;
+  disabled?: boolean;
+}
+
+const NAMED_KEYS = ["Space", "Insert", "Delete", "Home", "End", "PageUp", "PageDown"];
+
+//main key in the format the launcher parses, or null for keys it doesn't support
+function keyName(code: string): string | null {
+  if (/^Key[A-Z]$/.test(code)) return code.slice(3);
+  if (/^Digit[0-9]$/.test(code)) return code.slice(5);
+  if (/^F([1-9]|1[0-9]|2[0-4])$/.test(code)) return code;
+  if (NAMED_KEYS.includes(code)) return code;
+  return null;
+}
+
+export function SettingKeybind({
+  title,
+  description,
+  value,
+  onChange,
+  error,
+  disabled,
+}: SettingKeybindProps) {
+  const [recording, setRecording] = useState(false);
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (!recording) return;
+    e.preventDefault();
+    e.stopPropagation();
+
+    const modifiers = [
+      e.ctrlKey && "Ctrl",
+      e.altKey && "Alt",
+      e.shiftKey && "Shift",
+      e.metaKey && "Super",
+    ].filter(Boolean) as string[];
+
+    if (modifiers.length === 0 && e.code === "Escape") {
+      setRecording(false);
+      return;
+    }
+    if (modifiers.length === 0 && e.code === "Backspace") {
+      setRecording(false);
+      onChange("");
+      return;
+    }
+
+    const key = keyName(e.code);
+    //wait for the main key while only modifiers are held
+    if (!key) return;
+    //keys that type text need a modifier
+    if (modifiers.length === 0 && !key.startsWith("F")) return;
+
+    setRecording(false);
+    onChange([...modifiers, key].join("+"));
+  };
+
+  return (
+    <div
+      className={cn(
+        "w-full flex items-center justify-between gap-4 rounded-xl px-4 py-3",
+        "bg-muted/70 focus-within:bg-muted transition-colors",
+        disabled && "opacity-50",
+      )}
+    >
+      <div className="space-y-0.5">
+        <div className="text-sm font-medium text-foreground">{title}</div>
+        {(error || description) && (
+          <div
+            className={cn(
+              "text-xs max-w-md",
+              error ? "text-destructive" : "text-muted-foreground/70",
+            )}
+          >
+            {error ?? description}
+          </div>
+        )}
+      </div>
+
+      <button
+        type="button"
+        disabled={disabled}
+        onClick={() => setRecording((r) => !r)}
+        onBlur={() => setRecording(false)}
+        onKeyDown={handleKeyDown}
+        className={cn(
+          "min-w-24 h-8 px-3 rounded-md text-sm bg-background",
+          recording ? "ring-2 ring-ring" : value ? "text-foreground" : "text-muted-foreground",
+        )}
+      >
+        {recording ? "Press keys..." : value || "Not set"}
+      </button>
+    </div>
+  );
+}
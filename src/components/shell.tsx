@@ -2,23 +2,72 @@ import { Titlebar } from "./titlebar";
 import { ActionRail } from "./nav/action-rail";
 import { MainView } from "./main-view";
 import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
 import { useUpdater } from "@/lib/updater";
 import { config } from "@/lib/config";
 import { getBrand, applyBrand } from "@/lib/brand-color";
+import { useTabs, type TabId } from "@/lib/tabs";
+import { useNotificationRouter } from "@/lib/notification-router";
+import { useLookup } from "@/lib/lookup";
+import type { DeepLink } from "@/types/deeplink";
+import type { LauncherUpdate } from "@/types/updater";
+import { LauncherCrashedDialog } from "./dialogs/launcher-crashed";
+import { UpdateAvailableDialog } from "./dialogs/update-available";
+import { LaunchLinkDialog } from "./dialogs/launch-link";
+import { OrphanProxyDialog } from "./dialogs/orphan-proxy";
 
 export function Shell() {
   const checkAndInstall = useUpdater((s) => s.checkAndInstall);
+  const { setActiveTab } = useTabs();
   useEffect(() => {
     const update = async () => {
+      //without automatic updates, ask before installing
       const cfg = await config.get();
-      if (cfg.autoUpdate) {
-        await checkAndInstall();
-      }
+      await checkAndInstall(cfg.autoUpdate);
     };
 
     update();
+
+    //found by the periodic check
+    const unlisten = listen<LauncherUpdate>(
+      "launcher-update-available",
+      async (event) => {
+        const cfg = await config.get();
+        await useUpdater.getState().offer(event.payload, cfg.autoUpdate);
+      },
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, [checkAndInstall]);
 
+  useEffect(() => {
+    const unlisten = listen<DeepLink>("deep-link", (event) => {
+      if (event.payload.kind === "profile") {
+        useLookup.getState().request(event.payload.id);
+        setActiveTab("lookup");
+      } else if (event.payload.kind === "open") {
+        setActiveTab(event.payload.page);
+      }
+    });
+
+    //started with --page
+    invoke<TabId | null>("get_start_page")
+      .then((page) => {
+        if (page) setActiveTab(page);
+      })
+      .catch(() => {});
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  //focusing the launcher soon after a notification follows its route
+  useNotificationRouter();
+
   useEffect(() => {
     const saved = getBrand();
     if (saved) {
@@ -34,6 +83,11 @@ export function Shell() {
         <ActionRail />
         <MainView className="flex-1" />
       </div>
+
+      <LauncherCrashedDialog />
+      <UpdateAvailableDialog />
+      <LaunchLinkDialog />
+      <OrphanProxyDialog />
     </div>
   );
 }
@@ -0,0 +1,106 @@
+import {
+  FormEvent,
+  KeyboardEvent,
+  useEffect,
+  useRef,
+  useState,
+} from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { PaperPlaneRightIcon, TerminalWindowIcon } from "@phosphor-icons/react";
+import { useLogs } from "@/lib/proxy-logs";
+import { Button } from "../ui/button";
+import { Input } from "../ui/input";
+
+function strip(text: string) {
+  return text.replace(/\x1B\[[0-9;]*m/g, "");
+}
+
+export function Console() {
+  const logs = useLogs((s) => s.logs);
+  const containerRef = useRef<HTMLDivElement>(null);
+  const [command, setCommand] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [history, setHistory] = useState<string[]>([]);
+  const [historyIndex, setHistoryIndex] = useState<number | null>(null);
+
+  useEffect(() => {
+    const el = containerRef.current;
+    if (!el) return;
+    el.scrollTop = el.scrollHeight;
+  }, [logs]);
+
+  async function send(e: FormEvent) {
+    e.preventDefault();
+    const line = command.trim();
+    if (!line) return;
+
+    try {
+      await invoke("send_proxy_stdin", { line });
+      useLogs.getState().addLog(`> ${line}`);
+      setHistory((prev) => [...prev, line]);
+      setHistoryIndex(null);
+      setCommand("");
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  function browseHistory(e: KeyboardEvent<HTMLInputElement>) {
+    if (history.length === 0) return;
+    if (e.key !== "ArrowUp" && e.key !== "ArrowDown") return;
+    e.preventDefault();
+
+    const current = historyIndex ?? history.length;
+    const next =
+      e.key === "ArrowUp"
+        ? Math.max(0, current - 1)
+        : Math.min(history.length, current + 1);
+    setHistoryIndex(next === history.length ? null : next);
+    setCommand(next === history.length ? "" : history[next]);
+  }
+
+  return (
+    <div className="flex flex-col space-y-4">
+      <h2 className="text-base font-medium">Console</h2>
+
+      <div
+        style={{ height: "calc(100vh - 10rem)" }}
+        className="overflow-auto p-3 rounded-2xl bg-muted font-mono text-xs leading-relaxed text-muted-foreground"
+        ref={containerRef}
+      >
+        {logs.length === 0 ? (
+          <div className="flex flex-col gap-2 justify-center items-center text-center h-full text-muted-foreground/50">
+            <TerminalWindowIcon className="size-6" weight="light" />
+            Start the proxy to send commands
+          </div>
+        ) : (
+          logs.map((line, i) => (
+            <div
+              key={i}
+              className="whitespace-pre-wrap break-words select-text px-1"
+            >
+              {strip(line)}
+            </div>
+          ))
+        )}
+      </div>
+
+      <form onSubmit={send} className="flex items-center gap-1.5">
+        <Input
+          value={command}
+          onChange={(e) => setCommand(e.target.value)}
+          onKeyDown={browseHistory}
+          placeholder="Enter a proxy command"
+          className="font-mono"
+          spellCheck={false}
+          autoComplete="off"
+        />
+        <Button type="submit" size="icon-lg" variant="input">
+          <PaperPlaneRightIcon weight="fill" />
+        </Button>
+      </form>
+      {error && <p className="text-xs text-rose-400 -mt-2">{error}</p>}
+    </div>
+  );
+}
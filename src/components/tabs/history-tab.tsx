@@ -0,0 +1,343 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import {
+  CaretLeftIcon,
+  CaretRightIcon,
+  ClockCounterClockwiseIcon,
+} from "@phosphor-icons/react";
+import clsx from "clsx";
+import { Button } from "../ui/button";
+import { Input } from "../ui/input";
+import { Skeleton } from "../ui/skeleton";
+import type {
+  DigestPeriod,
+  MatchFilter,
+  MatchHistoryPage,
+  MatchRecord,
+  MatchResult,
+  MatchTimeline,
+  RoundSummary,
+  StatsDigest,
+} from "@/types/history";
+
+const RESULTS: { label: string; value?: MatchResult }[] = [
+  { label: "All" },
+  { label: "Wins", value: "win" },
+  { label: "Losses", value: "loss" },
+  { label: "Draws", value: "draw" },
+];
+
+//DUELS_BRIDGE_DUEL -> Bridge Duel
+function modeName(mode: string | null) {
+  if (!mode) return "Unknown mode";
+  return mode
+    .replace(/^DUELS_/i, "")
+    .toLowerCase()
+    .split("_")
+    .map((word) => word.charAt(0).toUpperCase() + word.slice(1))
+    .join(" ");
+}
+
+function formatDuration(secs: number) {
+  const minutes = Math.floor(secs / 60);
+  return minutes > 0 ? `${minutes}m ${secs % 60}s` : `${secs}s`;
+}
+
+//65000 -> 1:05
+function formatClock(ms: number) {
+  const secs = Math.floor(ms / 1000);
+  return `${Math.floor(secs / 60)}:${String(secs % 60).padStart(2, "0")}`;
+}
+
+function roundDetails(round: RoundSummary) {
+  const parts = [`${round.kills}K/${round.deaths}D`];
+  if (round.goals || round.goalsConceded) {
+    parts.push(`${round.goals}-${round.goalsConceded} goals`);
+  }
+  if (round.bedsBroken) parts.push(`${round.bedsBroken} beds broken`);
+  if (round.bedsLost) parts.push("bed lost");
+  return parts.join(" · ");
+}
+
+function Timeline({ matchId }: { matchId: number }) {
+  const [timeline, setTimeline] = useState<MatchTimeline | null | undefined>();
+
+  useEffect(() => {
+    invoke<MatchTimeline | null>("get_match_timeline", { matchId })
+      .then(setTimeline)
+      .catch(() => setTimeline(null));
+  }, [matchId]);
+
+  if (timeline === undefined) {
+    return <Skeleton className="bg-muted/70 h-8 rounded-xl" />;
+  }
+  if (!timeline) {
+    return (
+      <p className="text-xs text-muted-foreground/50 px-1">
+        No breakdown for this match
+      </p>
+    );
+  }
+
+  const finalBlow = timeline.events.find((event) => event.finalBlow);
+  return (
+    <div className="space-y-1">
+      {timeline.rounds.map((round) => (
+        <div
+          key={round.round}
+          className="flex items-center justify-between gap-3 rounded-xl bg-muted/70 px-2.5 py-1.5 text-xs"
+        >
+          <span className="font-medium">Round {round.round}</span>
+          <span className="text-muted-foreground truncate">
+            {roundDetails(round)} · {formatClock(round.endedAtMs)}
+          </span>
+        </div>
+      ))}
+      {finalBlow && (
+        <p className="text-xs text-muted-foreground/60 px-1">
+          Final blow at {formatClock(finalBlow.atMs)}
+          {finalBlow.player &&
+            (finalBlow.kind === "kill"
+              ? ` on ${finalBlow.player}`
+              : ` by ${finalBlow.player}`)}
+        </p>
+      )}
+    </div>
+  );
+}
+
+function DigestCard({ recorded }: { recorded: number }) {
+  const [period, setPeriod] = useState<DigestPeriod>("day");
+  const [digest, setDigest] = useState<StatsDigest | null>(null);
+
+  useEffect(() => {
+    invoke<StatsDigest>("get_stats_digest", { period })
+      .then(setDigest)
+      .catch(() => setDigest(null));
+  }, [period, recorded]);
+
+  const stats = digest
+    ? [
+        { label: "Games", value: digest.games.toLocaleString() },
+        {
+          label: "Win Rate",
+          value:
+            digest.winRate === null
+              ? "-"
+              : `${Math.round(digest.winRate * 100)}%`,
+        },
+        { label: "Best Streak", value: digest.longestStreak.toLocaleString() },
+      ]
+    : [];
+
+  return (
+    <div className="p-1.5 rounded-3xl bg-muted/70">
+      <div className="flex justify-between items-center gap-3 px-2 pt-1 mb-2">
+        <h2 className="text-xs font-bold tracking-widest uppercase text-muted-foreground/50">
+          {period === "day" ? "Today" : "This Week"}
+        </h2>
+        <div className="flex gap-1">
+          {(["day", "week"] as const).map((option) => (
+            <Button
+              key={option}
+              size="sm"
+              variant={period === option ? "input" : "ghost"}
+              onClick={() => setPeriod(option)}
+            >
+              {option === "day" ? "Day" : "Week"}
+            </Button>
+          ))}
+        </div>
+      </div>
+
+      <div className="rounded-2xl bg-background p-2.5 space-y-2">
+        {!digest ? (
+          <Skeleton className="bg-muted/70 h-10 w-full" />
+        ) : (
+          <>
+            <div className="grid grid-cols-3 gap-2">
+              {stats.map((stat) => (
+                <div key={stat.label} className="flex flex-col">
+                  <span className="text-xs text-muted-foreground">
+                    {stat.label}
+                  </span>
+                  <span className="text-sm font-medium">{stat.value}</span>
+                </div>
+              ))}
+            </div>
+            {digest.bestMode && (
+              <p className="text-xs text-muted-foreground/60">
+                Best mode: {modeName(digest.bestMode.mode)} (
+                {digest.bestMode.wins}/{digest.bestMode.games} won)
+              </p>
+            )}
+          </>
+        )}
+      </div>
+    </div>
+  );
+}
+
+function MatchRow({ match }: { match: MatchRecord }) {
+  const [expanded, setExpanded] = useState(false);
+
+  return (
+    <div className="rounded-2xl bg-background">
+      <button
+        type="button"
+        onClick={() => setExpanded(!expanded)}
+        className="w-full flex items-center gap-3 p-2.5 text-left"
+      >
+        <span
+          className={clsx(
+            "w-1 self-stretch rounded-full",
+            match.result === "win" && "bg-emerald-400",
+            match.result === "loss" && "bg-rose-400",
+            match.result === "draw" && "bg-muted-foreground/40",
+          )}
+        />
+        <div className="flex flex-col min-w-0 flex-1">
+          <span className="text-sm font-medium truncate">
+            {modeName(match.mode)}
+            {match.map && (
+              <span className="text-muted-foreground"> · {match.map}</span>
+            )}
+          </span>
+          <span className="text-xs text-muted-foreground truncate">
+            {match.opponents.length > 0
+              ? `vs ${match.opponents.join(", ")}`
+              : "Opponent unknown"}
+          </span>
+          <span className="text-xs text-muted-foreground/60">
+            {new Date(match.startedAt * 1000).toLocaleString()} ·{" "}
+            {formatDuration(match.durationSecs)} · {match.kills}K/{match.deaths}D
+          </span>
+        </div>
+        <CaretRightIcon
+          className={clsx(
+            "size-4 text-muted-foreground transition-transform",
+            expanded && "rotate-90",
+          )}
+        />
+      </button>
+      {expanded && (
+        <div className="px-2.5 pb-2.5">
+          <Timeline matchId={match.id} />
+        </div>
+      )}
+    </div>
+  );
+}
+
+export function History() {
+  const [history, setHistory] = useState<MatchHistoryPage | null>(null);
+  const [page, setPage] = useState(0);
+  const [result, setResult] = useState<MatchResult | undefined>();
+  const [opponent, setOpponent] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [recorded, setRecorded] = useState(0);
+
+  useEffect(() => {
+    const filter: MatchFilter = {
+      result,
+      opponent: opponent.trim() || undefined,
+    };
+    invoke<MatchHistoryPage>("get_match_history", { filter, page })
+      .then((history) => {
+        setHistory(history);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)));
+  }, [page, result, opponent, recorded]);
+
+  //refresh when a match finishes while the page is open
+  useEffect(() => {
+    const unlisten = listen("match-recorded", () => setRecorded((n) => n + 1));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const pageCount = history
+    ? Math.max(1, Math.ceil(history.total / history.pageSize))
+    : 1;
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-base font-medium">Match History</h2>
+
+      <DigestCard recorded={recorded} />
+
+      <div className="flex gap-1">
+        {RESULTS.map((option) => (
+          <Button
+            key={option.label}
+            size="sm"
+            variant={result === option.value ? "input" : "ghost"}
+            onClick={() => {
+              setResult(option.value);
+              setPage(0);
+            }}
+          >
+            {option.label}
+          </Button>
+        ))}
+      </div>
+      <Input
+        value={opponent}
+        onChange={(e) => {
+          setOpponent(e.target.value);
+          setPage(0);
+        }}
+        placeholder="Search opponents"
+        spellCheck={false}
+        autoComplete="off"
+      />
+
+      <div className="p-1 rounded-3xl bg-muted/70 space-y-1">
+        {!history ? (
+          Array.from({ length: 3 }).map((_, i) => (
+            <Skeleton key={i} className="bg-background h-16 rounded-2xl" />
+          ))
+        ) : history.matches.length === 0 ? (
+          <div className="flex flex-col gap-2 justify-center items-center text-center py-10 text-sm text-muted-foreground/50">
+            <ClockCounterClockwiseIcon className="size-6" weight="light" />
+            {history.total === 0 && !result && !opponent
+              ? "Matches you play through the proxy show up here"
+              : "No matches found"}
+          </div>
+        ) : (
+          history.matches.map((match) => (
+            <MatchRow key={match.id} match={match} />
+          ))
+        )}
+      </div>
+      {error && <p className="text-xs text-rose-400 -mt-2">{error}</p>}
+
+      {history && history.total > history.pageSize && (
+        <div className="flex items-center justify-between">
+          <Button
+            size="icon-sm"
+            variant="ghost"
+            disabled={page === 0}
+            onClick={() => setPage(page - 1)}
+          >
+            <CaretLeftIcon />
+          </Button>
+          <span className="text-xs text-muted-foreground">
+            Page {page + 1} of {pageCount}
+          </span>
+          <Button
+            size="icon-sm"
+            variant="ghost"
+            disabled={page + 1 >= pageCount}
+            onClick={() => setPage(page + 1)}
+          >
+            <CaretRightIcon />
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -2,11 +2,13 @@ import { Logo, LogoBeta } from "@/components/logo";
 import { WhatsNew } from "@/components/whats-new";
 import { LaunchButton } from "@/components/proxy/launch-button";
 import ServiceStatus from "@/components/service-status";
+import { PingIndicator } from "@/components/ping-indicator";
 import { Button } from "../ui/button";
 import { SiDiscord } from "@icons-pack/react-simple-icons";
 import { config as configApi } from "@/lib/config";
 import { useEffect, useState } from "react";
 import { SubscribeBanner } from "../nav/subscribe-banner";
+import { FriendsOnline } from "@/components/friends-online";
 
 export function Home() {
   const [isBeta, setIsBeta] = useState(false);
@@ -33,6 +35,7 @@ export function Home() {
             <div className="flex justify-start items-center gap-3">
               <LaunchButton isBeta={isBeta} />
               <ServiceStatus />
+              <PingIndicator />
             </div>
             <div>
               <a
@@ -49,6 +52,7 @@ export function Home() {
           </div>
         </div>
         <div className="space-y-3">
+          <FriendsOnline />
           <WhatsNew />
         </div>
       </div>
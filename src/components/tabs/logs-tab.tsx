@@ -1,6 +1,8 @@
 import { useEffect, useMemo, useRef, useState } from "react";
 import { LOG_LEVELS, LogLevel, getLogLevel, useLogs } from "@/lib/proxy-logs";
+import { invoke } from "@tauri-apps/api/core";
 import {
+  ArrowSquareOutIcon,
   BackspaceIcon,
   FunnelXIcon,
   PaletteIcon,
@@ -38,7 +40,8 @@ function LevelBadge({ level }: { level: string | null }) {
   );
 }
 
-export function Logs() {
+/** `detached` in the logs window, which has no button to open itself */
+export function Logs({ detached = false }: { detached?: boolean }) {
   const logs = useLogs((s) => s.logs);
   const containerRef = useRef<HTMLDivElement>(null);
   const [colors, setColors] = useState(() => {
@@ -122,6 +125,15 @@ export function Logs() {
       <div className="flex justify-between items-center gap-2">
         <h2 className="text-base font-medium">Logs</h2>
         <div className="flex items-center gap-1.5">
+          {!detached && (
+            <Button
+              size="icon-xs"
+              onClick={() => invoke("open_logs_window").catch(console.error)}
+              variant="input"
+            >
+              <ArrowSquareOutIcon weight="fill" />
+            </Button>
+          )}
           <Button
             size="icon-xs"
             onClick={() => useLogs.getState().clear()}
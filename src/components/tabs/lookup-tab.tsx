@@ -0,0 +1,238 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { MagnifyingGlassIcon, SpinnerIcon } from "@phosphor-icons/react";
+import { Button } from "../ui/button";
+import { Input } from "../ui/input";
+import { config as configApi } from "@/lib/config";
+import { useLookup } from "@/lib/lookup";
+import type { PlayerLookup } from "@/types/hypixel";
+
+function ratio(a: number, b: number) {
+  return (b === 0 ? a : a / b).toFixed(2);
+}
+
+function Stat({ label, value }: { label: string; value: string | number }) {
+  return (
+    <div className="flex flex-col rounded-2xl bg-background p-2.5">
+      <span className="text-xs text-muted-foreground">{label}</span>
+      <span className="text-sm font-medium">{value}</span>
+    </div>
+  );
+}
+
+function Section({
+  title,
+  children,
+}: {
+  title: string;
+  children: React.ReactNode;
+}) {
+  return (
+    <div className="space-y-1">
+      <h3 className="text-xs text-muted-foreground px-1">{title}</h3>
+      <div className="p-1 rounded-3xl bg-muted/70 grid grid-cols-2 gap-1">
+        {children}
+      </div>
+    </div>
+  );
+}
+
+function PlayerCard({ player }: { player: PlayerLookup }) {
+  const { duels, bedwars } = player;
+  return (
+    <div className="space-y-3">
+      <div className="flex flex-col min-w-0 px-1">
+        <span className="text-sm font-medium truncate">{player.name}</span>
+        <span className="text-xs text-muted-foreground/60 truncate">
+          {player.uuid}
+        </span>
+      </div>
+
+      {duels ? (
+        <Section title="Duels">
+          <Stat label="Wins" value={duels.wins} />
+          <Stat label="WLR" value={ratio(duels.wins, duels.losses)} />
+          <Stat label="Kills" value={duels.kills} />
+          <Stat label="KDR" value={ratio(duels.kills, duels.deaths)} />
+          <Stat label="Winstreak" value={duels.winstreak ?? "Hidden"} />
+          <Stat
+            label="Best winstreak"
+            value={duels.bestWinstreak ?? "Hidden"}
+          />
+        </Section>
+      ) : (
+        <p className="text-xs text-muted-foreground/50 px-1">
+          Never played Duels
+        </p>
+      )}
+
+      {bedwars ? (
+        <Section title="BedWars">
+          <Stat label="Stars" value={bedwars.level} />
+          <Stat label="WLR" value={ratio(bedwars.wins, bedwars.losses)} />
+          <Stat label="Final kills" value={bedwars.finalKills} />
+          <Stat
+            label="FKDR"
+            value={ratio(bedwars.finalKills, bedwars.finalDeaths)}
+          />
+          <Stat label="Wins" value={bedwars.wins} />
+          <Stat label="Beds broken" value={bedwars.bedsBroken} />
+        </Section>
+      ) : (
+        <p className="text-xs text-muted-foreground/50 px-1">
+          Never played BedWars
+        </p>
+      )}
+    </div>
+  );
+}
+
+function ApiKeyForm({ onSaved }: { onSaved: () => void }) {
+  const [key, setKey] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async () => {
+    try {
+      await invoke("set_hypixel_api_key", { key: key.trim() });
+      setError(null);
+      onSaved();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <p className="text-xs text-muted-foreground">
+        Lookups use your own Hypixel API key, from the Hypixel developer
+        dashboard. It's stored encrypted on this computer.
+      </p>
+      <div className="flex gap-2">
+        <Input
+          value={key}
+          onChange={(e) => setKey(e.target.value)}
+          placeholder="Hypixel API key"
+          type="password"
+          spellCheck={false}
+          autoComplete="off"
+        />
+        <Button variant="input" disabled={!key.trim()} onClick={save}>
+          Save
+        </Button>
+      </div>
+      {error && <p className="text-xs text-rose-400">{error}</p>}
+    </div>
+  );
+}
+
+export function Lookup() {
+  const [hasKey, setHasKey] = useState<boolean | null>(null);
+  const [name, setName] = useState("");
+  const [player, setPlayer] = useState<PlayerLookup | null>(null);
+  const [notFound, setNotFound] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    configApi
+      .getValue("hypixelApiKey")
+      .then((key) => setHasKey(key !== null))
+      .catch(() => setHasKey(false));
+  }, []);
+
+  const search = async (query = name) => {
+    if (!query.trim() || loading) return;
+    setLoading(true);
+    try {
+      const found = await invoke<PlayerLookup | null>("lookup_player", {
+        name: query.trim(),
+      });
+      setPlayer(found);
+      setNotFound(found === null);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  //opened from a profile link
+  const pending = useLookup((s) => s.pending);
+  useEffect(() => {
+    if (!pending || !hasKey) return;
+    const player = useLookup.getState().take();
+    if (player) {
+      setName(player);
+      search(player);
+    }
+  }, [pending, hasKey]);
+
+  const removeKey = async () => {
+    await invoke("set_hypixel_api_key", { key: null }).catch(() => {});
+    setHasKey(false);
+    setPlayer(null);
+  };
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-base font-medium">Player Lookup</h2>
+
+      {hasKey === false ? (
+        <ApiKeyForm onSaved={() => setHasKey(true)} />
+      ) : (
+        <>
+          <form
+            className="flex gap-2"
+            onSubmit={(e) => {
+              e.preventDefault();
+              search();
+            }}
+          >
+            <Input
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              placeholder="Username"
+              spellCheck={false}
+              autoComplete="off"
+              maxLength={16}
+            />
+            <Button
+              type="submit"
+              size="icon"
+              variant="input"
+              disabled={!name.trim() || loading}
+            >
+              {loading ? (
+                <SpinnerIcon className="animate-spin" />
+              ) : (
+                <MagnifyingGlassIcon />
+              )}
+            </Button>
+          </form>
+          {error && <p className="text-xs text-rose-400 -mt-2">{error}</p>}
+
+          {player ? (
+            <PlayerCard player={player} />
+          ) : (
+            <div className="flex flex-col gap-2 justify-center items-center text-center py-10 text-sm text-muted-foreground/50">
+              <MagnifyingGlassIcon className="size-6" weight="light" />
+              {notFound
+                ? "No Hypixel player with that name"
+                : "Look up anyone's Duels and BedWars stats"}
+            </div>
+          )}
+
+          <Button
+            size="sm"
+            variant="ghost"
+            className="text-muted-foreground"
+            onClick={removeKey}
+          >
+            Remove API key
+          </Button>
+        </>
+      )}
+    </div>
+  );
+}
@@ -1,8 +1,16 @@
 import { useEffect, useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { getVersion } from "@tauri-apps/api/app";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
 
-import type { Config } from "@/types/config";
+import type { Config, Keybinds } from "@/types/config";
+import type { HotkeyAction, HotkeyStatus } from "@/types/hotkeys";
+import type { RpcState, RpcStatus } from "@/types/rpc";
+import type { LogUpload } from "@/types/diagnostics";
+import type { ExportFormat, StatsExport } from "@/types/history";
+import type { ObsStatus } from "@/types/obs";
+import type { AppLockStatus } from "@/types/applock";
 import { settingDefinitions } from "@/settings/definitions";
 import { SettingSwitch } from "@/components/settings/switch";
 import { SettingsSection } from "@/components/settings/section";
@@ -16,17 +24,44 @@ import type { Theme } from "../theme-provider";
 import { SettingButton } from "../settings/button";
 import { RpcCustomizeDialog } from "@/components/dialogs/rpc-customize";
 import { SettingInput } from "../settings/input";
+import { SettingKeybind } from "../settings/keybind";
 import { User, hasPerm } from "@/lib/perm";
 import { getToken } from "@/lib/token";
 import { RestartPendingDialog } from "../dialogs/restart-pending";
 import { EnableBetaDialog } from "../dialogs/enable-beta";
+import { AppPinDialog } from "../dialogs/app-pin";
+import { getAppLockStatus } from "@/lib/applock";
 import { Skeleton } from "../ui/skeleton";
+import { notify } from "@/lib/notification";
 
 type ApiResponse<T> = {
   success: boolean;
   data: T;
 };
 
+const KEYBINDS: { action: HotkeyAction; title: string; description: string }[] = [
+  {
+    action: "showHideWindow",
+    title: "Show/Hide Launcher",
+    description: "Bring the launcher to the front, or hide it.",
+  },
+  {
+    action: "launchStopProxy",
+    title: "Launch/Stop Proxy",
+    description: "Launch the proxy, or stop it if it's running.",
+  },
+  {
+    action: "toggleRpcPrivacy",
+    title: "Toggle Presence Privacy",
+    description: "Hide or show your profile and location on Discord.",
+  },
+  {
+    action: "toggleOverlay",
+    title: "Show/Hide Overlay",
+    description: "Show opponent stats and your session W/L over the game.",
+  },
+];
+
 export function Settings() {
   const [user, setUser] = useState<User | null>(null);
   const [config, setConfig] = useState<Config | null>(null);
@@ -38,6 +73,9 @@ export function Settings() {
   const [pendingBetaValue, setPendingBetaValue] = useState<boolean | null>(
     null,
   );
+  const [exporting, setExporting] = useState(false);
+  const [exportingStats, setExportingStats] = useState(false);
+  const [uploading, setUploading] = useState(false);
 
   useEffect(() => {
     invoke<ApiResponse<User>>("get_user", {
@@ -62,6 +100,63 @@ export function Settings() {
   });
 
   const [rpcCustomizeOpen, setRpcCustomizeOpen] = useState(false);
+  const [rpcStatus, setRpcStatus] = useState<RpcStatus | null>(null);
+  const [hotkeyStatus, setHotkeyStatus] = useState<HotkeyStatus | null>(null);
+  const [obsStatus, setObsStatus] = useState<ObsStatus | null>(null);
+  const [lockStatus, setLockStatus] = useState<AppLockStatus | null>(null);
+  const [pinDialogOpen, setPinDialogOpen] = useState(false);
+
+  const refreshLockStatus = () =>
+    getAppLockStatus()
+      .then(setLockStatus)
+      .catch(() => {});
+
+  useEffect(() => {
+    refreshLockStatus();
+  }, []);
+
+  useEffect(() => {
+    //the server binds in the background, give it a moment
+    const timeout = setTimeout(() => {
+      invoke<ObsStatus>("get_obs_status")
+        .then(setObsStatus)
+        .catch(() => {});
+    }, 500);
+    return () => clearTimeout(timeout);
+  }, [config?.obsServer, config?.obsPort]);
+
+  useEffect(() => {
+    invoke<HotkeyStatus>("get_hotkey_status")
+      .then(setHotkeyStatus)
+      .catch(() => {});
+
+    const unlisten = listen<HotkeyStatus>("hotkeys-status", (event) => {
+      setHotkeyStatus(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const refresh = () =>
+      invoke<RpcState>("rpc_get_state")
+        .then((state) => setRpcStatus(state.status))
+        .catch(() => {});
+    refresh();
+
+    const unlisten = listen<RpcStatus>("rpc-status", (event) => {
+      setRpcStatus(event.payload);
+    });
+    // Saved RPC settings are applied in the background at startup
+    const unlistenReady = listen("launcher-ready", refresh);
+
+    return () => {
+      unlisten.then((fn) => fn());
+      unlistenReady.then((fn) => fn());
+    };
+  }, []);
 
   const isBetaEligible =
     hasPerm(user, "tester") ||
@@ -78,6 +173,15 @@ export function Settings() {
       .catch(() => {
         setConfig(defaultSettings);
       });
+
+    // Keys changed outside this tab, e.g. by the privacy toggle
+    const unlisten = listen<Partial<Config>>("config-changed", (event) => {
+      setConfig((prev) => (prev ? { ...prev, ...event.payload } : prev));
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   useEffect(() => {
@@ -135,6 +239,24 @@ export function Settings() {
     return disabled;
   };
 
+  const describe = (setting: (typeof settingDefinitions)[number]) => {
+    // Explain a toggle that is on but can't reach Discord
+    if (
+      setting.key === "enableRpc" &&
+      config?.enableRpc &&
+      rpcStatus?.status === "failed"
+    ) {
+      return rpcStatus.reason ?? "Discord not detected";
+    }
+    if (setting.key === "obsServer" && config?.obsServer && obsStatus) {
+      return (
+        obsStatus.error ??
+        `Add ws://localhost:${obsStatus.port} to a browser source, or poll http://localhost:${obsStatus.port}/stats.`
+      );
+    }
+    return setting.description;
+  };
+
   const handlePortChange = (raw: string) => {
     if (!/^\d*$/.test(raw)) return;
     if (raw === "") return;
@@ -145,6 +267,63 @@ export function Settings() {
     updateSetting("proxyPort", String(port));
   };
 
+  const updateKeybind = (action: keyof Keybinds, shortcut: string) => {
+    if (!config) return;
+    updateSetting("keybinds", { ...config.keybinds, [action]: shortcut });
+  };
+
+  const handleExportDiagnostics = async () => {
+    setExporting(true);
+    try {
+      const path = await invoke<string>("export_diagnostics");
+      await revealItemInDir(path);
+    } catch (err) {
+      console.error("Failed to export diagnostics:", err);
+      notify({
+        title: "Couldn't export diagnostics",
+        body: String(err),
+      });
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const handleExportStats = async (format: ExportFormat) => {
+    setExportingStats(true);
+    try {
+      const exported = await invoke<StatsExport>("export_stats", { format });
+      await revealItemInDir(exported.files[0]);
+    } catch (err) {
+      console.error("Failed to export stats:", err);
+      notify({
+        title: "Couldn't export stats",
+        body: String(err),
+      });
+    } finally {
+      setExportingStats(false);
+    }
+  };
+
+  const handleUploadLogs = async () => {
+    setUploading(true);
+    try {
+      const upload = await invoke<LogUpload>("upload_logs");
+      await navigator.clipboard.writeText(upload.url).catch(() => {});
+      notify({
+        title: "Logs uploaded",
+        body: `Link copied, paste it in your support ticket: ${upload.url}`,
+      });
+    } catch (err) {
+      console.error("Failed to upload logs:", err);
+      notify({
+        title: "Couldn't upload logs",
+        body: String(err),
+      });
+    } finally {
+      setUploading(false);
+    }
+  };
+
   if (!config || !user) {
     return (
       <div className="space-y-4">
@@ -208,7 +387,7 @@ export function Settings() {
             <SettingSwitch
               key={setting.key}
               title={setting.title}
-              description={setting.description}
+              description={describe(setting)}
               checked={config[setting.key] as boolean}
               disabled={isDisabled(setting)}
               onCheckedChange={(value) => updateSetting(setting.key, value)}
@@ -223,7 +402,49 @@ export function Settings() {
         </SettingsSection>
       )}
 
+      {grouped["Notifications"] && (
+        <SettingsSection title="Notifications">
+          {grouped["Notifications"].map((setting) => (
+            <SettingSwitch
+              key={setting.key}
+              title={setting.title}
+              description={describe(setting)}
+              checked={config[setting.key] as boolean}
+              disabled={isDisabled(setting)}
+              onCheckedChange={(value) => updateSetting(setting.key, value)}
+            />
+          ))}
+        </SettingsSection>
+      )}
+
+      <SettingsSection title="Shortcuts">
+        {KEYBINDS.map(({ action, title, description }) => (
+          <SettingKeybind
+            key={action}
+            title={title}
+            description={description}
+            value={config.keybinds[action]}
+            error={
+              hotkeyStatus?.conflicts.find((c) => c.action === action)?.reason ??
+              (config.keybinds[action] ? hotkeyStatus?.unsupported ?? undefined : undefined)
+            }
+            disabled={savingKey === "keybinds"}
+            onChange={(shortcut) => updateKeybind(action, shortcut)}
+          />
+        ))}
+      </SettingsSection>
+
       <SettingsSection title="Advanced">
+        {grouped["Advanced"]?.map((setting) => (
+          <SettingSwitch
+            key={setting.key}
+            title={setting.title}
+            description={setting.description}
+            checked={config[setting.key] as boolean}
+            disabled={isDisabled(setting)}
+            onCheckedChange={(value) => updateSetting(setting.key, value)}
+          />
+        ))}
         <SettingInput
           title="Proxy Port"
           description="Set the localhost port."
@@ -234,6 +455,50 @@ export function Settings() {
           step={1}
           onChange={handlePortChange}
         />
+        <SettingButton
+          title={lockStatus?.enabled ? "Change App PIN" : "Set App PIN"}
+          description={
+            lockStatus?.enabled
+              ? "Change or remove the PIN asked for before signing out or switching accounts."
+              : "Ask for a PIN before anyone signs out or switches accounts, for shared computers."
+          }
+          onClick={() => setPinDialogOpen(true)}
+        />
+        {lockStatus?.enabled && !lockStatus.locked && (
+          <SettingButton
+            title="Lock Now"
+            description={`Ask for the PIN again now, instead of after ${config.appLockTimeoutMins} minutes.`}
+            onClick={() =>
+              invoke("lock_app")
+                .then(refreshLockStatus)
+                .catch(console.error)
+            }
+          />
+        )}
+        <SettingButton
+          title="Export Diagnostics"
+          description="Save logs and system details to attach to a support ticket. Your token is never included."
+          disabled={exporting}
+          onClick={handleExportDiagnostics}
+        />
+        <SettingButton
+          title="Export Stats as CSV"
+          description="Save your match history and stat snapshots as spreadsheets."
+          disabled={exportingStats}
+          onClick={() => handleExportStats("csv")}
+        />
+        <SettingButton
+          title="Export Stats as JSON"
+          description="Save your match history and stat snapshots in one file, for other tools."
+          disabled={exportingStats}
+          onClick={() => handleExportStats("json")}
+        />
+        <SettingButton
+          title="Share Logs"
+          description="Upload your recent logs and copy a link to paste in Discord support. Your token is never included."
+          disabled={uploading}
+          onClick={handleUploadLogs}
+        />
       </SettingsSection>
 
       <div className="w-full gap-3 flex flex-col justify-center items-center">
@@ -279,6 +544,13 @@ export function Settings() {
         name={restartPendingName}
       />
 
+      <AppPinDialog
+        open={pinDialogOpen}
+        onOpenChange={setPinDialogOpen}
+        enabled={lockStatus?.enabled ?? false}
+        onSaved={refreshLockStatus}
+      />
+
       <EnableBetaDialog
         open={enableBetaDialogOpen}
         onOpenChange={setEnableBetaDialogOpen}
@@ -1,9 +1,19 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { Logo, Logomark, LogomarkCustom } from "@/components/logo";
 import { Skeleton } from "../ui/skeleton";
-import { ArrowUpRightIcon, WarningIcon } from "@phosphor-icons/react";
+import {
+  ArrowCounterClockwiseIcon,
+  ArrowUpRightIcon,
+  SwordIcon,
+  TrophyIcon,
+  WarningIcon,
+} from "@phosphor-icons/react";
 import { Button } from "../ui/button";
+import type { SessionStats } from "@/types/session";
+import type { TrendPoint, TrendRange } from "@/types/trends";
+import type { Leaderboard, LeaderboardUpdate } from "@/types/leaderboard";
 
 interface StatCardProps {
   title: string;
@@ -78,6 +88,220 @@ type GlobalStatsResponse = {
   };
 };
 
+function ratio(a: number, b: number) {
+  return (b === 0 ? a : a / b).toFixed(2);
+}
+
+function SessionCard() {
+  const [session, setSession] = useState<SessionStats | null>(null);
+
+  useEffect(() => {
+    invoke<SessionStats>("get_session_stats")
+      .then(setSession)
+      .catch(() => {});
+    const unlisten = listen<SessionStats>("session-stats-updated", (event) =>
+      setSession(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <div className="space-y-1.5">
+      <StatCard
+        title="Session"
+        icon={<SwordIcon weight="fill" className="size-3" />}
+        stats={[
+          { label: "Wins", value: session?.wins ?? "-" },
+          { label: "Losses", value: session?.losses ?? "-" },
+          {
+            label: "WLR",
+            value: session ? ratio(session.wins, session.losses) : "-",
+          },
+          {
+            label: "KDR",
+            value: session ? ratio(session.kills, session.deaths) : "-",
+          },
+          { label: "Win Streak", value: session?.winStreak ?? "-" },
+          { label: "Best Streak", value: session?.bestWinStreak ?? "-" },
+        ]}
+        loading={!session}
+      />
+      <div className="w-full flex justify-end">
+        <Button
+          variant="ghost"
+          size="sm"
+          onClick={() => invoke("reset_session_stats").catch(() => {})}
+        >
+          <ArrowCounterClockwiseIcon />
+          Reset session
+        </Button>
+      </div>
+    </div>
+  );
+}
+
+const RANGES: { label: string; value: TrendRange }[] = [
+  { label: "1W", value: "week" },
+  { label: "1M", value: "month" },
+  { label: "1Y", value: "year" },
+  { label: "All", value: "all" },
+];
+
+function Sparkline({ points }: { points: TrendPoint[] }) {
+  const values = points.map((p) => p.value);
+  const min = Math.min(...values);
+  const span = Math.max(...values) - min || 1;
+  const first = points[0].at;
+  const length = points[points.length - 1].at - first || 1;
+  const path = points
+    .map((p, i) => {
+      const x = ((p.at - first) / length) * 100;
+      const y = 36 - ((p.value - min) / span) * 32;
+      return `${i === 0 ? "M" : "L"}${x.toFixed(2)},${y.toFixed(2)}`;
+    })
+    .join(" ");
+
+  return (
+    <svg viewBox="0 0 100 40" preserveAspectRatio="none" className="w-full h-16">
+      <path
+        d={path}
+        fill="none"
+        stroke="currentColor"
+        strokeWidth={2}
+        vectorEffect="non-scaling-stroke"
+        className="text-primary"
+      />
+    </svg>
+  );
+}
+
+function TrendCard() {
+  const [range, setRange] = useState<TrendRange>("month");
+  const [points, setPoints] = useState<TrendPoint[] | null>(null);
+
+  useEffect(() => {
+    invoke<TrendPoint[]>("get_stats_trend", { stat: "winRate", range })
+      .then(setPoints)
+      .catch(() => setPoints([]));
+  }, [range]);
+
+  return (
+    <div className="p-1.5 rounded-3xl bg-muted/70">
+      <div className="flex justify-between items-center gap-3 px-2 pt-1 mb-2">
+        <h2 className="text-xs font-bold tracking-widest uppercase text-muted-foreground/50">
+          Win Rate
+        </h2>
+        <div className="flex gap-0.5">
+          {RANGES.map((option) => (
+            <Button
+              key={option.value}
+              size="icon-xs"
+              variant={range === option.value ? "input" : "ghost"}
+              className="text-[10px] w-auto px-1.5"
+              onClick={() => setRange(option.value)}
+            >
+              {option.label}
+            </Button>
+          ))}
+        </div>
+      </div>
+
+      <div className="rounded-2xl bg-background p-2">
+        {!points ? (
+          <Skeleton className="bg-muted/70 h-16 w-full" />
+        ) : points.length < 2 ? (
+          <p className="h-16 flex items-center justify-center text-xs text-muted-foreground/50 text-center">
+            Your progress shows up here after a few days
+          </p>
+        ) : (
+          <Sparkline points={points} />
+        )}
+      </div>
+    </div>
+  );
+}
+
+function LeaderboardCard() {
+  const [board, setBoard] = useState<Leaderboard | null>(null);
+  const [delta, setDelta] = useState<number | null>(null);
+  const [error, setError] = useState(false);
+
+  useEffect(() => {
+    invoke<Leaderboard>("get_leaderboard", { stat: "wins" })
+      .then(setBoard)
+      .catch(() => setError(true));
+    const unlisten = listen<LeaderboardUpdate>(
+      "leaderboard-updated",
+      (event) => {
+        if (event.payload.leaderboard.stat !== "wins") return;
+        setBoard(event.payload.leaderboard);
+        setDelta(event.payload.rankDelta);
+        setError(false);
+      },
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <div className="p-1.5 rounded-3xl bg-muted/70">
+      <div className="flex justify-between items-center gap-3 px-2 pt-1 mb-2">
+        <h2 className="text-xs font-bold tracking-widest uppercase text-muted-foreground/50">
+          Leaderboard
+        </h2>
+        <TrophyIcon weight="fill" className="size-3 text-muted-foreground/50" />
+      </div>
+
+      <div className="rounded-2xl bg-background p-2 space-y-1">
+        {error && !board ? (
+          <p className="text-xs text-muted-foreground/50 text-center py-2">
+            Couldn't load the leaderboard
+          </p>
+        ) : !board ? (
+          <Skeleton className="bg-muted/70 h-16 w-full" />
+        ) : (
+          <>
+            {board.entries.slice(0, 5).map((entry) => (
+              <div
+                key={entry.rank}
+                className="flex justify-between text-xs gap-2"
+              >
+                <span className="truncate">
+                  <span className="text-muted-foreground">#{entry.rank}</span>{" "}
+                  {entry.username}
+                </span>
+                <span className="font-medium">
+                  {entry.value.toLocaleString()}
+                </span>
+              </div>
+            ))}
+            {board.ownRank !== null && (
+              <p className="text-xs text-muted-foreground pt-1">
+                You're #{board.ownRank.toLocaleString()}
+                {delta !== null && delta > 0 && (
+                  <span className="text-emerald-400">
+                    {" "}
+                    · up {delta} {delta === 1 ? "place" : "places"}
+                  </span>
+                )}
+                {delta !== null && delta < 0 && (
+                  <span className="text-rose-400">
+                    {" "}
+                    · down {-delta} {delta === -1 ? "place" : "places"}
+                  </span>
+                )}
+              </p>
+            )}
+          </>
+        )}
+      </div>
+    </div>
+  );
+}
+
 export function Stats() {
   const [userStats, setUserStats] = useState<UserStatsResponse["stats"] | null>(
     null,
@@ -114,6 +338,8 @@ export function Stats() {
     <div className="space-y-4">
       <h2 className="text-base font-medium">Statistics</h2>
 
+      <SessionCard />
+
       <StatCard
         title="User"
         icon={
@@ -158,6 +384,10 @@ export function Stats() {
         loading={loading}
       />
 
+      <TrendCard />
+
+      <LeaderboardCard />
+
       <div className="w-full flex justify-center">
         <a
           href="https://dash.duelsplus.com/?tab=statistics"
@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * UI state kept by the backend, which survives the webview's storage being
+ * wiped. Anything JSON can be stored; `null` removes a key.
+ */
+export const appState = {
+  async get<T>(key: string): Promise<T | null> {
+    return invoke<T | null>("get_app_state", { key });
+  },
+
+  async set<T>(key: string, value: T | null): Promise<void> {
+    await invoke("set_app_state", { key, value });
+  },
+};
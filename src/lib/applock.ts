@@ -0,0 +1,42 @@
+import { invoke } from "@tauri-apps/api/core";
+import { create } from "zustand";
+import type { AppLockStatus } from "@/types/applock";
+
+//returned by PIN-guarded commands while locked, see applock.rs
+const LOCKED = "The launcher is locked";
+
+interface AppLockStore {
+  /** Resolves the open unlock prompt, `null` while none is open */
+  prompt: ((unlocked: boolean) => void) | null;
+  /** Asks for the PIN, resolving to whether it was entered */
+  requestUnlock: () => Promise<boolean>;
+  /** Closes the prompt */
+  finish: (unlocked: boolean) => void;
+}
+
+export const useAppLock = create<AppLockStore>((set, get) => ({
+  prompt: null,
+  requestUnlock() {
+    get().prompt?.(false);
+    return new Promise((resolve) => set({ prompt: resolve }));
+  },
+  finish(unlocked) {
+    get().prompt?.(unlocked);
+    set({ prompt: null });
+  },
+}));
+
+export function getAppLockStatus() {
+  return invoke<AppLockStatus>("get_app_lock_status");
+}
+
+/** Runs a PIN-guarded command, asking for the PIN first if it's locked */
+export async function withUnlock<T>(run: () => Promise<T>): Promise<T> {
+  try {
+    return await run();
+  } catch (err) {
+    if (!String(err).startsWith(LOCKED)) throw err;
+    if (!(await useAppLock.getState().requestUnlock())) throw err;
+    return await run();
+  }
+}
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { withUnlock } from "@/lib/applock";
 
 export type DiscordAuthResult = {
   success: boolean;
@@ -29,7 +30,9 @@ export async function startDiscordAuth(): Promise<DiscordAuthOutcome> {
                 { token: payload.token },
               );
               if (verifyResult.success) {
-                await invoke("save_token", { token: payload.token });
+                await withUnlock(() =>
+                  invoke("save_token", { token: payload.token }),
+                );
                 resolve({ success: true, token: payload.token });
               } else {
                 throw new Error;
@@ -0,0 +1,20 @@
+import { create } from "zustand";
+
+interface LookupStore {
+  /** A player to look up when the lookup tab opens, from a profile link */
+  pending: string | null;
+  request: (player: string) => void;
+  take: () => string | null;
+}
+
+export const useLookup = create<LookupStore>((set, get) => ({
+  pending: null,
+  request(player) {
+    set({ pending: player });
+  },
+  take() {
+    const pending = get().pending;
+    if (pending) set({ pending: null });
+    return pending;
+  },
+}));
@@ -0,0 +1,49 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
+import { useTabs } from "@/lib/tabs";
+import { invoke } from "@tauri-apps/api/core";
+import { useUpdater } from "@/lib/updater";
+import type { LauncherUpdate } from "@/types/updater";
+import type {
+  NotificationAction,
+  NotificationRoute,
+} from "@/types/notifications";
+
+async function run(action: NotificationAction) {
+  switch (action.kind) {
+    case "showUpdate": {
+      //focusing isn't a click, so only show the prompt; skipped if the
+      //update was put off since
+      const update = await invoke<LauncherUpdate | null>(
+        "check_launcher_update",
+      );
+      if (update) await useUpdater.getState().offer(update, false);
+      break;
+    }
+    case "viewCrashReport":
+      await revealItemInDir(action.path);
+      break;
+    case "openLogs":
+      //the page is enough
+      break;
+  }
+}
+
+/** Follows notifications clicked while the launcher was in the background */
+export function useNotificationRouter() {
+  const { setActiveTab } = useTabs();
+
+  useEffect(() => {
+    const unlisten = listen<NotificationRoute>("navigate", (event) => {
+      setActiveTab(event.payload.page);
+      if (event.payload.action) {
+        run(event.payload.action).catch(console.error);
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+}
@@ -1,4 +1,5 @@
 import { createContext, useContext, useEffect, useState } from "react";
+import { appState } from "@/lib/app-state";
 
 type OnboardingContextValue = {
   isOnboarded: boolean;
@@ -17,9 +18,27 @@ export function OnboardingProvider({
     return localStorage.getItem(storageKey) === "true";
   });
 
+  //webview storage was wiped, restore progress from the backend before
+  //saving over it
+  const [restored, setRestored] = useState(
+    () => localStorage.getItem(storageKey) !== null,
+  );
+
+  useEffect(() => {
+    if (restored) return;
+    appState
+      .get<boolean>(storageKey)
+      .then((saved) => {
+        if (saved) setIsOnboarded(true);
+      })
+      .catch(() => {})
+      .finally(() => setRestored(true));
+  }, []);
+
   useEffect(() => {
     localStorage.setItem(storageKey, String(isOnboarded));
-  }, [isOnboarded]);
+    if (restored) appState.set(storageKey, isOnboarded).catch(() => {});
+  }, [isOnboarded, restored]);
 
   return (
     <OnboardingContext.Provider
@@ -0,0 +1,57 @@
+import type { CommandTiming } from "@/types/perf";
+
+type Invoke = (
+  cmd: string,
+  args?: unknown,
+  options?: unknown,
+) => Promise<unknown>;
+
+const reportCommand = "record_command_timings";
+const flushInterval = 10_000;
+//dropped past this if the backend stops taking them
+const maxPending = 1000;
+
+let pending: CommandTiming[] = [];
+
+/**
+ * Times every command from the call to its reply and sends the timings to
+ * the backend, which keeps per-command percentiles (`get_perf_metrics`)
+ */
+export function installCommandTimings() {
+  const internals = (
+    window as unknown as { __TAURI_INTERNALS__?: { invoke: Invoke } }
+  ).__TAURI_INTERNALS__;
+  if (!internals) return;
+
+  const original: Invoke = internals.invoke.bind(internals);
+  internals.invoke = (cmd, args, options) => {
+    //plugin commands aren't ours, and timing the report itself never ends
+    if (cmd.startsWith("plugin:") || cmd === reportCommand) {
+      return original(cmd, args, options);
+    }
+
+    const start = performance.now();
+    const record = (error: boolean) => {
+      if (pending.length < maxPending) {
+        pending.push({ command: cmd, ms: performance.now() - start, error });
+      }
+    };
+    return original(cmd, args, options).then(
+      (value) => {
+        record(false);
+        return value;
+      },
+      (err) => {
+        record(true);
+        throw err;
+      },
+    );
+  };
+
+  setInterval(() => {
+    if (pending.length === 0) return;
+    const timings = pending;
+    pending = [];
+    original(reportCommand, { timings }).catch(() => {});
+  }, flushInterval);
+}
@@ -1,5 +1,6 @@
 import { create } from "zustand";
 import { listen } from "@tauri-apps/api/event";
+import type { ScopedEvent } from "@/types/proxy";
 
 export const LOG_LEVELS = [
   "DEBUG",
@@ -21,9 +22,9 @@ interface LogsStore {
 }
 
 export const useLogs = create<LogsStore>((set) => {
-  listen<string>("log-message", (event) => {
+  listen<ScopedEvent<string>>("log-message", (event) => {
     set((state) => ({
-      logs: [...state.logs, event.payload],
+      logs: [...state.logs, event.payload.payload],
     }));
   });
 
@@ -1,6 +1,14 @@
 import { createContext, useContext, useEffect, useState } from "react";
+import { appState } from "@/lib/app-state";
 
-export type TabId = "home" | "logs" | "stats" | "settings";
+export type TabId =
+  | "home"
+  | "logs"
+  | "console"
+  | "stats"
+  | "history"
+  | "lookup"
+  | "settings";
 
 type TabsContextValue = {
   activeTab: TabId;
@@ -21,10 +29,28 @@ export function TabsProvider({ children }: { children: React.ReactNode }) {
     activeTab !== "home",
   );
 
+  //webview storage was wiped, restore the tab from the backend before
+  //saving over it
+  const [restored, setRestored] = useState(
+    () => localStorage.getItem("active-tab") !== null,
+  );
+
+  useEffect(() => {
+    if (restored) return;
+    appState
+      .get<TabId>("activeTab")
+      .then((saved) => {
+        if (saved) setActiveTab(saved);
+      })
+      .catch(() => {})
+      .finally(() => setRestored(true));
+  }, []);
+
   useEffect(() => {
     localStorage.setItem("active-tab", activeTab);
+    if (restored) appState.set("activeTab", activeTab).catch(() => {});
     setIsDrawerOpen(activeTab !== "home");
-  }, [activeTab]);
+  }, [activeTab, restored]);
 
   const toggleTab = (tab: TabId) => {
     if (tab === "home") {
@@ -1,48 +1,83 @@
-import { check } from "@tauri-apps/plugin-updater";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { create } from "zustand";
+import type { LauncherUpdate, UpdateProgress } from "@/types/updater";
 
 export type UpdateStatus =
   | { state: "idle" }
   | { state: "checking" }
+  | { state: "available"; update: LauncherUpdate }
   | { state: "downloading"; chunkLength: number }
   | { state: "pending-restart" }
   | { state: "error" };
 
 interface UpdaterStore {
   status: UpdateStatus;
-  checkAndInstall: () => Promise<void>;
+  /** Checks for an update, downloading it right away if `autoInstall` */
+  checkAndInstall: (autoInstall?: boolean) => Promise<void>;
+  /** Offers an update found in the background, unless one is in progress */
+  offer: (update: LauncherUpdate, autoInstall: boolean) => Promise<void>;
+  /** Downloads and installs the update found by the last check */
+  install: () => Promise<void>;
+  /** Hides the update and skips automatic checks for a day */
+  remindLater: () => Promise<void>;
 }
 
-export const useUpdater = create<UpdaterStore>((set) => ({
+export const useUpdater = create<UpdaterStore>((set, get) => ({
   status: { state: "idle" },
-  async checkAndInstall() {
+  async checkAndInstall(autoInstall = true) {
     try {
       set({ status: { state: "checking" } });
 
-      const update = await check();
+      const update = await invoke<LauncherUpdate | null>(
+        "check_launcher_update",
+      );
       if (update === null) {
         set({ status: { state: "idle" } });
         return;
       }
 
-      set({ status: { state: "downloading", chunkLength: 0 } });
-
-      await update.downloadAndInstall((event) => {
-        if (event.event === "Progress") {
-          set({
-            status: {
-              state: "downloading",
-              chunkLength: event.data.chunkLength,
-            },
-          });
-        }
-      });
+      set({ status: { state: "idle" } });
+      await get().offer(update, autoInstall);
+    } catch (err) {
+      set({ status: { state: "error" } });
+    }
+  },
+  async offer(update, autoInstall) {
+    if (get().status.state !== "idle") return;
+    if (autoInstall) await get().install();
+    else set({ status: { state: "available", update } });
+  },
+  async install() {
+    set({ status: { state: "downloading", chunkLength: 0 } });
+    let last = 0;
+    const unlisten = await listen<UpdateProgress>(
+      "launcher-update-progress",
+      (event) => {
+        set({
+          status: {
+            state: "downloading",
+            chunkLength: event.payload.downloaded - last,
+          },
+        });
+        last = event.payload.downloaded;
+      },
+    );
 
+    try {
+      //installed now or on quit, either way a restart applies it
+      await invoke<boolean>("download_launcher_update");
       set({ status: { state: "pending-restart" } });
     } catch (err) {
       set({ status: { state: "error" } });
+    } finally {
+      unlisten();
     }
   },
+  async remindLater() {
+    set({ status: { state: "idle" } });
+    await invoke("defer_launcher_update").catch(console.error);
+  },
 }));
 
 export const useMockUpdater = create<UpdaterStore>((set) => ({
@@ -57,4 +92,9 @@ export const useMockUpdater = create<UpdaterStore>((set) => ({
 
     set({ status: { state: "pending-restart" } });
   },
+  async offer() {},
+  async install() {},
+  async remindLater() {
+    set({ status: { state: "idle" } });
+  },
 }));
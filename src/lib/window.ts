@@ -4,3 +4,8 @@ export async function showWindow() {
   await getCurrentWindow().show();
   await getCurrentWindow().setFocus();
 }
+
+export async function showMinimized() {
+  await getCurrentWindow().show();
+  await getCurrentWindow().minimize();
+}
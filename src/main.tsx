@@ -1,11 +1,26 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import { Overlay } from "./components/overlay";
+import { LogsWindow } from "./components/logs-window";
+import { installCommandTimings } from "./lib/perf";
 import "./styles/globals.css";
 import "m3-ripple/ripple.css";
 
+// The overlay and logs windows load the same page
+const label = getCurrentWindow().label;
+
+installCommandTimings();
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {label === "overlay" ? (
+      <Overlay />
+    ) : label === "logs" ? (
+      <LogsWindow />
+    ) : (
+      <App />
+    )}
   </React.StrictMode>,
 );
@@ -2,14 +2,60 @@ import type { Config } from "@/types/config";
 
 export const defaultSettings: Config = {
   minimizeToTray: false,
+  autostart: false,
+  autostartMinimized: false,
+  startMinimized: false,
+  trayOnly: false,
+  launchProxyOnStart: false,
   autoUpdate: true,
+  updateChannel: "stable",
+  updateDeferredUntil: null,
+  installUpdatesOnQuit: false,
   openLogsOnLaunch: true,
   reducedMotion: false,
   enableRpc: true,
   rpcAnonymizeProfile: false,
   rpcAnonymizeLocation: false,
+  streamerMode: false,
   rpcImage: "logo-v1",
+  rpcButtons: true,
+  rpcShowStreak: true,
+  rpcLauncherProfile: false,
+  rpcClientId: "",
+  rpcAfkTimeoutMins: 10,
+  rpcTimerMode: "session",
+  rpcDiscordClient: "auto",
+  reportUnknownModes: false,
+  telemetry: false,
+  pingIndicator: false,
+  obsServer: false,
+  obsPort: 24050,
+  appPin: null,
+  hypixelApiKey: null,
+  appLockTimeoutMins: 5,
+  locale: "en",
   proxyPort: "25565",
   enableMsa: false,
   receiveBetaReleases: false,
+  disabledAddons: [],
+  keepProxyOnExit: false,
+  proxyShutdownTimeoutSecs: 5,
+  launchMinecraft: false,
+  postLaunchCommand: "",
+  enableProxyConsole: false,
+  proxyPriority: "normal",
+  keybinds: {
+    showHideWindow: "",
+    launchStopProxy: "",
+    toggleRpcPrivacy: "",
+    toggleOverlay: "",
+  },
+  notifyProxyCrash: true,
+  notifyUpdates: true,
+  notifyDownloads: true,
+  notifyFriends: false,
+  notifyWinStreaks: true,
+  notifyLeaderboard: true,
+  notifyWeeklyDigest: false,
+  overlayPosition: null,
 };
@@ -10,24 +10,84 @@ export type SettingDefinition = {
 };
 
 export const settingDefinitions: SettingDefinition[] = [
-  /*{
+  {
     key: "minimizeToTray",
     title: "Minimize to Tray",
     description: "Keep the launcher running in the background when closed.",
     section: "General",
-  },*/
+  },
+  {
+    key: "autostart",
+    title: "Start on Login",
+    description: "Open the launcher when you sign in to your computer.",
+    section: "General",
+  },
+  {
+    key: "autostartMinimized",
+    title: "Start Minimized on Login",
+    description: "Start hidden in the system tray when opened on login.",
+    section: "General",
+    dependsOn: (config) => config.autostart && !config.startMinimized,
+  },
+  {
+    key: "startMinimized",
+    title: "Always Start Minimized",
+    description:
+      "Start hidden in the system tray, or minimized if there's no tray.",
+    section: "General",
+  },
+  {
+    key: "trayOnly",
+    title: "Background Mode",
+    description:
+      "Run from the system tray without a window until you open it. Closing the window frees its memory.",
+    section: "General",
+    restartRequired: true,
+  },
+  {
+    key: "launchProxyOnStart",
+    title: "Launch Proxy on Start",
+    description: "Launch the proxy as soon as the launcher opens.",
+    section: "General",
+  },
   {
     key: "autoUpdate",
     title: "Automatic Updates",
     description: "Automatically download and install launcher updates.",
     section: "General",
   },
+  {
+    key: "installUpdatesOnQuit",
+    title: "Install Updates on Quit",
+    description:
+      "Wait until you quit the launcher to install a downloaded update.",
+    section: "General",
+  },
   {
     key: "openLogsOnLaunch",
     title: "Open Logs on Launch",
     description: "Switch to the logs tab on proxy launch.",
     section: "General",
   },
+  {
+    key: "keepProxyOnExit",
+    title: "Keep Proxy Running",
+    description:
+      "Leave the proxy running when the launcher is closed. Applies from the next proxy launch.",
+    section: "General",
+  },
+  {
+    key: "launchMinecraft",
+    title: "Launch Minecraft",
+    description: "Start your Minecraft launcher once the proxy is running.",
+    section: "General",
+  },
+  {
+    key: "pingIndicator",
+    title: "Latency Indicator",
+    description: "Show your ping to Hypixel on the home page.",
+    section: "General",
+  },
   /*{
     key: "reducedMotion",
     title: "Reduced Motion",
@@ -46,7 +106,7 @@ export const settingDefinitions: SettingDefinition[] = [
     title: "Anonymize Profile",
     description: "Hide your in-game profile from Discord Rich Presence.",
     section: "Integrations",
-    dependsOn: "enableRpc",
+    dependsOn: (config) => config.enableRpc && !config.streamerMode,
     restartRequired: true,
   },
   {
@@ -54,9 +114,108 @@ export const settingDefinitions: SettingDefinition[] = [
     title: "Anonymize Location",
     description: "Hide your in-game location from Discord Rich Presence.",
     section: "Integrations",
-    dependsOn: "enableRpc",
+    dependsOn: (config) => config.enableRpc && !config.streamerMode,
     restartRequired: true,
   },
+  {
+    key: "streamerMode",
+    title: "Streamer Mode",
+    description:
+      "Hide your IGN and profile on Discord, in the logs and in notifications while you're live.",
+    section: "Integrations",
+  },
+  {
+    key: "rpcButtons",
+    title: "Activity Buttons",
+    description: "Show stats and website buttons on your Discord activity.",
+    section: "Integrations",
+    dependsOn: "enableRpc",
+  },
+  {
+    key: "rpcShowStreak",
+    title: "Win Streak",
+    description: "Show your session win streak on Discord while playing Duels.",
+    section: "Integrations",
+    dependsOn: "enableRpc",
+  },
+  {
+    key: "rpcLauncherProfile",
+    title: "Launcher Presence",
+    description:
+      "Show your Duels+ username on Discord while using the launcher.",
+    section: "Integrations",
+    dependsOn: "enableRpc",
+  },
+  {
+    key: "reportUnknownModes",
+    title: "Report Unknown Modes",
+    description:
+      "Send game modes Discord can't name yet to Duels+ so they can be added.",
+    section: "Integrations",
+    dependsOn: "enableRpc",
+  },
+  {
+    key: "notifyProxyCrash",
+    title: "Proxy Crashes",
+    description: "Notify when the proxy stops unexpectedly.",
+    section: "Notifications",
+  },
+  {
+    key: "notifyUpdates",
+    title: "Proxy Updates",
+    description: "Notify when a new version of the proxy is available.",
+    section: "Notifications",
+  },
+  {
+    key: "notifyDownloads",
+    title: "Downloads",
+    description: "Notify when the proxy finishes downloading.",
+    section: "Notifications",
+  },
+  {
+    key: "notifyFriends",
+    title: "Friends Online",
+    description: "Notify when a Hypixel friend joins the server.",
+    section: "Notifications",
+  },
+  {
+    key: "notifyWinStreaks",
+    title: "Win Streaks",
+    description: "Notify every 5 wins in a row.",
+    section: "Notifications",
+  },
+  {
+    key: "notifyLeaderboard",
+    title: "Leaderboard",
+    description: "Notify when you move up the leaderboard.",
+    section: "Notifications",
+  },
+  {
+    key: "notifyWeeklyDigest",
+    title: "Weekly Recap",
+    description: "Sum up last week's matches every Monday.",
+    section: "Notifications",
+  },
+  {
+    key: "telemetry",
+    title: "Usage Statistics",
+    description:
+      "Send anonymous counts of launches, features used and errors to help improve Duels+. Never includes your IGN or account.",
+    section: "Advanced",
+  },
+  {
+    key: "obsServer",
+    title: "OBS Overlay Server",
+    description:
+      "Serve your session stats, mode and map on localhost for OBS browser sources.",
+    section: "Advanced",
+  },
+  {
+    key: "enableProxyConsole",
+    title: "Proxy Console",
+    description: "Show a console tab for sending commands to the proxy.",
+    section: "Advanced",
+  },
   /*{
     key: "enableMsa",
     title: "Microsoft Account Authentication",
@@ -0,0 +1,5 @@
+/** Whether a PIN is set and the launcher is locked, as returned by `get_app_lock_status` */
+export interface AppLockStatus {
+  enabled: boolean;
+  locked: boolean;
+}
@@ -0,0 +1,18 @@
+/** A kind of cached or accumulating file, as passed to `clear_cache` */
+export type CacheCategory = "avatars" | "releases" | "stats" | "logs" | "crashes";
+
+/** Space used by one category */
+export interface CategoryUsage {
+  category: CacheCategory;
+  bytes: number;
+  files: number;
+}
+
+/** Space used by the launcher, as returned by `get_disk_usage` */
+export interface DiskUsage {
+  /** The installed proxy */
+  installBytes: number;
+  categories: CategoryUsage[];
+  /** Everything above together */
+  totalBytes: number;
+}
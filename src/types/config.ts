@@ -1,13 +1,71 @@
 export interface Config {
   minimizeToTray: boolean;
+  autostart: boolean;
+  autostartMinimized: boolean;
+  startMinimized: boolean;
+  trayOnly: boolean;
+  launchProxyOnStart: boolean;
   autoUpdate: boolean;
+  updateChannel: "stable" | "beta";
+  updateDeferredUntil: number | null;
+  installUpdatesOnQuit: boolean;
   openLogsOnLaunch: boolean;
   reducedMotion: boolean;
   enableRpc: boolean;
   rpcAnonymizeProfile: boolean;
   rpcAnonymizeLocation: boolean;
+  streamerMode: boolean;
   rpcImage: string;
+  rpcButtons: boolean;
+  rpcShowStreak: boolean;
+  rpcLauncherProfile: boolean;
+  rpcClientId: string;
+  rpcAfkTimeoutMins: number;
+  rpcTimerMode: "session" | "per-game";
+  rpcDiscordClient: "auto" | "stable" | "ptb" | "canary";
+  reportUnknownModes: boolean;
+  telemetry: boolean;
+  pingIndicator: boolean;
+  obsServer: boolean;
+  obsPort: number;
+  /** Always null here, the hash stays in the backend; see `get_app_lock_status` */
+  appPin: string | null;
+  /** Sealed Hypixel API key, only changed through `set_hypixel_api_key` */
+  hypixelApiKey: string | null;
+  appLockTimeoutMins: number;
+  locale: string;
   proxyPort: string;
   enableMsa: boolean;
   receiveBetaReleases: boolean;
+  disabledAddons: string[];
+  keepProxyOnExit: boolean;
+  proxyShutdownTimeoutSecs: number;
+  launchMinecraft: boolean;
+  postLaunchCommand: string;
+  enableProxyConsole: boolean;
+  proxyPriority: "low" | "normal" | "high";
+  keybinds: Keybinds;
+  notifyProxyCrash: boolean;
+  notifyUpdates: boolean;
+  notifyDownloads: boolean;
+  notifyFriends: boolean;
+  notifyWinStreaks: boolean;
+  notifyLeaderboard: boolean;
+  notifyWeeklyDigest: boolean;
+  /** Where the overlay was last moved to; null puts it in the top right corner */
+  overlayPosition: WindowPosition | null;
+}
+
+/** Global shortcuts, e.g. `Ctrl+Shift+D`; empty leaves an action unbound */
+export interface Keybinds {
+  showHideWindow: string;
+  launchStopProxy: string;
+  toggleRpcPrivacy: string;
+  toggleOverlay: string;
+}
+
+/** Position of a window's top left corner, in physical pixels */
+export interface WindowPosition {
+  x: number;
+  y: number;
 }
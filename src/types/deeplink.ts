@@ -0,0 +1,8 @@
+import type { TabId } from "@/lib/tabs";
+
+/** A handled `duelsplus://` link, emitted as `deep-link` */
+export type DeepLink =
+  | { kind: "launch"; port: number | null }
+  | { kind: "auth" }
+  | { kind: "profile"; id: string }
+  | { kind: "open"; page: TabId };
@@ -0,0 +1,7 @@
+/** Uploaded logs, as returned by `upload_logs` */
+export interface LogUpload {
+  /** Short id support can look the logs up by */
+  id: string;
+  /** Link to the uploaded logs */
+  url: string;
+}
@@ -0,0 +1,13 @@
+export type FriendStatus = "inGame" | "online" | "offline";
+
+/** A friend and what they're doing, as returned by `get_friends_presence` */
+export interface FriendPresence {
+  /** Duels+ user ID */
+  id: string;
+  username: string;
+  status: FriendStatus;
+  /** Game mode being played, only while in game */
+  mode: string | null;
+  /** Unix time in seconds the status was first seen, 0 if unknown */
+  since: number;
+}
@@ -0,0 +1,119 @@
+import type { GameEventKind } from "./proxy";
+
+/** How a match ended for the player */
+export type MatchResult = "win" | "loss" | "draw";
+
+/** A finished match, as recorded from the proxy's game events */
+export interface MatchRecord {
+  /** Increasing from 1, in the order matches were played */
+  id: number;
+  mode: string | null;
+  map: string | null;
+  result: MatchResult;
+  /** Unix time the match started, in seconds */
+  startedAt: number;
+  durationSecs: number;
+  kills: number;
+  deaths: number;
+  opponents: string[];
+}
+
+/** Which matches `get_match_history` returns; every field is optional */
+export interface MatchFilter {
+  mode?: string;
+  result?: MatchResult;
+  /** Part of an opponent's name, ignoring case */
+  opponent?: string;
+  /** Unix time in seconds, inclusive */
+  since?: number;
+  /** Unix time in seconds, exclusive */
+  until?: number;
+}
+
+/** A page of the match history, as returned by `get_match_history` */
+export interface MatchHistoryPage {
+  /** Newest first */
+  matches: MatchRecord[];
+  /** Matches passing the filter, on every page */
+  total: number;
+  /** From 0 */
+  page: number;
+  pageSize: number;
+}
+
+export type ExportFormat = "csv" | "json";
+
+/** What `export_stats` wrote */
+export interface StatsExport {
+  /** Files written, the matches first */
+  files: string[];
+  matches: number;
+  snapshots: number;
+}
+
+/** Something that happened in a match */
+export interface TimelineEvent {
+  /** Milliseconds since the match started */
+  atMs: number;
+  /** A kill, death, goal or bed */
+  kind: GameEventKind;
+  player: string | null;
+  /** From 1 */
+  round: number;
+  /** Whether this decided the match */
+  finalBlow: boolean;
+}
+
+/** How one round went */
+export interface RoundSummary {
+  round: number;
+  kills: number;
+  deaths: number;
+  goals: number;
+  goalsConceded: number;
+  bedsBroken: number;
+  bedsLost: number;
+  /** When its last event happened, in milliseconds since the match started */
+  endedAtMs: number;
+}
+
+/** A match's timeline, as returned by `get_match_timeline` */
+export interface MatchTimeline {
+  matchId: number;
+  /** In the order they happened */
+  events: TimelineEvent[];
+  /** Rounds with any events, first to last */
+  rounds: RoundSummary[];
+}
+
+export type DigestPeriod = "day" | "week";
+
+/** How a mode went */
+export interface ModeSummary {
+  mode: string;
+  games: number;
+  wins: number;
+  /** Wins over games, from 0 to 1 */
+  winRate: number;
+}
+
+/** A summary of one day or week, as returned by `get_stats_digest` */
+export interface StatsDigest {
+  period: DigestPeriod;
+  /** Unix time the period started, in seconds */
+  start: number;
+  /** Unix time it ends, exclusive */
+  end: number;
+  games: number;
+  wins: number;
+  losses: number;
+  draws: number;
+  kills: number;
+  deaths: number;
+  /** Wins over games, from 0 to 1; null without games */
+  winRate: number | null;
+  /** The mode with the best win rate, null without games */
+  bestMode: ModeSummary | null;
+  /** Most wins in a row, where losses end a streak and draws don't */
+  longestStreak: number;
+}
@@ -0,0 +1,18 @@
+import type { Keybinds } from "./config";
+
+/** Something a global shortcut can do, named after its `keybinds` field */
+export type HotkeyAction = keyof Keybinds;
+
+/** A shortcut that couldn't be registered */
+export interface HotkeyConflict {
+  action: HotkeyAction;
+  shortcut: string;
+  reason: string;
+}
+
+/** Result of registering the global shortcuts, emitted as `hotkeys-status` */
+export interface HotkeyStatus {
+  /** Why shortcuts don't work on this system, if they don't */
+  unsupported: string | null;
+  conflicts: HotkeyConflict[];
+}
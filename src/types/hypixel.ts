@@ -0,0 +1,33 @@
+/** A player's Hypixel Duels stats */
+export interface DuelsStats {
+  wins: number;
+  losses: number;
+  kills: number;
+  deaths: number;
+  /** `null` when the player hides it in their API settings */
+  winstreak: number | null;
+  bestWinstreak: number | null;
+}
+
+/** A player's Hypixel BedWars stats */
+export interface BedwarsStats {
+  /** Stars */
+  level: number;
+  wins: number;
+  losses: number;
+  finalKills: number;
+  finalDeaths: number;
+  bedsBroken: number;
+}
+
+/** A player's stats, as returned by `lookup_player` */
+export interface PlayerLookup {
+  /** With dashes */
+  uuid: string;
+  /** Name as shown on Hypixel */
+  name: string;
+  /** `null` if the player never played Duels */
+  duels: DuelsStats | null;
+  /** `null` if the player never played BedWars */
+  bedwars: BedwarsStats | null;
+}
@@ -0,0 +1,7 @@
+/** A locale the launcher backend has translations for, from `get_available_locales` */
+export interface Locale {
+  /** Value for the `locale` config, e.g. `de` */
+  code: string;
+  /** The locale's name in its own language, e.g. "Deutsch" */
+  name: string;
+}
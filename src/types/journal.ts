@@ -0,0 +1,14 @@
+/** An operation that was in progress when the launcher was killed */
+export type Operation =
+  | { kind: "download"; path: string }
+  | { kind: "stagedUpdate"; path: string; version: string };
+
+/** An interrupted operation and what was done about it */
+export interface RecoveredOperation {
+  operation: Operation;
+  /** `resumed` picked it up again, `rolledBack` removed what it left */
+  recovery: "resumed" | "rolledBack";
+}
+
+/** Payload of `operations-recovered`, emitted once after startup */
+export type OperationsRecovered = RecoveredOperation[];
@@ -0,0 +1,28 @@
+/** One place on a leaderboard */
+export interface LeaderboardEntry {
+  /** 1 for first place */
+  rank: number;
+  username: string;
+  value: number;
+}
+
+/** A leaderboard, as returned by `get_leaderboard` */
+export interface Leaderboard {
+  /** The stat it ranks by, e.g. "wins" */
+  stat: string;
+  /** Best first */
+  entries: LeaderboardEntry[];
+  /** The signed-in player's rank, `null` if signed out or unranked */
+  ownRank: number | null;
+  /** Unix time in seconds */
+  fetchedAt: number;
+}
+
+/** Payload of `leaderboard-updated` */
+export interface LeaderboardUpdate {
+  leaderboard: Leaderboard;
+  /** The player's rank on the last fetch */
+  previousRank: number | null;
+  /** Places moved up, negative for down */
+  rankDelta: number | null;
+}
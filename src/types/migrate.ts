@@ -0,0 +1,12 @@
+/** A file or directory moved to a new location */
+export interface MovedPath {
+  from: string;
+  to: string;
+}
+
+/** Payload of `data-migrated`, emitted once after launcher data is moved */
+export interface DataMigrated {
+  moved: MovedPath[];
+  /** Why some paths couldn't be moved; they're tried again next start */
+  failed: string[];
+}
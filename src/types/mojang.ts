@@ -0,0 +1,7 @@
+/** A Minecraft account, as returned by `resolve_username` and `resolve_uuid` */
+export interface MojangProfile {
+  /** With dashes */
+  uuid: string;
+  /** Current name, in its exact case */
+  name: string;
+}
@@ -0,0 +1,2 @@
+/** Whether the network and the Duels+ API are reachable, emitted as `network-status` */
+export type NetworkStatus = "online" | "apiDown" | "offline";
@@ -0,0 +1,13 @@
+import type { TabId } from "@/lib/tabs";
+
+/** Something to do once the launcher is open */
+export type NotificationAction =
+  | { kind: "showUpdate" }
+  | { kind: "viewCrashReport"; path: string }
+  | { kind: "openLogs" };
+
+/** Where clicking a notification takes the user, emitted as `navigate` */
+export interface NotificationRoute {
+  page: TabId;
+  action: NotificationAction | null;
+}
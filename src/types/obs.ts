@@ -0,0 +1,19 @@
+import type { SessionStats } from "./session";
+
+/** What the OBS server sends overlays, over the WebSocket and from `/stats` */
+export interface ObsState {
+  inGame: boolean;
+  mode: string | null;
+  map: string | null;
+  /** Including the win streak */
+  session: SessionStats;
+}
+
+/** Whether the OBS server is running, as returned by `get_obs_status` */
+export interface ObsStatus {
+  running: boolean;
+  /** Port it's on, or would be on if it's off */
+  port: number;
+  /** Why it couldn't start, e.g. the port is in use */
+  error: string | null;
+}
@@ -0,0 +1,22 @@
+/** The player's opponent in the current duel, as looked up by the proxy */
+export interface Opponent {
+  name: string;
+  uuid: string | null;
+  /** Duels stats; null when the proxy couldn't look them up, e.g. for a nicked player */
+  wins: number | null;
+  losses: number | null;
+  winstreak: number | null;
+  bestWinstreak: number | null;
+}
+
+/** What the overlay shows, emitted to the overlay window as `overlay-update` */
+export interface OverlayData {
+  inGame: boolean;
+  mode: string | null;
+  map: string | null;
+  opponent: Opponent | null;
+  /** Wins since the launcher started */
+  wins: number;
+  /** Losses since the launcher started */
+  losses: number;
+}
@@ -0,0 +1,19 @@
+/** One command call, sent in `record_command_timings` */
+export interface CommandTiming {
+  command: string;
+  /** From the call to the reply */
+  ms: number;
+  error: boolean;
+}
+
+/** Timings of a command, as returned by `get_perf_metrics` */
+export interface CommandMetrics {
+  command: string;
+  calls: number;
+  errors: number;
+  /** Percentiles over the latest calls, in milliseconds */
+  p50Ms: number;
+  p90Ms: number;
+  p99Ms: number;
+  maxMs: number;
+}
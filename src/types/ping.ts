@@ -0,0 +1,17 @@
+export type PingTarget = "hypixel" | "proxy";
+
+/** Connect times to one target, as returned by `measure_ping` and emitted as `ping-updated` */
+export interface PingResult {
+  target: PingTarget;
+  /** Host and port connected to */
+  address: string;
+  /** Connects that succeeded */
+  received: number;
+  /** Connects that failed or timed out */
+  lost: number;
+  /** `null` when every connect failed, like the averages */
+  minMs: number | null;
+  avgMs: number | null;
+  /** Average difference between consecutive connect times */
+  jitterMs: number | null;
+}
@@ -9,3 +9,106 @@ export interface ProxyError {
   context: string | null;
   timestamp: number;
 }
+
+export type ConnectionState =
+  | "stopped"
+  | "proxyStarting"
+  | "waitingForClient"
+  | "clientConnected"
+  | "inGame"
+  | "disconnected";
+
+export interface LaunchFailure {
+  reason: string;
+  stderr: string[];
+}
+
+export interface ProxyCrashInfo {
+  path: string;
+  exitCode: number | null;
+}
+
+export type FirewallStatus = "allowed" | "blocked" | "unknown" | "unsupported";
+
+export interface ProtocolMismatch {
+  launcherVersion: number;
+  proxyVersion: number;
+}
+
+export type GameEventKind =
+  | "kill"
+  | "death"
+  | "win"
+  | "loss"
+  | "game_start"
+  | "game_end"
+  | "goal"
+  | "goal_conceded"
+  | "bed_broken"
+  | "bed_lost";
+
+export interface GameEvent {
+  event: GameEventKind;
+  mode: string | null;
+  map: string | null;
+  /** The other player in it: who was killed, who killed you, or who scored */
+  player: string | null;
+  /** Round of the match from 1, for modes played in rounds */
+  round: number | null;
+  /** Whether a kill or death decided the match */
+  finalBlow: boolean;
+}
+
+export interface ProxyMetrics {
+  activeConnections: number;
+  packetsPerSec: number;
+  bytesProxied: number;
+  pingMs: number | null;
+}
+
+export type PreflightCheckId =
+  | "platform"
+  | "installDir"
+  | "port"
+  | "api"
+  | "binary";
+
+export type PreflightStatus = "passed" | "warning" | "failed" | "skipped";
+
+export interface PreflightCheck {
+  id: PreflightCheckId;
+  status: PreflightStatus;
+  message: string;
+}
+
+export interface PreflightReport {
+  ready: boolean;
+  checks: PreflightCheck[];
+}
+
+/** Envelope of every event emitted by the proxy backend */
+export interface ScopedEvent<T> {
+  instanceId: string;
+  proxyVersion: string | null;
+  payload: T;
+}
+
+/** An addon and its state, as returned by `list_addons` */
+export interface AddonInfo {
+  id: string;
+  name: string;
+  description: string;
+  /** `null` if the addon is installed but no longer offered */
+  latestVersion: string | null;
+  /** `null` if the addon isn't installed */
+  installedVersion: string | null;
+  /** Whether the proxy loads it, following `disabledAddons` */
+  enabled: boolean;
+  updateAvailable: boolean;
+}
+
+/** A proxy left running by an earlier session, as returned by `get_orphan_proxy` */
+export interface OrphanProxyInfo {
+  pid: number;
+  port: number;
+}
@@ -0,0 +1,88 @@
+export interface RpcState {
+  connected: boolean;
+  enabled: boolean;
+  ign: string | null;
+  uuid: string | null;
+  mode: string | null;
+  map: string | null;
+  gametype: string | null;
+  inLobby: boolean;
+  isPlaying: boolean;
+  spectating: string | null;
+  anonymizeProfile: boolean;
+  anonymizeLocation: boolean;
+  customImage: string | null;
+  showButtons: boolean;
+  timerMode: "session" | "per-game";
+  locale: string;
+  sessionStart: number;
+  gameStart: number | null;
+  /** Expected end of a timed game, shown as a countdown */
+  gameEnd: number | null;
+  details: string;
+  state: string | null;
+  showStreak: boolean;
+  showLauncherProfile: boolean;
+  sessionWins: number;
+  sessionLosses: number;
+  winStreak: number;
+  timerStart: number;
+  customActivity: CustomActivity | null;
+  previewing: boolean;
+  status: RpcStatus;
+}
+
+export interface RpcStatus {
+  status: "connected" | "disconnected" | "failed" | "rateLimited";
+  reason: string | null;
+}
+
+export interface CustomButton {
+  label: string;
+  url: string;
+}
+
+export interface CustomActivity {
+  details?: string | null;
+  state?: string | null;
+  largeImage?: string | null;
+  largeText?: string | null;
+  smallImage?: string | null;
+  smallText?: string | null;
+  buttons?: CustomButton[];
+  startTimestamp?: number | null;
+  endTimestamp?: number | null;
+}
+
+/** Sample game for `rpc_preview`; missing fields use a Classic Duel on Arena */
+export interface RpcPreview {
+  mode?: string | null;
+  map?: string | null;
+  gametype?: string | null;
+  ign?: string | null;
+}
+
+export interface ModeTime {
+  mode: string;
+  name: string;
+  secs: number;
+}
+
+/** Launcher session recap from `get_session_summary` */
+export interface SessionSummary {
+  sessionStart: number;
+  totalSecs: number;
+  inGameSecs: number;
+  inLauncherSecs: number;
+  /** Longest first */
+  modes: ModeTime[];
+  wins: number;
+  losses: number;
+}
+
+/** Game mode seen without a display name, from `get_unknown_modes` */
+export interface UnknownMode {
+  mode: string;
+  count: number;
+  firstSeen: number;
+}
@@ -0,0 +1,20 @@
+export type Service = "hypixel" | "mojangSession";
+
+export type ServiceState = "up" | "degraded" | "down";
+
+/** One service's check */
+export interface ServiceCheck {
+  service: Service;
+  state: ServiceState;
+  /** What the service reports about itself, or why it's down */
+  detail: string | null;
+}
+
+/** Every check, as returned by `get_service_status` */
+export interface ServiceStatus {
+  services: ServiceCheck[];
+  /** A sentence for the user when something's wrong, `null` when all is up */
+  summary: string | null;
+  /** Unix time in seconds */
+  checkedAt: number;
+}
@@ -0,0 +1,12 @@
+/** Stats since the proxy launched, emitted as `session-stats-updated` */
+export interface SessionStats {
+  wins: number;
+  losses: number;
+  kills: number;
+  deaths: number;
+  /** Consecutive wins, reset by a loss */
+  winStreak: number;
+  bestWinStreak: number;
+  /** Unix time the session started, in seconds; 0 until the proxy first launches */
+  startedAt: number;
+}
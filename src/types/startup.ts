@@ -0,0 +1,14 @@
+/** A startup step and when it finished */
+export interface StartupPhase {
+  /** e.g. `config`, `firstFrame` or `ready` */
+  name: string;
+  /** Milliseconds since the process started */
+  ms: number;
+}
+
+/** Payload of the `startup-timings` event */
+export interface StartupTimings {
+  phases: StartupPhase[];
+  /** When the last step finished */
+  totalMs: number;
+}
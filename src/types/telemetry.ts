@@ -0,0 +1,15 @@
+/** What an upload sends, as returned by `get_telemetry_preview` */
+export interface TelemetryReport {
+  launcherVersion: string;
+  /** Version of the proxy run this session, if any */
+  proxyVersion: string | null;
+  os: string;
+  arch: string;
+  /** Unix time counting started, 0 while nothing is counted */
+  since: number;
+  launches: number;
+  /** Times each feature was used, e.g. `overlay` */
+  features: Record<string, number>;
+  /** Times each kind of error happened, e.g. `proxyCrash` */
+  errors: Record<string, number>;
+}
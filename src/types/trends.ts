@@ -0,0 +1,17 @@
+/** The stats at one point in time, saved about once a day */
+export interface StatSnapshot {
+  /** Unix time in seconds */
+  takenAt: number;
+  /** Every number in the API's stats, by name */
+  stats: Record<string, number>;
+}
+
+/** How far back `get_stats_trend` goes */
+export type TrendRange = "week" | "month" | "year" | "all";
+
+/** A value of a stat at a point in time */
+export interface TrendPoint {
+  /** Unix time in seconds */
+  at: number;
+  value: number;
+}
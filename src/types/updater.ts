@@ -0,0 +1,14 @@
+/** An available launcher update, from `check_launcher_update` */
+export interface LauncherUpdate {
+  version: string;
+  currentVersion: string;
+  /** Release notes, in Markdown */
+  notes: string | null;
+  date: string | null;
+}
+
+/** Emitted as `launcher-update-progress` while downloading */
+export interface UpdateProgress {
+  downloaded: number;
+  total: number | null;
+}
@@ -0,0 +1,2 @@
+/** How the launcher window appears on startup, from `get_start_mode` */
+export type StartMode = "normal" | "tray" | "minimized";